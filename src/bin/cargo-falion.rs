@@ -0,0 +1,390 @@
+use clap::{Args, Parser};
+use falion::search::error_query;
+use serde::Deserialize;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+use thiserror::Error;
+
+const CARGO_MESSAGE_REASON: &str = "compiler-message";
+const QUERY_TAG: &str = "rust";
+
+/// The type of errors cargo-falion can return.
+///
+/// * `RunCargo` - cargo check couldn't be run.
+/// * `ReadFile` - The saved diagnostics file couldn't be read.
+/// * `NoDiagnostics` - cargo check didn't report any diagnostic.
+/// * `InvalidPick` - The picked diagnostic doesn't exist.
+/// * `RunFalion` - falion couldn't be run.
+#[derive(Error, Debug)]
+enum CargoFalionError {
+    #[error("Failed to run cargo check. Error: {0}")]
+    RunCargo(std::io::Error),
+    #[error("Failed to read the diagnostics file at: {path}. Error: {error}")]
+    ReadFile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("No diagnostics were found, there's nothing to search for.")]
+    NoDiagnostics,
+    #[error("There is no diagnostic with the number: {0}")]
+    InvalidPick(String),
+    #[error("Failed to run falion, make sure it's installed next to cargo-falion or in PATH. Error: {0}")]
+    RunFalion(std::io::Error),
+}
+
+/// Command line options, cargo calls cargo-falion with falion as the first argument.
+#[derive(Parser)]
+#[command(name = "cargo", bin_name = "cargo")]
+enum CargoCli {
+    /// Search the diagnostics of the latest build with falion
+    Falion(FalionArgs),
+}
+
+/// The options of cargo falion.
+///
+/// # Options
+///
+/// file - optional, read the diagnostics from a saved file instead of running cargo check.
+/// pick - optional, the number of the diagnostic to search instead of asking for it.
+/// warnings - optional, list the warnings aswell not only the errors.
+/// mouse - optional, passed to falion to capture the mouse.
+/// cargo_args - optional, extra arguments for cargo check.
+#[derive(Args)]
+#[command(author, version, about, long_about = None)]
+struct FalionArgs {
+    /// Read the diagnostics from a file saved with `cargo check --message-format=json`
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+
+    /// The number of the diagnostic to search, instead of asking for it
+    #[arg(short, long)]
+    pick: Option<usize>,
+
+    /// List the warnings aswell, not only the errors
+    #[arg(short, long)]
+    warnings: bool,
+
+    /// Capture the mouse in falion's cli
+    #[arg(short, long)]
+    mouse: bool,
+
+    /// Extra arguments for cargo check (ex: cargo falion -- --all-targets)
+    #[arg(last = true)]
+    cargo_args: Vec<String>,
+}
+
+/// A message cargo prints with --message-format=json, only compiler messages have a diagnostic.
+#[derive(Deserialize, Debug)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+}
+
+/// A diagnostic of rustc.
+#[derive(Deserialize, Debug, Clone)]
+struct Diagnostic {
+    message: String,
+    code: Option<DiagnosticCode>,
+    level: String,
+    spans: Vec<DiagnosticSpan>,
+    rendered: Option<String>,
+}
+
+/// The code of a diagnostic (ex: E0308).
+#[derive(Deserialize, Debug, Clone)]
+struct DiagnosticCode {
+    code: String,
+}
+
+/// The location in the source code a diagnostic points to.
+#[derive(Deserialize, Debug, Clone)]
+struct DiagnosticSpan {
+    file_name: String,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+    label: Option<String>,
+}
+
+impl Diagnostic {
+    /// Get the header of the diagnostic like rustc prints it. (ex: error[E0308]: mismatched types)
+    fn header(&self) -> String {
+        match &self.code {
+            Some(code) => format!("{}[{}]: {}", self.level, code.code, self.message),
+            None => format!("{}: {}", self.level, self.message),
+        }
+    }
+
+    /// Get the primary location of the diagnostic. (ex: src/main.rs:4:18)
+    fn location(&self) -> Option<String> {
+        self.spans
+            .iter()
+            .find(|span| span.is_primary)
+            .or_else(|| self.spans.first())
+            .map(|span| {
+                format!(
+                    "{}:{}:{}",
+                    span.file_name, span.line_start, span.column_start
+                )
+            })
+    }
+
+    /// Get the query to search for, the code and the message stripped of the user's identifiers,
+    /// tagged with rust.
+    fn query(&self) -> String {
+        let message = error_query::strip_specifics(&self.message);
+        match &self.code {
+            Some(code) => format!("{} {} {}", QUERY_TAG, code.code, message),
+            None => format!("{} {}", QUERY_TAG, message),
+        }
+    }
+
+    /// Get the spans of the diagnostic as text, rendered by rustc if possible.
+    fn spans_text(&self) -> String {
+        if let Some(rendered) = &self.rendered {
+            return rendered.trim_end().to_string();
+        }
+
+        let mut text = self.header();
+        for span in &self.spans {
+            text.push_str(&format!(
+                "\n  --> {}:{}:{}",
+                span.file_name, span.line_start, span.column_start
+            ));
+            if let Some(label) = &span.label {
+                text.push_str(&format!(" {}", label));
+            }
+        }
+
+        text
+    }
+}
+
+/// Parse the diagnostics out of cargo's json messages, the ones without any span (like "aborting
+/// due to previous error") and the duplicates are left out.
+///
+/// # Arguments
+///
+/// * `json` - cargo's output with --message-format=json, one message per line.
+/// * `warnings` - if the warnings should be kept aswell, not only the errors.
+fn parse_diagnostics(json: &str, warnings: bool) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+
+    for line in json.lines() {
+        let message = match serde_json::from_str::<CargoMessage>(line) {
+            Ok(message) => message,
+            Err(_) => continue,
+        };
+        if message.reason != CARGO_MESSAGE_REASON {
+            continue;
+        }
+        let diagnostic = match message.message {
+            Some(diagnostic) => diagnostic,
+            None => continue,
+        };
+
+        let is_wanted = diagnostic.level == "error" || (warnings && diagnostic.level == "warning");
+        let is_duplicate = diagnostics.iter().any(|other| {
+            other.rendered == diagnostic.rendered && other.header() == diagnostic.header()
+        });
+        if is_wanted && !diagnostic.spans.is_empty() && !is_duplicate {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    diagnostics
+}
+
+/// Get the diagnostics json, either from the given file or by running cargo check.
+///
+/// # Arguments
+///
+/// * `args` - cargo falion's options.
+///
+/// # Errors
+///
+/// * `ReadFile` - The saved diagnostics file couldn't be read.
+/// * `RunCargo` - cargo check couldn't be run.
+fn get_diagnostics_json(args: &FalionArgs) -> Result<String, CargoFalionError> {
+    if let Some(path) = &args.file {
+        return match std::fs::read_to_string(path) {
+            Ok(json) => Ok(json),
+            Err(error) => Err(CargoFalionError::ReadFile {
+                path: path.to_path_buf(),
+                error,
+            }),
+        };
+    }
+
+    // cargo tells its subcommands which cargo binary called them
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = match Command::new(cargo)
+        .arg("check")
+        .arg("--message-format=json")
+        .args(&args.cargo_args)
+        .stderr(std::process::Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(error) => return Err(CargoFalionError::RunCargo(error)),
+    };
+
+    // cargo check exits with an error code when there are errors, that's expected
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// List the diagnostics and ask which one to search for, if there is only one it's picked right
+/// away.
+///
+/// # Arguments
+///
+/// * `diagnostics` - the diagnostics to pick from.
+/// * `pick` - the number of the diagnostic given in the command line options, if any.
+///
+/// # Errors
+///
+/// * `NoDiagnostics` - There are no diagnostics to pick from.
+/// * `InvalidPick` - The picked diagnostic doesn't exist.
+fn pick_diagnostic(
+    diagnostics: &[Diagnostic],
+    pick: Option<usize>,
+) -> Result<&Diagnostic, CargoFalionError> {
+    if diagnostics.is_empty() {
+        return Err(CargoFalionError::NoDiagnostics);
+    }
+
+    let pick = match pick {
+        Some(pick) => pick.to_string(),
+        None if diagnostics.len() == 1 => "1".to_string(),
+        None => {
+            for (index, diagnostic) in diagnostics.iter().enumerate() {
+                println!(
+                    "({}) {} {}",
+                    index + 1,
+                    diagnostic.header(),
+                    diagnostic.location().unwrap_or_default()
+                );
+            }
+            print!("Pick a diagnostic to search [1..{}]: ", diagnostics.len());
+            let _ = std::io::stdout().flush();
+
+            let mut pick = String::new();
+            if let Err(error) = std::io::stdin().read_line(&mut pick) {
+                eprintln!("Failed to read the picked diagnostic. Error: {}", error);
+            }
+            pick.trim().to_string()
+        }
+    };
+
+    match pick.parse::<usize>() {
+        Ok(number) if number >= 1 && number <= diagnostics.len() => Ok(&diagnostics[number - 1]),
+        _ => Err(CargoFalionError::InvalidPick(pick)),
+    }
+}
+
+/// Run falion's cli with the query of the diagnostic, its spans are shown above the answers.
+///
+/// # Arguments
+///
+/// * `diagnostic` - the diagnostic to search for.
+/// * `mouse` - if falion should capture the mouse.
+///
+/// # Errors
+///
+/// * `RunFalion` - falion couldn't be run.
+fn run_falion(diagnostic: &Diagnostic, mouse: bool) -> Result<ExitCode, CargoFalionError> {
+    // prefer the falion installed togheter with cargo-falion
+    let falion = match std::env::current_exe() {
+        Ok(exe) => {
+            let sibling = exe.with_file_name(format!("falion{}", std::env::consts::EXE_SUFFIX));
+            if sibling.exists() {
+                sibling
+            } else {
+                PathBuf::from("falion")
+            }
+        }
+        Err(_) => PathBuf::from("falion"),
+    };
+
+    let mut command = Command::new(falion);
+    command.arg("--context").arg(diagnostic.spans_text());
+    if mouse {
+        command.arg("--mouse");
+    }
+    command.arg("--").arg(diagnostic.query());
+
+    match command.status() {
+        Ok(status) if status.success() => Ok(ExitCode::SUCCESS),
+        Ok(_) => Ok(ExitCode::FAILURE),
+        Err(error) => Err(CargoFalionError::RunFalion(error)),
+    }
+}
+
+/// Main cargo-falion execution
+fn main() -> ExitCode {
+    let CargoCli::Falion(args) = CargoCli::parse();
+
+    let result = get_diagnostics_json(&args).and_then(|json| {
+        let diagnostics = parse_diagnostics(&json, args.warnings);
+        let diagnostic = pick_diagnostic(&diagnostics, args.pick)?;
+        run_falion(diagnostic, args.mouse)
+    });
+
+    match result {
+        Ok(code) => code,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_JSON: &str = r#"{"reason":"compiler-artifact","package_id":"app 0.1.0","target":{"name":"app"}}
+{"reason":"compiler-message","package_id":"app 0.1.0","message":{"message":"mismatched types","code":{"code":"E0308","explanation":null},"level":"error","spans":[{"file_name":"src/main.rs","byte_start":40,"byte_end":47,"line_start":4,"line_end":4,"column_start":18,"column_end":25,"is_primary":true,"text":[],"label":"expected `u32`, found `&str`","suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[],"rendered":"error[E0308]: mismatched types\n --> src/main.rs:4:18\n"}}
+{"reason":"compiler-message","package_id":"app 0.1.0","message":{"message":"unused variable: `count`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","byte_start":10,"byte_end":15,"line_start":2,"line_end":2,"column_start":9,"column_end":14,"is_primary":true,"text":[],"label":null,"suggested_replacement":null,"suggestion_applicability":null,"expansion":null}],"children":[],"rendered":"warning: unused variable: `count`\n"}}
+{"reason":"compiler-message","package_id":"app 0.1.0","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[],"rendered":"error: aborting due to 1 previous error\n"}}
+{"reason":"build-finished","success":false}"#;
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let errors = parse_diagnostics(CARGO_JSON, false);
+        let all = parse_diagnostics(CARGO_JSON, true);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(all.len(), 2);
+        assert_eq!(errors[0].header(), "error[E0308]: mismatched types");
+        assert_eq!(errors[0].location().as_deref(), Some("src/main.rs:4:18"));
+        assert_eq!(
+            errors[0].spans_text(),
+            "error[E0308]: mismatched types\n --> src/main.rs:4:18"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_query() {
+        let all = parse_diagnostics(CARGO_JSON, true);
+
+        assert_eq!(all[0].query(), "rust E0308 mismatched types");
+        assert_eq!(
+            all[1].query(),
+            "rust unused_variables unused variable: count"
+        );
+    }
+
+    #[test]
+    fn test_pick_diagnostic() {
+        let errors = parse_diagnostics(CARGO_JSON, false);
+
+        assert!(pick_diagnostic(&errors, None).is_ok());
+        assert!(pick_diagnostic(&errors, Some(2)).is_err());
+        assert!(matches!(
+            pick_diagnostic(&[], None),
+            Err(CargoFalionError::NoDiagnostics)
+        ));
+    }
+}
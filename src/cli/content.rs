@@ -1,11 +1,13 @@
+use crate::content::{self, Loaded};
+use crate::search::engine::SearchHit;
 use hashbrown::HashMap;
 use tokio::task::JoinHandle;
 
-type ResultsStaticType<E> = Vec<(String, JoinHandle<Result<String, E>>)>;
-type ResultsDynType<E> = Vec<(String, JoinHandle<Result<Vec<String>, E>>)>;
+type ResultsType<T, E> = Vec<(SearchHit, JoinHandle<Result<T, E>>)>;
 
-/// Get dynamic type content (in form of a vector). Either await it if it wasn't already, it it was
-/// get it from the awaited list.
+/// Get the content for a result. Either await it if it wasn't already, it it was get it from the
+/// awaited list. Once awaited the result is renamed to its title with its metadata, under which
+/// it's kept in the awaited list.
 ///
 /// # Arguments
 ///
@@ -13,47 +15,28 @@ type ResultsDynType<E> = Vec<(String, JoinHandle<Result<Vec<String>, E>>)>;
 /// `results_awaited_ref` - mutable reference to the object keeping already awaited resources.
 /// `results_index` - which result to get back, it's index.
 #[tracing::instrument(skip_all)]
-pub async fn get_dyn_result_content<'a, E>(
-    results_ref: &'a mut Result<ResultsDynType<E>, E>,
-    results_awaited_ref: &'a mut HashMap<String, Vec<String>>,
+pub async fn get_result_content<'a, T, E>(
+    results_ref: &'a mut Result<ResultsType<T, E>, E>,
+    results_awaited_ref: &'a mut HashMap<String, T>,
     results_index: usize,
-) -> Option<&'a Vec<String>>
+) -> Option<&'a T>
 where
+    T: Loaded,
     E: std::fmt::Display,
 {
     match results_ref {
         Ok(res) => {
             if let Some(unawaited_res) = res.get_mut(results_index) {
-                let (title, handle) = unawaited_res;
-                if results_awaited_ref.contains_key(title) {
-                    match results_awaited_ref.get(title) {
-                        Some(res) => Some(res),
-                        None => None,
-                    }
-                } else {
-                    let awaited = match handle.await {
-                        Ok(handled) => match handled {
-                            Ok(content) => content,
-                            Err(error) => {
-                                tracing::error!("There was an error getting the contetn for this a result. Error: {}", error);
-                                vec![format!("There has been an error getting the content for this result. Error: {}", error)]
-                            }
-                        },
-                        Err(error) => {
-                            tracing::error!(
-                                "There was an error handeling the future for a result. Error: {}",
-                                error
-                            );
-                            vec![format!("There has been an error handeling the future for this result. Error: {}", error)]
-                        }
-                    };
+                let (hit, handle) = unawaited_res;
+                if !results_awaited_ref.contains_key(&hit.title) {
+                    let awaited = content::await_content(handle).await;
 
-                    // save already awaited
-                    results_awaited_ref.insert(title.to_owned(), awaited);
-
-                    // unwrap is safe since we just inserted this element
-                    results_awaited_ref.get(title)
+                    // save already awaited, under the title with the metadata
+                    hit.title = awaited.row_title(&hit.title);
+                    results_awaited_ref.insert(hit.title.to_owned(), awaited);
                 }
+
+                results_awaited_ref.get(&hit.title)
             } else {
                 None
             }
@@ -68,66 +51,60 @@ where
     }
 }
 
-/// Get the static content for a result (String type). Either await it or get it from the list of
-/// already awaited.
+/// Await the results whose content finished loading in the background, so they're listed under
+/// their title with their metadata.
 ///
 /// # Arguments
 ///
 /// `results_ref` - mutable reference to results.
 /// `results_awaited_ref` - mutable reference to the object keeping already awaited resources.
-/// `results_index` - which result to get back, it's index.
+///
+/// # Returns
+///
+/// If any result was renamed and if any result is still loading.
 #[tracing::instrument(skip_all)]
-pub async fn get_static_result_content<'a, E>(
-    results_ref: &'a mut Result<ResultsStaticType<E>, E>,
-    results_awaited_ref: &'a mut HashMap<String, String>,
-    results_index: usize,
-) -> Option<&'a String>
+pub async fn load_finished<T, E>(
+    results_ref: &mut Result<ResultsType<T, E>, E>,
+    results_awaited_ref: &mut HashMap<String, T>,
+) -> (bool, bool)
 where
+    T: Loaded,
     E: std::fmt::Display,
 {
-    match results_ref {
-        Ok(res) => {
-            if let Some(unawaited_res) = res.get_mut(results_index) {
-                let (title, handle) = unawaited_res;
-                if results_awaited_ref.contains_key(title) {
-                    match results_awaited_ref.get(title) {
-                        Some(res) => Some(res),
-                        None => None,
-                    }
-                } else {
-                    let awaited = match handle.await {
-                        Ok(handled) => match handled {
-                            Ok(content) => content,
-                            Err(error) => {
-                                tracing::error!("There was an error getting the contetn for this a result. Error: {}", error);
-                                format!("There has been an error getting the content for this result. Error: {}", error)
-                            }
-                        },
-                        Err(error) => {
-                            tracing::error!(
-                                "There was an error handeling the future for a result. Error: {}",
-                                error
-                            );
-                            format!("There has been an error handeling the future for this result. Error: {}", error)
-                        }
-                    };
-
-                    // save already awaited
-                    results_awaited_ref.insert(title.to_owned(), awaited);
-
-                    // unwrap is safe since we just inserted this element
-                    results_awaited_ref.get(title)
-                }
-            } else {
-                None
+    let mut renamed = false;
+    let mut loading = false;
+    if let Ok(res) = results_ref {
+        for (hit, handle) in res.iter_mut() {
+            if results_awaited_ref.contains_key(&hit.title) {
+                continue;
             }
-        }
-        Err(error) => {
-            tracing::info!(
-                "User tryed accessing a resource that has been deemed unavailable. Error: {}",
-                error
-            );
-            None
+            if !handle.is_finished() {
+                loading = true;
+                continue;
+            }
+
+            let awaited = content::await_content(handle).await;
+            hit.title = awaited.row_title(&hit.title);
+            results_awaited_ref.insert(hit.title.to_owned(), awaited);
+            renamed = true;
         }
     }
+
+    (renamed, loading)
+}
+
+/// Get the votes of a result, known once its content loaded if its resource has any.
+///
+/// # Arguments
+///
+/// `hit` - the result.
+/// `results_awaited_ref` - the object keeping already awaited resources.
+pub fn votes<T>(hit: &SearchHit, results_awaited_ref: &HashMap<String, T>) -> Option<i64>
+where
+    T: Loaded,
+{
+    results_awaited_ref
+        .get(&hit.title)
+        .and_then(|awaited| awaited.metadata())
+        .and_then(|metadata| metadata.score)
 }
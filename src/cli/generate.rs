@@ -0,0 +1,75 @@
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+/// Write the completions script of falion for the given shell. They're generated from the Cli
+/// struct so they always cover every option and its possible values.
+///
+/// # Arguments
+///
+/// * `shell` - the shell to generate the completions for.
+/// * `out` - where to write the script, usually stdout.
+#[tracing::instrument(skip_all)]
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = super::Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Write the man page of falion (roff format), generated from the Cli struct.
+///
+/// # Arguments
+///
+/// * `out` - where to write the man page, usually stdout.
+///
+/// # Errors
+///
+/// If writing to the given output fails.
+#[tracing::instrument(skip_all)]
+pub fn write_man_page(out: &mut dyn Write) -> Result<(), std::io::Error> {
+    let man = clap_mangen::Man::new(super::Cli::command());
+    man.render(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Cli;
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn test_write_completions() {
+        let mut script = Vec::new();
+        write_completions(Shell::Bash, &mut script);
+        let script = String::from_utf8(script).unwrap();
+
+        assert!(script.contains("--mouse"));
+        assert!(script.contains("--keybinds"));
+        assert!(script.contains("--lang"));
+    }
+
+    #[test]
+    fn test_write_man_page() {
+        let mut page = Vec::new();
+        write_man_page(&mut page).unwrap();
+        let page = String::from_utf8(page).unwrap();
+
+        assert!(page.contains(".TH falion"));
+        assert!(page.contains("\\-\\-config"));
+    }
+
+    #[test]
+    fn test_queries_starting_with_generated_names() {
+        let cli = Cli::try_parse_from(["falion", "man", "tar", "options"]).unwrap();
+        assert_eq!(cli.query, ["man", "tar", "options"]);
+        assert!(!cli.generate_man);
+
+        let cli = Cli::try_parse_from(["falion", "completions", "of", "something"]).unwrap();
+        assert_eq!(cli.query, ["completions", "of", "something"]);
+        assert!(cli.generate_completions.is_none());
+
+        let cli = Cli::try_parse_from(["falion", "--generate-completions", "zsh"]).unwrap();
+        assert_eq!(cli.generate_completions, Some(Shell::Zsh));
+        assert!(cli.query.is_empty());
+    }
+}
@@ -0,0 +1,755 @@
+use crate::config::KeymapConfig;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use hashbrown::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+// What SHIFT + [1..9, 0] types on an US keyboard
+const SHIFTED_DIGITS: [char; 10] = ['!', '@', '#', '$', '%', '^', '&', '*', '(', ')'];
+// What ALT (option) + [1..9, 0] types on macos
+const MACOS_ALT_DIGITS: [char; 10] = ['¡', '™', '£', '¢', '∞', '§', '¶', '•', 'ª', 'º'];
+// The letters the presets bind for the resources after the tenth (r for reddit, h for hacker
+// news), the resources after the letters are left unbound
+const RESOURCE_LETTERS: [char; 2] = ['r', 'h'];
+
+/// The type of errors building a keymap can return.
+///
+/// * `UnknownPreset` - The preset name is not one of default, vim or emacs.
+/// * `UnknownAction` - The action name doesn't correspond to any action.
+/// * `InvalidKeyChord` - The key chord couldn't be parsed.
+#[derive(Error, Debug)]
+pub enum KeymapError {
+    #[error("The keymap preset: {0} doesn't exist. Available presets: default, vim, emacs.")]
+    UnknownPreset(String),
+    #[error("The action: {0} doesn't exist. Do --keybinds to see the available actions.")]
+    UnknownAction(String),
+    #[error("The key chord: {0} is not valid. Ex of valid key chords: n, shift+n, ctrl+c, alt+1.")]
+    InvalidKeyChord(String),
+}
+
+/// Something the user can do in the cli by pressing a key chord.
+///
+/// * `AccessResource` - Enter the content menu of a resource (1 based).
+/// * `NextResource` - Go to the next element in the list of a resource (1 based).
+/// * `PreviousResource` - Go to the previous element in the list of a resource (1 based).
+/// * `NextAll` - Move every resource to the next element in their list.
+/// * `PreviousAll` - Move every resource to the previous element in their list.
+/// * `AccessMerged` - Enter the content menu of the current result of the merged view.
+/// * `NextMerged` - Go to the next result of the merged view.
+/// * `PreviousMerged` - Go to the previous result of the merged view.
+/// * `NextContent` - Move to the next element in the content list (ex: answers).
+/// * `PreviousContent` - Move to the previous element in the content list.
+/// * `ToggleComments` - Show or hide the comments of the current element.
+/// * `Return` - Go back to the main menu.
+/// * `Exit` - Clear the terminal and exit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    AccessResource(usize),
+    NextResource(usize),
+    PreviousResource(usize),
+    NextAll,
+    PreviousAll,
+    AccessMerged,
+    NextMerged,
+    PreviousMerged,
+    NextContent,
+    PreviousContent,
+    ToggleComments,
+    Return,
+    Exit,
+}
+
+impl Action {
+    /// The name of the action as used in the config file.
+    pub fn name(&self) -> String {
+        match self {
+            Action::AccessResource(n) => format!("access-resource-{}", n),
+            Action::NextResource(n) => format!("next-resource-{}", n),
+            Action::PreviousResource(n) => format!("previous-resource-{}", n),
+            Action::NextAll => String::from("next-all"),
+            Action::PreviousAll => String::from("previous-all"),
+            Action::AccessMerged => String::from("access-merged"),
+            Action::NextMerged => String::from("next-merged"),
+            Action::PreviousMerged => String::from("previous-merged"),
+            Action::NextContent => String::from("next-content"),
+            Action::PreviousContent => String::from("previous-content"),
+            Action::ToggleComments => String::from("toggle-comments"),
+            Action::Return => String::from("return"),
+            Action::Exit => String::from("exit"),
+        }
+    }
+
+    /// A short description of what the action does, used for printing the keybinds list.
+    pub fn description(&self) -> String {
+        match self {
+            Action::AccessResource(n) => format!("Access resource {}.", n),
+            Action::NextResource(n) => {
+                format!("Go to the next element in the list of resource {}.", n)
+            }
+            Action::PreviousResource(n) => {
+                format!("Go to the previous element in the list of resource {}.", n)
+            }
+            Action::NextAll => {
+                String::from("Move to the next element in the list of every resource.")
+            }
+            Action::PreviousAll => {
+                String::from("Move back to the previous element in the list of every resource.")
+            }
+            Action::AccessMerged => {
+                String::from("Access the current result of the merged view (--merged).")
+            }
+            Action::NextMerged => String::from("Go to the next result of the merged view."),
+            Action::PreviousMerged => {
+                String::from("Go back to the previous result of the merged view.")
+            }
+            Action::NextContent => String::from(
+                "Move to the next element in the content list (like questions & answers).",
+            ),
+            Action::PreviousContent => {
+                String::from("Move back to the previous element in the content list.")
+            }
+            Action::ToggleComments => {
+                String::from("Show or hide the comments of the current element.")
+            }
+            Action::Return => String::from("Go back to the main menu."),
+            Action::Exit => String::from("Clear terminal and exit."),
+        }
+    }
+
+    /// Parse the name of an action as used in the config file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - the name of the action. (ex: next-resource-1)
+    /// * `resources` - how many resources there are, in order to validate the resource number.
+    fn from_name(name: &str, resources: usize) -> Option<Action> {
+        let resource = |number: &str| match number.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= resources => Some(n),
+            _ => None,
+        };
+
+        match name {
+            "next-all" => Some(Action::NextAll),
+            "previous-all" => Some(Action::PreviousAll),
+            "access-merged" => Some(Action::AccessMerged),
+            "next-merged" => Some(Action::NextMerged),
+            "previous-merged" => Some(Action::PreviousMerged),
+            "next-content" => Some(Action::NextContent),
+            "previous-content" => Some(Action::PreviousContent),
+            "toggle-comments" => Some(Action::ToggleComments),
+            "return" => Some(Action::Return),
+            "exit" => Some(Action::Exit),
+            _ => {
+                if let Some(n) = name.strip_prefix("access-resource-") {
+                    resource(n).map(Action::AccessResource)
+                } else if let Some(n) = name.strip_prefix("next-resource-") {
+                    resource(n).map(Action::NextResource)
+                } else if let Some(n) = name.strip_prefix("previous-resource-") {
+                    resource(n).map(Action::PreviousResource)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// If the action can be done from the main menu.
+    fn is_main(&self) -> bool {
+        !matches!(
+            self,
+            Action::NextContent | Action::PreviousContent | Action::ToggleComments | Action::Return
+        )
+    }
+
+    /// If the action can be done from the content menus.
+    fn is_content(&self) -> bool {
+        matches!(
+            self,
+            Action::NextContent
+                | Action::PreviousContent
+                | Action::ToggleComments
+                | Action::Return
+                | Action::Exit
+        )
+    }
+}
+
+/// A key together with its modifiers, ex: ctrl+c.
+///
+/// Key chords are normalized so the same physical key press results in the same chord on every
+/// platform: an uppercase letter always has SHIFT, while symbols never have it since the symbol
+/// already is the result of pressing SHIFT (windows reports SHIFT + '!', unix just '!').
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Create a new normalized key chord.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - the key.
+    /// * `modifiers` - the modifiers held down, anything besides SHIFT, CONTROL and ALT is
+    /// ignored.
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::SHIFT | KeyModifiers::CONTROL | KeyModifiers::ALT);
+        let code = match code {
+            KeyCode::Char(c) if c.is_ascii_alphabetic() => {
+                if c.is_ascii_uppercase() {
+                    modifiers.insert(KeyModifiers::SHIFT);
+                    KeyCode::Char(c)
+                } else if modifiers.contains(KeyModifiers::SHIFT) {
+                    KeyCode::Char(c.to_ascii_uppercase())
+                } else {
+                    KeyCode::Char(c)
+                }
+            }
+            // keep SHIFT on digits so SHIFT + 1 can be bound on layouts where it isn't '!'
+            KeyCode::Char(c) if c.is_ascii_digit() => KeyCode::Char(c),
+            KeyCode::Char(c) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c)
+            }
+            code => code,
+        };
+
+        Self { code, modifiers }
+    }
+}
+
+impl From<&KeyEvent> for KeyChord {
+    fn from(event: &KeyEvent) -> Self {
+        KeyChord::new(event.code, event.modifiers)
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = KeymapError;
+
+    /// Parse a key chord like: n, N, shift+n, ctrl+c, alt+1, !, enter, f1, ctrl++
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || KeymapError::InvalidKeyChord(s.to_string());
+
+        // the '+' key itself would break splitting on '+'
+        let (modifiers_part, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(rest) = s.strip_suffix("++") {
+            (rest, "+")
+        } else {
+            match s.rsplit_once('+') {
+                Some((modifiers, key)) => (modifiers, key),
+                None => ("", s),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in modifiers_part.split('+').filter(|m| !m.is_empty()) {
+            match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers.insert(KeyModifiers::CONTROL),
+                "alt" | "meta" | "option" => modifiers.insert(KeyModifiers::ALT),
+                "shift" => modifiers.insert(KeyModifiers::SHIFT),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            (Some(_), Some(_)) => match key.to_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                function => match function.strip_prefix('f').map(str::parse::<u8>) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+            _ => return Err(invalid()),
+        };
+
+        Ok(KeyChord::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_ascii_lowercase()),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+/// The built in keymaps you can start from.
+///
+/// * `Default` - falion's original key binds.
+/// * `Vim` - j/k navigation on top of the default ones.
+/// * `Emacs` - ctrl+n/ctrl+p navigation and ctrl+g to return.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Default,
+    Vim,
+    Emacs,
+}
+
+impl FromStr for Preset {
+    type Err = KeymapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "default" => Ok(Preset::Default),
+            "vim" => Ok(Preset::Vim),
+            "emacs" => Ok(Preset::Emacs),
+            _ => Err(KeymapError::UnknownPreset(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Preset::Default => write!(f, "default"),
+            Preset::Vim => write!(f, "vim"),
+            Preset::Emacs => write!(f, "emacs"),
+        }
+    }
+}
+
+impl Preset {
+    /// The key chords of every action for this preset, in the order they should be printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `resources` - how many resources the main menu has.
+    fn bindings(&self, resources: usize) -> Vec<(Action, Vec<String>)> {
+        let mut bindings = Vec::with_capacity(resources * 3 + 10);
+
+        // the resources are the same across presets, SHIFT + digit is bound both by the digit
+        // and the US symbol and ALT + digit by the macos symbol aswell, so every platform works
+        // out of the box
+        for n in 1..=resources {
+            // there are only ten digits, the resources after them get a letter like the merged
+            // view and the ones after the letters are left unbound
+            if n > SHIFTED_DIGITS.len() {
+                let letter = RESOURCE_LETTERS.get(n - SHIFTED_DIGITS.len() - 1);
+                let chords = |modifier: &str| match letter {
+                    Some(letter) => vec![format!("{}{}", modifier, letter)],
+                    None => Vec::new(),
+                };
+                bindings.push((Action::AccessResource(n), chords("")));
+                bindings.push((Action::NextResource(n), chords("shift+")));
+                bindings.push((Action::PreviousResource(n), chords("alt+")));
+                continue;
+            }
+            let digit = n % 10;
+            let symbol_index = (digit + 9) % 10;
+            bindings.push((Action::AccessResource(n), vec![digit.to_string()]));
+            bindings.push((
+                Action::NextResource(n),
+                vec![
+                    format!("shift+{}", digit),
+                    SHIFTED_DIGITS[symbol_index].to_string(),
+                ],
+            ));
+            bindings.push((
+                Action::PreviousResource(n),
+                vec![
+                    format!("alt+{}", digit),
+                    MACOS_ALT_DIGITS[symbol_index].to_string(),
+                ],
+            ));
+        }
+
+        let (next, previous, ret): (&[&str], &[&str], &[&str]) = match self {
+            Preset::Default => (&["n"], &["shift+n"], &["q"]),
+            Preset::Vim => (&["j", "n"], &["k", "shift+n"], &["q", "h"]),
+            Preset::Emacs => (&["ctrl+n", "n"], &["ctrl+p", "shift+n"], &["ctrl+g", "q"]),
+        };
+        let to_strings = |chords: &[&str]| chords.iter().map(|c| c.to_string()).collect();
+
+        bindings.push((Action::NextAll, to_strings(next)));
+        bindings.push((Action::PreviousAll, to_strings(previous)));
+        bindings.push((Action::AccessMerged, vec![String::from("m")]));
+        bindings.push((Action::NextMerged, vec![String::from("shift+m")]));
+        bindings.push((Action::PreviousMerged, vec![String::from("alt+m")]));
+        bindings.push((Action::NextContent, to_strings(next)));
+        bindings.push((Action::PreviousContent, to_strings(previous)));
+        bindings.push((Action::ToggleComments, vec![String::from("c")]));
+        bindings.push((Action::Return, to_strings(ret)));
+        bindings.push((Action::Exit, vec![String::from("ctrl+c")]));
+
+        bindings
+    }
+}
+
+/// The key chords bound to every action in the cli.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    preset: Preset,
+    bindings: Vec<(Action, Vec<KeyChord>)>,
+    main: HashMap<KeyChord, Action>,
+    content: HashMap<KeyChord, Action>,
+}
+
+impl Keymap {
+    /// Create the keymap of a preset.
+    ///
+    /// # Arguments
+    ///
+    /// * `preset` - the preset to use.
+    /// * `resources` - how many resources the main menu has.
+    pub fn from_preset(preset: Preset, resources: usize) -> Self {
+        // unwrap is safe since the presets are made out of valid key chords
+        let bindings = preset
+            .bindings(resources)
+            .into_iter()
+            .map(|(action, chords)| (action, chords.iter().map(|c| c.parse().unwrap()).collect()))
+            .collect();
+
+        Self::with_bindings(preset, bindings)
+    }
+
+    /// Create the keymap from the config file, starting from the configured preset and replacing
+    /// the key chords of the actions found in the bindings.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - the keymap section of the config file.
+    /// * `resources` - how many resources the main menu has.
+    ///
+    /// # Errors
+    ///
+    /// * `UnknownPreset` - The preset name is not one of default, vim or emacs.
+    /// * `UnknownAction` - The action name doesn't correspond to any action.
+    /// * `InvalidKeyChord` - The key chord couldn't be parsed.
+    pub fn from_config(config: &KeymapConfig, resources: usize) -> Result<Self, KeymapError> {
+        let preset = match &config.preset {
+            Some(preset) => preset.parse()?,
+            None => Preset::Default,
+        };
+        let mut bindings = Self::from_preset(preset, resources).bindings;
+
+        for (name, chords) in &config.bindings {
+            let action = match Action::from_name(name, resources) {
+                Some(action) => action,
+                None => return Err(KeymapError::UnknownAction(name.to_owned())),
+            };
+            let chords = chords
+                .clone()
+                .into_vec()
+                .iter()
+                .map(|c| c.parse())
+                .collect::<Result<Vec<KeyChord>, KeymapError>>()?;
+
+            // unwrap is safe since every action is in the preset bindings
+            let binding = bindings.iter_mut().find(|(a, _)| *a == action).unwrap();
+            binding.1 = chords;
+        }
+
+        Ok(Self::with_bindings(preset, bindings))
+    }
+
+    /// Build the lookup tables for the given bindings. If the same key chord is bound to multiple
+    /// actions in the same menu the first one in the bindings wins.
+    fn with_bindings(preset: Preset, bindings: Vec<(Action, Vec<KeyChord>)>) -> Self {
+        let mut main = HashMap::with_capacity(bindings.len() * 2);
+        let mut content = HashMap::with_capacity(16);
+
+        for (action, chords) in &bindings {
+            for chord in chords {
+                if action.is_main() {
+                    main.entry(*chord).or_insert(*action);
+                }
+                if action.is_content() {
+                    content.entry(*chord).or_insert(*action);
+                }
+            }
+        }
+
+        Self {
+            preset,
+            bindings,
+            main,
+            content,
+        }
+    }
+
+    /// Get the action bound to the key press in the main menu, if any.
+    pub fn main_action(&self, event: &KeyEvent) -> Option<Action> {
+        self.main.get(&KeyChord::from(event)).copied()
+    }
+
+    /// Get the action bound to the key press in a content menu, if any.
+    pub fn content_action(&self, event: &KeyEvent) -> Option<Action> {
+        self.content.get(&KeyChord::from(event)).copied()
+    }
+
+    /// Get the first key chord bound to an action, to hint at it in the menus. (ex: c)
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - the action to get the key chord of.
+    pub fn chord_of(&self, action: Action) -> Option<String> {
+        self.bindings
+            .iter()
+            .find(|(a, _)| *a == action)
+            .and_then(|(_, chords)| chords.first())
+            .map(|chord| chord.to_string())
+    }
+
+    /// Create the keybinds list out of the actual bindings so it's never out of date.
+    pub fn keybinds_list(&self) -> String {
+        let format_binding = |(action, chords): &(Action, Vec<KeyChord>)| {
+            let chords = if chords.is_empty() {
+                String::from("(unbound)")
+            } else {
+                chords
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" | ")
+            };
+            format!(
+                "{} = {} ({})\n",
+                chords,
+                action.description(),
+                action.name()
+            )
+        };
+
+        let mut list = format!("Keybinds list for falion (preset: {}).\n", self.preset);
+        list.push_str("Note: the action name in parentheses is what you use to rebind it in the ");
+        list.push_str("[keymap.bindings] section of the config file.\n");
+
+        list.push_str("\nMain menu:\n");
+        self.bindings
+            .iter()
+            .filter(|(action, _)| action.is_main())
+            .for_each(|binding| list.push_str(&format_binding(binding)));
+
+        list.push_str("\nSub menus for the resources:\n");
+        self.bindings
+            .iter()
+            .filter(|(action, _)| action.is_content())
+            .for_each(|binding| list.push_str(&format_binding(binding)));
+
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key_event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[test]
+    fn test_parse_key_chord() {
+        assert_eq!(
+            "ctrl+c".parse::<KeyChord>().unwrap(),
+            KeyChord::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            "N".parse::<KeyChord>().unwrap(),
+            "shift+n".parse::<KeyChord>().unwrap()
+        );
+        assert_eq!(
+            "ctrl++".parse::<KeyChord>().unwrap(),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!("f5".parse::<KeyChord>().unwrap().code, KeyCode::F(5));
+        assert!("hyper+c".parse::<KeyChord>().is_err());
+        assert!("f13".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn test_key_chord_display_round_trip() {
+        for chord in ["ctrl+c", "shift+n", "alt+1", "!", "enter", "f1", "space"] {
+            let parsed = chord.parse::<KeyChord>().unwrap();
+            assert_eq!(parsed.to_string(), chord);
+            assert_eq!(parsed.to_string().parse::<KeyChord>().unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn test_shifted_symbols_match_on_every_platform() {
+        let keymap = Keymap::from_preset(Preset::Default, 5);
+
+        // unix reports the symbol alone, windows the symbol with SHIFT
+        let unix = key_event(KeyCode::Char('!'), KeyModifiers::NONE);
+        let windows = key_event(KeyCode::Char('!'), KeyModifiers::SHIFT);
+        // non US layouts with keyboard enhancement report the digit with SHIFT
+        let digit = key_event(KeyCode::Char('1'), KeyModifiers::SHIFT);
+
+        assert_eq!(keymap.main_action(&unix), Some(Action::NextResource(1)));
+        assert_eq!(keymap.main_action(&windows), Some(Action::NextResource(1)));
+        assert_eq!(keymap.main_action(&digit), Some(Action::NextResource(1)));
+    }
+
+    #[test]
+    fn test_keymap_from_config() {
+        let mut config = KeymapConfig {
+            preset: Some(String::from("vim")),
+            ..Default::default()
+        };
+        config.bindings.insert(
+            String::from("exit"),
+            crate::config::OneOrMany::One(String::from("ctrl+q")),
+        );
+        let keymap = Keymap::from_config(&config, 5).unwrap();
+
+        let j = key_event(KeyCode::Char('j'), KeyModifiers::NONE);
+        let ctrl_q = key_event(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        let ctrl_c = key_event(KeyCode::Char('c'), KeyModifiers::CONTROL);
+
+        assert_eq!(keymap.main_action(&j), Some(Action::NextAll));
+        assert_eq!(keymap.content_action(&j), Some(Action::NextContent));
+        assert_eq!(keymap.content_action(&ctrl_q), Some(Action::Exit));
+        assert_eq!(keymap.main_action(&ctrl_c), None);
+    }
+
+    #[test]
+    fn test_resources_past_the_digits() {
+        let zero = key_event(KeyCode::Char('0'), KeyModifiers::NONE);
+        let r = key_event(KeyCode::Char('r'), KeyModifiers::NONE);
+        let shift_r = key_event(KeyCode::Char('R'), KeyModifiers::SHIFT);
+        let alt_r = key_event(KeyCode::Char('r'), KeyModifiers::ALT);
+        let h = key_event(KeyCode::Char('h'), KeyModifiers::NONE);
+        let shift_h = key_event(KeyCode::Char('H'), KeyModifiers::SHIFT);
+        let alt_h = key_event(KeyCode::Char('h'), KeyModifiers::ALT);
+
+        for preset in [Preset::Default, Preset::Vim, Preset::Emacs] {
+            let keymap = Keymap::from_preset(preset, 13);
+
+            assert_eq!(keymap.main_action(&zero), Some(Action::AccessResource(10)));
+            assert_eq!(keymap.main_action(&r), Some(Action::AccessResource(11)));
+            assert_eq!(keymap.main_action(&shift_r), Some(Action::NextResource(11)));
+            assert_eq!(
+                keymap.main_action(&alt_r),
+                Some(Action::PreviousResource(11))
+            );
+            assert_eq!(keymap.main_action(&h), Some(Action::AccessResource(12)));
+            assert_eq!(keymap.main_action(&shift_h), Some(Action::NextResource(12)));
+            assert_eq!(
+                keymap.main_action(&alt_h),
+                Some(Action::PreviousResource(12))
+            );
+            assert!(keymap
+                .bindings
+                .iter()
+                .any(|(action, chords)| *action == Action::NextResource(13) && chords.is_empty()));
+        }
+
+        // h returns to the main menu in vim, which doesn't clash since that's a content action
+        let keymap = Keymap::from_preset(Preset::Vim, 12);
+        assert_eq!(keymap.content_action(&h), Some(Action::Return));
+
+        let mut config = KeymapConfig::default();
+        config.bindings.insert(
+            String::from("access-resource-11"),
+            crate::config::OneOrMany::One(String::from("x")),
+        );
+        let keymap = Keymap::from_config(&config, 12).unwrap();
+        let x = key_event(KeyCode::Char('x'), KeyModifiers::NONE);
+
+        assert_eq!(keymap.main_action(&x), Some(Action::AccessResource(11)));
+        assert_eq!(keymap.main_action(&r), None);
+    }
+
+    #[test]
+    fn test_toggle_comments_is_content_only() {
+        let keymap = Keymap::from_preset(Preset::Emacs, 5);
+        let c = key_event(KeyCode::Char('c'), KeyModifiers::NONE);
+
+        assert_eq!(keymap.content_action(&c), Some(Action::ToggleComments));
+        assert_eq!(keymap.main_action(&c), None);
+        assert_eq!(
+            keymap.chord_of(Action::ToggleComments),
+            Some(String::from("c"))
+        );
+    }
+
+    #[test]
+    fn test_merged_actions() {
+        let keymap = Keymap::from_preset(Preset::Vim, 5);
+        let m = key_event(KeyCode::Char('m'), KeyModifiers::NONE);
+        let shift_m = key_event(KeyCode::Char('M'), KeyModifiers::SHIFT);
+
+        assert_eq!(keymap.main_action(&m), Some(Action::AccessMerged));
+        assert_eq!(keymap.main_action(&shift_m), Some(Action::NextMerged));
+        assert_eq!(keymap.content_action(&m), None);
+        assert_eq!(
+            Action::from_name("previous-merged", 5),
+            Some(Action::PreviousMerged)
+        );
+    }
+
+    #[test]
+    fn test_keymap_from_invalid_config() {
+        let mut config = KeymapConfig::default();
+        config.bindings.insert(
+            String::from("next-resource-9"),
+            crate::config::OneOrMany::One(String::from("x")),
+        );
+
+        assert!(matches!(
+            Keymap::from_config(&config, 5),
+            Err(KeymapError::UnknownAction(_))
+        ));
+
+        let config = KeymapConfig {
+            preset: Some(String::from("nano")),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            Keymap::from_config(&config, 5),
+            Err(KeymapError::UnknownPreset(_))
+        ));
+    }
+}
@@ -1,88 +1,85 @@
 mod content;
+mod generate;
+mod keymap;
+mod mouse;
 mod print;
 mod util;
 use super::search;
+use super::search::ddg::{DdgError, SafeSearch, TimeRange};
 use super::search::ddg_search::DdgSearchError;
-use super::search::geeksforgeeks::GfgError;
-use super::search::github_gist::GithubGistError;
+use super::search::discourse::DiscourseError;
+use super::search::engine::SearchHit;
+use super::search::geeksforgeeks::{Article, GfgError};
+use super::search::github_gist::{Gist, GithubGistError};
+use super::search::github_issues::GithubIssuesError;
+use super::search::hacker_news::HackerNewsError;
+use super::search::lang::{self, Lang, LangMode};
+use super::search::man_pages::{ManError, ManPage};
+use super::search::merge::{self, Source};
+use super::search::offline_docs::{OfflineDoc, OfflineDocsError};
+use super::search::reddit::RedditError;
+use super::search::rust_docs::{Doc, RustDocsError};
+use super::search::se_sites::{self, SeSite};
 use super::search::stackexchange::SeError;
 use super::search::stackoverflow::SofError;
+use super::search::thread::{AnswerSort, Thread};
+use crate::config::ApiSource;
+use crate::content::{Paged, Tagged};
 use clap::Parser;
 use crossterm::event;
 use crossterm::style;
 use crossterm::style::Stylize;
 use hashbrown::HashMap;
 use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::task::JoinHandle;
 
-//
-// CLI Key mapping
-//
-
-// Key press event modifiers
-// On windows even though SHIFT + 1 results in ! we still need SHIFT as the modifier
-// forward results modifiers
-#[cfg(windows)]
-const FORWARD_RESOURCE_MODIFIER: event::KeyModifiers = event::KeyModifiers::SHIFT;
-#[cfg(not(windows))]
-const FORWARD_RESOURCE_MODIFIER: event::KeyModifiers = event::KeyModifiers::NONE;
-// backward results modifiers
-#[cfg(target_os = "macos")]
-const BACKWARD_RESOURCE_MODIFIER: event::KeyModifiers = event::KeyModifiers::NONE;
-#[cfg(not(target_os = "macos"))]
-const BACKWARD_RESOURCE_MODIFIER: event::KeyModifiers = event::KeyModifiers::ALT;
-
-// Keys for accessing the resources
-const ACCESS_RESOURCE_1_CHAR: char = '1';
-const ACCESS_RESOURCE_2_CHAR: char = '2';
-const ACCESS_RESOURCE_3_CHAR: char = '3';
-const ACCESS_RESOURCE_4_CHAR: char = '4';
-const ACCESS_RESOURCE_5_CHAR: char = '5';
-
-// Keys for moving the results forward
-const FORWARD_RESOURCE_1_CHAR: char = '!';
-const FORWARD_RESOURCE_2_CHAR: char = '@';
-const FORWARD_RESOURCE_3_CHAR: char = '#';
-const FORWARD_RESOURCE_4_CHAR: char = '$';
-const FORWARD_RESOURCE_5_CHAR: char = '%';
-
-// Keys for moving results back because on macos using alt with numbers inputs symbols
-// first resource
-#[cfg(target_os = "macos")]
-const BACKWARD_RESOURCE_1_CHAR: char = '¡';
-#[cfg(not(target_os = "macos"))]
-const BACKWARD_RESOURCE_1_CHAR: char = '1';
-// first resource
-#[cfg(target_os = "macos")]
-const BACKWARD_RESOURCE_2_CHAR: char = '™';
-#[cfg(not(target_os = "macos"))]
-const BACKWARD_RESOURCE_2_CHAR: char = '2';
-// first resource
-#[cfg(target_os = "macos")]
-const BACKWARD_RESOURCE_3_CHAR: char = '£';
-#[cfg(not(target_os = "macos"))]
-const BACKWARD_RESOURCE_3_CHAR: char = '3';
-// first resource
-#[cfg(target_os = "macos")]
-const BACKWARD_RESOURCE_4_CHAR: char = '¢';
-#[cfg(not(target_os = "macos"))]
-const BACKWARD_RESOURCE_4_CHAR: char = '4';
-// first resource
-#[cfg(target_os = "macos")]
-const BACKWARD_RESOURCE_5_CHAR: char = '∞';
-#[cfg(not(target_os = "macos"))]
-const BACKWARD_RESOURCE_5_CHAR: char = '5';
+type ThreadHandle<E> = JoinHandle<Result<Thread, E>>;
+type GistHandle = JoinHandle<Result<Gist, GithubGistError>>;
+type ArticleHandle = JoinHandle<Result<Article, GfgError>>;
+type PageHandle = JoinHandle<Result<String, DdgSearchError>>;
+type DocHandle = JoinHandle<Result<Doc, RustDocsError>>;
+type ManHandle = JoinHandle<Result<ManPage, ManError>>;
+type OfflineHandle = JoinHandle<Result<OfflineDoc, OfflineDocsError>>;
+
+// How many resources the main menu has
+const RESOURCES_COUNT: usize = 12;
+// How long to wait for input before checking again if results finished loading
+const LOADING_POLL: Duration = Duration::from_millis(250);
 
 /// Command line options, cli setup done with clap.
 ///
 /// # Options
 ///
-/// query - mandatory, what to search for
+/// query - mandatory, what to search for, or - to extract it from the error output piped to stdin
 /// verbose - optional, enable debug logging to stdout
 /// disable_logs - optional, disable log completely, including writting to files.
+/// config - optional, use another config file than the default one.
+/// mouse - optional, capture the mouse in order to click resources and scroll content.
+/// context - optional, hidden, text to show above the content of every result.
+/// api - optional, sources that should use the StackExchange api instead of scraping.
+/// se_sites - optional, the sites of the StackExchange network to search.
+/// sort - optional, how to order the answers of threads.
+/// min_score - optional, hide the answers with a lower score than this.
+/// max_answers - optional, show at most this many answers per thread.
+/// region - optional, the region of the web search results.
+/// safe_search - optional, how much explicit content the web searches let through.
+/// time_range - optional, how recent the web search results have to be.
+/// exclude_sites - optional, sites the web search results can't be on.
+/// phrases - optional, phrases the web search results have to contain as they are.
+/// file_types - optional, extensions of the files the web search results have to be.
+/// merged - optional, show the results of every resource interleaved in one more row.
+/// lang - optional, the language to narrow down the searches to.
+/// lang_mode - optional, if the web search results are boosted or filtered by the language.
+/// no_lang - optional, don't narrow down the searches to the language of the config file.
+/// no_cargo_lock - optional, don't read the versions of the crates of the project.
+/// generate_completions - optional, hidden, print the completions script for a shell and exit.
+/// generate_man - optional, hidden, print the man page and exit.
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    /// Search query
+    /// Search query, or - to read the error output of a compiler or program from stdin
     pub query: Vec<String>,
 
     /// Turn debugging information on
@@ -100,6 +97,177 @@ pub struct Cli {
     /// Print the keybinds list
     #[arg(short, long)]
     pub keybinds: bool,
+
+    /// Path to the config file to use instead of the default one
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Capture the mouse: click resources, scroll and click to copy blocks of content
+    #[arg(short, long)]
+    pub mouse: bool,
+
+    /// Text to show above the content of every result, used by cargo-falion for the diagnostic
+    #[arg(long, hide = true)]
+    pub context: Option<String>,
+
+    /// Get the content of these sources from the StackExchange api instead of scraping it
+    #[arg(short, long, value_enum, value_delimiter = ',')]
+    pub api: Vec<crate::config::ApiSource>,
+
+    /// Only search these StackExchange sites, by name or host (ex: unix,superuser,serverfault)
+    #[arg(long, value_delimiter = ',', value_parser = parse_se_site)]
+    pub se_sites: Vec<&'static SeSite>,
+
+    /// How to order the answers of StackOverflow and StackExchange threads
+    #[arg(short, long, value_parser = answer_sort_parser())]
+    pub sort: Option<AnswerSort>,
+
+    /// Hide the answers with a lower score than this
+    #[arg(long, allow_negative_numbers = true)]
+    pub min_score: Option<i64>,
+
+    /// Show at most this many answers per thread
+    #[arg(long)]
+    pub max_answers: Option<usize>,
+
+    /// Region of the web search results, as a DuckDuckGo region code (ex: us-en, de-de)
+    #[arg(long)]
+    pub region: Option<String>,
+
+    /// How much explicit content the web searches let through
+    #[arg(long, value_parser = safe_search_parser())]
+    pub safe_search: Option<SafeSearch>,
+
+    /// Only get web search results from the past day, week, month or year
+    #[arg(short, long, value_parser = time_range_parser())]
+    pub time_range: Option<TimeRange>,
+
+    /// Leave out the web search results of these sites (ex: w3schools.com,geeksforgeeks.org)
+    #[arg(long = "exclude-site", value_delimiter = ',')]
+    pub exclude_sites: Vec<String>,
+
+    /// Only get web search results that contain this exact phrase, can be given more than once
+    #[arg(long = "phrase")]
+    pub phrases: Vec<String>,
+
+    /// Only get web search results that are files of these types (ex: pdf,md)
+    #[arg(long = "file-type", value_delimiter = ',')]
+    pub file_types: Vec<String>,
+
+    /// Show the results of every resource interleaved, ranked by search rank, votes and resource
+    #[arg(long)]
+    pub merged: bool,
+
+    /// Narrow down the searches to a language, instead of the one of the project (ex: rust, python, c++)
+    #[arg(short, long, value_parser = parse_lang)]
+    pub lang: Option<&'static Lang>,
+
+    /// Add the language to the web searches (boost) or only keep the results mentioning it (filter)
+    #[arg(long, value_parser = lang_mode_parser())]
+    pub lang_mode: Option<LangMode>,
+
+    /// Don't narrow down the searches to the language of the config file or of the project
+    #[arg(long, conflicts_with = "lang")]
+    pub no_lang: bool,
+
+    /// Don't read the Cargo.lock of the project: no versions added to the queries, no answers flagged
+    #[arg(long)]
+    pub no_cargo_lock: bool,
+
+    /// Print the completions script for the given shell, used by the packages
+    #[arg(long, value_name = "SHELL", hide = true)]
+    pub generate_completions: Option<clap_complete::Shell>,
+
+    /// Print the man page, used by the packages
+    #[arg(long, hide = true)]
+    pub generate_man: bool,
+}
+
+/// Parse the name of an answer sort, the names are listed in the help and the completions.
+fn answer_sort_parser() -> impl clap::builder::TypedValueParser<Value = AnswerSort> {
+    use clap::builder::TypedValueParser;
+
+    clap::builder::PossibleValuesParser::new(AnswerSort::ALL.map(|sort| sort.name()))
+        // the possible values are the names of the sorts, so parsing them can't fail
+        .map(|name| name.parse::<AnswerSort>().unwrap_or_default())
+}
+
+/// Parse the name of a safe search level, the names are listed in the help and the completions.
+fn safe_search_parser() -> impl clap::builder::TypedValueParser<Value = SafeSearch> {
+    use clap::builder::TypedValueParser;
+
+    clap::builder::PossibleValuesParser::new(SafeSearch::ALL.map(|level| level.name()))
+        // the possible values are the names of the levels, so parsing them can't fail
+        .map(|name| name.parse::<SafeSearch>().unwrap_or(SafeSearch::Moderate))
+}
+
+/// Parse the name of a time range, the names are listed in the help and the completions.
+fn time_range_parser() -> impl clap::builder::TypedValueParser<Value = TimeRange> {
+    use clap::builder::TypedValueParser;
+
+    clap::builder::PossibleValuesParser::new(TimeRange::ALL.map(|range| range.name()))
+        // the possible values are the names of the ranges, so parsing them can't fail
+        .map(|name| name.parse::<TimeRange>().unwrap_or(TimeRange::Year))
+}
+
+/// Parse the name of a language mode, the names are listed in the help and the completions.
+fn lang_mode_parser() -> impl clap::builder::TypedValueParser<Value = LangMode> {
+    use clap::builder::TypedValueParser;
+
+    clap::builder::PossibleValuesParser::new(LangMode::ALL.map(|mode| mode.name()))
+        // the possible values are the names of the modes, so parsing them can't fail
+        .map(|name| name.parse::<LangMode>().unwrap_or_default())
+}
+
+/// Parse the name or an alias of a language.
+fn parse_lang(name: &str) -> Result<&'static Lang, String> {
+    lang::find(name).ok_or_else(|| {
+        format!(
+            "{} is not a known language. Ex of languages: rust, python, javascript, go, c++.",
+            name
+        )
+    })
+}
+
+/// Parse the name or the host of a site of the StackExchange network.
+fn parse_se_site(name: &str) -> Result<&'static SeSite, String> {
+    se_sites::find(name).ok_or_else(|| {
+        format!(
+            "{} is not a StackExchange site. Ex of sites: unix, superuser, serverfault, askubuntu.",
+            name
+        )
+    })
+}
+
+/// Generate the completions or the man page if the command line options ask for them. This is
+/// done before deciding between the cli and the gui, so the output can be redirected to a file.
+/// They're options instead of subcommands so queries starting with "man" or "completions" are
+/// searched as they are.
+///
+/// # Returns
+///
+/// true if something was generated and falion should exit, false otherwise.
+#[tracing::instrument(skip_all)]
+pub fn run_command() -> bool {
+    // invalid options are reported later by the cli, where they're actually parsed
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(_) => return false,
+    };
+
+    let mut stdout = std::io::stdout();
+    if let Some(shell) = cli.generate_completions {
+        generate::write_completions(shell, &mut stdout);
+        return true;
+    }
+    if cli.generate_man {
+        if let Err(error) = generate::write_man_page(&mut stdout) {
+            eprintln!("Failed to write the man page. Error: {}", error);
+        }
+        return true;
+    }
+
+    false
 }
 
 /// The main cli function for falion. Show results and lets you scroll through them.
@@ -117,6 +285,20 @@ pub struct Cli {
 /// passed entierly (no reference)
 /// `ddg_search_results` - ddg results which should be got in the main function and
 /// passed entierly (no reference)
+/// `rust_docs_results` - rust documentation results which should be got in the main function and
+/// passed entierly (no reference)
+/// `man_pages_results` - man and tldr pages results which should be got in the main function and
+/// passed entierly (no reference)
+/// `offline_docs_results` - offline documentation results which should be got in the main
+/// function and passed entierly (no reference)
+/// `github_issues_results` - github issues and discussions results which should be got in the main
+/// function and passed entierly (no reference)
+/// `discourse_results` - discourse forums results which should be got in the main function and
+/// passed entierly (no reference)
+/// `reddit_results` - reddit results which should be got in the main function and passed
+/// entierly (no reference)
+/// `hacker_news_results` - hacker news results which should be got in the main function and
+/// passed entierly (no reference)
 #[tracing::instrument(skip_all)]
 pub async fn cli() {
     tracing::info!("User chose the cli.");
@@ -125,8 +307,29 @@ pub async fn cli() {
 
     // get results
     // setup cli and get query
-    let query = match util::setup_cli() {
-        Ok(query) => query,
+    let util::Setup {
+        query,
+        keymap,
+        mouse,
+        context,
+        api_key,
+        github_token,
+        api_sources,
+        se_sites,
+        forums,
+        subreddits,
+        hn_endpoint,
+        engines,
+        search_options,
+        answer_options,
+        merged,
+        lang,
+        lang_mode,
+        cargo_lock,
+        man_pages,
+        offline_docs,
+    } = match util::setup_cli() {
+        Ok(setup) => setup,
         Err(err) => match err.kind() {
             std::io::ErrorKind::Other => {
                 tracing::info!("User chose to run the gui from the cli.");
@@ -139,6 +342,10 @@ pub async fn cli() {
                 );
                 return;
             }
+            std::io::ErrorKind::InvalidData | std::io::ErrorKind::InvalidInput => {
+                eprintln!("{}", err);
+                return;
+            }
             _ => return,
         },
     };
@@ -148,18 +355,79 @@ pub async fn cli() {
 
     // Make objects
     let client = search::util::client_with_special_settings();
-    let stackoverflow = search::stackoverflow::StackOverflow::with_client(client.clone());
-    let stackexchange = search::stackexchange::StackExchange::with_client(client.clone());
-    let github_gist = search::github_gist::GithubGist::with_client(client.clone());
-    let geeksforgeeks = search::geeksforgeeks::GeeksForGeeks::with_client(client.clone());
-    let ddg_search = search::ddg_search::DdgSearch::with_client(client.clone());
+    let api = search::stackexchange_api::StackExchangeApi::with_client(client.clone(), api_key);
+    let stackoverflow = if api_sources.contains(&ApiSource::Stackoverflow) {
+        search::stackoverflow::StackOverflow::with_api(client.clone(), api.clone())
+    } else {
+        search::stackoverflow::StackOverflow::with_client(client.clone())
+    };
+    let stackexchange = if api_sources.contains(&ApiSource::Stackexchange) {
+        search::stackexchange::StackExchange::with_api(client.clone(), api.clone())
+    } else {
+        search::stackexchange::StackExchange::with_client(client.clone())
+    }
+    .with_sites(se_sites)
+    .with_lang(lang)
+    .with_engines(engines.clone())
+    .with_options(search_options.clone());
+    let stackoverflow = stackoverflow
+        .with_lang(lang)
+        .with_engines(engines.clone())
+        .with_options(search_options.clone());
+    let github_gist = search::github_gist::GithubGist::with_client(client.clone())
+        .with_lang(lang)
+        .with_engines(engines.clone())
+        .with_options(search_options.clone());
+    let geeksforgeeks = search::geeksforgeeks::GeeksForGeeks::with_client(client.clone())
+        .with_engines(engines.clone())
+        .with_options(search_options.clone());
+    let ddg_search = search::ddg_search::DdgSearch::with_client(client.clone())
+        .with_lang(lang, lang_mode)
+        .with_engines(engines.clone())
+        .with_options(search_options.clone());
+    let rust_docs = search::rust_docs::RustDocs::with_client(client.clone())
+        .with_engines(engines.clone())
+        .with_options(search_options.clone());
+    let github_issues =
+        search::github_issues::GithubIssues::with_token(client.clone(), github_token)
+            .with_engines(engines.clone())
+            .with_options(search_options.clone());
+    let discourse = search::discourse::Discourse::with_client(client.clone())
+        .with_forums(forums)
+        .with_engines(engines.clone())
+        .with_options(search_options.clone());
+    let reddit = search::reddit::Reddit::with_client(client.clone())
+        .with_subreddits(subreddits)
+        .with_engines(engines)
+        .with_options(search_options);
+    let hacker_news = match &hn_endpoint {
+        Some(endpoint) => {
+            search::hacker_news::HackerNews::with_client(client.clone()).with_endpoint(endpoint)
+        }
+        None => search::hacker_news::HackerNews::with_client(client.clone()),
+    };
+
+    // the web searches get the versions of the crates of the project, the local ones don't
+    let search_query = match &cargo_lock {
+        Some(cargo_lock) => cargo_lock.annotate(&query),
+        None => query.clone(),
+    };
 
     // Get results
-    let stackoverflow_results = stackoverflow.get_multiple_questions_content(&query, Some(5));
-    let stackexchange_results = stackexchange.get_multiple_questions_content(&query, Some(5));
-    let github_gist_results = github_gist.get_multiple_gists_content(&query, Some(5));
-    let geeksforgeeks_results = geeksforgeeks.get_multiple_pages_content(&query, Some(5));
-    let ddg_search_results = ddg_search.get_multiple_pages_content(&query, Some(5));
+    let stackoverflow_results =
+        stackoverflow.get_multiple_questions_content(&search_query, Some(5));
+    let stackexchange_results =
+        stackexchange.get_multiple_questions_content(&search_query, Some(5));
+    let github_gist_results = github_gist.get_multiple_gists_content(&search_query, Some(5));
+    let geeksforgeeks_results = geeksforgeeks.get_multiple_pages_content(&search_query, Some(5));
+    let ddg_search_results = ddg_search.get_multiple_pages_content(&search_query, Some(5));
+    let rust_docs_results = rust_docs.get_multiple_docs_content(&search_query, Some(5));
+    let man_pages_results = man_pages.get_multiple_pages_content(&query, Some(5));
+    let offline_docs_results = offline_docs.get_multiple_docs_content(&query, Some(5));
+    let github_issues_results = github_issues.get_multiple_issues_content(&search_query, Some(5));
+    let discourse_results = discourse.get_multiple_topics_content(&search_query, Some(5));
+    let reddit_results = reddit.get_multiple_posts_content(&search_query, Some(5));
+    let hacker_news_results = hacker_news.get_multiple_stories_content(&query, Some(5));
 
     // await all results at the same time
     let results_awaited = futures::join!(
@@ -167,7 +435,14 @@ pub async fn cli() {
         stackexchange_results,
         github_gist_results,
         geeksforgeeks_results,
-        ddg_search_results
+        ddg_search_results,
+        rust_docs_results,
+        man_pages_results,
+        offline_docs_results,
+        github_issues_results,
+        discourse_results,
+        reddit_results,
+        hacker_news_results
     );
 
     // transfer the awaited futures back
@@ -176,6 +451,67 @@ pub async fn cli() {
     let mut github_gist_results = results_awaited.2;
     let mut geeksforgeeks_results = results_awaited.3;
     let mut ddg_search_results = results_awaited.4;
+    let mut rust_docs_results = results_awaited.5;
+    let mut man_pages_results = results_awaited.6;
+    let mut offline_docs_results = results_awaited.7;
+    let mut github_issues_results = results_awaited.8;
+    let mut discourse_results = results_awaited.9;
+    let mut reddit_results = results_awaited.10;
+    let mut hacker_news_results = results_awaited.11;
+
+    // the same page is often found by more than one resource, keep it only in the first one so
+    // the web search is left with what the other resources didn't find
+    let mut seen = std::collections::HashSet::new();
+    for source in Source::DEDUP_ORDER {
+        match source {
+            Source::StackOverflow => {
+                merge::dedup_results(&mut stackoverflow_results, &mut seen, || {
+                    SofError::DdgError(DdgError::Duplicates)
+                })
+            }
+            Source::StackExchange => {
+                merge::dedup_results(&mut stackexchange_results, &mut seen, || {
+                    SeError::DdgError(DdgError::Duplicates)
+                })
+            }
+            Source::GithubGist => merge::dedup_results(&mut github_gist_results, &mut seen, || {
+                GithubGistError::DdgError(DdgError::Duplicates)
+            }),
+            Source::GeeksForGeeks => {
+                merge::dedup_results(&mut geeksforgeeks_results, &mut seen, || {
+                    GfgError::DdgError(DdgError::Duplicates)
+                })
+            }
+            Source::RustDocs => merge::dedup_results(&mut rust_docs_results, &mut seen, || {
+                RustDocsError::DdgError(DdgError::Duplicates)
+            }),
+            Source::ManPages => merge::dedup_results(&mut man_pages_results, &mut seen, || {
+                ManError::NoResults(query.clone())
+            }),
+            Source::OfflineDocs => {
+                merge::dedup_results(&mut offline_docs_results, &mut seen, || {
+                    OfflineDocsError::NoResults(query.clone())
+                })
+            }
+            Source::GithubIssues => {
+                merge::dedup_results(&mut github_issues_results, &mut seen, || {
+                    GithubIssuesError::DdgError(DdgError::Duplicates)
+                })
+            }
+            Source::Discourse => merge::dedup_results(&mut discourse_results, &mut seen, || {
+                DiscourseError::DdgError(DdgError::Duplicates)
+            }),
+            Source::Reddit => merge::dedup_results(&mut reddit_results, &mut seen, || {
+                RedditError::DdgError(DdgError::Duplicates)
+            }),
+            Source::HackerNews => merge::dedup_results(&mut hacker_news_results, &mut seen, || {
+                HackerNewsError::NoResults(query.clone())
+            }),
+            Source::DdgSearch => merge::dedup_results(&mut ddg_search_results, &mut seen, || {
+                DdgSearchError::DdgError(DdgError::Duplicates)
+            }),
+        }
+    }
 
     // hide the cursor
     if let Err(error) = crossterm::execute!(&mut stdout, crossterm::cursor::Hide) {
@@ -183,24 +519,78 @@ pub async fn cli() {
     };
 
     // create vars
-    let mut stackoverflow_results_awaited: HashMap<String, Vec<String>> = HashMap::with_capacity(5);
+    let mut stackoverflow_results_awaited: HashMap<String, Thread> = HashMap::with_capacity(5);
     let mut stackoverflow_index = 0;
-    let mut stackexchange_results_awaited: HashMap<String, Vec<String>> = HashMap::with_capacity(5);
+    let mut stackexchange_results_awaited: HashMap<String, Thread> = HashMap::with_capacity(5);
     let mut stackexchange_index = 0;
-    let mut github_gist_results_awaited: HashMap<String, Vec<String>> = HashMap::with_capacity(5);
+    let mut github_gist_results_awaited: HashMap<String, Gist> = HashMap::with_capacity(5);
     let mut github_gist_index = 0;
-    let mut geeksforgeeks_results_awaited: HashMap<String, String> = HashMap::with_capacity(5);
+    let mut geeksforgeeks_results_awaited: HashMap<String, Article> = HashMap::with_capacity(5);
     let mut geeksforgeeks_index = 0;
     let mut ddg_search_results_awaited: HashMap<String, String> = HashMap::with_capacity(5);
     let mut ddg_search_index = 0;
+    let mut rust_docs_results_awaited: HashMap<String, Doc> = HashMap::with_capacity(5);
+    let mut rust_docs_index = 0;
+    let mut man_pages_results_awaited: HashMap<String, ManPage> = HashMap::with_capacity(5);
+    let mut man_pages_index = 0;
+    let mut offline_docs_results_awaited: HashMap<String, OfflineDoc> = HashMap::with_capacity(5);
+    let mut offline_docs_index = 0;
+    let mut github_issues_results_awaited: HashMap<String, Thread> = HashMap::with_capacity(5);
+    let mut github_issues_index = 0;
+    let mut discourse_results_awaited: HashMap<String, Thread> = HashMap::with_capacity(5);
+    let mut discourse_index = 0;
+    let mut reddit_results_awaited: HashMap<String, Thread> = HashMap::with_capacity(5);
+    let mut reddit_index = 0;
+    let mut hacker_news_results_awaited: HashMap<String, Thread> = HashMap::with_capacity(5);
+    let mut hacker_news_index = 0;
+    let mut merged_index = 0;
     // actual cli
     // reusable prints
-    let query_print = format!("{} {}", "Your search query is:".green(), query.blue());
+    let mut query_print = format!(
+        "{} {}",
+        "Your search query is:".green(),
+        query.as_str().blue()
+    );
+    // show the language the searches were narrowed down to
+    if let Some(lang) = lang {
+        query_print.push_str(&format!(" {} {}", "| Language:".green(), lang.title.blue()));
+    }
+    // show the versions of the crates the query is about
+    if let Some(cargo_lock) = &cargo_lock {
+        let crates = cargo_lock
+            .crates_in(&query)
+            .iter()
+            .map(|locked| format!("{} {}", locked.name, locked.version))
+            .collect::<Vec<_>>();
+        if !crates.is_empty() {
+            query_print.push_str(&format!(
+                " {} {}",
+                "| Cargo.lock:".green(),
+                crates.join(", ").blue()
+            ));
+        }
+    }
+    // report the quota left if the api was used
+    if let Some(quota) = api.quota() {
+        query_print.push_str(&format!(
+            " {} {}",
+            "| StackExchange api quota:".green(),
+            format!("{}/{}", quota.remaining, quota.max).blue()
+        ));
+    }
     let sof_print = format!("{} {} ", "(1)".green(), "[  StackOverFlow  ]".yellow());
     let se_print = format!("{} {} ", "(2)".green(), "[  StackExchange  ]".yellow());
     let gg_print = format!("{} {} ", "(3)".green(), "[   Github Gist   ]".yellow());
     let gfg_print = format!("{} {} ", "(4)".green(), "[  GeeksForGeeks  ]".yellow());
     let ddg_print = format!("{} {} ", "(5)".green(), "[DuckDuckGo Search]".yellow());
+    let docs_print = format!("{} {} ", "(6)".green(), "[    Rust Docs    ]".yellow());
+    let man_print = format!("{} {} ", "(7)".green(), "[    Man Pages    ]".yellow());
+    let offline_print = format!("{} {} ", "(8)".green(), "[  Offline Docs   ]".yellow());
+    let issues_print = format!("{} {} ", "(9)".green(), "[  GitHub Issues  ]".yellow());
+    let discourse_print = format!("{} {} ", "(0)".green(), "[    Discourse    ]".yellow());
+    let reddit_print = format!("{} {} ", "(r)".green(), "[     Reddit      ]".yellow());
+    let hn_print = format!("{} {} ", "(h)".green(), "[   Hacker News   ]".yellow());
+    let merged_print = format!("{} {} ", "(m)".green(), "[     Merged      ]".yellow());
     // clear terminal
     util::clear_terminal(&mut stdout);
 
@@ -212,6 +602,42 @@ pub async fn cli() {
         let github_gist_results_ref = &mut github_gist_results;
         let geeksforgeeks_results_ref = &mut geeksforgeeks_results;
         let ddg_search_results_ref = &mut ddg_search_results;
+        let rust_docs_results_ref = &mut rust_docs_results;
+        let man_pages_results_ref = &mut man_pages_results;
+        let offline_docs_results_ref = &mut offline_docs_results;
+        let github_issues_results_ref = &mut github_issues_results;
+        let discourse_results_ref = &mut discourse_results;
+        let reddit_results_ref = &mut reddit_results;
+        let hacker_news_results_ref = &mut hacker_news_results;
+        // list the results that finished loading under their title with their metadata
+        let mut loading = load_all_finished(
+            &mut stackoverflow_results_awaited,
+            stackoverflow_results_ref,
+            &mut stackexchange_results_awaited,
+            stackexchange_results_ref,
+            &mut github_gist_results_awaited,
+            github_gist_results_ref,
+            &mut geeksforgeeks_results_awaited,
+            geeksforgeeks_results_ref,
+            &mut ddg_search_results_awaited,
+            ddg_search_results_ref,
+            &mut rust_docs_results_awaited,
+            rust_docs_results_ref,
+            &mut man_pages_results_awaited,
+            man_pages_results_ref,
+            &mut offline_docs_results_awaited,
+            offline_docs_results_ref,
+            &mut github_issues_results_awaited,
+            github_issues_results_ref,
+            &mut discourse_results_awaited,
+            discourse_results_ref,
+            &mut reddit_results_awaited,
+            reddit_results_ref,
+            &mut hacker_news_results_awaited,
+            hacker_news_results_ref,
+        )
+        .await
+        .1;
         // display query
         if let Err(error) = crossterm::queue!(
             &mut stdout,
@@ -221,37 +647,168 @@ pub async fn cli() {
             tracing::warn!("There was an error printing some text. Error: {}", error);
         };
 
-        // display resources
-        print::print_resource::<Vec<String>, SofError>(
+        // display resources, keeping track of the rows they take in order to know which one is
+        // clicked
+        let mut row = mouse::rows_taken(&query_print);
+        let mut resource_rows = Vec::with_capacity(RESOURCES_COUNT);
+        let rows = print::print_resource::<Thread, SofError>(
             &mut stdout,
             stackoverflow_index,
             &sof_print,
             stackoverflow_results_ref,
+            &stackoverflow_results_awaited,
         );
-        print::print_resource::<Vec<String>, SeError>(
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Thread, SeError>(
             &mut stdout,
             stackexchange_index,
             &se_print,
             stackexchange_results_ref,
+            &stackexchange_results_awaited,
         );
-        print::print_resource::<Vec<String>, GithubGistError>(
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Gist, GithubGistError>(
             &mut stdout,
             github_gist_index,
             &gg_print,
             github_gist_results_ref,
+            &github_gist_results_awaited,
         );
-        print::print_resource::<String, GfgError>(
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Article, GfgError>(
             &mut stdout,
             geeksforgeeks_index,
             &gfg_print,
             geeksforgeeks_results_ref,
+            &geeksforgeeks_results_awaited,
         );
-        print::print_resource::<String, DdgSearchError>(
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<String, DdgSearchError>(
             &mut stdout,
             ddg_search_index,
             &ddg_print,
             ddg_search_results_ref,
+            &ddg_search_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Doc, RustDocsError>(
+            &mut stdout,
+            rust_docs_index,
+            &docs_print,
+            rust_docs_results_ref,
+            &rust_docs_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<ManPage, ManError>(
+            &mut stdout,
+            man_pages_index,
+            &man_print,
+            man_pages_results_ref,
+            &man_pages_results_awaited,
         );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<OfflineDoc, OfflineDocsError>(
+            &mut stdout,
+            offline_docs_index,
+            &offline_print,
+            offline_docs_results_ref,
+            &offline_docs_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Thread, GithubIssuesError>(
+            &mut stdout,
+            github_issues_index,
+            &issues_print,
+            github_issues_results_ref,
+            &github_issues_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Thread, DiscourseError>(
+            &mut stdout,
+            discourse_index,
+            &discourse_print,
+            discourse_results_ref,
+            &discourse_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Thread, RedditError>(
+            &mut stdout,
+            reddit_index,
+            &reddit_print,
+            reddit_results_ref,
+            &reddit_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        row += rows;
+        let rows = print::print_resource::<Thread, HackerNewsError>(
+            &mut stdout,
+            hacker_news_index,
+            &hn_print,
+            hacker_news_results_ref,
+            &hacker_news_results_awaited,
+        );
+        resource_rows.push((row, row + rows));
+        // the merged view is listed after the resources, recomputed every time since the votes
+        // of the results are known once they loaded
+        let mut merged_results = Vec::new();
+        let mut merged_rows = None;
+        if let Some(weights) = &merged {
+            let results = [
+                merge::merged_results(Source::StackOverflow, stackoverflow_results_ref, |hit| {
+                    content::votes(hit, &stackoverflow_results_awaited)
+                }),
+                merge::merged_results(Source::StackExchange, stackexchange_results_ref, |hit| {
+                    content::votes(hit, &stackexchange_results_awaited)
+                }),
+                merge::merged_results(Source::GithubGist, github_gist_results_ref, |hit| {
+                    content::votes(hit, &github_gist_results_awaited)
+                }),
+                merge::merged_results(Source::GeeksForGeeks, geeksforgeeks_results_ref, |hit| {
+                    content::votes(hit, &geeksforgeeks_results_awaited)
+                }),
+                merge::merged_results(Source::DdgSearch, ddg_search_results_ref, |hit| {
+                    content::votes(hit, &ddg_search_results_awaited)
+                }),
+                merge::merged_results(Source::RustDocs, rust_docs_results_ref, |hit| {
+                    content::votes(hit, &rust_docs_results_awaited)
+                }),
+                merge::merged_results(Source::ManPages, man_pages_results_ref, |hit| {
+                    content::votes(hit, &man_pages_results_awaited)
+                }),
+                merge::merged_results(Source::OfflineDocs, offline_docs_results_ref, |hit| {
+                    content::votes(hit, &offline_docs_results_awaited)
+                }),
+                merge::merged_results(Source::GithubIssues, github_issues_results_ref, |hit| {
+                    content::votes(hit, &github_issues_results_awaited)
+                }),
+                merge::merged_results(Source::Discourse, discourse_results_ref, |hit| {
+                    content::votes(hit, &discourse_results_awaited)
+                }),
+                merge::merged_results(Source::Reddit, reddit_results_ref, |hit| {
+                    content::votes(hit, &reddit_results_awaited)
+                }),
+                merge::merged_results(Source::HackerNews, hacker_news_results_ref, |hit| {
+                    content::votes(hit, &hacker_news_results_awaited)
+                }),
+            ]
+            .concat();
+            merged_results = merge::rank(results, weights);
+            merged_index = merged_index.min(merged_results.len().saturating_sub(1));
+            let merged_row = row + rows;
+            let rows =
+                print::print_merged(&mut stdout, merged_index, &merged_print, &merged_results);
+            merged_rows = Some((merged_row, merged_row + rows));
+        }
 
         // flush in order to print content
         if let Err(error) = stdout.flush() {
@@ -261,6 +818,48 @@ pub async fn cli() {
             );
         }
 
+        // while results are loading only wait for input for a while, so the results that finish
+        // loading in the meantime are redrawn with their metadata
+        let mut renamed = false;
+        while loading && !renamed {
+            match event::poll(LOADING_POLL) {
+                Ok(false) => {
+                    (renamed, loading) = load_all_finished(
+                        &mut stackoverflow_results_awaited,
+                        stackoverflow_results_ref,
+                        &mut stackexchange_results_awaited,
+                        stackexchange_results_ref,
+                        &mut github_gist_results_awaited,
+                        github_gist_results_ref,
+                        &mut geeksforgeeks_results_awaited,
+                        geeksforgeeks_results_ref,
+                        &mut ddg_search_results_awaited,
+                        ddg_search_results_ref,
+                        &mut rust_docs_results_awaited,
+                        rust_docs_results_ref,
+                        &mut man_pages_results_awaited,
+                        man_pages_results_ref,
+                        &mut offline_docs_results_awaited,
+                        offline_docs_results_ref,
+                        &mut github_issues_results_awaited,
+                        github_issues_results_ref,
+                        &mut discourse_results_awaited,
+                        discourse_results_ref,
+                        &mut reddit_results_awaited,
+                        reddit_results_ref,
+                        &mut hacker_news_results_awaited,
+                        hacker_news_results_ref,
+                    )
+                    .await;
+                }
+                _ => break,
+            }
+        }
+        if renamed {
+            util::clear_terminal(&mut stdout);
+            continue;
+        }
+
         // listen for key presses
         let event_read = match event::read() {
             Ok(ev) => ev,
@@ -270,20 +869,59 @@ pub async fn cli() {
             }
         };
 
-        // matching the pressed key
-        match event_read {
+        // get the action bound to the pressed key or mouse event
+        let action = match event_read {
+            event::Event::Key(key_event) if key_event.kind == event::KeyEventKind::Press => {
+                keymap.main_action(&key_event)
+            }
+            event::Event::Mouse(mouse_event) if mouse => {
+                mouse::main_action(&mouse_event, &resource_rows, merged_rows)
+            }
+            _ => None,
+        };
+
+        // accessing a result of the merged view moves its resource to it and accesses that
+        let action = match action {
+            Some(keymap::Action::AccessMerged) => match merged_results.get(merged_index) {
+                Some(result) => {
+                    tracing::info!(
+                        "Accessing the merged result at index: {} from {}",
+                        merged_index,
+                        result.source
+                    );
+                    match result.source {
+                        Source::StackOverflow => stackoverflow_index = result.index,
+                        Source::StackExchange => stackexchange_index = result.index,
+                        Source::GithubGist => github_gist_index = result.index,
+                        Source::GeeksForGeeks => geeksforgeeks_index = result.index,
+                        Source::DdgSearch => ddg_search_index = result.index,
+                        Source::RustDocs => rust_docs_index = result.index,
+                        Source::ManPages => man_pages_index = result.index,
+                        Source::OfflineDocs => offline_docs_index = result.index,
+                        Source::GithubIssues => github_issues_index = result.index,
+                        Source::Discourse => discourse_index = result.index,
+                        Source::Reddit => reddit_index = result.index,
+                        Source::HackerNews => hacker_news_index = result.index,
+                    }
+                    Source::ALL
+                        .iter()
+                        .position(|source| *source == result.source)
+                        .map(|resource| keymap::Action::AccessResource(resource + 1))
+                }
+                None => None,
+            },
+            action => action,
+        };
+
+        // matching the action
+        match action {
             // enter the menu for first resource
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(ACCESS_RESOURCE_1_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(keymap::Action::AccessResource(1)) => {
                 tracing::info!(
                     "Accessing content for resource 1 at index: {}",
                     stackoverflow_index
                 );
-                match content::get_dyn_result_content(
+                match content::get_result_content(
                     stackoverflow_results_ref,
                     &mut stackoverflow_results_awaited,
                     stackoverflow_index,
@@ -292,7 +930,13 @@ pub async fn cli() {
                 {
                     Some(content) => {
                         util::clear_terminal(&mut stdout);
-                        if print::print_dyn_content(&mut stdout, content, true) {
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
                             util::clean(&mut stdout);
                             return;
                         }
@@ -304,13 +948,8 @@ pub async fn cli() {
                     }
                 }
             }
-            // go to next element in the first resource (using ! because of terminal limitations)
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(FORWARD_RESOURCE_1_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: FORWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            // go to next element in the first resource
+            Some(keymap::Action::NextResource(1)) => {
                 // stackoverflow next result
                 match &stackoverflow_results {
                     Ok(res) => {
@@ -322,30 +961,20 @@ pub async fn cli() {
                     Err(_) => (),
                 }
             }
-            // go to the previous element in the first resource (using alt instead of ctrl because of terminal limitations)
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(BACKWARD_RESOURCE_1_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: BACKWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            // go to the previous element in the first resource
+            Some(keymap::Action::PreviousResource(1)) => {
                 // stackoverflow back results by one
                 stackoverflow_index = stackoverflow_index.saturating_sub(1);
             }
 
             // enter the menu of the second resource
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(ACCESS_RESOURCE_2_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(keymap::Action::AccessResource(2)) => {
                 tracing::info!(
                     "Accessing content for resource 2 at index: {}",
                     stackexchange_index
                 );
                 // stackexchange current result content
-                match content::get_dyn_result_content(
+                match content::get_result_content(
                     stackexchange_results_ref,
                     &mut stackexchange_results_awaited,
                     stackexchange_index,
@@ -354,7 +983,13 @@ pub async fn cli() {
                 {
                     Some(content) => {
                         util::clear_terminal(&mut stdout);
-                        if print::print_dyn_content(&mut stdout, content, true) {
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
                             util::clean(&mut stdout);
                             return;
                         }
@@ -367,12 +1002,7 @@ pub async fn cli() {
                 }
             }
             // go to the next element in the second resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(FORWARD_RESOURCE_2_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: FORWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::NextResource(2)) => {
                 // stackexchange next result
                 match stackexchange_results_ref {
                     Ok(res) => {
@@ -385,29 +1015,19 @@ pub async fn cli() {
                 }
             }
             // go to previous element in the second resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(BACKWARD_RESOURCE_2_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: BACKWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::PreviousResource(2)) => {
                 // stackexchange back results by one
                 stackexchange_index = stackexchange_index.saturating_sub(1);
             }
 
             // enter the menu for the third resource
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(ACCESS_RESOURCE_3_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(keymap::Action::AccessResource(3)) => {
                 tracing::info!(
                     "Accessing content for resource 3 at index: {}",
                     github_gist_index
                 );
                 // github_gist show current result content
-                match content::get_dyn_result_content(
+                match content::get_result_content(
                     github_gist_results_ref,
                     &mut github_gist_results_awaited,
                     github_gist_index,
@@ -416,7 +1036,13 @@ pub async fn cli() {
                 {
                     Some(content) => {
                         util::clear_terminal(&mut stdout);
-                        if print::print_dyn_content(&mut stdout, content, false) {
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
                             util::clean(&mut stdout);
                             return;
                         }
@@ -429,12 +1055,7 @@ pub async fn cli() {
                 }
             }
             // go to the next element in the third resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(FORWARD_RESOURCE_3_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: FORWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::NextResource(3)) => {
                 // github gist next result
                 match github_gist_results_ref {
                     Ok(res) => {
@@ -447,29 +1068,19 @@ pub async fn cli() {
                 }
             }
             // go to the previous element in the third resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(BACKWARD_RESOURCE_3_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: BACKWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::PreviousResource(3)) => {
                 // github gist back results by one
                 github_gist_index = github_gist_index.saturating_sub(1);
             }
 
             // enter the forth resource menu
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(ACCESS_RESOURCE_4_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(keymap::Action::AccessResource(4)) => {
                 tracing::info!(
                     "Accessing content for resource 4 at index: {}",
                     geeksforgeeks_index
                 );
                 // geeksforgeeks show content for current result
-                match content::get_static_result_content(
+                match content::get_result_content(
                     geeksforgeeks_results_ref,
                     &mut geeksforgeeks_results_awaited,
                     geeksforgeeks_index,
@@ -478,7 +1089,13 @@ pub async fn cli() {
                 {
                     Some(content) => {
                         util::clear_terminal(&mut stdout);
-                        if print::print_static_content(&mut stdout, content) {
+                        if print::print_static_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            content.text(),
+                        ) {
                             util::clean(&mut stdout);
                             return;
                         }
@@ -491,12 +1108,7 @@ pub async fn cli() {
                 }
             }
             // go to the next element in the forth resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(FORWARD_RESOURCE_4_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: FORWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::NextResource(4)) => {
                 // geeksforgeeks next result
                 match geeksforgeeks_results_ref {
                     Ok(res) => {
@@ -509,29 +1121,19 @@ pub async fn cli() {
                 }
             }
             // go to the previous element in the forth resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(BACKWARD_RESOURCE_4_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: BACKWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::PreviousResource(4)) => {
                 // geeksforgeeks back results by one
                 geeksforgeeks_index = geeksforgeeks_index.saturating_sub(1);
             }
 
             // enter the fifth resource menu
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(ACCESS_RESOURCE_5_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(keymap::Action::AccessResource(5)) => {
                 tracing::info!(
                     "Accessing content for resource 5 at index: {}",
                     ddg_search_index
                 );
                 // ddg search show content for current result
-                match content::get_static_result_content(
+                match content::get_result_content(
                     ddg_search_results_ref,
                     &mut ddg_search_results_awaited,
                     ddg_search_index,
@@ -540,7 +1142,13 @@ pub async fn cli() {
                 {
                     Some(content) => {
                         util::clear_terminal(&mut stdout);
-                        if print::print_static_content(&mut stdout, content) {
+                        if print::print_static_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            content.text(),
+                        ) {
                             util::clean(&mut stdout);
                             return;
                         }
@@ -553,12 +1161,7 @@ pub async fn cli() {
                 }
             }
             // go to the next element in the fifth resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(FORWARD_RESOURCE_5_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: FORWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::NextResource(5)) => {
                 // ddg search next result
                 match ddg_search_results_ref {
                     Ok(res) => {
@@ -571,23 +1174,384 @@ pub async fn cli() {
                 }
             }
             // go to the previous element in the fifth resource list
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char(BACKWARD_RESOURCE_5_CHAR),
-                kind: event::KeyEventKind::Press,
-                modifiers: BACKWARD_RESOURCE_MODIFIER,
-                ..
-            }) => {
+            Some(keymap::Action::PreviousResource(5)) => {
                 // ddg search back results by one
                 ddg_search_index = ddg_search_index.saturating_sub(1);
             }
 
+            // enter the sixth resource menu
+            Some(keymap::Action::AccessResource(6)) => {
+                tracing::info!(
+                    "Accessing content for resource 6 at index: {}",
+                    rust_docs_index
+                );
+                // rust docs show current result content
+                match content::get_result_content(
+                    rust_docs_results_ref,
+                    &mut rust_docs_results_awaited,
+                    rust_docs_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing rust docs which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the sixth resource list
+            Some(keymap::Action::NextResource(6)) => {
+                // rust docs next result
+                match rust_docs_results_ref {
+                    Ok(res) => {
+                        if rust_docs_index < res.len() - 1 {
+                            rust_docs_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the sixth resource list
+            Some(keymap::Action::PreviousResource(6)) => {
+                // rust docs back results by one
+                rust_docs_index = rust_docs_index.saturating_sub(1);
+            }
+
+            // enter the seventh resource menu
+            Some(keymap::Action::AccessResource(7)) => {
+                tracing::info!(
+                    "Accessing content for resource 7 at index: {}",
+                    man_pages_index
+                );
+                // man pages show content for current result
+                match content::get_result_content(
+                    man_pages_results_ref,
+                    &mut man_pages_results_awaited,
+                    man_pages_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_static_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            content.text(),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing man pages which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the seventh resource list
+            Some(keymap::Action::NextResource(7)) => {
+                // man pages next result
+                match man_pages_results_ref {
+                    Ok(res) => {
+                        if man_pages_index < res.len() - 1 {
+                            man_pages_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the seventh resource list
+            Some(keymap::Action::PreviousResource(7)) => {
+                // man pages back results by one
+                man_pages_index = man_pages_index.saturating_sub(1);
+            }
+
+            // enter the eighth resource menu
+            Some(keymap::Action::AccessResource(8)) => {
+                tracing::info!(
+                    "Accessing content for resource 8 at index: {}",
+                    offline_docs_index
+                );
+                // offline docs show content for current result
+                match content::get_result_content(
+                    offline_docs_results_ref,
+                    &mut offline_docs_results_awaited,
+                    offline_docs_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_static_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            content.text(),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing offline docs which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the eighth resource list
+            Some(keymap::Action::NextResource(8)) => {
+                // offline docs next result
+                match offline_docs_results_ref {
+                    Ok(res) => {
+                        if offline_docs_index < res.len() - 1 {
+                            offline_docs_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the eighth resource list
+            Some(keymap::Action::PreviousResource(8)) => {
+                // offline docs back results by one
+                offline_docs_index = offline_docs_index.saturating_sub(1);
+            }
+
+            // enter the ninth resource menu
+            Some(keymap::Action::AccessResource(9)) => {
+                tracing::info!(
+                    "Accessing content for resource 9 at index: {}",
+                    github_issues_index
+                );
+                // github issues show content for current result
+                match content::get_result_content(
+                    github_issues_results_ref,
+                    &mut github_issues_results_awaited,
+                    github_issues_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing github issues which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the ninth resource list
+            Some(keymap::Action::NextResource(9)) => {
+                // github issues next result
+                match github_issues_results_ref {
+                    Ok(res) => {
+                        if github_issues_index < res.len() - 1 {
+                            github_issues_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the ninth resource list
+            Some(keymap::Action::PreviousResource(9)) => {
+                // github issues back results by one
+                github_issues_index = github_issues_index.saturating_sub(1);
+            }
+
+            // enter the tenth resource menu
+            Some(keymap::Action::AccessResource(10)) => {
+                tracing::info!(
+                    "Accessing content for resource 10 at index: {}",
+                    discourse_index
+                );
+                // discourse show content for current result
+                match content::get_result_content(
+                    discourse_results_ref,
+                    &mut discourse_results_awaited,
+                    discourse_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing discourse which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the tenth resource list
+            Some(keymap::Action::NextResource(10)) => {
+                // discourse next result
+                match discourse_results_ref {
+                    Ok(res) => {
+                        if discourse_index < res.len() - 1 {
+                            discourse_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the tenth resource list
+            Some(keymap::Action::PreviousResource(10)) => {
+                // discourse back results by one
+                discourse_index = discourse_index.saturating_sub(1);
+            }
+
+            // enter the eleventh resource menu
+            Some(keymap::Action::AccessResource(11)) => {
+                tracing::info!(
+                    "Accessing content for resource 11 at index: {}",
+                    reddit_index
+                );
+                // reddit show content for current result
+                match content::get_result_content(
+                    reddit_results_ref,
+                    &mut reddit_results_awaited,
+                    reddit_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing reddit which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the eleventh resource list
+            Some(keymap::Action::NextResource(11)) => {
+                // reddit next result
+                match reddit_results_ref {
+                    Ok(res) => {
+                        if reddit_index < res.len() - 1 {
+                            reddit_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the eleventh resource list
+            Some(keymap::Action::PreviousResource(11)) => {
+                // reddit back results by one
+                reddit_index = reddit_index.saturating_sub(1);
+            }
+
+            // enter the twelfth resource menu
+            Some(keymap::Action::AccessResource(12)) => {
+                tracing::info!(
+                    "Accessing content for resource 12 at index: {}",
+                    hacker_news_index
+                );
+                // hacker news show content for current result
+                match content::get_result_content(
+                    hacker_news_results_ref,
+                    &mut hacker_news_results_awaited,
+                    hacker_news_index,
+                )
+                .await
+                {
+                    Some(content) => {
+                        util::clear_terminal(&mut stdout);
+                        if print::print_dyn_content(
+                            &mut stdout,
+                            &keymap,
+                            mouse,
+                            context.as_deref(),
+                            &content.elements(&answer_options),
+                        ) {
+                            util::clean(&mut stdout);
+                            return;
+                        }
+                    }
+                    None => {
+                        tracing::warn!(
+                            "User tried accessing hacker news which has been deemed unavailable."
+                        );
+                    }
+                }
+            }
+            // go to the next element in the twelfth resource list
+            Some(keymap::Action::NextResource(12)) => {
+                // hacker news next result
+                match hacker_news_results_ref {
+                    Ok(res) => {
+                        if hacker_news_index < res.len() - 1 {
+                            hacker_news_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+            }
+            // go to the previous element in the twelfth resource list
+            Some(keymap::Action::PreviousResource(12)) => {
+                // hacker news back results by one
+                hacker_news_index = hacker_news_index.saturating_sub(1);
+            }
+
             // move every resource to it's next element in the list, if any more
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('n'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(keymap::Action::NextAll) => {
                 // move all resources to the next element
                 match stackoverflow_results_ref {
                     Ok(res) => {
@@ -634,29 +1598,100 @@ pub async fn cli() {
                     // we already handled the error
                     Err(_) => (),
                 }
+                match rust_docs_results_ref {
+                    Ok(res) => {
+                        if rust_docs_index < res.len() - 1 {
+                            rust_docs_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+                match man_pages_results_ref {
+                    Ok(res) => {
+                        if man_pages_index < res.len() - 1 {
+                            man_pages_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+                match offline_docs_results_ref {
+                    Ok(res) => {
+                        if offline_docs_index < res.len() - 1 {
+                            offline_docs_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+                match github_issues_results_ref {
+                    Ok(res) => {
+                        if github_issues_index < res.len() - 1 {
+                            github_issues_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+                match discourse_results_ref {
+                    Ok(res) => {
+                        if discourse_index < res.len() - 1 {
+                            discourse_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+                match reddit_results_ref {
+                    Ok(res) => {
+                        if reddit_index < res.len() - 1 {
+                            reddit_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
+                match hacker_news_results_ref {
+                    Ok(res) => {
+                        if hacker_news_index < res.len() - 1 {
+                            hacker_news_index += 1;
+                        }
+                    }
+                    // we already handled the error
+                    Err(_) => (),
+                }
             }
             // move to the previous element in the list of every resource, if any more
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('N'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::SHIFT,
-                ..
-            }) => {
+            Some(keymap::Action::PreviousAll) => {
                 // move all the resources to the previous element
                 stackoverflow_index = stackoverflow_index.saturating_sub(1);
                 stackexchange_index = stackexchange_index.saturating_sub(1);
                 github_gist_index = github_gist_index.saturating_sub(1);
                 geeksforgeeks_index = geeksforgeeks_index.saturating_sub(1);
                 ddg_search_index = ddg_search_index.saturating_sub(1);
+                rust_docs_index = rust_docs_index.saturating_sub(1);
+                man_pages_index = man_pages_index.saturating_sub(1);
+                offline_docs_index = offline_docs_index.saturating_sub(1);
+                github_issues_index = github_issues_index.saturating_sub(1);
+                discourse_index = discourse_index.saturating_sub(1);
+                reddit_index = reddit_index.saturating_sub(1);
+                hacker_news_index = hacker_news_index.saturating_sub(1);
+            }
+
+            // go to the next result of the merged view
+            Some(keymap::Action::NextMerged) => {
+                if merged_index + 1 < merged_results.len() {
+                    merged_index += 1;
+                }
+            }
+            // go to the previous result of the merged view
+            Some(keymap::Action::PreviousMerged) => {
+                merged_index = merged_index.saturating_sub(1);
             }
 
             // clear the terminal and exit the program
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('c'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::CONTROL,
-                ..
-            }) => {
+            Some(keymap::Action::Exit) => {
                 tracing::info!("Exit app on user command!");
                 util::clean(&mut stdout);
                 return;
@@ -667,3 +1702,64 @@ pub async fn cli() {
         util::clear_terminal(&mut stdout);
     }
 }
+
+/// Await the results of every resource whose content finished loading in the background, so
+/// they're listed under their title with their metadata.
+///
+/// # Returns
+///
+/// If any result was renamed and if any result is still loading.
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip_all)]
+async fn load_all_finished(
+    stackoverflow_awaited: &mut HashMap<String, Thread>,
+    stackoverflow_results: &mut Result<Vec<(SearchHit, ThreadHandle<SofError>)>, SofError>,
+    stackexchange_awaited: &mut HashMap<String, Thread>,
+    stackexchange_results: &mut Result<Vec<(SearchHit, ThreadHandle<SeError>)>, SeError>,
+    github_gist_awaited: &mut HashMap<String, Gist>,
+    github_gist_results: &mut Result<Vec<(SearchHit, GistHandle)>, GithubGistError>,
+    geeksforgeeks_awaited: &mut HashMap<String, Article>,
+    geeksforgeeks_results: &mut Result<Vec<(SearchHit, ArticleHandle)>, GfgError>,
+    ddg_search_awaited: &mut HashMap<String, String>,
+    ddg_search_results: &mut Result<Vec<(SearchHit, PageHandle)>, DdgSearchError>,
+    rust_docs_awaited: &mut HashMap<String, Doc>,
+    rust_docs_results: &mut Result<Vec<(SearchHit, DocHandle)>, RustDocsError>,
+    man_pages_awaited: &mut HashMap<String, ManPage>,
+    man_pages_results: &mut Result<Vec<(SearchHit, ManHandle)>, ManError>,
+    offline_docs_awaited: &mut HashMap<String, OfflineDoc>,
+    offline_docs_results: &mut Result<Vec<(SearchHit, OfflineHandle)>, OfflineDocsError>,
+    github_issues_awaited: &mut HashMap<String, Thread>,
+    github_issues_results: &mut Result<
+        Vec<(SearchHit, ThreadHandle<GithubIssuesError>)>,
+        GithubIssuesError,
+    >,
+    discourse_awaited: &mut HashMap<String, Thread>,
+    discourse_results: &mut Result<Vec<(SearchHit, ThreadHandle<DiscourseError>)>, DiscourseError>,
+    reddit_awaited: &mut HashMap<String, Thread>,
+    reddit_results: &mut Result<Vec<(SearchHit, ThreadHandle<RedditError>)>, RedditError>,
+    hacker_news_awaited: &mut HashMap<String, Thread>,
+    hacker_news_results: &mut Result<
+        Vec<(SearchHit, ThreadHandle<HackerNewsError>)>,
+        HackerNewsError,
+    >,
+) -> (bool, bool) {
+    let progress = [
+        content::load_finished(stackoverflow_results, stackoverflow_awaited).await,
+        content::load_finished(stackexchange_results, stackexchange_awaited).await,
+        content::load_finished(github_gist_results, github_gist_awaited).await,
+        content::load_finished(geeksforgeeks_results, geeksforgeeks_awaited).await,
+        content::load_finished(ddg_search_results, ddg_search_awaited).await,
+        content::load_finished(rust_docs_results, rust_docs_awaited).await,
+        content::load_finished(man_pages_results, man_pages_awaited).await,
+        content::load_finished(offline_docs_results, offline_docs_awaited).await,
+        content::load_finished(github_issues_results, github_issues_awaited).await,
+        content::load_finished(discourse_results, discourse_awaited).await,
+        content::load_finished(reddit_results, reddit_awaited).await,
+        content::load_finished(hacker_news_results, hacker_news_awaited).await,
+    ];
+
+    (
+        progress.iter().any(|(renamed, _)| *renamed),
+        progress.iter().any(|(_, loading)| *loading),
+    )
+}
@@ -0,0 +1,334 @@
+use super::keymap::Action;
+use base64::Engine;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crossterm::style;
+use crossterm::style::Stylize;
+use std::io::Write;
+
+// How many lines the mouse wheel scrolls at once
+const SCROLL_LINES: usize = 3;
+
+/// Get how many columns a string takes in the terminal, ignoring the escape sequences used for
+/// styling it.
+///
+/// # Arguments
+///
+/// * `text` - the text, styled or not.
+pub fn visible_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the whole escape sequence, they end with a char from '@' to '~'
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else if c != '\n' && c != '\r' {
+            width += 1;
+        }
+    }
+
+    width
+}
+
+/// Get how many rows a line of text takes in the terminal, considering it wraps.
+///
+/// # Arguments
+///
+/// * `text` - the line of text, styled or not.
+pub fn rows_taken(text: &str) -> u16 {
+    let term_width: usize = match crossterm::terminal::size() {
+        Ok(size) => size.0.max(1).into(),
+        Err(_) => 100,
+    };
+
+    // a line always takes at least one row even if it's empty
+    let rows = visible_width(text).max(1).div_ceil(term_width);
+    rows.try_into().unwrap_or(u16::MAX)
+}
+
+/// Get the main menu action for a mouse event. Clicking a resource's row accesses it and
+/// scrolling over it moves through its list, the same goes for the row of the merged view.
+///
+/// # Arguments
+///
+/// * `event` - the mouse event.
+/// * `resource_rows` - the rows every resource takes in the terminal, from (inclusive) to
+/// (exclusive), in the order of the resources.
+/// * `merged_rows` - the rows the merged view takes in the terminal, if it's shown.
+pub fn main_action(
+    event: &MouseEvent,
+    resource_rows: &[(u16, u16)],
+    merged_rows: Option<(u16, u16)>,
+) -> Option<Action> {
+    if merged_rows.is_some_and(|(from, to)| (from..to).contains(&event.row)) {
+        return match event.kind {
+            MouseEventKind::Down(MouseButton::Left) => Some(Action::AccessMerged),
+            MouseEventKind::ScrollDown => Some(Action::NextMerged),
+            MouseEventKind::ScrollUp => Some(Action::PreviousMerged),
+            _ => None,
+        };
+    }
+    let resource = resource_rows
+        .iter()
+        .position(|(from, to)| (*from..*to).contains(&event.row))?
+        + 1;
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => Some(Action::AccessResource(resource)),
+        MouseEventKind::ScrollDown => Some(Action::NextResource(resource)),
+        MouseEventKind::ScrollUp => Some(Action::PreviousResource(resource)),
+        _ => None,
+    }
+}
+
+/// Copy text to the clipboard using the OSC 52 escape sequence, this works through ssh aswell,
+/// but not every terminal supports it.
+///
+/// # Arguments
+///
+/// * `stdout` - std::io::stdout() you should have one in main you reference across functions.
+/// * `text` - the text to copy.
+#[tracing::instrument(skip_all)]
+pub fn copy_to_clipboard(stdout: &mut std::io::Stdout, text: &str) {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    if let Err(error) = write!(stdout, "\x1b]52;c;{}\x07", encoded) {
+        tracing::warn!(
+            "Failed to copy the selection to the clipboard. Error: {}",
+            error
+        );
+    }
+}
+
+/// The visible part of a content that doesn't fit in the terminal, used when the mouse is
+/// captured since the terminal can't be scrolled anymore.
+///
+/// Blocks of text (lines between empty lines, like code blocks) can be selected by clicking them
+/// which highlights and copies them to the clipboard.
+#[derive(Debug)]
+pub struct Viewport {
+    // the lines of the content
+    lines: Vec<String>,
+    // the lines wrapped to the terminal width togheter with the index of their line
+    rows: Vec<(usize, String)>,
+    // how many rows are before the content, like the title
+    header_rows: u16,
+    height: usize,
+    offset: usize,
+    // the first and last line of the selected block
+    selected: Option<(usize, usize)>,
+}
+
+impl Viewport {
+    /// Create a new viewport for a content, sized to the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - the content to display.
+    /// * `header_rows` - how many rows are printed before the content.
+    pub fn new(content: &str, header_rows: u16) -> Self {
+        let (term_width, term_height): (usize, usize) = match crossterm::terminal::size() {
+            Ok(size) => (size.0.max(1).into(), size.1.into()),
+            Err(_) => (100, 30),
+        };
+
+        let lines: Vec<String> = content.lines().map(|line| line.to_string()).collect();
+        let mut rows = Vec::with_capacity(lines.len());
+        for (index, line) in lines.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.is_empty() {
+                rows.push((index, String::new()));
+            }
+            for chunk in chars.chunks(term_width) {
+                rows.push((index, chunk.iter().collect()));
+            }
+        }
+
+        Self {
+            lines,
+            rows,
+            header_rows,
+            // leave the last row empty so the terminal doesn't scroll
+            height: term_height
+                .saturating_sub(header_rows.into())
+                .saturating_sub(1)
+                .max(1),
+            offset: 0,
+            selected: None,
+        }
+    }
+
+    /// Scroll down the content, if it isn't already at the bottom.
+    pub fn scroll_down(&mut self) {
+        let max_offset = self.rows.len().saturating_sub(self.height);
+        self.offset = (self.offset + SCROLL_LINES).min(max_offset);
+    }
+
+    /// Scroll up the content, if it isn't already at the top.
+    pub fn scroll_up(&mut self) {
+        self.offset = self.offset.saturating_sub(SCROLL_LINES);
+    }
+
+    /// Select the block of text at the given terminal row, clicking an empty line or the
+    /// already selected block clears the selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - the row in the terminal that was clicked.
+    ///
+    /// # Returns
+    ///
+    /// The text of the newly selected block, if any.
+    pub fn select(&mut self, row: u16) -> Option<String> {
+        let index = usize::from(row.checked_sub(self.header_rows)?) + self.offset;
+        let (line, _) = self.rows.get(index)?;
+        let line = *line;
+
+        if self.lines[line].trim().is_empty() {
+            self.selected = None;
+            return None;
+        }
+
+        // expand the selection up and down until an empty line
+        let is_filled = |l: &usize| !self.lines[*l].trim().is_empty();
+        let first = (0..line).rev().take_while(is_filled).last().unwrap_or(line);
+        let last = (line + 1..self.lines.len())
+            .take_while(is_filled)
+            .last()
+            .unwrap_or(line);
+
+        if self.selected == Some((first, last)) {
+            self.selected = None;
+            return None;
+        }
+
+        self.selected = Some((first, last));
+        Some(self.lines[first..=last].join("\n"))
+    }
+
+    /// Handle a mouse event in the content view: scroll with the wheel and select blocks of
+    /// text by clicking them.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdout` - std::io::stdout() you should have one in main you reference across functions.
+    /// * `event` - the mouse event.
+    pub fn handle(&mut self, stdout: &mut std::io::Stdout, event: &MouseEvent) {
+        match event.kind {
+            MouseEventKind::ScrollDown => self.scroll_down(),
+            MouseEventKind::ScrollUp => self.scroll_up(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                if let Some(text) = self.select(event.row) {
+                    copy_to_clipboard(stdout, &text);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Queue printing the visible rows of the content, highlighting the selected block.
+    ///
+    /// # Arguments
+    ///
+    /// * `stdout` - std::io::stdout() you should have one in main you reference across functions.
+    #[tracing::instrument(skip_all)]
+    pub fn queue(&self, stdout: &mut std::io::Stdout) {
+        for (line, row) in self.rows.iter().skip(self.offset).take(self.height) {
+            let is_selected = match self.selected {
+                Some((first, last)) => (first..=last).contains(line),
+                None => false,
+            };
+
+            let result = if is_selected {
+                crossterm::queue!(
+                    stdout,
+                    style::PrintStyledContent(row.as_str().reverse()),
+                    style::Print("\n\r")
+                )
+            } else {
+                crossterm::queue!(stdout, style::Print(row), style::Print("\n\r"))
+            };
+
+            if let Err(error) = result {
+                tracing::warn!(
+                    "There was an error printing a row of the content. Error: {}",
+                    error
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn mouse_event(kind: MouseEventKind, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column: 0,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_visible_width() {
+        let styled = format!("{} {}", "(1)".green(), "[  StackOverFlow  ]".yellow());
+
+        assert_eq!(visible_width(&styled), 23);
+        assert_eq!(visible_width("plain"), 5);
+    }
+
+    #[test]
+    fn test_main_action() {
+        let rows = [(1, 2), (2, 4), (4, 5)];
+
+        assert_eq!(
+            main_action(
+                &mouse_event(MouseEventKind::Down(MouseButton::Left), 3),
+                &rows,
+                None
+            ),
+            Some(Action::AccessResource(2))
+        );
+        assert_eq!(
+            main_action(&mouse_event(MouseEventKind::ScrollDown, 4), &rows, None),
+            Some(Action::NextResource(3))
+        );
+        assert_eq!(
+            main_action(&mouse_event(MouseEventKind::ScrollUp, 0), &rows, None),
+            None
+        );
+        assert_eq!(
+            main_action(
+                &mouse_event(MouseEventKind::ScrollUp, 5),
+                &rows,
+                Some((5, 6))
+            ),
+            Some(Action::PreviousMerged)
+        );
+    }
+
+    #[test]
+    fn test_viewport_select_block() {
+        let mut viewport = Viewport::new("text\n\nfn main() {\n    foo();\n}\n\nmore text", 2);
+
+        // rows 0 and 1 are the header, row 5 is "    foo();"
+        assert_eq!(
+            viewport.select(5).as_deref(),
+            Some("fn main() {\n    foo();\n}")
+        );
+        // clicking the same block again clears the selection
+        assert_eq!(viewport.select(4), None);
+        // clicking an empty line selects nothing
+        assert_eq!(viewport.select(3), None);
+        // clicking the header selects nothing
+        assert_eq!(viewport.select(0), None);
+    }
+}
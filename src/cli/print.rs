@@ -1,12 +1,24 @@
+use super::keymap::{Action, Keymap};
+use super::mouse::Viewport;
+use crate::content::Element;
+use crate::search::engine::SearchHit;
+use crate::search::merge::MergedResult;
 use crossterm::event;
 use crossterm::style;
 use crossterm::style::Stylize;
+use hashbrown::HashMap;
 use std::io::Write;
 use tokio::task::JoinHandle;
 
-type ResultsType<T, S> = Vec<(String, JoinHandle<Result<T, S>>)>;
+type ResultsType<T, S> = Vec<(SearchHit, JoinHandle<Result<T, S>>)>;
 
-/// Print the given print followed by the title of the given index result.
+// How many rows the title of a content takes, including the empty row after it
+const CONTENT_HEADER_ROWS: u16 = 2;
+// How many characters of the snippet of a result are previewed under it
+const PREVIEW_CHARS: usize = 160;
+
+/// Print the given print followed by the title of the given index result. While the content of
+/// the result is loading, its snippet from the search is previewed under it.
 ///
 /// # Arguments
 ///
@@ -14,13 +26,20 @@ type ResultsType<T, S> = Vec<(String, JoinHandle<Result<T, S>>)>;
 /// used to manipulate the terminal.
 /// `resource_index` - The index of the given resource to print.
 /// `resource_results` - Actual results of the resource you want to print.
+/// `results_awaited` - The results of the resource whose content already loaded.
+///
+/// # Returns
+///
+/// How many rows in the terminal the printed resource takes.
 #[tracing::instrument(skip_all)]
 pub fn print_resource<T, S>(
     stdout: &mut std::io::Stdout,
     resource_index: usize,
     resource_print: &str,
     resource_results: &Result<ResultsType<T, S>, S>,
-) where
+    results_awaited: &HashMap<String, T>,
+) -> u16
+where
     S: std::string::ToString,
 {
     match resource_results {
@@ -36,99 +55,272 @@ pub fn print_resource<T, S>(
                 }
             };
             // display the current result with the given print
+            let hit = &current_result.0;
+            let line = resource_print.to_string() + &hit.title;
             if let Err(error) = crossterm::queue!(
                 stdout,
-                style::PrintStyledContent(
-                    (resource_print.to_string() + &current_result.0).stylize()
-                ),
+                style::PrintStyledContent(line.as_str().stylize()),
                 style::Print("\n\r")
             ) {
                 tracing::warn!("There was an error printing some text. Error: {}", error);
             }
+            let mut rows = super::mouse::rows_taken(&line);
+
+            // preview the snippet until the content is there
+            if !hit.snippet.is_empty() && !results_awaited.contains_key(&hit.title) {
+                let preview = preview_line(&hit.snippet);
+                if let Err(error) = crossterm::queue!(
+                    stdout,
+                    style::PrintStyledContent(preview.as_str().dark_grey()),
+                    style::Print("\n\r")
+                ) {
+                    tracing::warn!("There was an error printing a preview. Error: {}", error);
+                }
+                rows = rows.saturating_add(super::mouse::rows_taken(&preview));
+            }
+
+            rows
         }
         Err(error) => {
+            let line = error.to_string();
             if let Err(error) = crossterm::queue!(
                 stdout,
-                style::PrintStyledContent(error.to_string().red()),
+                style::PrintStyledContent(line.as_str().red()),
                 style::Print("\n\r")
             ) {
                 tracing::warn!("There was an error printing some text. Error: {}", error);
             }
+
+            super::mouse::rows_taken(&line)
         }
     }
 }
 
-/// Create a screen similar to the cli one where you go through a content that is iterable.
+/// Print the given print followed by the current result of the merged view, tagged with the
+/// resource it's from.
 ///
 /// # Arguments
 ///
 /// `stdout` - std::io::stdout() you should have one in main you reference across functions. It's
 /// used to manipulate the terminal.
-/// `content` - the iterable content to display
-/// `is_thread` - Specify if the content is thread type, so the first element is gonna be tagged as
-/// question and the rest as answers, if not each element is gonna be tagged a file.
+/// `merged_index` - The index of the current result of the merged view.
+/// `merged_print` - What to print before the result.
+/// `merged` - The results of every resource, ranked.
+///
+/// # Returns
+///
+/// How many rows in the terminal the printed merged view takes.
 #[tracing::instrument(skip_all)]
-pub fn print_dyn_content(
+pub fn print_merged(
     stdout: &mut std::io::Stdout,
-    content: &[String],
-    is_thread: bool,
-) -> bool {
-    let mut current_index = 0;
-    let max_index = content.len() - 1;
-    // depending on is_thread set to either question or file 1 as for the first element.
-    let question_title = if is_thread {
-        "Question:".green().bold()
-    } else {
-        "File 1:".green().bold()
+    merged_index: usize,
+    merged_print: &str,
+    merged: &[MergedResult],
+) -> u16 {
+    let line = match merged.get(merged_index) {
+        Some(result) => format!(
+            "{}{} {}",
+            merged_print,
+            format!("[{}]", result.source.label()).cyan(),
+            result.title
+        ),
+        None => format!("{}{}", merged_print, "No resource has any results.".red()),
     };
-    // cli for the given content
-    loop {
-        // print content
-        let content = match content.get(current_index) {
+    if let Err(error) = crossterm::queue!(
+        stdout,
+        style::PrintStyledContent(line.as_str().stylize()),
+        style::Print("\n\r")
+    ) {
+        tracing::warn!("There was an error printing some text. Error: {}", error);
+    }
+
+    super::mouse::rows_taken(&line)
+}
+
+/// Get the line previewing the snippet of a result, indented under its title and cut short.
+///
+/// # Arguments
+///
+/// `snippet` - the snippet of the result.
+fn preview_line(snippet: &str) -> String {
+    let mut preview: String = snippet.chars().take(PREVIEW_CHARS).collect();
+    if snippet.chars().nth(PREVIEW_CHARS).is_some() {
+        preview.push('…');
+    }
+    format!("   {}", preview)
+}
+
+/// Queue printing a content, the whole of it or only the visible part of the viewport when the
+/// mouse is captured.
+///
+/// # Arguments
+///
+/// `stdout` - std::io::stdout() you should have one in main you reference across functions. It's
+/// used to manipulate the terminal.
+/// `content` - the content to print.
+/// `viewport` - the viewport of the content, if the mouse is captured.
+fn queue_content(stdout: &mut std::io::Stdout, content: &str, viewport: Option<&Viewport>) {
+    match viewport {
+        Some(viewport) => viewport.queue(stdout),
+        None => {
             // replace \n to \n\r because in terminal raw mode a new line doesn't bring you the
             // beginning of the row, it only goes down one line literally.
-            Some(content) => content.replace('\n', "\n\r"),
-            None => "There has been error getting the contents for this result".to_string(),
-        };
-        // print first element tag or not
-        if current_index == 0 {
-            if let Err(error) = crossterm::queue!(
-                stdout,
-                style::PrintStyledContent(question_title),
-                style::Print("\n\r\n\r")
-            ) {
-                tracing::warn!(
-                    "There was an error printing the title of thread's current entry. Error: {}",
-                    error
-                );
-            }
-
-            if let Err(error) = crossterm::queue!(stdout, style::Print(content)) {
+            if let Err(error) =
+                crossterm::queue!(stdout, style::Print(content.replace('\n', "\n\r")))
+            {
                 tracing::warn!(
                     "There was an error printing a thread's content. Error: {}",
                     error
                 );
             }
-        } else {
-            if let Err(error) = crossterm::queue!(
-                stdout,
-                style::PrintStyledContent(format!("Answer {}:", current_index).green()),
-                style::Print("\n\r\n\r")
-            ) {
-                tracing::warn!(
-                    "There was an error printing the title of thread's current entry. Error: {}",
-                    error
-                );
-            }
+        }
+    }
+}
 
-            if let Err(error) = crossterm::queue!(stdout, style::Print(content)) {
-                tracing::warn!(
-                    "There was an error printing a thread's content. Error: {}",
-                    error
-                );
+/// Queue printing the context given to falion (like the diagnostic a search was made for) followed
+/// by an empty line, if there is one.
+///
+/// # Arguments
+///
+/// `stdout` - std::io::stdout() you should have one in main you reference across functions. It's
+/// used to manipulate the terminal.
+/// `context` - the context to print.
+///
+/// # Returns
+///
+/// How many rows in the terminal the printed context takes.
+fn queue_context(stdout: &mut std::io::Stdout, context: Option<&str>) -> u16 {
+    let context = match context {
+        Some(context) => context.trim_end(),
+        None => return 0,
+    };
+
+    if let Err(error) = crossterm::queue!(
+        stdout,
+        style::PrintStyledContent(context.replace('\n', "\n\r").dark_grey()),
+        style::Print("\n\r\n\r")
+    ) {
+        tracing::warn!("There was an error printing the context. Error: {}", error);
+    }
+
+    context
+        .lines()
+        .map(super::mouse::rows_taken)
+        .sum::<u16>()
+        .saturating_add(1)
+}
+
+/// Add the comments of an element under its content. Collapsed only how many comments there are
+/// is shown, with the key chord that expands them.
+///
+/// # Arguments
+///
+/// `element` - the element to get the content and the comments of.
+/// `expanded` - if every comment is shown.
+/// `toggle` - the key chord bound to showing and hiding the comments, if any.
+fn with_comments(element: &Element, expanded: bool, toggle: Option<&str>) -> String {
+    let count = element.comments.len();
+    if count == 0 {
+        return element.content.clone();
+    }
+
+    let plural = if count == 1 { "" } else { "s" };
+    let (mark, verb) = if expanded {
+        ("▾", "hide")
+    } else {
+        ("▸", "show")
+    };
+    let hint = match toggle {
+        Some(chord) => format!(" ({} to {})", chord, verb),
+        None => String::new(),
+    };
+    let mut content = format!(
+        "{}\n\n{} {} comment{}{}\n",
+        element.content.trim_end(),
+        mark,
+        count,
+        plural,
+        hint
+    );
+
+    if expanded {
+        for comment in &element.comments {
+            content.push_str(&format!("\n  • {}\n", comment.replace('\n', "\n    ")));
+        }
+    }
+
+    content
+}
+
+/// Create a screen similar to the cli one where you go through a content that is iterable.
+///
+/// # Arguments
+///
+/// `stdout` - std::io::stdout() you should have one in main you reference across functions. It's
+/// used to manipulate the terminal.
+/// `keymap` - the key chords bound to the actions of the menu.
+/// `mouse` - if the mouse is captured, in which case the content is scrolled with the wheel.
+/// `context` - optional, text printed above the content, like the diagnostic that was searched.
+/// `elements` - the elements of the content to go through, with their tags. The tag of the
+/// first element and of the accepted answer are highlighted. Their comments are collapsed under
+/// them until toggled.
+#[tracing::instrument(skip_all)]
+pub fn print_dyn_content(
+    stdout: &mut std::io::Stdout,
+    keymap: &Keymap,
+    mouse: bool,
+    context: Option<&str>,
+    elements: &[Element],
+) -> bool {
+    let mut current_index = 0;
+    let mut viewport: Option<Viewport> = None;
+    let mut show_comments = false;
+    let toggle = keymap.chord_of(Action::ToggleComments);
+    let max_index = elements.len().saturating_sub(1);
+    // cli for the given content
+    loop {
+        // print content
+        let (tag, content) = match elements.get(current_index) {
+            Some(element) => {
+                let tag = format!("{}:", element.tag);
+                // the first element and the accepted answer stand out
+                let tag = if current_index == 0 || element.is_accepted {
+                    tag.green().bold()
+                } else {
+                    tag.green()
+                };
+                (
+                    tag,
+                    with_comments(element, show_comments, toggle.as_deref()),
+                )
             }
+            None => (
+                "Error:".to_string().red(),
+                String::from("There has been error getting the contents for this result"),
+            ),
+        };
+        // print the context and create the viewport of the current element if the mouse is captured
+        let context_rows = queue_context(stdout, context);
+        if mouse && viewport.is_none() {
+            viewport = Some(Viewport::new(
+                &content,
+                CONTENT_HEADER_ROWS.saturating_add(context_rows),
+            ));
+        }
+        // print the element's tag
+        if let Err(error) = crossterm::queue!(
+            stdout,
+            style::PrintStyledContent(tag),
+            style::Print("\n\r\n\r")
+        ) {
+            tracing::warn!(
+                "There was an error printing the title of thread's current entry. Error: {}",
+                error
+            );
         }
 
+        queue_content(stdout, &content, viewport.as_ref());
+
         // flush stdout queued commands
         if let Err(error) = stdout.flush() {
             tracing::warn!(
@@ -146,41 +338,44 @@ pub fn print_dyn_content(
             }
         };
 
-        match event_read {
+        // get the action bound to the pressed key, the mouse is handled by the viewport
+        let action = match event_read {
+            event::Event::Key(key_event) if key_event.kind == event::KeyEventKind::Press => {
+                keymap.content_action(&key_event)
+            }
+            event::Event::Mouse(mouse_event) => {
+                if let Some(viewport) = viewport.as_mut() {
+                    viewport.handle(stdout, &mouse_event);
+                }
+                None
+            }
+            _ => None,
+        };
+
+        match action {
             // go to next content
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('n'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(Action::NextContent) => {
                 if current_index < max_index {
                     current_index += 1;
+                    viewport = None;
                 }
             }
             // go to previous content
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('N'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::SHIFT,
-                ..
-            }) => {
-                current_index = current_index.saturating_sub(1);
+            Some(Action::PreviousContent) => {
+                if current_index > 0 {
+                    current_index -= 1;
+                    viewport = None;
+                }
             }
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('q'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            // expand or collapse the comments, of every element until toggled again
+            Some(Action::ToggleComments) => {
+                show_comments = !show_comments;
+                viewport = None;
+            }
+            Some(Action::Return) => {
                 return false;
             }
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('c'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::CONTROL,
-                ..
-            }) => {
+            Some(Action::Exit) => {
                 tracing::info!("Exit app on user command!");
                 return true;
             }
@@ -198,14 +393,29 @@ pub fn print_dyn_content(
 ///
 /// `stdout` - std::io::stdout() you should have one in main you reference across functions. It's
 /// used to manipulate the terminal.
+/// `keymap` - the key chords bound to the actions of the menu.
+/// `mouse` - if the mouse is captured, in which case the content is scrolled with the wheel.
+/// `context` - optional, text printed above the content, like the diagnostic that was searched.
 /// `content` - the content to create the cli for.
 #[tracing::instrument(skip_all)]
-pub fn print_static_content(stdout: &mut std::io::Stdout, content: &str) -> bool {
-    // replace \n to \n\r because in terminal raw mode a new line doesn't bring you the
-    // beginning of the row, it only goes down one line literally.
-    let content = content.replace('\n', "\n\r");
+pub fn print_static_content(
+    stdout: &mut std::io::Stdout,
+    keymap: &Keymap,
+    mouse: bool,
+    context: Option<&str>,
+    content: &str,
+) -> bool {
+    let mut viewport: Option<Viewport> = None;
     let page_title = "Page:".green().bold();
     loop {
+        // print the context and create the viewport if the mouse is captured
+        let context_rows = queue_context(stdout, context);
+        if mouse && viewport.is_none() {
+            viewport = Some(Viewport::new(
+                content,
+                CONTENT_HEADER_ROWS.saturating_add(context_rows),
+            ));
+        }
         // print content
         if let Err(error) = crossterm::queue!(
             stdout,
@@ -218,12 +428,7 @@ pub fn print_static_content(stdout: &mut std::io::Stdout, content: &str) -> bool
             );
         }
 
-        if let Err(error) = crossterm::queue!(stdout, style::Print(&content)) {
-            tracing::warn!(
-                "There was an error printing a thread's content. Error: {}",
-                error
-            );
-        }
+        queue_content(stdout, content, viewport.as_ref());
 
         if let Err(error) = stdout.flush() {
             tracing::warn!(
@@ -240,24 +445,27 @@ pub fn print_static_content(stdout: &mut std::io::Stdout, content: &str) -> bool
             }
         };
 
-        // listen to key presses
-        match event_read {
+        // listen to key presses, the mouse is handled by the viewport
+        let action = match event_read {
+            event::Event::Key(key_event) if key_event.kind == event::KeyEventKind::Press => {
+                keymap.content_action(&key_event)
+            }
+            event::Event::Mouse(mouse_event) => {
+                if let Some(viewport) = viewport.as_mut() {
+                    viewport.handle(stdout, &mouse_event);
+                }
+                None
+            }
+            _ => None,
+        };
+
+        match action {
             // return to main menu
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('q'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::NONE,
-                ..
-            }) => {
+            Some(Action::Return) => {
                 return false;
             }
             // quit app
-            event::Event::Key(event::KeyEvent {
-                code: event::KeyCode::Char('c'),
-                kind: event::KeyEventKind::Press,
-                modifiers: event::KeyModifiers::CONTROL,
-                ..
-            }) => {
+            Some(Action::Exit) => {
                 tracing::info!("Exit app on user command!");
                 return true;
             }
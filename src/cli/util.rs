@@ -1,10 +1,74 @@
+use super::keymap::Keymap;
+use crate::config::{ApiSource, Config};
+use crate::search::cargo_lock::CargoLock;
+use crate::search::ddg::DdgQuery;
+use crate::search::discourse::Forum;
+use crate::search::engine::Engines;
+use crate::search::error_query;
+use crate::search::lang::{Lang, LangMode};
+use crate::search::man_pages::ManPages;
+use crate::search::merge::Weights;
+use crate::search::offline_docs::OfflineDocs;
+use crate::search::se_sites::SeSite;
+use crate::search::thread::AnswerOptions;
 use crate::util::setup_logs;
 use clap::Parser;
 use crossterm::terminal;
 use std::io::Write;
+use std::sync::Arc;
 
-/// Reset the terminal basically. Disable raw mode, reset colors, show cursor, clear screen
-/// scroll up the terminal, move the cursor to the beginning.
+// The query that tells falion to read the error output from stdin
+const STDIN_QUERY: &str = "-";
+
+/// Everything the cli needs that's got from the command line options and the config file.
+///
+/// * `query` - what to search for.
+/// * `keymap` - the key chords bound to every action.
+/// * `mouse` - if the mouse is captured.
+/// * `context` - text to show above the content of every result, if any.
+/// * `api_key` - the StackExchange api app key, if any.
+/// * `github_token` - the GitHub token, if any.
+/// * `api_sources` - the sources that should use the StackExchange api instead of scraping.
+/// * `se_sites` - the sites of the StackExchange network to search, every site if empty.
+/// * `forums` - the Discourse forums to search, the Rust users and internals forums if empty.
+/// * `subreddits` - the subreddits to search, r/rust and r/learnprogramming if empty.
+/// * `hn_endpoint` - another address for the hacker news search api, if any.
+/// * `engines` - the search engines the sources find their results with, tried in order.
+/// * `search_options` - the region, safe search, time range, excluded sites, exact phrases and
+/// file types of the web searches.
+/// * `answer_options` - how to order and filter the answers of threads.
+/// * `merged` - the weights of the sources in the merged view, if it's shown.
+/// * `lang` - the language the searches are narrowed down to, if any.
+/// * `lang_mode` - if the web search results are boosted or filtered by the language.
+/// * `cargo_lock` - the crates of the Rust project the search is made from, if any.
+/// * `man_pages` - the man pages source, reading the tldr pages from where the config says.
+/// * `offline_docs` - the offline docs source, reading the documentation from where the config
+/// says.
+pub struct Setup {
+    pub query: String,
+    pub keymap: Keymap,
+    pub mouse: bool,
+    pub context: Option<String>,
+    pub api_key: Option<String>,
+    pub github_token: Option<String>,
+    pub api_sources: Vec<ApiSource>,
+    pub se_sites: Vec<&'static SeSite>,
+    pub forums: Vec<Forum>,
+    pub subreddits: Vec<String>,
+    pub hn_endpoint: Option<String>,
+    pub engines: Engines,
+    pub search_options: DdgQuery,
+    pub answer_options: AnswerOptions,
+    pub merged: Option<Weights>,
+    pub lang: Option<&'static Lang>,
+    pub lang_mode: LangMode,
+    pub cargo_lock: Option<Arc<CargoLock>>,
+    pub man_pages: ManPages,
+    pub offline_docs: OfflineDocs,
+}
+
+/// Reset the terminal basically. Disable raw mode, reset colors, show cursor, release the mouse,
+/// clear screen scroll up the terminal, move the cursor to the beginning.
 ///
 /// # Arguments
 ///
@@ -21,6 +85,9 @@ pub fn clean(stdout: &mut std::io::Stdout) {
     if let Err(error) = crossterm::queue!(stdout, crossterm::cursor::Show) {
         tracing::warn!("Failed to show back cursor. Error: {}", error);
     }
+    if let Err(error) = crossterm::queue!(stdout, crossterm::event::DisableMouseCapture) {
+        tracing::warn!("Failed to release the mouse. Error: {}", error);
+    }
     if let Err(error) = crossterm::queue!(stdout, terminal::Clear(terminal::ClearType::Purge)) {
         tracing::warn!("Failed to clear terminal. Error: {}", error);
     }
@@ -59,14 +126,43 @@ pub fn clear_terminal(stdout: &mut std::io::Stdout) {
     }
 }
 
+/// Read the output of a compiler or program piped to stdin and extract the query out of its most
+/// relevant error line.
+///
+/// # Errors
+///
+/// If stdin couldn't be read or no error line was found in it (InvalidInput).
+fn query_from_stdin() -> Result<String, std::io::Error> {
+    let output = match std::io::read_to_string(std::io::stdin()) {
+        Ok(output) => output,
+        Err(error) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Failed to read the query from stdin. Error: {}", error),
+            ))
+        }
+    };
+
+    match error_query::extract_error_query(&output) {
+        Some(query) => Ok(query),
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Couldn't find an error to search for in the output given on stdin.",
+        )),
+    }
+}
+
 /// Setup the cli. Setup the arguments for bin, get the given values and panic if a query equal or
-/// long to 5 in length hasn't been given. Enable terminal raw mode, hide the cursor, setup
-/// logging. Create an std::io::Stdout instance.
+/// long to 5 in length hasn't been given. If the query is - it's extracted from the error output
+/// piped to stdin. Load the config file and build the keymap out of it. Enable terminal raw mode,
+/// capture the mouse if enabled, setup logging.
 ///
 /// # Errors
 ///
-/// If the user hasn't provided a query shorter than 5 chars or none at all
-pub fn setup_cli() -> Result<String, std::io::Error> {
+/// If the user hasn't provided a query shorter than 5 chars or none at all (NotFound), if the
+/// config file or the keymap in it is invalid (InvalidData), if the query should've been read
+/// from stdin but no error was found there (InvalidInput).
+pub fn setup_cli() -> Result<Setup, std::io::Error> {
     // initiate cli
     let cli = super::Cli::parse();
 
@@ -79,9 +175,29 @@ pub fn setup_cli() -> Result<String, std::io::Error> {
         ));
     }
 
+    // load the config and the keymap
+    let config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(error) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                error.to_string(),
+            ))
+        }
+    };
+    let keymap = match Keymap::from_config(&config.keymap, super::RESOURCES_COUNT) {
+        Ok(keymap) => keymap,
+        Err(error) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Invalid keymap in the config file. Error: {}", error),
+            ))
+        }
+    };
+
     // next check if for keybinds
     if cli.keybinds {
-        print_keybindings();
+        println!("{}", keymap.keybinds_list());
         return Err(std::io::Error::new(
             std::io::ErrorKind::Other,
             "User chose to print the keybinds list",
@@ -89,9 +205,122 @@ pub fn setup_cli() -> Result<String, std::io::Error> {
     }
 
     // get values
-    let query = cli.query.join(" ");
+    let query = if cli.query == [STDIN_QUERY] {
+        query_from_stdin()?
+    } else {
+        cli.query.join(" ")
+    };
     let verbose = cli.verbose;
     let disable_logs = cli.disable_logs;
+    let mouse = cli.mouse || config.cli.mouse;
+    let context = cli.context;
+    let api_key = config.stackexchange_api.key;
+    let github_token = config.github.token();
+    let mut api_sources = config.stackexchange_api.sources;
+    api_sources.extend(cli.api);
+    // the sites given on the command line replace the ones of the config file
+    let se_sites = if cli.se_sites.is_empty() {
+        match config.stackexchange.sites() {
+            Ok(sites) => sites,
+            Err(error) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    error.to_string(),
+                ))
+            }
+        }
+    } else {
+        cli.se_sites
+    };
+    let forums = match config.discourse.forums() {
+        Ok(forums) => forums,
+        Err(error) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                error.to_string(),
+            ))
+        }
+    };
+    let subreddits = match config.reddit.subreddits() {
+        Ok(subreddits) => subreddits,
+        Err(error) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                error.to_string(),
+            ))
+        }
+    };
+    let hn_endpoint = config.hacker_news.endpoint;
+    let engines = match config.search.engines() {
+        Ok(engines) => engines,
+        Err(error) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                error.to_string(),
+            ))
+        }
+    };
+    let mut search_options = DdgQuery::new();
+    if let Some(region) = &cli.region {
+        search_options = search_options.with_region(region);
+    }
+    if let Some(safe_search) = cli.safe_search {
+        search_options = search_options.with_safe_search(safe_search);
+    }
+    if let Some(time_range) = cli.time_range {
+        search_options = search_options.with_time_range(time_range);
+    }
+    for site in &cli.exclude_sites {
+        search_options = search_options.with_excluded_site(site);
+    }
+    for phrase in &cli.phrases {
+        search_options = search_options.with_phrase(phrase);
+    }
+    for file_type in &cli.file_types {
+        search_options = search_options.with_file_type(file_type);
+    }
+    // the command line options override the config file
+    let mut answer_options = AnswerOptions {
+        sort: cli.sort.unwrap_or(config.answers.sort),
+        min_score: cli.min_score.or(config.answers.min_score),
+        limit: cli.max_answers.or(config.answers.max_answers),
+        ..Default::default()
+    };
+    let merged = if cli.merged || config.merged.show {
+        match config.merged.weights() {
+            Ok(weights) => Some(weights),
+            Err(error) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    error.to_string(),
+                ))
+            }
+        }
+    } else {
+        None
+    };
+    // the language given on the command line wins over the one of the project
+    let lang = if cli.no_lang {
+        None
+    } else if cli.lang.is_some() {
+        cli.lang
+    } else {
+        match config
+            .lang
+            .lang(&std::env::current_dir().unwrap_or_default())
+        {
+            Ok(lang) => lang,
+            Err(error) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    error.to_string(),
+                ))
+            }
+        }
+    };
+    let lang_mode = cli.lang_mode.unwrap_or(config.lang.mode);
+    let man_pages = config.man_pages.man_pages();
+    let offline_docs = config.offline_docs.offline_docs();
 
     // check if query is not shorter than 5 characters
     if query.len() < 5 {
@@ -107,33 +336,57 @@ pub fn setup_cli() -> Result<String, std::io::Error> {
         panic!("Failed to enable raw mode: {}", err);
     }
 
+    // capture the mouse only if asked to, since it disables the terminal's text selection
+    if mouse {
+        if let Err(err) =
+            crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)
+        {
+            eprintln!("Failed to capture the mouse: {}", err);
+        }
+    }
+
     // enable (or not) logs based on flag
     if !disable_logs {
         setup_logs(verbose);
     }
 
-    Ok(query)
-}
+    // read the versions of the crates of the project, a broken Cargo.lock doesn't stop the search
+    let cargo_lock = if cli.no_cargo_lock {
+        None
+    } else {
+        match config
+            .cargo_lock
+            .cargo_lock(&std::env::current_dir().unwrap_or_default())
+        {
+            Ok(cargo_lock) => cargo_lock.map(Arc::new),
+            Err(error) => {
+                tracing::warn!("Ignoring the Cargo.lock of the project. Error: {}", error);
+                None
+            }
+        }
+    };
+    answer_options.cargo_lock = cargo_lock.clone();
 
-/// Simple println statement to print the keybinds for the cli
-pub fn print_keybindings() {
-    let keybinds_list = r#"
-Keybinds list for falion.
-Note: where you see ".." it means from that to that i.e "1..5" would mean from 1 to 5.
-   
-Main menu:
-[1..5] = Access that resource.
-SHIFT + [1..5] = Go to the next element in the list of that resource.
-ALT + [1..5] = Go to the previous element in the list of that resource.
-n = Move to the next element in the list of every resource.
-SHIFT + n = Move back to the previous element in the list of every resource.
-CTRL + c = Clear terminal and exit.
-
-Sub menus for the resources:
-n = Move to the next element in the content list (like questions & answers).
-SHIFT + n = Move back to the previous element in the content list.
-q = Go back to the main menu.
-CTRL + c = Clear terminal and exit.
-    "#;
-    println!("{keybinds_list}");
+    Ok(Setup {
+        query,
+        keymap,
+        mouse,
+        context,
+        api_key,
+        github_token,
+        api_sources,
+        se_sites,
+        forums,
+        subreddits,
+        hn_endpoint,
+        engines,
+        search_options,
+        answer_options,
+        merged,
+        lang,
+        lang_mode,
+        cargo_lock,
+        man_pages,
+        offline_docs,
+    })
 }
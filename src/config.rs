@@ -0,0 +1,845 @@
+use crate::search::cargo_lock::{CargoLock, CargoLockError};
+use crate::search::discourse::Forum;
+use crate::search::engine::{self, Engines};
+use crate::search::lang::{self, Lang, LangMode};
+use crate::search::man_pages::ManPages;
+use crate::search::merge::{Source, Weights};
+use crate::search::offline_docs::OfflineDocs;
+use crate::search::reddit;
+use crate::search::se_sites::{self, SeSite};
+use crate::search::thread::{AnswerOptions, AnswerSort};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+const CONFIG_DIR: &str = "falion";
+const CONFIG_FILE: &str = "config.toml";
+const GITHUB_TOKEN_VAR: &str = "GITHUB_TOKEN";
+
+/// The type of errors Config::load() can return.
+///
+/// * `NoConfigDir` - The config directory of the platform couldn't be determined.
+/// * `ReadFile` - The config file exists but couldn't be read.
+/// * `Parse` - The config file isn't valid toml or doesn't match the expected layout.
+/// * `UnknownSeSite` - A site of the StackExchange section isn't a site of the network.
+/// * `InvalidForum` - A forum of the Discourse section isn't a valid absolute url.
+/// * `InvalidSubreddit` - A subreddit of the Reddit section isn't a valid subreddit name.
+/// * `UnknownEngine` - An engine of the search section isn't one falion can search with.
+/// * `MissingEngineSetting` - An engine of the search section needs a setting that isn't there.
+/// * `UnknownSource` - A source weighed in the merged section isn't one falion gets results from.
+/// * `UnknownLang` - A language of the lang section isn't one the searches can be narrowed down
+/// to.
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to get the config dir of this platform.")]
+    NoConfigDir,
+    #[error("Failed to read the config file at: {path}. Error: {error}")]
+    ReadFile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Failed to parse the config file at: {path}. Error: {error}")]
+    Parse {
+        path: PathBuf,
+        error: toml::de::Error,
+    },
+    #[error("The StackExchange site: {0} in the config file doesn't exist. Ex of sites: unix, superuser, serverfault, askubuntu.")]
+    UnknownSeSite(String),
+    #[error("The Discourse forum: {0} in the config file isn't a valid url. Ex of forums: https://users.rust-lang.org")]
+    InvalidForum(String),
+    #[error("The subreddit: {0} in the config file isn't a valid subreddit name. Ex of subreddits: rust, r/learnprogramming")]
+    InvalidSubreddit(String),
+    #[error("The search engine: {0} in the config file doesn't exist. Engines: duckduckgo, duckduckgo-html, duckduckgo-lite, searxng, brave, mojeek.")]
+    UnknownEngine(String),
+    #[error("The search engine: {engine} in the config file needs the {setting} setting of the search section.")]
+    MissingEngineSetting {
+        engine: String,
+        setting: &'static str,
+    },
+    #[error("The source: {0} in the merged section of the config file doesn't exist. Ex of sources: stackoverflow, ddg-search, github-issues, hacker-news.")]
+    UnknownSource(String),
+    #[error("The language: {0} in the config file isn't known. Ex of languages: rust, python, javascript, go, c++.")]
+    UnknownLang(String),
+}
+
+/// Either a single value or a list of values, so `"ctrl+c"` and `["ctrl+c", "q"]` are both
+/// accepted in the config file.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    /// Get the values as a vector no matter how they were written.
+    pub fn into_vec(self) -> Vec<String> {
+        match self {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }
+    }
+}
+
+/// The keymap section of the config file.
+///
+/// * `preset` - the name of the preset to start from (default, vim or emacs).
+/// * `bindings` - action names mapped to the key chords that trigger them, these replace the
+/// preset's key chords for that action.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct KeymapConfig {
+    pub preset: Option<String>,
+    pub bindings: HashMap<String, OneOrMany>,
+}
+
+/// The cli section of the config file.
+///
+/// * `mouse` - capture the mouse in the cli, so resources can be clicked and content scrolled,
+/// with the downside of the terminal's text selection not working anymore.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct CliConfig {
+    pub mouse: bool,
+}
+
+/// The sources that can get their content from the official StackExchange api instead of
+/// scraping it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiSource {
+    Stackoverflow,
+    Stackexchange,
+}
+
+/// The StackExchange api section of the config file.
+///
+/// * `key` - optional, the app key, it raises the daily quota from 300 to 10,000 requests.
+/// * `sources` - the sources that should use the api instead of scraping.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct StackExchangeApiConfig {
+    pub key: Option<String>,
+    pub sources: Vec<ApiSource>,
+}
+
+/// The GitHub section of the config file.
+///
+/// * `token` - optional, a GitHub token (without any scope) for the GitHub issues source, it raises
+/// the rate limit of the api from 60 to 5,000 requests an hour and is needed for discussions. The
+/// GITHUB_TOKEN environment variable is used when it's not set.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct GithubConfig {
+    pub token: Option<String>,
+}
+
+impl GithubConfig {
+    /// Get the token from the section, or from the environment if the section doesn't have one.
+    pub fn token(&self) -> Option<String> {
+        self.token
+            .clone()
+            .or_else(|| std::env::var(GITHUB_TOKEN_VAR).ok())
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// The StackExchange section of the config file.
+///
+/// * `sites` - the sites of the network to search, by name or host, every site if empty.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct StackExchangeConfig {
+    pub sites: Vec<String>,
+}
+
+impl StackExchangeConfig {
+    /// Get the sites the section selects.
+    ///
+    /// # Errors
+    ///
+    /// * `UnknownSeSite` - One of the sites isn't a site of the network.
+    pub fn sites(&self) -> Result<Vec<&'static SeSite>, ConfigError> {
+        self.sites
+            .iter()
+            .map(|name| {
+                se_sites::find(name).ok_or_else(|| ConfigError::UnknownSeSite(name.clone()))
+            })
+            .collect()
+    }
+}
+
+/// The Discourse section of the config file.
+///
+/// * `forums` - the urls of the Discourse forums to search, the Rust users and internals forums if
+/// empty.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct DiscourseConfig {
+    pub forums: Vec<String>,
+}
+
+impl DiscourseConfig {
+    /// Get the forums the section selects.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidForum` - One of the forums isn't a valid absolute url.
+    pub fn forums(&self) -> Result<Vec<Forum>, ConfigError> {
+        self.forums
+            .iter()
+            .map(|forum| Forum::parse(forum).map_err(|_| ConfigError::InvalidForum(forum.clone())))
+            .collect()
+    }
+}
+
+/// The Reddit section of the config file.
+///
+/// * `subreddits` - the names of the subreddits to search, r/rust and r/learnprogramming if empty.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct RedditConfig {
+    pub subreddits: Vec<String>,
+}
+
+impl RedditConfig {
+    /// Get the names of the subreddits the section selects.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSubreddit` - One of the subreddits isn't a valid subreddit name.
+    pub fn subreddits(&self) -> Result<Vec<String>, ConfigError> {
+        self.subreddits
+            .iter()
+            .map(|sub| {
+                reddit::subreddit(sub).map_err(|_| ConfigError::InvalidSubreddit(sub.clone()))
+            })
+            .collect()
+    }
+}
+
+/// The hacker news section of the config file.
+///
+/// * `endpoint` - optional, another address for the Algolia search api of hacker news, like a
+/// mirror. (ex: https://hn.algolia.com/api/v1)
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct HackerNewsConfig {
+    pub endpoint: Option<String>,
+}
+
+/// The search section of the config file, which engines the sources find their results with.
+///
+/// * `engines` - the engines to try in order, the next one is used when one fails or doesn't find
+/// anything. duckduckgo then duckduckgo-html if empty.
+/// * `searxng_url` - optional, the address of the SearXNG instance, needed by searxng.
+/// * `brave_token` - optional, the subscription token of the brave search api, needed by brave.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct SearchConfig {
+    pub engines: Vec<String>,
+    pub searxng_url: Option<String>,
+    pub brave_token: Option<String>,
+}
+
+impl SearchConfig {
+    /// Get the engines the section selects, in order.
+    ///
+    /// # Errors
+    ///
+    /// * `UnknownEngine` - One of the engines isn't one falion can search with.
+    /// * `MissingEngineSetting` - searxng or brave is selected without its setting.
+    pub fn engines(&self) -> Result<Engines, ConfigError> {
+        if self.engines.is_empty() {
+            return Ok(engine::default_engines());
+        }
+
+        self.engines
+            .iter()
+            .map(
+                |name| -> Result<Arc<dyn engine::SearchEngine>, ConfigError> {
+                    let missing = |setting| ConfigError::MissingEngineSetting {
+                        engine: name.clone(),
+                        setting,
+                    };
+                    match name.to_lowercase().as_str() {
+                        "duckduckgo" => Ok(Arc::new(engine::DuckDuckGo)),
+                        "duckduckgo-html" => Ok(Arc::new(engine::DuckDuckGoHtml::html())),
+                        "duckduckgo-lite" => Ok(Arc::new(engine::DuckDuckGoHtml::lite())),
+                        "mojeek" => Ok(Arc::new(engine::Mojeek)),
+                        "searxng" => match &self.searxng_url {
+                            Some(url) => Ok(Arc::new(engine::Searxng::new(url))),
+                            None => Err(missing("searxng_url")),
+                        },
+                        "brave" => match &self.brave_token {
+                            Some(token) => Ok(Arc::new(engine::Brave::new(token))),
+                            None => Err(missing("brave_token")),
+                        },
+                        _ => Err(ConfigError::UnknownEngine(name.clone())),
+                    }
+                },
+            )
+            .collect()
+    }
+}
+
+/// The man pages section of the config file.
+///
+/// * `tldr_dir` - optional, where the tldr pages are, the tldr-pages repository (or its extracted
+/// archive) or its pages directory. By default the cache of the tldr clients is used if any.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct ManPagesConfig {
+    pub tldr_dir: Option<PathBuf>,
+}
+
+impl ManPagesConfig {
+    /// Get the man pages source reading the tldr pages from where the section says.
+    pub fn man_pages(&self) -> ManPages {
+        match &self.tldr_dir {
+            Some(dir) => ManPages::with_tldr_dir(dir.clone()),
+            None => ManPages::new(),
+        }
+    }
+}
+
+/// The offline docs section of the config file.
+///
+/// * `dir` - optional, the directory with the devdocs bundles (each extracted in its own
+/// directory) and the dash or zeal docsets. By default the falion data directory is used, or the
+/// docsets of zeal or dash if it doesn't exist.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct OfflineDocsConfig {
+    pub dir: Option<PathBuf>,
+}
+
+impl OfflineDocsConfig {
+    /// Get the offline docs source reading the documentation from where the section says.
+    pub fn offline_docs(&self) -> OfflineDocs {
+        match &self.dir {
+            Some(dir) => OfflineDocs::with_docs_dir(dir.clone()),
+            None => OfflineDocs::new(),
+        }
+    }
+}
+
+/// The answers section of the config file, how the answers of StackOverflow and StackExchange
+/// threads are shown.
+///
+/// * `sort` - how to order the answers. (page, score, recency or accepted-first)
+/// * `min_score` - optional, hide the answers with a lower score than this.
+/// * `max_answers` - optional, show at most this many answers per thread.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct AnswersConfig {
+    pub sort: AnswerSort,
+    pub min_score: Option<i64>,
+    pub max_answers: Option<usize>,
+}
+
+impl AnswersConfig {
+    /// Get the answer options the section describes.
+    pub fn options(&self) -> AnswerOptions {
+        AnswerOptions {
+            sort: self.sort,
+            min_score: self.min_score,
+            limit: self.max_answers,
+            ..Default::default()
+        }
+    }
+}
+
+/// The merged section of the config file, the view interleaving the results of every source.
+///
+/// * `show` - show the merged view, in the cli it's the row after the resources.
+/// * `weights` - source names mapped to how much their results count in the merged ranking, the
+/// sources that aren't there keep their default weight.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct MergedConfig {
+    pub show: bool,
+    pub weights: HashMap<String, f64>,
+}
+
+impl MergedConfig {
+    /// Get the weights of the sources the section describes.
+    ///
+    /// # Errors
+    ///
+    /// * `UnknownSource` - One of the weighed sources isn't one falion gets results from.
+    pub fn weights(&self) -> Result<Weights, ConfigError> {
+        self.weights
+            .iter()
+            .try_fold(Weights::new(), |weights, (name, weight)| {
+                match name.to_lowercase().parse::<Source>() {
+                    Ok(source) => Ok(weights.with_weight(source, *weight)),
+                    Err(_) => Err(ConfigError::UnknownSource(name.clone())),
+                }
+            })
+    }
+}
+
+/// The lang section of the config file, the language the searches are narrowed down to when none
+/// is given on the command line.
+///
+/// * `default` - optional, the language of the searches made outside of any project.
+/// * `detect` - detect the language of the project the current directory is in from the files at
+/// its root, like Cargo.toml, package.json or go.mod.
+/// * `mode` - how the web searches are narrowed down to the language. (boost or filter)
+/// * `projects` - directories mapped to the language of the project in them, the closest one to
+/// the current directory wins over the detected language.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct LangConfig {
+    pub default: Option<String>,
+    pub detect: bool,
+    pub mode: LangMode,
+    pub projects: HashMap<PathBuf, String>,
+}
+
+impl LangConfig {
+    /// Get the language of the searches made from a directory: the one of the closest project
+    /// it's in, the detected one or the default one.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory the searches are made from, usually the current one.
+    ///
+    /// # Errors
+    ///
+    /// * `UnknownLang` - One of the languages of the section isn't known.
+    pub fn lang(&self, dir: &Path) -> Result<Option<&'static Lang>, ConfigError> {
+        let find =
+            |name: &String| lang::find(name).ok_or_else(|| ConfigError::UnknownLang(name.clone()));
+        let default = self.default.as_ref().map(find).transpose()?;
+        let projects = self
+            .projects
+            .iter()
+            .map(|(project, name)| Ok((project.as_path(), find(name)?)))
+            .collect::<Result<HashMap<&Path, &'static Lang>, ConfigError>>()?;
+
+        let project = dir
+            .ancestors()
+            .find_map(|ancestor| projects.get(ancestor).copied());
+        let detected = || {
+            if self.detect {
+                lang::detect(dir)
+            } else {
+                None
+            }
+        };
+
+        Ok(project.or_else(detected).or(default))
+    }
+}
+
+/// The cargo lock section of the config file, the versions of the crates of the Rust project the
+/// searches are made from.
+///
+/// * `ignore` - don't read the Cargo.lock of the project, so the queries aren't given the
+/// versions of its crates and the answers about other versions aren't flagged.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct CargoLockConfig {
+    pub ignore: bool,
+}
+
+impl CargoLockConfig {
+    /// Get the crates of the Rust project a directory is in, unless the section ignores them.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - the directory the searches are made from, usually the current one.
+    ///
+    /// # Errors
+    ///
+    /// * `ReadFile` - The Cargo.lock of the project couldn't be read.
+    /// * `Parse` - The Cargo.lock of the project isn't valid.
+    pub fn cargo_lock(&self, dir: &Path) -> Result<Option<CargoLock>, CargoLockError> {
+        if self.ignore {
+            return Ok(None);
+        }
+
+        CargoLock::find(dir)
+            .map(|path| CargoLock::load(&path))
+            .transpose()
+    }
+}
+
+/// Falion's config file, every section is optional.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub cli: CliConfig,
+    pub keymap: KeymapConfig,
+    pub stackexchange: StackExchangeConfig,
+    pub stackexchange_api: StackExchangeApiConfig,
+    pub discourse: DiscourseConfig,
+    pub reddit: RedditConfig,
+    pub hacker_news: HackerNewsConfig,
+    pub search: SearchConfig,
+    pub github: GithubConfig,
+    pub answers: AnswersConfig,
+    pub man_pages: ManPagesConfig,
+    pub offline_docs: OfflineDocsConfig,
+    pub merged: MergedConfig,
+    pub lang: LangConfig,
+    pub cargo_lock: CargoLockConfig,
+}
+
+impl Config {
+    /// Get the default location of the config file. (ex: ~/.config/falion/config.toml)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join(CONFIG_DIR).join(CONFIG_FILE))
+    }
+
+    /// Load the config file. If no path is given the default location is used and a missing file
+    /// there simply means the default config, while a missing file at a given path is an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Optional, the path to the config file.
+    ///
+    /// # Errors
+    ///
+    /// * `NoConfigDir` - The config directory of the platform couldn't be determined.
+    /// * `ReadFile` - The config file exists but couldn't be read.
+    /// * `Parse` - The config file isn't valid toml or doesn't match the expected layout.
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let (path, explicit) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Err(ConfigError::NoConfigDir),
+            },
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(error) => {
+                if !explicit && error.kind() == std::io::ErrorKind::NotFound {
+                    return Ok(Config::default());
+                }
+                return Err(ConfigError::ReadFile { path, error });
+            }
+        };
+
+        match toml::from_str(&content) {
+            Ok(config) => Ok(config),
+            Err(error) => Err(ConfigError::Parse { path, error }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_keymap_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [keymap]
+            preset = "vim"
+
+            [keymap.bindings]
+            exit = "ctrl+q"
+            next-content = ["j", "ctrl+n"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.keymap.preset.as_deref(), Some("vim"));
+        assert_eq!(
+            config.keymap.bindings["exit"].clone().into_vec(),
+            vec!["ctrl+q"]
+        );
+        assert_eq!(
+            config.keymap.bindings["next-content"].clone().into_vec(),
+            vec!["j", "ctrl+n"]
+        );
+    }
+
+    #[test]
+    fn test_parse_stackexchange_api_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [stackexchange_api]
+            key = "app-key"
+            sources = ["stackoverflow"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.stackexchange_api.key.as_deref(), Some("app-key"));
+        assert_eq!(
+            config.stackexchange_api.sources,
+            vec![ApiSource::Stackoverflow]
+        );
+    }
+
+    #[test]
+    fn test_parse_stackexchange_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [stackexchange]
+            sites = ["unix", "superuser.com"]
+            "#,
+        )
+        .unwrap();
+        let hosts: Vec<&str> = config
+            .stackexchange
+            .sites()
+            .unwrap()
+            .iter()
+            .map(|site| site.host)
+            .collect();
+
+        assert_eq!(hosts, ["unix.stackexchange.com", "superuser.com"]);
+
+        let config: Config = toml::from_str("[stackexchange]\nsites = [\"unixx\"]").unwrap();
+        assert!(matches!(
+            config.stackexchange.sites(),
+            Err(ConfigError::UnknownSeSite(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_discourse_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [discourse]
+            forums = ["https://internals.rust-lang.org/", "https://discourse.nixos.org"]
+            "#,
+        )
+        .unwrap();
+        let sites: Vec<String> = config
+            .discourse
+            .forums()
+            .unwrap()
+            .into_iter()
+            .map(|forum| forum.site)
+            .collect();
+
+        assert_eq!(sites, ["internals.rust-lang.org", "discourse.nixos.org"]);
+
+        let config: Config =
+            toml::from_str("[discourse]\nforums = [\"users.rust-lang.org\"]").unwrap();
+        assert!(matches!(
+            config.discourse.forums(),
+            Err(ConfigError::InvalidForum(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_reddit_config() {
+        let config: Config =
+            toml::from_str("[reddit]\nsubreddits = [\"r/Golang\", \"rust_gamedev\"]").unwrap();
+
+        assert_eq!(
+            config.reddit.subreddits().unwrap(),
+            ["golang", "rust_gamedev"]
+        );
+
+        let config: Config = toml::from_str("[reddit]\nsubreddits = [\"r/c++\"]").unwrap();
+        assert!(matches!(
+            config.reddit.subreddits(),
+            Err(ConfigError::InvalidSubreddit(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_hacker_news_config() {
+        let config: Config =
+            toml::from_str("[hacker_news]\nendpoint = \"http://localhost:8080/api/v1\"").unwrap();
+
+        assert_eq!(
+            config.hacker_news.endpoint.as_deref(),
+            Some("http://localhost:8080/api/v1")
+        );
+    }
+
+    #[test]
+    fn test_parse_search_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [search]
+            engines = ["searxng", "DuckDuckGo-Lite", "mojeek"]
+            searxng_url = "https://searx.example.org/"
+            "#,
+        )
+        .unwrap();
+        let names: Vec<&str> = config
+            .search
+            .engines()
+            .unwrap()
+            .iter()
+            .map(|engine| engine.name())
+            .collect();
+
+        assert_eq!(names, ["searxng", "duckduckgo-lite", "mojeek"]);
+        assert_eq!(Config::default().search.engines().unwrap().len(), 2);
+
+        let config: Config = toml::from_str("[search]\nengines = [\"brave\"]").unwrap();
+        assert!(matches!(
+            config.search.engines(),
+            Err(ConfigError::MissingEngineSetting { .. })
+        ));
+
+        let config: Config = toml::from_str("[search]\nengines = [\"google\"]").unwrap();
+        assert!(matches!(
+            config.search.engines(),
+            Err(ConfigError::UnknownEngine(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_answers_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [answers]
+            sort = "accepted-first"
+            min_score = -2
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.answers.options(),
+            AnswerOptions {
+                sort: AnswerSort::AcceptedFirst,
+                min_score: Some(-2),
+                limit: None,
+                cargo_lock: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_man_pages_config() {
+        let config: Config = toml::from_str("[man_pages]\ntldr_dir = \"/home/user/tldr\"").unwrap();
+
+        assert_eq!(
+            config.man_pages.tldr_dir,
+            Some(PathBuf::from("/home/user/tldr"))
+        );
+    }
+
+    #[test]
+    fn test_parse_offline_docs_config() {
+        let config: Config = toml::from_str("[offline_docs]\ndir = \"/home/user/docs\"").unwrap();
+
+        assert_eq!(
+            config.offline_docs.dir,
+            Some(PathBuf::from("/home/user/docs"))
+        );
+    }
+
+    #[test]
+    fn test_parse_merged_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [merged]
+            show = true
+            weights = { reddit = 0.2, Hacker-News = 0 }
+            "#,
+        )
+        .unwrap();
+        let weights = config.merged.weights().unwrap();
+
+        assert!(config.merged.show);
+        assert_eq!(weights.of(Source::Reddit), 0.2);
+        assert_eq!(weights.of(Source::HackerNews), 0.0);
+        assert_eq!(
+            weights.of(Source::StackOverflow),
+            Source::StackOverflow.default_weight()
+        );
+
+        let config: Config = toml::from_str("[merged]\nweights = { so = 2.0 }").unwrap();
+        assert!(matches!(
+            config.merged.weights(),
+            Err(ConfigError::UnknownSource(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_lang_config() {
+        let config: Config = toml::from_str(
+            r#"
+            [lang]
+            default = "Rust"
+            mode = "filter"
+            projects = { "/home/someone/api" = "golang", "/home/someone/api/web" = "ts" }
+            "#,
+        )
+        .unwrap();
+        let lang_of = |dir: &str| {
+            config
+                .lang
+                .lang(Path::new(dir))
+                .unwrap()
+                .map(|lang| lang.name)
+        };
+
+        assert_eq!(config.lang.mode, LangMode::Filter);
+        assert_eq!(lang_of("/home/someone/api/cmd"), Some("go"));
+        assert_eq!(lang_of("/home/someone/api/web/src"), Some("typescript"));
+        assert_eq!(lang_of("/home/someone/notes"), Some("rust"));
+
+        let config: Config = toml::from_str(
+            "[lang]
+default = \"english\"",
+        )
+        .unwrap();
+        assert!(matches!(
+            config.lang.lang(Path::new("/")),
+            Err(ConfigError::UnknownLang(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_cargo_lock_config() {
+        let config: Config = toml::from_str("[cargo_lock]\nignore = true").unwrap();
+
+        assert!(config.cargo_lock.ignore);
+        assert!(config
+            .cargo_lock
+            .cargo_lock(Path::new(env!("CARGO_MANIFEST_DIR")))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_github_config() {
+        let config: Config = toml::from_str("[github]\ntoken = \"ghp_token\"").unwrap();
+
+        assert_eq!(config.github.token().as_deref(), Some("ghp_token"));
+    }
+
+    #[test]
+    fn test_empty_config() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert!(!config.cli.mouse);
+        assert!(config.stackexchange_api.key.is_none());
+        assert!(config.stackexchange_api.sources.is_empty());
+        assert!(config.stackexchange.sites().unwrap().is_empty());
+        assert!(config.discourse.forums().unwrap().is_empty());
+        assert!(config.reddit.subreddits().unwrap().is_empty());
+        assert!(config.keymap.preset.is_none());
+        assert!(config.keymap.bindings.is_empty());
+        assert_eq!(config.answers.options(), AnswerOptions::default());
+        assert!(config.man_pages.tldr_dir.is_none());
+        assert!(config.offline_docs.dir.is_none());
+        assert!(config.github.token.is_none());
+        assert!(!config.merged.show);
+        assert_eq!(config.merged.weights().unwrap(), Weights::new());
+        assert_eq!(config.lang.mode, LangMode::Boost);
+        assert!(!config.cargo_lock.ignore);
+        assert!(config.lang.lang(Path::new("/")).unwrap().is_none());
+    }
+}
@@ -0,0 +1,522 @@
+use crate::search::geeksforgeeks::Article;
+use crate::search::github_gist::Gist;
+use crate::search::man_pages::ManPage;
+use crate::search::metadata::Metadata;
+use crate::search::offline_docs::OfflineDoc;
+use crate::search::rust_docs::Doc;
+use crate::search::thread::{AnswerOptions, Comment, Thread};
+use tokio::task::JoinHandle;
+
+// The mark appended to the tag of the accepted answer
+const ACCEPTED_MARK: &str = "✔ Accepted";
+// The mark appended to the tag of an answer about other versions of the crates of the project
+const OTHER_VERSIONS_MARK: &str = "⚠ Other version:";
+
+/// One element of a content that's gone through one by one, like the question or an answer of a
+/// thread, or a file of a gist.
+///
+/// * `tag` - the title of the element. (ex: Answer 2 [score 15] ✔ Accepted, or
+/// Answer 3 ⚠ Other version: tokio 0.2 (locked: 1.38.0))
+/// * `content` - the element itself.
+/// * `is_accepted` - if the element is the accepted answer, so it can be marked.
+/// * `comments` - the comments on the element, shown collapsed under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub tag: String,
+    pub content: String,
+    pub is_accepted: bool,
+    pub comments: Vec<String>,
+}
+
+/// The content of a result once it's loaded, no matter how it's shown.
+pub trait Loaded: Sized {
+    /// Create the content shown when getting the actual content failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - the message explaining what failed.
+    fn from_error(error: String) -> Self;
+
+    /// Get the metadata of the content, if the source has any.
+    fn metadata(&self) -> Option<&Metadata>;
+
+    /// Get the title the result should be listed under now that its content is loaded: the real
+    /// title if known, followed by the summary of the metadata. (ex: Title [▲ 12 · 3 answers])
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - the title the result was listed under until now, the one from the search.
+    fn row_title(&self, title: &str) -> String {
+        let metadata = match self.metadata() {
+            Some(metadata) => metadata,
+            None => return title.to_string(),
+        };
+        let title = metadata.title.as_deref().unwrap_or(title);
+        let summary = metadata.summary(chrono::Utc::now().timestamp());
+
+        if summary.is_empty() {
+            title.to_string()
+        } else {
+            format!("{} [{}]", title, summary)
+        }
+    }
+}
+
+/// Content made of elements the cli and the gui go through one by one.
+pub trait Tagged: Loaded {
+    /// Get the elements to show, in order, with their tags.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - how to order and filter the answers of a thread, ignored by other contents.
+    fn elements(&self, options: &AnswerOptions) -> Vec<Element>;
+}
+
+/// Content shown all at once, as a single page of text.
+pub trait Paged: Loaded {
+    /// Get the text of the page.
+    fn text(&self) -> &str;
+}
+
+/// Await the content of a result, turning any error into a content that shows it.
+///
+/// # Arguments
+///
+/// * `handle` - the handle of the task getting the content.
+#[tracing::instrument(skip_all)]
+pub async fn await_content<T, E>(handle: &mut JoinHandle<Result<T, E>>) -> T
+where
+    T: Loaded,
+    E: std::fmt::Display,
+{
+    match handle.await {
+        Ok(handled) => match handled {
+            Ok(content) => content,
+            Err(error) => {
+                tracing::error!(
+                    "There was an error getting the contetn for this a result. Error: {}",
+                    error
+                );
+                T::from_error(format!(
+                    "There has been an error getting the content for this result. Error: {}",
+                    error
+                ))
+            }
+        },
+        Err(error) => {
+            tracing::error!(
+                "There was an error handeling the future for a result. Error: {}",
+                error
+            );
+            T::from_error(format!(
+                "There has been an error handeling the future for this result. Error: {}",
+                error
+            ))
+        }
+    }
+}
+
+impl Loaded for Thread {
+    fn from_error(error: String) -> Self {
+        Thread {
+            question: error,
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Tagged for Thread {
+    fn elements(&self, options: &AnswerOptions) -> Vec<Element> {
+        let mut elements = Vec::with_capacity(self.answers.len() + 1);
+        elements.push(Element {
+            tag: "Question".to_string(),
+            content: self.question.clone(),
+            is_accepted: false,
+            comments: format_comments(&self.comments),
+        });
+
+        elements.extend(self.arranged(options).into_iter().map(|answer| {
+            // answers keep the number they have on the page no matter how they're sorted
+            let number = self
+                .answers
+                .iter()
+                .position(|other| std::ptr::eq(other, answer))
+                .unwrap_or_default()
+                + 1;
+            let mut tag = format!("Answer {}", number);
+            if let Some(score) = answer.score {
+                tag.push_str(&format!(" [score {}]", score));
+            }
+            if answer.is_accepted {
+                tag.push(' ');
+                tag.push_str(ACCEPTED_MARK);
+            }
+            // flag the answers about another major version of a crate of the project
+            if let Some(cargo_lock) = &options.cargo_lock {
+                let other_versions = cargo_lock
+                    .other_versions(&answer.content)
+                    .iter()
+                    .map(|other| other.to_string())
+                    .collect::<Vec<_>>();
+                if !other_versions.is_empty() {
+                    tag.push(' ');
+                    tag.push_str(OTHER_VERSIONS_MARK);
+                    tag.push(' ');
+                    tag.push_str(&other_versions.join(", "));
+                }
+            }
+
+            Element {
+                tag,
+                content: answer.content.clone(),
+                is_accepted: answer.is_accepted,
+                comments: format_comments(&answer.comments),
+            }
+        }));
+
+        elements
+    }
+}
+
+/// Format the comments of a post to be shown as is, with their score if known.
+/// (ex: [score 3] Use an Rc instead.)
+fn format_comments(comments: &[Comment]) -> Vec<String> {
+    comments
+        .iter()
+        .map(|comment| match comment.score {
+            Some(score) => format!("[score {}] {}", score, comment.content),
+            None => comment.content.clone(),
+        })
+        .collect()
+}
+
+impl Loaded for Gist {
+    fn from_error(error: String) -> Self {
+        Gist {
+            files: vec![error],
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Tagged for Gist {
+    fn elements(&self, _options: &AnswerOptions) -> Vec<Element> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| Element {
+                tag: format!("File {}", index + 1),
+                content: file.clone(),
+                is_accepted: false,
+                comments: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+impl Loaded for Doc {
+    fn from_error(error: String) -> Self {
+        Doc {
+            description: error,
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Tagged for Doc {
+    fn elements(&self, _options: &AnswerOptions) -> Vec<Element> {
+        let mut elements = Vec::with_capacity(self.examples.len() + 2);
+        if !self.signature.is_empty() {
+            elements.push(Element {
+                tag: "Signature".to_string(),
+                content: self.signature.clone(),
+                is_accepted: false,
+                comments: Vec::new(),
+            });
+        }
+        elements.push(Element {
+            tag: "Description".to_string(),
+            content: self.description.clone(),
+            is_accepted: false,
+            comments: Vec::new(),
+        });
+        elements.extend(
+            self.examples
+                .iter()
+                .enumerate()
+                .map(|(index, example)| Element {
+                    tag: format!("Example {}", index + 1),
+                    content: example.clone(),
+                    is_accepted: false,
+                    comments: Vec::new(),
+                }),
+        );
+
+        elements
+    }
+}
+
+impl Loaded for Article {
+    fn from_error(error: String) -> Self {
+        Article {
+            content: error,
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Paged for Article {
+    fn text(&self) -> &str {
+        &self.content
+    }
+}
+
+impl Loaded for ManPage {
+    fn from_error(error: String) -> Self {
+        ManPage {
+            content: error,
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Paged for ManPage {
+    fn text(&self) -> &str {
+        &self.content
+    }
+}
+
+impl Loaded for OfflineDoc {
+    fn from_error(error: String) -> Self {
+        OfflineDoc {
+            content: error,
+            ..Default::default()
+        }
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        Some(&self.metadata)
+    }
+}
+
+impl Paged for OfflineDoc {
+    fn text(&self) -> &str {
+        &self.content
+    }
+}
+
+impl Loaded for String {
+    fn from_error(error: String) -> Self {
+        error
+    }
+
+    fn metadata(&self) -> Option<&Metadata> {
+        None
+    }
+}
+
+impl Paged for String {
+    fn text(&self) -> &str {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::cargo_lock::CargoLock;
+    use crate::search::thread::{Answer, AnswerSort};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_thread_elements() {
+        let thread = Thread {
+            question: "question".to_string(),
+            answers: vec![
+                Answer {
+                    content: "first".to_string(),
+                    score: Some(2),
+                    ..Default::default()
+                },
+                Answer {
+                    content: "accepted".to_string(),
+                    score: Some(5),
+                    is_accepted: true,
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let options = AnswerOptions {
+            sort: AnswerSort::AcceptedFirst,
+            ..Default::default()
+        };
+        let tags: Vec<String> = thread
+            .elements(&options)
+            .into_iter()
+            .map(|element| element.tag)
+            .collect();
+
+        assert_eq!(
+            tags,
+            [
+                "Question",
+                "Answer 2 [score 5] ✔ Accepted",
+                "Answer 1 [score 2]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thread_other_versions() {
+        let lock = CargoLock::parse(
+            r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["tokio"]
+
+[[package]]
+name = "tokio"
+version = "1.38.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+        )
+        .unwrap();
+        let thread = Thread {
+            answers: vec![
+                Answer {
+                    content: "Add tokio = \"0.2\" and use tokio::run".to_string(),
+                    ..Default::default()
+                },
+                Answer {
+                    content: "With tokio 1.x use #[tokio::main]".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+        let options = AnswerOptions {
+            cargo_lock: Some(Arc::new(lock)),
+            ..Default::default()
+        };
+        let tags: Vec<String> = thread
+            .elements(&options)
+            .into_iter()
+            .map(|element| element.tag)
+            .collect();
+
+        assert_eq!(
+            tags,
+            [
+                "Question",
+                "Answer 1 ⚠ Other version: tokio 0.2 (locked: 1.38.0)",
+                "Answer 2"
+            ]
+        );
+        // without the Cargo.lock nothing is flagged
+        assert_eq!(
+            thread.elements(&AnswerOptions::default())[1].tag,
+            "Answer 1"
+        );
+    }
+
+    #[test]
+    fn test_thread_comments() {
+        let thread = Thread {
+            comments: vec![Comment {
+                content: "What did you try?".to_string(),
+                score: Some(3),
+            }],
+            answers: vec![Answer {
+                comments: vec![Comment {
+                    content: "Works".to_string(),
+                    score: None,
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let elements = thread.elements(&AnswerOptions::default());
+
+        assert_eq!(elements[0].comments, ["[score 3] What did you try?"]);
+        assert_eq!(elements[1].comments, ["Works"]);
+    }
+
+    #[test]
+    fn test_files_elements() {
+        let gist = Gist {
+            files: vec!["fn main() {}".to_string(), "[package]".to_string()],
+            ..Default::default()
+        };
+        let elements = gist.elements(&AnswerOptions::default());
+
+        assert_eq!(elements[1].tag, "File 2");
+        assert_eq!(elements[1].content, "[package]");
+    }
+
+    #[test]
+    fn test_doc_elements() {
+        let doc = Doc {
+            signature: "pub fn retain<F>(&mut self, f: F)".to_string(),
+            description: "Retains only the elements specified by the predicate.".to_string(),
+            examples: vec!["vec.retain(|&x| x % 2 == 0);".to_string()],
+            ..Default::default()
+        };
+        let tags: Vec<String> = doc
+            .elements(&AnswerOptions::default())
+            .into_iter()
+            .map(|element| element.tag)
+            .collect();
+
+        assert_eq!(tags, ["Signature", "Description", "Example 1"]);
+        assert_eq!(
+            Doc::from_error("error".to_string()).elements(&AnswerOptions::default())[0].tag,
+            "Description"
+        );
+    }
+
+    #[test]
+    fn test_row_title() {
+        let thread = Thread {
+            metadata: Metadata {
+                title: Some("Lifetimes in Rust".to_string()),
+                score: Some(12),
+                answer_count: Some(3),
+                is_answered: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let article = Article::from_error("error".to_string());
+
+        assert_eq!(
+            thread.row_title("lifetimes in rust"),
+            "Lifetimes in Rust [▲ 12 · 3 answers ✔]"
+        );
+        assert_eq!(article.row_title("rust basics"), "rust basics");
+        assert_eq!("page".to_string().row_title("rust basics"), "rust basics");
+    }
+}
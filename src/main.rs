@@ -1,5 +1,7 @@
 use std::io::{stdout, IsTerminal};
 mod cli;
+mod config;
+mod content;
 mod search;
 mod ui;
 mod util;
@@ -7,6 +9,11 @@ mod util;
 /// Main Falion execution
 #[tokio::main]
 async fn main() {
+    // subcommands only print something, so they're run no matter where the output goes
+    if cli::run_command() {
+        return;
+    }
+
     // If the app is run from a terminal run the cli, otherwise the gui
     if stdout().is_terminal() {
         match util::is_parent_explorer() {
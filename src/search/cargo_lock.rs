@@ -0,0 +1,535 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const CARGO_LOCK: &str = "Cargo.lock";
+// the characters that can be between the name of a crate and its version when a text mentions
+// them. (ex: tokio 0.2, tokio = "0.2", tokio = { version = "0.2" }, docs.rs/tokio/0.2.22)
+const VERSION_SEP: &str = " \t=\"'{}:@/";
+const VERSION_KEY: &str = "version";
+// how far after the name of a crate its version can be
+const MAX_SEP_LEN: usize = 20;
+
+/// The type of errors CargoLock::load() can return.
+///
+/// * `ReadFile` - The Cargo.lock exists but couldn't be read.
+/// * `Parse` - The Cargo.lock isn't valid toml or isn't laid out like a Cargo.lock.
+#[derive(Error, Debug)]
+pub enum CargoLockError {
+    #[error("Failed to read the Cargo.lock at: {path}. Error: {error}")]
+    ReadFile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Failed to parse the Cargo.lock. Error: {0}")]
+    Parse(toml::de::Error),
+}
+
+/// A package of a Cargo.lock, the workspace members are the ones without a source.
+#[derive(Deserialize, Debug)]
+struct LockPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    #[serde(default)]
+    dependencies: Vec<String>,
+}
+
+/// A Cargo.lock, only its packages are needed.
+#[derive(Deserialize, Debug)]
+struct LockFile {
+    #[serde(default)]
+    package: Vec<LockPackage>,
+}
+
+/// A crate the project depends on and the version its Cargo.lock pins it to.
+///
+/// * `name` - the name of the crate. (ex: tokio)
+/// * `version` - the version the crate is pinned to. (ex: 1.38.0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedCrate {
+    pub name: String,
+    pub version: String,
+}
+
+impl LockedCrate {
+    /// Get the versions the pinned one is compatible with, the way cargo sees it: the major
+    /// version, or the minor one for the 0.x versions. (ex: 1 for 1.38.0, 0.14 for 0.14.28)
+    pub fn compatible(&self) -> String {
+        compatible(&self.version)
+    }
+}
+
+/// A mention of another version of a crate than the one it's pinned to, not compatible with it.
+///
+/// * `name` - the name of the crate. (ex: tokio)
+/// * `mentioned` - the version mentioned. (ex: 0.2)
+/// * `locked` - the version the Cargo.lock pins the crate to. (ex: 1.38.0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherVersion {
+    pub name: String,
+    pub mentioned: String,
+    pub locked: String,
+}
+
+impl std::fmt::Display for OtherVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} (locked: {})",
+            self.name, self.mentioned, self.locked
+        )
+    }
+}
+
+/// The crates a Rust project depends on directly, with the versions its Cargo.lock pins them to.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::cargo_lock::CargoLock;
+///
+/// let lock = CargoLock::parse(r#"
+/// [[package]]
+/// name = "app"
+/// version = "0.1.0"
+/// dependencies = ["tokio"]
+///
+/// [[package]]
+/// name = "tokio"
+/// version = "1.38.0"
+/// source = "registry+https://github.com/rust-lang/crates.io-index"
+/// "#).unwrap();
+///
+/// assert_eq!(lock.crates_in("tokio spawn blocking")[0].version, "1.38.0");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CargoLock {
+    crates: Vec<LockedCrate>,
+}
+
+impl CargoLock {
+    /// Parse a Cargo.lock and keep the crates the members of the workspace depend on, the ones
+    /// coming from a registry or a git repository. Every crate is kept when it has no member.
+    ///
+    /// # Arguments
+    ///
+    /// * `lock` - The content of the Cargo.lock.
+    ///
+    /// # Errors
+    ///
+    /// * `Parse` - The Cargo.lock isn't valid toml or isn't laid out like a Cargo.lock.
+    pub fn parse(lock: &str) -> Result<CargoLock, CargoLockError> {
+        let packages = match toml::from_str::<LockFile>(lock) {
+            Ok(lock) => lock.package,
+            Err(error) => {
+                tracing::error!("The Cargo.lock isn't valid. Error: {}", &error);
+                return Err(CargoLockError::Parse(error));
+            }
+        };
+        let (members, dependencies): (Vec<&LockPackage>, Vec<&LockPackage>) = packages
+            .iter()
+            .partition(|package| package.source.is_none());
+
+        // the dependencies of the members are written as: name, name version or
+        // name version (source), the version is only there when more than one is locked
+        let direct: Vec<(&str, Option<&str>)> = members
+            .iter()
+            .flat_map(|member| &member.dependencies)
+            .map(|dependency| {
+                let mut parts = dependency.split_whitespace();
+                (parts.next().unwrap_or_default(), parts.next())
+            })
+            .collect();
+
+        let mut crates: Vec<LockedCrate> = dependencies
+            .into_iter()
+            .filter(|package| {
+                members.is_empty()
+                    || direct.iter().any(|(name, version)| {
+                        *name == package.name
+                            && version.iter().all(|version| *version == package.version)
+                    })
+            })
+            .map(|package| LockedCrate {
+                name: package.name.clone(),
+                version: package.version.clone(),
+            })
+            .collect();
+        crates.sort_by(|a, b| a.name.cmp(&b.name));
+        // when a crate is locked at more than one version the first one is kept
+        crates.dedup_by(|a, b| a.name == b.name);
+
+        Ok(CargoLock { crates })
+    }
+
+    /// Load a Cargo.lock.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the Cargo.lock.
+    ///
+    /// # Errors
+    ///
+    /// * `ReadFile` - The Cargo.lock couldn't be read.
+    /// * `Parse` - The Cargo.lock isn't valid toml or isn't laid out like a Cargo.lock.
+    pub fn load(path: &Path) -> Result<CargoLock, CargoLockError> {
+        let lock = match std::fs::read_to_string(path) {
+            Ok(lock) => lock,
+            Err(error) => {
+                tracing::error!("Failed to read {}. Error: {}", path.display(), &error);
+                return Err(CargoLockError::ReadFile {
+                    path: path.to_path_buf(),
+                    error,
+                });
+            }
+        };
+
+        Self::parse(&lock)
+    }
+
+    /// Find the Cargo.lock of the project a directory is in, the directory and then its parents
+    /// are checked so the one of the workspace is found from any of its crates.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to start from, usually the current one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use falion::search::cargo_lock::CargoLock;
+    ///
+    /// let lock = CargoLock::find(&std::env::current_dir().unwrap())
+    ///     .map(|path| CargoLock::load(&path));
+    /// ```
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(CARGO_LOCK))
+            .find(|path| path.is_file())
+    }
+
+    /// Get the crates a query is about, the ones it has the name of as a word.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - What to search for.
+    pub fn crates_in(&self, query: &str) -> Vec<&LockedCrate> {
+        let words: Vec<String> = query
+            .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+            .map(normalize)
+            .collect();
+
+        self.crates
+            .iter()
+            .filter(|locked| words.contains(&normalize(&locked.name)))
+            .collect()
+    }
+
+    /// Get the query with the versions compatible with the pinned ones added for the crates it's
+    /// about, unless it already gives a version for them. The compatible versions are used
+    /// instead of the pinned ones so the results aren't narrowed down to a single patch.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - What to search for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::cargo_lock::CargoLock;
+    ///
+    /// let lock = CargoLock::parse(r#"
+    /// [[package]]
+    /// name = "hyper"
+    /// version = "0.14.28"
+    /// source = "registry+https://github.com/rust-lang/crates.io-index"
+    /// "#).unwrap();
+    ///
+    /// assert_eq!(lock.annotate("hyper server graceful shutdown"), "hyper server graceful shutdown hyper 0.14");
+    /// assert_eq!(lock.annotate("hyper 1.0 server"), "hyper 1.0 server");
+    /// ```
+    pub fn annotate(&self, query: &str) -> String {
+        let annotations: Vec<String> = self
+            .crates_in(query)
+            .into_iter()
+            .filter(|locked| mentioned_versions(query, &locked.name).is_empty())
+            .map(|locked| format!("{} {}", locked.name, locked.compatible()))
+            .collect();
+
+        if annotations.is_empty() {
+            query.to_string()
+        } else {
+            format!("{} {}", query, annotations.join(" "))
+        }
+    }
+
+    /// Get the mentions of versions of the crates the text is about that aren't compatible with
+    /// the pinned ones. A text that also mentions a compatible version isn't flagged for that
+    /// crate, it's likely comparing them.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to look in, like an answer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::cargo_lock::CargoLock;
+    ///
+    /// let lock = CargoLock::parse(r#"
+    /// [[package]]
+    /// name = "tokio"
+    /// version = "1.38.0"
+    /// source = "registry+https://github.com/rust-lang/crates.io-index"
+    /// "#).unwrap();
+    /// let answer = "Add tokio = { version = \"0.2\", features = [\"full\"] } to your Cargo.toml";
+    ///
+    /// assert_eq!(lock.other_versions(answer)[0].to_string(), "tokio 0.2 (locked: 1.38.0)");
+    /// assert!(lock.other_versions("Since tokio 1.0 it's tokio::spawn").is_empty());
+    /// ```
+    pub fn other_versions(&self, text: &str) -> Vec<OtherVersion> {
+        self.crates
+            .iter()
+            .filter_map(|locked| {
+                let versions = mentioned_versions(text, &locked.name);
+                let compatible = locked.compatible();
+                if versions
+                    .iter()
+                    .any(|version| compatible_with(version, &compatible))
+                {
+                    return None;
+                }
+
+                versions.into_iter().next().map(|mentioned| OtherVersion {
+                    name: locked.name.clone(),
+                    mentioned,
+                    locked: locked.version.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Get the name of a crate the way cargo compares them, in lowercase and with `_` for `-`.
+///
+/// # Arguments
+///
+/// * `name` - The name of the crate.
+fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase().replace('-', "_")
+}
+
+/// Get the versions a version is compatible with, the way cargo sees it: the major version, or
+/// the minor one for the 0.x versions. (ex: 1 for 1.38.0, 0.14 for 0.14.28, 0.0.3 for 0.0.3)
+///
+/// # Arguments
+///
+/// * `version` - The version, it can be partial. (ex: 0.2)
+fn compatible(version: &str) -> String {
+    let parts: Vec<&str> = version
+        .split(['.', '-', '+'])
+        .take(3)
+        .map(|part| part.trim_start_matches('0'))
+        .map(|part| if part.is_empty() { "0" } else { part })
+        .collect();
+    let significant = parts
+        .iter()
+        .position(|part| *part != "0")
+        .unwrap_or(parts.len().saturating_sub(1));
+
+    parts[..=significant].join(".")
+}
+
+/// Checks if a mentioned version is compatible with the given compatible version. A version
+/// missing its minor version is compatible with any 0.x version. (ex: 0 with 0.14)
+///
+/// # Arguments
+///
+/// * `version` - The version mentioned. (ex: 1.0)
+/// * `compatible_version` - What the pinned version is compatible with. (ex: 1)
+fn compatible_with(version: &str, compatible_version: &str) -> bool {
+    let version = compatible(version);
+    version == compatible_version
+        || compatible_version
+            .strip_prefix(&version)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Get the versions of a crate a text mentions right after the name of the crate, like in
+/// `tokio 0.2`, `tokio = "0.2"`, `tokio = { version = "0.2" }`, `tokio@0.2.1`, `tokio v0.2` or
+/// `docs.rs/tokio/0.2.22`. A version without a dot has to be quoted or prefixed by v, otherwise
+/// it's just a number.
+///
+/// # Arguments
+///
+/// * `text` - The text to look in.
+/// * `name` - The name of the crate.
+fn mentioned_versions(text: &str, name: &str) -> Vec<String> {
+    // the ascii lowercase has the same byte indexes as the text
+    let lowercase = normalize(text);
+    let name = normalize(name);
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    lowercase
+        .match_indices(&name)
+        .filter(|(start, _)| !lowercase[..*start].ends_with(is_name_char))
+        .filter_map(|(start, _)| {
+            let after = &lowercase[start + name.len()..];
+            if after.starts_with(is_name_char) {
+                return None;
+            }
+
+            // skip what's between the name and the version
+            let mut rest = after.trim_start_matches(|c| VERSION_SEP.contains(c));
+            if let Some(stripped) = rest.strip_prefix(VERSION_KEY) {
+                rest = stripped.trim_start_matches(|c| VERSION_SEP.contains(c));
+            }
+            let sep = &after[..after.len() - rest.len()];
+            let prefixed = rest.starts_with('v');
+            let rest = rest.strip_prefix('v').unwrap_or(rest);
+            if sep.len() > MAX_SEP_LEN || sep.contains('\n') {
+                return None;
+            }
+
+            let version: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            let version = version.trim_end_matches('.');
+            let quoted = sep.contains(['"', '\'']);
+            if version.is_empty() || !(version.contains('.') || quoted || prefixed) {
+                return None;
+            }
+
+            Some(version.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCK: &str = r#"
+# This file is automatically @generated by Cargo.
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = [
+ "hyper 0.14.28",
+ "serde_json",
+ "tokio",
+]
+
+[[package]]
+name = "bytes"
+version = "1.6.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "hyper"
+version = "0.14.28"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "hyper"
+version = "1.3.1"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "serde_json"
+version = "1.0.117"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "tokio"
+version = "1.38.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn test_parse() {
+        let lock = CargoLock::parse(LOCK).unwrap();
+
+        assert_eq!(
+            lock.crates
+                .iter()
+                .map(|locked| (locked.name.as_str(), locked.version.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("hyper", "0.14.28"),
+                ("serde_json", "1.0.117"),
+                ("tokio", "1.38.0")
+            ]
+        );
+        assert_eq!(lock.crates_in("serde-json from_str")[0].version, "1.0.117");
+        assert!(lock.crates_in("bytes from_static").is_empty());
+        assert!(matches!(
+            CargoLock::parse("[[package]]\nname = 1"),
+            Err(CargoLockError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_compatible() {
+        assert_eq!(compatible("1.38.0"), "1");
+        assert_eq!(compatible("0.14.28"), "0.14");
+        assert_eq!(compatible("0.0.3"), "0.0.3");
+        assert_eq!(compatible("0.2"), "0.2");
+        assert_eq!(compatible("1.0.0-beta.1"), "1");
+        assert!(compatible_with("1.0", "1"));
+        assert!(compatible_with("0", "0.14"));
+        assert!(!compatible_with("0.2", "1"));
+        assert!(!compatible_with("0.1", "0.14"));
+    }
+
+    #[test]
+    fn test_mentioned_versions() {
+        assert_eq!(mentioned_versions("Use Tokio 0.2.", "tokio"), ["0.2"]);
+        assert_eq!(mentioned_versions("tokio = \"1\"", "tokio"), ["1"]);
+        assert_eq!(
+            mentioned_versions("serde-json = { version = \"1.0\" }", "serde_json"),
+            ["1.0"]
+        );
+        assert_eq!(
+            mentioned_versions("https://docs.rs/tokio/0.2.22/tokio/", "tokio"),
+            ["0.2.22"]
+        );
+        assert_eq!(mentioned_versions("tokio@v1.38", "tokio"), ["1.38"]);
+        assert!(mentioned_versions("tokio 2 times", "tokio").is_empty());
+        assert!(mentioned_versions("tokio-util 0.7", "tokio").is_empty());
+        assert!(mentioned_versions("mytokio 0.7", "tokio").is_empty());
+    }
+
+    #[test]
+    fn test_annotate_and_flag() {
+        let lock = CargoLock::parse(LOCK).unwrap();
+
+        assert_eq!(
+            lock.annotate("tokio spawn blocking"),
+            "tokio spawn blocking tokio 1"
+        );
+        assert_eq!(lock.annotate("sort a vec"), "sort a vec");
+        assert_eq!(
+            lock.other_versions("With hyper = \"0.13\" and tokio 0.2 use Server::bind"),
+            [
+                OtherVersion {
+                    name: "hyper".to_string(),
+                    mentioned: "0.13".to_string(),
+                    locked: "0.14.28".to_string()
+                },
+                OtherVersion {
+                    name: "tokio".to_string(),
+                    mentioned: "0.2".to_string(),
+                    locked: "1.38.0".to_string()
+                }
+            ]
+        );
+        assert!(lock
+            .other_versions("tokio 0.2 is old, in tokio 1.0 it's")
+            .is_empty());
+    }
+}
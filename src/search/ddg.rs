@@ -1,14 +1,15 @@
+use super::engine::{self, Engines, SearchHit};
 use super::util;
+use std::collections::HashSet;
 use thiserror::Error;
 
-const BASE_ADDRESS: &str = "https://duckduckgo.com/?q={QUERY}%20site%3A{SITE}&ia=web";
-const BASE_ADDRESS_MINUS_SITE: &str = "https://duckduckgo.com/?q={QUERY}&ia=web";
-const ALLOWED_CHARS_IN_SITE: &str = "abcdefghijklmnopqrstuvwxyz1234567890.-/";
-const LINKS_URL_SPLIT1: &str = "id=\"deep_preload_link\" rel=\"preload\" as=\"script\" href=\"";
-const LINKS_URL_SPLIT2: &str = "\"><script async id=\"deep_preload_script\"";
-const LINKS_SPLIT1: &str = "{\"en\":[\"";
-const LINKS_SPLIT2: &str = "\"]});";
-const LINKS_SEP: &str = "\",\"";
+const ALLOWED_CHARS_IN_SITE: &str = "abcdefghijklmnopqrstuvwxyz1234567890.-_/";
+// query parameters that only track where a link was clicked, the ones starting with utm_ too
+const TRACKING_PARAMS: [&str; 11] = [
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "mc_cid", "mc_eid", "ref_src",
+    "_hsenc", "_hsmi",
+];
+const STACKOVERFLOW_HOST: &str = "stackoverflow.com";
 
 /// The type of errors the ddg::get_links() function can return.
 ///
@@ -17,7 +18,10 @@ const LINKS_SEP: &str = "\",\"";
 /// * `InvalidRequest` - Reqwest could not process the request due to rate limiting, bad internet
 /// etc.
 /// * `NoResults` - No results wore found for the provided query and site.
+/// * `LayoutChanged` - The page of the search engine isn't laid out the way it used to be, so the
+/// results couldn't be found in it.
 /// * `ErrorCode` - The search returned an error code.
+/// * `Duplicates` - Every result was already found by another source, see merge::dedup_results.
 #[derive(Error, Debug)]
 pub enum DdgError {
     #[error("The given site: {0} it's not valid.")]
@@ -32,14 +36,270 @@ pub enum DdgError {
     InvalidResponseBody(reqwest::Error),
     #[error("Failed to get any results for the provided query (and site). Error at: {at} | with index: {index}")]
     NoResults { at: String, index: usize },
+    #[error("The results page of {engine} changed its layout, the results couldn't be found in it. Error at: {at}")]
+    LayoutChanged { engine: String, at: String },
     #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
     ErrorCode(reqwest::StatusCode),
+    #[error("Every result found was already listed by another resource.")]
+    Duplicates,
 }
 
-/// Get search results from duckduckgo
+/// How much explicit content the search engine lets through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeSearch {
+    Strict,
+    Moderate,
+    Off,
+}
+
+impl SafeSearch {
+    /// Every level of safe search, from the strictest one.
+    pub const ALL: [SafeSearch; 3] = [SafeSearch::Strict, SafeSearch::Moderate, SafeSearch::Off];
+
+    /// Get the name of the level, as accepted by from_str. (ex: moderate)
+    pub fn name(&self) -> &'static str {
+        match self {
+            SafeSearch::Strict => "strict",
+            SafeSearch::Moderate => "moderate",
+            SafeSearch::Off => "off",
+        }
+    }
+}
+
+impl std::str::FromStr for SafeSearch {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        SafeSearch::ALL
+            .into_iter()
+            .find(|level| level.name() == name)
+            .ok_or_else(|| format!("Unknown safe search level: {}", name))
+    }
+}
+
+impl std::fmt::Display for SafeSearch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How recent the results have to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeRange {
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl TimeRange {
+    /// Every time range, from the shortest one.
+    pub const ALL: [TimeRange; 4] = [
+        TimeRange::Day,
+        TimeRange::Week,
+        TimeRange::Month,
+        TimeRange::Year,
+    ];
+
+    /// Get the name of the time range, as accepted by from_str. (ex: year)
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeRange::Day => "day",
+            TimeRange::Week => "week",
+            TimeRange::Month => "month",
+            TimeRange::Year => "year",
+        }
+    }
+}
+
+impl std::str::FromStr for TimeRange {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        TimeRange::ALL
+            .into_iter()
+            .find(|range| range.name() == name)
+            .ok_or_else(|| format!("Unknown time range: {}", name))
+    }
+}
+
+impl std::fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The options every search is made with, on top of its query and sites. The region, safe search
+/// and time range are given to the engines that support them, the excluded sites, exact phrases
+/// and file types are added to the query as operators. The sources that search the web take them
+/// with `with_options` and make every one of their searches with them.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::ddg::{DdgQuery, TimeRange};
+///
+/// let options = DdgQuery::new()
+///     .with_region("us-en")
+///     .with_time_range(TimeRange::Year)
+///     .with_excluded_site("w3schools.com")
+///     .with_phrase("borrowed value does not live long enough");
+///
+/// assert_eq!(options.region.as_deref(), Some("us-en"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DdgQuery {
+    /// The region of the results, as a duckduckgo `kl` code. (ex: us-en, de-de)
+    pub region: Option<String>,
+    pub safe_search: Option<SafeSearch>,
+    pub time_range: Option<TimeRange>,
+    /// Sites the results can't be on, or on their subdomains. (ex: w3schools.com)
+    pub excluded_sites: Vec<String>,
+    /// Phrases the results have to contain as they are.
+    pub phrases: Vec<String>,
+    /// Extensions of the files the results have to be, any of them. (ex: pdf)
+    pub file_types: Vec<String>,
+}
+
+impl DdgQuery {
+    /// Create options that don't change the search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only get results of a region.
+    ///
+    /// # Arguments
+    ///
+    /// * `region` - The duckduckgo `kl` code of the region. (ex: us-en, wt-wt for no region)
+    pub fn with_region(mut self, region: &str) -> Self {
+        self.region = Some(region.to_lowercase());
+        self
+    }
+
+    /// Filter the explicit results.
+    ///
+    /// # Arguments
+    ///
+    /// * `safe_search` - How much explicit content is let through.
+    pub fn with_safe_search(mut self, safe_search: SafeSearch) -> Self {
+        self.safe_search = Some(safe_search);
+        self
+    }
+
+    /// Only get results from a recent time range.
+    ///
+    /// # Arguments
+    ///
+    /// * `time_range` - How recent the results have to be.
+    pub fn with_time_range(mut self, time_range: TimeRange) -> Self {
+        self.time_range = Some(time_range);
+        self
+    }
+
+    /// Leave out the results of a site and its subdomains.
+    ///
+    /// # Arguments
+    ///
+    /// * `site` - The site to leave out. (ex: w3schools.com)
+    pub fn with_excluded_site(mut self, site: &str) -> Self {
+        self.excluded_sites.push(site.to_lowercase());
+        self
+    }
+
+    /// Only get results that contain the phrase as it is.
+    ///
+    /// # Arguments
+    ///
+    /// * `phrase` - The exact phrase.
+    pub fn with_phrase(mut self, phrase: &str) -> Self {
+        self.phrases.push(phrase.to_string());
+        self
+    }
+
+    /// Only get files with the extension, or any of the other file types given.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_type` - The extension of the files. (ex: pdf)
+    pub fn with_file_type(mut self, file_type: &str) -> Self {
+        self.file_types
+            .push(file_type.trim_start_matches('.').to_lowercase());
+        self
+    }
+
+    /// Get the operators to add to the query: the exact phrases, the file types and the excluded
+    /// sites. (ex: "exact phrase" filetype:pdf -site:w3schools.com)
+    fn operators(&self) -> String {
+        let phrases = self
+            .phrases
+            .iter()
+            .map(|phrase| format!("\"{}\"", phrase.replace('"', "")));
+        let file_types = self
+            .file_types
+            .iter()
+            .map(|file_type| format!("filetype:{}", file_type))
+            .collect::<Vec<String>>()
+            .join(" OR ");
+        let excluded_sites = self
+            .excluded_sites
+            .iter()
+            .map(|site| format!("-site:{}", site));
+
+        phrases
+            .chain(Some(file_types).filter(|file_types| !file_types.is_empty()))
+            .chain(excluded_sites)
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// Get the full query: the query itself, the sites and the operators.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - What to search for.
+    /// * `sites_query` - The `site:` operators, if any.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidSite` - One of the excluded sites is not in a valid domain scheme.
+    /// * `QueryTooLong` - The query exceeds 500 characters (including the sites and operators)
+    fn full_query(&self, query: &str, sites_query: &str) -> Result<String, DdgError> {
+        if let Some(site) = self.excluded_sites.iter().find(|site| !is_site_valid(site)) {
+            tracing::error!("Excluded site: {} is not valid", &site);
+            return Err(DdgError::InvalidSite(site.to_string()));
+        }
+
+        let operators = self.operators();
+        if query.len() > 494 - (sites_query.len() + operators.len()).min(494) {
+            tracing::error!("Query: {} is too long.", &query);
+            return Err(DdgError::QueryTooLong(query.len()));
+        }
+
+        Ok([query, sites_query, &operators]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" "))
+    }
+
+    /// Checks if a link is on one of the excluded sites, engines don't always respect `-site:`.
+    ///
+    /// # Arguments
+    ///
+    /// * `link` - The link to check.
+    fn excludes(&self, link: &str) -> bool {
+        let sites: Vec<&str> = self.excluded_sites.iter().map(String::as_str).collect();
+        is_on_sites(link, &sites)
+    }
+}
+
+/// Get search results from duckduckgo, or the other search engines it's given.
 #[derive(std::fmt::Debug)]
 pub struct Ddg {
     client: reqwest::Client,
+    engines: Engines,
+    options: DdgQuery,
 }
 
 /// Checks if a site is valid.
@@ -62,6 +322,160 @@ fn is_site_valid(site: &str) -> bool {
         == site.len()
 }
 
+/// Parse a link if it's an https one.
+///
+/// # Arguments
+///
+/// * `link` - The link to parse.
+fn parse_https(link: &str) -> Option<url::Url> {
+    url::Url::parse(link)
+        .ok()
+        .filter(|url| url.scheme() == "https")
+}
+
+/// Checks if a url is on the site, its host has to be the one of the site (or one of its
+/// subdomains, if allowed). A site can have a path, then the url has to be under it, segment by
+/// segment. (ex: reddit.com/r/rust)
+///
+/// # Arguments
+///
+/// * `url` - The url to check.
+/// * `site` - The site the url has to be on. (ex: stackexchange.com)
+/// * `allow_subdomain` - If the url can be on a subdomain of the site.
+fn is_on_site(url: &url::Url, site: &str, allow_subdomain: bool) -> bool {
+    let host = url.host_str().unwrap_or_default();
+    let path = url.path().to_lowercase();
+    let (domain, site_path) = site.split_once('/').unwrap_or((site, ""));
+
+    let on_domain = host == domain
+        || (allow_subdomain
+            && host
+                .strip_suffix(domain)
+                .is_some_and(|subdomain| subdomain.ends_with('.')));
+    let site_path = site_path.trim_end_matches('/');
+    let under_path = site_path.is_empty()
+        || path
+            .strip_prefix('/')
+            .and_then(|path| path.strip_prefix(site_path))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+
+    on_domain && under_path
+}
+
+/// Checks if a link is an https link to one of the sites or one of their subdomains. A site can
+/// have a path, then the link has to be under it. (ex: reddit.com/r/rust)
+///
+/// # Arguments
+///
+/// * `link` - The link to check.
+/// * `sites` - The sites the link can be on. (ex: stackexchange.com)
+fn is_on_sites(link: &str, sites: &[&str]) -> bool {
+    parse_https(link).is_some_and(|url| sites.iter().any(|site| is_on_site(&url, site, true)))
+}
+
+/// Get the canonical form of the link of a result, so the same page is always linked the same
+/// way: without a fragment, tracking parameters or a trailing slash, and StackOverflow questions
+/// as /questions/{id} without their slug. The title is taken from the slug if the result has
+/// none, since the sources fall back on it.
+///
+/// # Arguments
+///
+/// * `hit` - The result to normalize.
+///
+/// # Returns
+///
+/// The result with its canonical link, or None if the link isn't an http(s) one.
+fn normalize(hit: SearchHit) -> Option<SearchHit> {
+    let mut url = url::Url::parse(hit.url.trim()).ok()?;
+    if url.scheme() != "https" && url.scheme() != "http" {
+        return None;
+    }
+    let mut title = hit.title;
+
+    url.set_fragment(None);
+    let params: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if params.is_empty() {
+        url.set_query(None);
+    } else if url.query_pairs().count() != params.len() {
+        url.query_pairs_mut().clear().extend_pairs(params);
+    }
+
+    // stackoverflow questions: /questions/{id}/{slug} or /q/{id}
+    let host = url.host_str().unwrap_or_default();
+    let question = match url
+        .path_segments()
+        .map(|segments| segments.collect::<Vec<&str>>())
+    {
+        Some(segments)
+            if (host == STACKOVERFLOW_HOST || host == format!("www.{}", STACKOVERFLOW_HOST))
+                && segments.len() >= 2
+                && (segments[0] == "questions" || segments[0] == "q")
+                && !segments[1].is_empty()
+                && segments[1].chars().all(|c| c.is_ascii_digit()) =>
+        {
+            let slug = segments.get(2).copied().unwrap_or_default();
+            Some((segments[1].to_string(), slug.replace('-', " ")))
+        }
+        _ => None,
+    };
+    if let Some((id, slug)) = question {
+        if title.is_empty() {
+            title = slug;
+        }
+        url.set_host(Some(STACKOVERFLOW_HOST)).ok()?;
+        url.set_path(&format!("/questions/{}", id));
+        url.set_query(None);
+    }
+
+    let path = url.path();
+    if path.len() > 1 && path.ends_with('/') {
+        let trimmed = path.trim_end_matches('/').to_string();
+        url.set_path(&trimmed);
+    }
+    let link = url.to_string();
+    let link = match link.strip_suffix('/') {
+        Some(stripped) => stripped.to_string(),
+        None => link,
+    };
+
+    Some(SearchHit {
+        url: link,
+        title,
+        ..hit
+    })
+}
+
+/// Get the canonical form of a link, the one the results of the searches are linked with, so
+/// links to the same page found by different sources can be compared.
+///
+/// # Arguments
+///
+/// * `link` - The link to get the canonical form of.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::ddg;
+///
+/// let link = ddg::canonical_url("https://stackoverflow.com/questions/1/some-slug#answer-2");
+/// assert_eq!(link.as_deref(), Some("https://stackoverflow.com/questions/1"));
+/// ```
+///
+/// # Returns
+///
+/// The canonical link, or None if the link isn't an http(s) one.
+pub fn canonical_url(link: &str) -> Option<String> {
+    normalize(SearchHit {
+        url: link.to_string(),
+        ..Default::default()
+    })
+    .map(|hit| hit.url)
+}
+
 impl Ddg {
     /// Create a new Ddg instance with a custom client that generates a random UA (user-agent) in
     /// order to avoid getting limited by duckduckgo.
@@ -76,6 +490,8 @@ impl Ddg {
     pub fn new() -> Self {
         Self {
             client: util::client_with_special_settings(),
+            engines: engine::default_engines(),
+            options: DdgQuery::default(),
         }
     }
 
@@ -90,15 +506,66 @@ impl Ddg {
     /// let ddg = ddg::Ddg::with_client(reqwest::Client::new());
     /// ```
     pub fn with_client(client: reqwest::Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            engines: engine::default_engines(),
+            options: DdgQuery::default(),
+        }
     }
 
-    /// Using a provided query (and optional site specifier) returns duckduckgo results.
+    /// Search with the given engines instead of the default ones. They are tried in order, the
+    /// next one is used when one fails or doesn't find anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `engines` - The engines to search with, the default ones are kept if there are none.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::ddg;
+    /// use falion::search::engine;
+    /// use std::sync::Arc;
+    ///
+    /// let ddg = ddg::Ddg::new().with_engines(vec![
+    ///     Arc::new(engine::Searxng::new("https://searx.example.org")),
+    ///     Arc::new(engine::Mojeek),
+    /// ]);
+    /// ```
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        if !engines.is_empty() {
+            self.engines = engines;
+        }
+        self
+    }
+
+    /// Make every search with the given options: region, safe search, time range, excluded sites,
+    /// exact phrases and file types.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The options of the searches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::ddg::{Ddg, DdgQuery, TimeRange};
+    ///
+    /// let ddg = Ddg::new().with_options(DdgQuery::new().with_time_range(TimeRange::Year));
+    /// ```
+    pub fn with_options(mut self, options: DdgQuery) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Using a provided query (and optional site specifier) returns duckduckgo results, with their
+    /// title and snippet when the engine gives them and their rank once filtered.
     ///
     /// # Arguments
     ///
     /// * `query` - What to search for.
-    /// * `site` - Optional, specific site to get results from.
+    /// * `site` - Optional, specific site to get results from, the host of the results has to be
+    /// the one of the site. A site can have a path, then the results have to be under it.
     /// * `allow_subdomain` - Optional, if you want to allow something before the site like
     /// (something.site.com)
     /// * `contains_filter` - Is gonna filter the results so they DON'T contain whatever is in the
@@ -122,13 +589,16 @@ impl Ddg {
     ///
     /// returns ddg::DdgError
     ///
-    /// * `InvalidSite` - The provided site is not in a valid domain scheme.
-    /// * `QueryTooLong` - The query exceeds 500 characters (including the site)
+    /// * `InvalidSite` - The provided site or an excluded one is not in a valid domain scheme.
+    /// * `QueryTooLong` - The query exceeds 500 characters (including the site and the operators
+    /// of the options)
     /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
     /// due to rate limiting, bad internet etc.
     /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
     /// internet.
     /// * `NoResults` - No results matched your query or site.
+    /// * `LayoutChanged` - The page of the last search engine tried changed its layout, so the
+    /// results couldn't be found in it.
     /// * `ErrorCode` - The search returned an error code
     #[tracing::instrument(skip_all)]
     pub async fn get_links(
@@ -138,7 +608,7 @@ impl Ddg {
         allow_subdomain: Option<bool>,
         contains_filter: Option<&[&str]>,
         limit: Option<usize>,
-    ) -> Result<Vec<String>, DdgError> {
+    ) -> Result<Vec<SearchHit>, DdgError> {
         tracing::info!(
             "Get search results for query {}, on site: {:#?}, with flag allow_subdomain: {:#?} and limit: {:#?}", 
             &query, &site, &allow_subdomain, &limit
@@ -155,242 +625,140 @@ impl Ddg {
             return Err(DdgError::InvalidSite(site.to_string()));
         }
 
-        // Check if query is too long
-        if query.len() > 494 - site.len() {
-            tracing::error!("Query: {} is too long.", &query);
-            return Err(DdgError::QueryTooLong(query.len()));
-        }
-
-        // encode query
-        let query = urlencoding::encode(query);
-
-        // create request url
-        let request_url = if !site.is_empty() {
-            BASE_ADDRESS
-                .replace("{QUERY}", &query)
-                .replace("{SITE}", site)
+        // create the full query, checking if it's too long
+        let site_query = if !site.is_empty() {
+            format!("site:{}", site)
         } else {
-            BASE_ADDRESS_MINUS_SITE.replace("{QUERY}", &query)
+            String::new()
         };
+        let full_query = self.options.full_query(query, &site_query)?;
 
-        tracing::debug!(
-            "Making get request to: {} in order to get ddg links url.",
-            &request_url
-        );
-        // get request ddg querry page
-        let response_body = match self.client.get(&request_url).send().await {
-            Ok(res) => {
-                if res.status() != reqwest::StatusCode::OK {
-                    tracing::error!(
-                        "Get request to {} returned status code: {}",
-                        &request_url,
-                        &res.status()
-                    );
-                    return Err(DdgError::ErrorCode(res.status()));
-                }
+        let links = self.search(&full_query).await?;
 
-                match res.text().await {
-                    Ok(body) => body,
-                    Err(error) => {
-                        tracing::error!(
-                            "The response body recieved from {} is invalid. Error: {}",
-                            &request_url,
-                            &error
-                        );
-                        return Err(DdgError::InvalidResponseBody(error));
-                    }
-                }
-            }
-            Err(error) => {
-                tracing::error!(
-                    "Failed to make a get request to {}. Error: {}",
-                    &request_url,
-                    &error
-                );
-                return Err(DdgError::InvalidRequest(error));
-            }
-        };
+        tracing::debug!("Links before filtering: {:#?}", &links);
 
-        // get links url
-        let links_url = match response_body.split_once(LINKS_URL_SPLIT1) {
-            Some(start) => match start.1.split_once(LINKS_URL_SPLIT2) {
-                Some(full) => full.0,
-                None => {
-                    tracing::error!("Failed to second split the response body from ddg search. Response body: {}", &response_body);
-                    return Err(DdgError::NoResults {
-                        at: String::from("Second split of the response body for the links url."),
-                        index: 0,
-                    });
-                }
-            },
-            None => {
-                tracing::error!(
-                    "Failed to first split the response body from ddg search. Response body: {}",
-                    &response_body
-                );
-                return Err(DdgError::NoResults {
-                    at: String::from("First split of the response body for the links url."),
-                    index: 1,
-                });
-            }
-        };
+        let filters = contains_filter.unwrap_or_default();
+        let links: Vec<SearchHit> = links
+            .into_iter()
+            .filter(|hit| {
+                parse_https(&hit.url)
+                    .is_some_and(|url| site.is_empty() || is_on_site(&url, site, allow_subdomain))
+            })
+            .filter(|hit| !filters.iter().any(|filter| hit.url.contains(filter)))
+            .filter(|hit| !self.options.excludes(&hit.url))
+            .take(limit.unwrap_or(100))
+            .enumerate()
+            .map(|(index, hit)| SearchHit {
+                rank: index + 1,
+                ..hit
+            })
+            .collect();
 
-        tracing::debug!(
-            "Making get request to ddg links url: {} in order to get results.",
-            &links_url
-        );
-        // get requests the links url
-        let links_response_body = match self.client.get(links_url).send().await {
-            Ok(res) => {
-                if res.status() != reqwest::StatusCode::OK {
-                    tracing::error!(
-                        "Get request to {} returned status code: {}",
-                        &links_url,
-                        &res.status()
-                    );
-                    return Err(DdgError::ErrorCode(res.status()));
-                }
+        // check if we even have links
+        if links.is_empty() {
+            tracing::error!("After filtering the links there were no more left.");
+            return Err(DdgError::NoResults {
+                at: String::from("Checking if we got any search results"),
+                index: 4,
+            });
+        }
 
-                match res.text().await {
-                    Ok(body) => body,
-                    Err(error) => {
-                        tracing::error!(
-                            "The resposne body recieved from {} is invalid. Error: {}",
-                            &links_url,
-                            &error
-                        );
-                        return Err(DdgError::InvalidResponseBody(error));
-                    }
-                }
-            }
-            Err(error) => {
-                tracing::error!(
-                    "Failed to make a request to {}. Error: {}",
-                    &links_url,
-                    &error
-                );
-                return Err(DdgError::InvalidRequest(error));
-            }
-        };
+        // return got links
+        Ok(links)
+    }
 
-        // get links
-        let mut links = match links_response_body.split_once(LINKS_SPLIT1) {
-            Some(start) => match start.1.split_once(LINKS_SPLIT2) {
-                Some(full) => full
-                    .0
-                    .split(LINKS_SEP)
-                    .map(|s| match s.strip_suffix('/') {
-                        Some(s_stripped) => s_stripped,
-                        None => s,
-                    })
-                    .collect::<Vec<&str>>(),
-                None => {
-                    tracing::error!(
-                        "Failed to second split response body from ddg links. Response body: {}",
-                        &links_response_body
-                    );
-                    return Err(DdgError::NoResults {
-                        at: String::from("Second split of the response body for search results"),
-                        index: 2,
-                    });
-                }
-            },
-            None => {
-                tracing::error!(
-                    "Failed to first split response body from ddg links. Response body: {}",
-                    &links_response_body
-                );
-                return Err(DdgError::NoResults {
-                    at: String::from("First split of the response body for search results"),
-                    index: 3,
-                });
-            }
-        };
+    /// Get duckduckgo results from any of the given sites, or their subdomains, with their title,
+    /// snippet and rank. The sites are searched all at once.
+    /// (ex: rust threading site:superuser.com OR site:serverfault.com)
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - What to search for.
+    /// * `sites` - The sites to get results from, at least one.
+    /// * `contains_filter` - Is gonna filter the results so they DON'T contain whatever is in the
+    /// array.
+    /// * `limit` - Optional, limit the results to the first 10 for example.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::ddg;
+    ///
+    /// # async fn run() -> Result<(), ddg::DdgError> {
+    /// let ddg = ddg::Ddg::new();
+    /// let links = ddg
+    ///     .get_links_on_sites("Rust", &["superuser.com", "serverfault.com"], None, None)
+    ///     .await
+    ///     .unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns ddg::DdgError
+    ///
+    /// * `InvalidSite` - One of the sites or of the excluded ones is not in a valid domain scheme,
+    /// or there are no sites.
+    /// * `QueryTooLong` - The query exceeds 500 characters (including the sites and the operators
+    /// of the options)
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+    /// due to rate limiting, bad internet etc.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+    /// internet.
+    /// * `NoResults` - No results matched your query or sites.
+    /// * `LayoutChanged` - The page of the last search engine tried changed its layout, so the
+    /// results couldn't be found in it.
+    /// * `ErrorCode` - The search returned an error code
+    #[tracing::instrument(skip_all)]
+    pub async fn get_links_on_sites(
+        &self,
+        query: &str,
+        sites: &[&str],
+        contains_filter: Option<&[&str]>,
+        limit: Option<usize>,
+    ) -> Result<Vec<SearchHit>, DdgError> {
+        tracing::info!(
+            "Get search results for query {}, on sites: {:#?} and limit: {:#?}",
+            &query,
+            &sites,
+            &limit
+        );
+        // Check if the sites are valid
+        if sites.is_empty() {
+            tracing::error!("No sites were given to search on.");
+            return Err(DdgError::InvalidSite(String::new()));
+        }
+        if let Some(site) = sites.iter().find(|site| !is_site_valid(site)) {
+            tracing::error!("Site: {} is not valid", &site);
+            return Err(DdgError::InvalidSite(site.to_string()));
+        }
 
-        // remove possible consecutive duplicates
-        links.dedup();
+        // create the full query, checking if it's too long
+        let sites_query = sites
+            .iter()
+            .map(|site| format!("site:{}", site))
+            .collect::<Vec<String>>()
+            .join(" OR ");
+        let full_query = self.options.full_query(query, &sites_query)?;
+
+        let links = self.search(&full_query).await?;
 
         tracing::debug!("Links before filtering: {:#?}", &links);
 
-        let links: Vec<String> = if allow_subdomain {
-            match contains_filter {
-                Some(filters) => links
-                    .into_iter()
-                    .filter_map(|s| {
-                        if s.contains("https://") && s.contains(site) {
-                            let mut valid = true;
-                            for filter in filters {
-                                if s.contains(filter) {
-                                    valid = false;
-                                }
-                            }
-                            if valid {
-                                Some(s.to_string())
-                            } else {
-                                None
-                            }
-                        } else {
-                            None
-                        }
-                    })
-                    .take(limit.unwrap_or(100))
-                    .collect(),
-                None => links
-                    .into_iter()
-                    .filter_map(|s| {
-                        if s.contains("https://") && s.contains(site) {
-                            Some(s.to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .take(limit.unwrap_or(100))
-                    .collect(),
-            }
-        } else {
-            // filter links
-            let mut site_filter = String::with_capacity(8 + site.len());
-            site_filter.push_str("https://");
-            site_filter.push_str(site);
-
-            match contains_filter {
-                Some(filters) => {
-                    links
-                        .into_iter()
-                        .filter_map(|s| {
-                            if s.contains(&site_filter) {
-                                // Some(s.to_string())
-                                let mut valid = true;
-                                for filter in filters {
-                                    if s.contains(filter) {
-                                        valid = false;
-                                    }
-                                }
-                                if valid {
-                                    Some(s.to_string())
-                                } else {
-                                    None
-                                }
-                            } else {
-                                None
-                            }
-                        })
-                        .take(limit.unwrap_or(100))
-                        .collect()
-                }
-                None => links
-                    .into_iter()
-                    .filter_map(|s| {
-                        if s.contains(&site_filter) {
-                            Some(s.to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .take(limit.unwrap_or(100))
-                    .collect(),
-            }
-        };
+        let filters = contains_filter.unwrap_or_default();
+        let links: Vec<SearchHit> = links
+            .into_iter()
+            .filter(|hit| is_on_sites(&hit.url, sites))
+            .filter(|hit| !filters.iter().any(|filter| hit.url.contains(filter)))
+            .filter(|hit| !self.options.excludes(&hit.url))
+            .take(limit.unwrap_or(100))
+            .enumerate()
+            .map(|(index, hit)| SearchHit {
+                rank: index + 1,
+                ..hit
+            })
+            .collect();
 
         // check if we even have links
         if links.is_empty() {
@@ -401,9 +769,72 @@ impl Ddg {
             });
         }
 
-        // return got links
         Ok(links)
     }
+
+    /// Make the search with the engines in order and get every result of the first one that
+    /// finds something, with their links in their canonical form and without duplicates, but
+    /// without any other filtering.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The full query, with the site operators and the ones of the options in it.
+    /// The rest of the options are given to the engines.
+    ///
+    /// # Errors
+    ///
+    /// The error of the last engine, or `NoResults` if it didn't find anything.
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `NoResults` - The engine didn't find anything.
+    /// * `LayoutChanged` - The results couldn't be found in the page of the engine.
+    /// * `ErrorCode` - The search returned an error code
+    #[tracing::instrument(skip_all)]
+    async fn search(&self, query: &str) -> Result<Vec<SearchHit>, DdgError> {
+        let mut last_error = DdgError::NoResults {
+            at: String::from("No search engine to search with"),
+            index: 5,
+        };
+
+        for engine in &self.engines {
+            tracing::debug!("Searching for: {} with {}.", query, engine.name());
+            match engine.search(&self.client, query, &self.options).await {
+                Ok(hits) if !hits.is_empty() => {
+                    // remove the duplicates once every link is in its canonical form, keeping
+                    // the first one
+                    let mut seen = HashSet::new();
+                    let hits: Vec<SearchHit> = hits
+                        .into_iter()
+                        .filter_map(normalize)
+                        .filter(|hit| seen.insert(hit.url.clone()))
+                        .collect();
+
+                    return Ok(hits);
+                }
+                Ok(_) => {
+                    tracing::warn!(
+                        "{} found no results, trying the next engine.",
+                        engine.name()
+                    );
+                    last_error = DdgError::NoResults {
+                        at: format!("Search results of {}", engine.name()),
+                        index: 4,
+                    };
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        "{} failed to search, trying the next engine. Error: {}",
+                        engine.name(),
+                        &error
+                    );
+                    last_error = error;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
 }
 
 impl Default for Ddg {
@@ -431,15 +862,215 @@ mod tests {
             .await
             .unwrap();
 
-        for link in links {
-            assert!(url::Url::parse(&link).is_ok());
+        for (index, hit) in links.iter().enumerate() {
+            assert!(url::Url::parse(&hit.url).is_ok());
+            assert_eq!(hit.rank, index + 1);
+        }
+    }
+
+    /// An engine that always returns the same links, or fails if it has none.
+    #[derive(Debug)]
+    struct FakeEngine(&'static [&'static str]);
+
+    impl engine::SearchEngine for FakeEngine {
+        fn name(&self) -> &'static str {
+            "fake"
+        }
+
+        fn search<'a>(
+            &'a self,
+            _client: &'a reqwest::Client,
+            _query: &'a str,
+            _options: &'a DdgQuery,
+        ) -> futures::future::BoxFuture<'a, Result<Vec<SearchHit>, DdgError>> {
+            Box::pin(async move {
+                if self.0.is_empty() {
+                    return Err(DdgError::ErrorCode(reqwest::StatusCode::FORBIDDEN));
+                }
+                Ok(self
+                    .0
+                    .iter()
+                    .map(|link| SearchHit {
+                        url: link.to_string(),
+                        ..Default::default()
+                    })
+                    .collect())
+            })
         }
     }
 
+    #[tokio::test]
+    async fn test_engines_fallback() {
+        let ddg = Ddg::with_client(reqwest::Client::new()).with_engines(vec![
+            std::sync::Arc::new(FakeEngine(&[])),
+            std::sync::Arc::new(FakeEngine(&[
+                "https://superuser.com/questions/1/",
+                "https://stackoverflow.com/questions/2",
+            ])),
+        ]);
+        let links = ddg
+            .get_links_on_sites("Rust", &["superuser.com"], None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://superuser.com/questions/1");
+        assert_eq!(links[0].rank, 1);
+
+        let ddg = Ddg::with_client(reqwest::Client::new())
+            .with_engines(vec![std::sync::Arc::new(FakeEngine(&[]))]);
+
+        assert!(matches!(
+            ddg.get_links("Rust", None, None, None, None).await,
+            Err(DdgError::ErrorCode(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_query_options() {
+        let options = DdgQuery::new()
+            .with_phrase("does not \"live\" long enough")
+            .with_file_type(".PDF")
+            .with_file_type("md")
+            .with_excluded_site("w3schools.com");
+
+        assert_eq!(
+            options.full_query("rust", "site:a.com").unwrap(),
+            "rust site:a.com \"does not live long enough\" filetype:pdf OR filetype:md -site:w3schools.com"
+        );
+        assert_eq!(DdgQuery::new().full_query("rust", "").unwrap(), "rust");
+        assert!(matches!(
+            DdgQuery::new()
+                .with_excluded_site("$bad.com")
+                .full_query("rust", ""),
+            Err(DdgError::InvalidSite(_))
+        ));
+
+        // the excluded sites are left out even when the engine doesn't respect -site:
+        let ddg = Ddg::with_client(reqwest::Client::new())
+            .with_engines(vec![std::sync::Arc::new(FakeEngine(&[
+                "https://www.w3schools.com/rust",
+                "https://doc.rust-lang.org/book",
+            ]))])
+            .with_options(options);
+        let links = ddg.get_links("Rust", None, None, None, None).await.unwrap();
+
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].url, "https://doc.rust-lang.org/book");
+    }
+
+    #[test]
+    fn test_normalize() {
+        let link = |url: &str| normalize(SearchHit::titled(url, "title", 1)).map(|hit| hit.url);
+
+        assert_eq!(
+            link("https://stackoverflow.com/questions/17490716/lifetimes-in-rust?noredirect=1#comment1"),
+            Some(String::from("https://stackoverflow.com/questions/17490716"))
+        );
+        assert_eq!(
+            link("https://www.stackoverflow.com/q/17490716/"),
+            Some(String::from("https://stackoverflow.com/questions/17490716"))
+        );
+        assert_eq!(
+            link("https://stackoverflow.com/questions/tagged/rust/"),
+            Some(String::from(
+                "https://stackoverflow.com/questions/tagged/rust"
+            ))
+        );
+        assert_eq!(
+            link("https://Docs.rs/tokio/latest/?utm_source=x&fbclid=y&version=1#select"),
+            Some(String::from("https://docs.rs/tokio/latest?version=1"))
+        );
+        assert_eq!(
+            link("https://example.com/"),
+            Some(String::from("https://example.com"))
+        );
+        assert_eq!(link("ftp://example.com/file"), None);
+
+        // the title of questions without one is their slug
+        let hit = normalize(SearchHit::titled(
+            "https://stackoverflow.com/questions/17490716/lifetimes-in-rust",
+            "",
+            1,
+        ))
+        .unwrap();
+        assert_eq!(hit.title, "lifetimes in rust");
+    }
+
+    #[tokio::test]
+    async fn test_links_on_site() {
+        let ddg = Ddg::with_client(reqwest::Client::new()).with_engines(vec![std::sync::Arc::new(
+            FakeEngine(&[
+                "https://evil.com/?x=stackoverflow.com/questions/",
+                "https://stackoverflow.com.evil.com/questions/1",
+                "https://stackoverflow.com/questions/2/title?utm_source=ddg",
+                "https://meta.stackoverflow.com/questions/3",
+                "https://stackoverflow.com/questions/4",
+                "https://stackoverflow.com/questions/2/other-title#answer-5",
+            ]),
+        )]);
+        let links = ddg
+            .get_links(
+                "Rust",
+                Some("stackoverflow.com/questions/"),
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        let links: Vec<&str> = links.iter().map(|hit| hit.url.as_str()).collect();
+
+        assert_eq!(
+            links,
+            [
+                "https://stackoverflow.com/questions/2",
+                "https://stackoverflow.com/questions/4"
+            ]
+        );
+
+        let links = ddg
+            .get_links("Rust", Some("stackoverflow.com"), Some(true), None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[1].url, "https://meta.stackoverflow.com/questions/3");
+    }
+
     #[test]
     fn test_is_site_valid() {
         assert!(is_site_valid("stackoverflow.com"));
         assert!(is_site_valid("www.some-site.xyz"));
         assert!(!is_site_valid("www.$31-site.com"));
+        assert!(is_site_valid("reddit.com/r/rust_gamedev"));
+    }
+
+    #[test]
+    fn test_is_on_sites() {
+        let sites = ["stackexchange.com", "superuser.com"];
+
+        assert!(is_on_sites(
+            "https://unix.stackexchange.com/questions/1",
+            &sites
+        ));
+        assert!(is_on_sites("https://superuser.com/questions/1", &sites));
+        assert!(!is_on_sites("https://notsuperuser.com/questions/1", &sites));
+        assert!(!is_on_sites("http://superuser.com/questions/1", &sites));
+        assert!(!is_on_sites(
+            "https://stackoverflow.com/questions/1",
+            &sites
+        ));
+
+        let sites = ["reddit.com/r/rust"];
+        assert!(is_on_sites(
+            "https://www.reddit.com/r/Rust/comments/abc/title/",
+            &sites
+        ));
+        assert!(!is_on_sites(
+            "https://www.reddit.com/r/rustjerk/comments/abc/title/",
+            &sites
+        ));
+        assert!(!is_on_sites("https://www.reddit.com/r/golang/", &sites));
     }
 }
@@ -1,4 +1,6 @@
 use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::lang::{Lang, LangMode};
 use super::util;
 use thiserror::Error;
 
@@ -12,6 +14,7 @@ type DdgPage = Result<String, DdgSearchError>;
 /// internet.
 /// * `ErrorCode` - The website returned an error code
 /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+/// * `NotInLang` - None of the results mention the language they're filtered by.
 #[derive(Error, Debug)]
 pub enum DdgSearchError {
     #[error("Failed to make a request with the provided query/url: {0}")]
@@ -22,6 +25,8 @@ pub enum DdgSearchError {
     ErrorCode(reqwest::StatusCode),
     #[error("There was an error retrieving search results from duckduckgo: {0}")]
     DdgError(ddg::DdgError),
+    #[error("None of the search results mention {0}.")]
+    NotInLang(String),
 }
 
 /// Scrape pages returned by ddg
@@ -29,6 +34,7 @@ pub enum DdgSearchError {
 pub struct DdgSearch {
     client: reqwest::Client,
     ddg: ddg::Ddg,
+    lang: Option<(&'static Lang, LangMode)>,
 }
 
 impl DdgSearch {
@@ -46,6 +52,7 @@ impl DdgSearch {
         Self {
             client: util::client_with_special_settings(),
             ddg: ddg::Ddg::new(),
+            lang: None,
         }
     }
 
@@ -61,9 +68,41 @@ impl DdgSearch {
         Self {
             client: client.clone(),
             ddg: ddg::Ddg::with_client(client),
+            lang: None,
         }
     }
 
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Narrow down the searches to a language: add it to the query, and only keep the results
+    /// that mention it when filtering.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language, the searches aren't narrowed down if None.
+    /// * `mode` - If the results that don't mention the language are kept or not.
+    ///
+    /// ```
+    /// use falion::search::ddg_search;
+    /// use falion::search::lang::{self, LangMode};
+    ///
+    /// let source = ddg_search::DdgSearch::new().with_lang(lang::find("go"), LangMode::Filter);
+    /// ```
+    pub fn with_lang(mut self, lang: Option<&'static Lang>, mode: LangMode) -> Self {
+        self.lang = lang.map(|lang| (lang, mode));
+        self
+    }
+
     /// Get the contents of a page inside a String.
     ///
     /// # Arguments
@@ -79,7 +118,7 @@ impl DdgSearch {
     /// # async fn run() -> Result<(), ddg_search::DdgSearchError> {
     /// let ddg = ddg::Ddg::new();
     /// let ddg_search = ddg_search::DdgSearch::new();
-    /// let link = &ddg.get_links("Rust basics", None, None, None, Some(1)).await.unwrap()[0];
+    /// let link = &ddg.get_links("Rust basics", None, None, None, Some(1)).await.unwrap()[0].url;
     ///
     /// let page_content = ddg_search.get_page_content(&link).await.unwrap();
     /// # Ok(())
@@ -183,6 +222,7 @@ impl DdgSearch {
     /// returns ddg_search::DdgSearchError;
     ///
     /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+    /// * `NotInLang` - None of the results mention the language, when they're filtered by it.
     ///
     /// First error is for duckduckgo, second is for the future hanle, third is for the actual
     /// page content
@@ -191,17 +231,35 @@ impl DdgSearch {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<(String, tokio::task::JoinHandle<DdgPage>)>, DdgSearchError> {
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<DdgPage>)>, DdgSearchError> {
         tracing::info!("Get multiple pages and their content for search query: {} with a results limit of: {:#?}", &query, &limit);
+        // add the language to the query, if any, and get every link when they're filtered by it
+        let (query, search_limit) = match self.lang {
+            Some((lang, LangMode::Filter)) => (lang.boosted(query), None),
+            Some((lang, LangMode::Boost)) => (lang.boosted(query), limit),
+            None => (query.to_string(), limit),
+        };
+
         // get the links from duckduckgo
         let links = match self
             .ddg
-            .get_links(query, None, Some(true), None, limit)
+            .get_links(&query, None, Some(true), None, search_limit)
             .await
         {
             Ok(res) => res,
             Err(err) => return Err(DdgSearchError::DdgError(err)),
         };
+        let links = match self.lang {
+            Some((lang, LangMode::Filter)) => {
+                let links = filter_by_lang(links, lang, limit);
+                if links.is_empty() {
+                    tracing::error!("None of the search results mention {}.", lang);
+                    return Err(DdgSearchError::NotInLang(lang.to_string()));
+                }
+                links
+            }
+            _ => links,
+        };
 
         // create a new Vec
         let mut pages_content = Vec::with_capacity(links.len());
@@ -209,7 +267,8 @@ impl DdgSearch {
         // start looping through the links associating the page title and the joinhandle for
         // the future the scrapes the content of the page by inserting them togheter in the
         // Vec inside a tuple
-        for link in links {
+        for hit in links {
+            let link = hit.url.clone();
             // unwrap is safe here since ddg does all the checks
             let mut name = String::from("");
             let domain = link.split_once("https://").unwrap().1;
@@ -228,7 +287,10 @@ impl DdgSearch {
             // insert page content
             let client = self.client.clone();
             pages_content.push((
-                full_name,
+                SearchHit {
+                    title: hit.title_or(full_name),
+                    ..hit
+                },
                 tokio::task::spawn(async move {
                     Self::with_client(client).get_page_content(&link).await
                 }),
@@ -240,6 +302,26 @@ impl DdgSearch {
     }
 }
 
+/// Only keep the search results that mention a language, ranked again.
+///
+/// # Arguments
+///
+/// * `links` - The search results, in the order of the search.
+/// * `lang` - The language they have to mention.
+/// * `limit` - Optional, how many of them to keep at most.
+fn filter_by_lang(links: Vec<SearchHit>, lang: &Lang, limit: Option<usize>) -> Vec<SearchHit> {
+    links
+        .into_iter()
+        .filter(|hit| lang.mentions(hit))
+        .take(limit.unwrap_or(100))
+        .enumerate()
+        .map(|(index, hit)| SearchHit {
+            rank: index + 1,
+            ..hit
+        })
+        .collect()
+}
+
 impl Default for DdgSearch {
     fn default() -> Self {
         DdgSearch::new()
@@ -266,6 +348,32 @@ mod tests {
         assert!(!page_content.is_empty())
     }
 
+    #[test]
+    fn test_filter_by_lang() {
+        let links = vec![
+            SearchHit::titled("https://docs.python.org/3/howto/sorting.html", "Sorting", 1),
+            SearchHit::titled(
+                "https://doc.rust-lang.org/std/vec/struct.Vec.html",
+                "Vec",
+                2,
+            ),
+            SearchHit::titled("https://example.com/sort", "Sorting in Rust", 3),
+            SearchHit::titled("https://example.com/sort", "Sorting in Go", 4),
+            SearchHit::titled("https://example.com/rust", "Rust iterators", 5),
+        ];
+        let rust = search::lang::find("rust").unwrap();
+
+        let filtered = filter_by_lang(links, rust, Some(2));
+
+        assert_eq!(
+            filtered
+                .iter()
+                .map(|hit| (hit.title.as_str(), hit.rank))
+                .collect::<Vec<_>>(),
+            [("Vec", 1), ("Sorting in Rust", 2)]
+        );
+    }
+
     #[ignore] // ignore to pass github code actions, it work on local machine
     #[test]
     fn test_get_multiple_ddg_pages_content() {
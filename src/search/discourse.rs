@@ -0,0 +1,688 @@
+use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::metadata::{self, Metadata};
+use super::thread::{Answer, Thread};
+use super::util;
+use serde::Deserialize;
+use thiserror::Error;
+
+const DEFAULT_FORUMS: [&str; 2] = [
+    "https://users.rust-lang.org",
+    "https://internals.rust-lang.org",
+];
+const TOPIC_PATH: &str = "t";
+const TOPIC_URL: &str = "{FORUM}/t/{ID}.json";
+const POSTS_URL: &str = "{FORUM}/t/{ID}/posts.json";
+const FORUM_INVALID: [&str; 4] = ["/c/", "/u/", "/tag/", "/latest"];
+// the kind of action in the summary of a post that's a like
+const LIKE_ACTION: u64 = 2;
+// the kind of post that's written by a user, the others are moderator notes and small actions
+const REGULAR_POST: u64 = 1;
+// the topic comes with its first 20 posts, the rest are got in one request of at most this many
+const MAX_EXTRA_POSTS: usize = 80;
+
+type TopicThread = Result<Thread, DiscourseError>;
+
+/// These are the errors the functions associated with Discourse will return.
+///
+/// * `InvalidForum` - The given forum url isn't a valid absolute url.
+/// * `NotTopic` - The given url is not a topic on one of the forums.
+/// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+/// due to rate limiting, bad internet etc.
+/// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+/// internet.
+/// * `ErrorCode` - The forum returned an error code, like when the topic is private.
+/// * `NoResults` - None of the links duckduckgo found are topics.
+/// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+#[derive(Error, Debug)]
+pub enum DiscourseError {
+    #[error("The given forum: {0} is not a valid absolute url.")]
+    InvalidForum(String),
+    #[error("The given page: {0} is not a topic on any of the Discourse forums.")]
+    NotTopic(String),
+    #[error("Failed to make a request to the Discourse forum: {0}")]
+    InvalidRequest(reqwest::Error),
+    #[error("A request has been successfully made, but there was an error getting the response body: {0}")]
+    InvalidResponseBody(reqwest::Error),
+    #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
+    ErrorCode(reqwest::StatusCode),
+    #[error("None of the results for the query: {0} are topics on the Discourse forums.")]
+    NoResults(String),
+    #[error("There was an error retrieving search results from duckduckgo: {0}")]
+    DdgError(ddg::DdgError),
+}
+
+/// A Discourse forum.
+///
+/// * `base` - the url the forum is at, without a trailing slash. (ex: https://users.rust-lang.org)
+/// * `site` - the host and path of the forum, as searched on duckduckgo. (ex: users.rust-lang.org)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forum {
+    pub base: String,
+    pub site: String,
+}
+
+impl Forum {
+    /// Get the forum at the given url, which can have a path for forums not at the root of
+    /// their site.
+    ///
+    /// # Arguments
+    ///
+    /// * `forum_url` - The absolute url of the forum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::discourse::Forum;
+    ///
+    /// let forum = Forum::parse("https://users.rust-lang.org/").unwrap();
+    ///
+    /// assert_eq!(forum.base, "https://users.rust-lang.org");
+    /// assert_eq!(forum.site, "users.rust-lang.org");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidForum` - The url isn't a valid absolute url.
+    pub fn parse(forum_url: &str) -> Result<Forum, DiscourseError> {
+        let url = match url::Url::parse(forum_url) {
+            Ok(url) => url,
+            Err(_) => return Err(DiscourseError::InvalidForum(forum_url.to_string())),
+        };
+        let host = match url.host_str() {
+            Some(host) => host,
+            None => return Err(DiscourseError::InvalidForum(forum_url.to_string())),
+        };
+        let path = url.path().trim_end_matches('/');
+
+        Ok(Forum {
+            base: format!("{}{}", url.origin().ascii_serialization(), path),
+            site: format!("{}{}", host, path),
+        })
+    }
+
+    /// Get the id of the topic an url points to, if it's a topic of this forum.
+    /// (ex: https://users.rust-lang.org/t/some-title/1234/5 -> 1234)
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_url` - The absolute url to the topic.
+    pub fn topic_id(&self, topic_url: &str) -> Option<u64> {
+        let rest = topic_url
+            .strip_prefix(&self.base)?
+            .strip_prefix('/')?
+            .strip_prefix(TOPIC_PATH)?
+            .strip_prefix('/')?;
+        let rest = rest.split(['?', '#']).next()?;
+        let mut segments = rest.split('/');
+        let first = segments.next()?;
+
+        // the slug is optional and the post number can follow the id (ex: /t/1234/5)
+        match first.parse() {
+            Ok(id) => Some(id),
+            Err(_) => segments.next()?.parse().ok(),
+        }
+    }
+}
+
+/// The kinds of tags Discourse returns, names on older versions and objects on newer ones.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum ApiTag {
+    Name(String),
+    Tag { name: String },
+}
+
+impl ApiTag {
+    fn name(&self) -> &str {
+        match self {
+            ApiTag::Name(name) => name,
+            ApiTag::Tag { name } => name,
+        }
+    }
+}
+
+/// A topic as returned by the forum.
+#[derive(Deserialize, Debug, Clone)]
+struct ApiTopic {
+    title: String,
+    #[serde(default)]
+    posts_count: usize,
+    created_at: Option<String>,
+    last_posted_at: Option<String>,
+    #[serde(default)]
+    tags: Vec<ApiTag>,
+    #[serde(default)]
+    has_accepted_answer: bool,
+    post_stream: PostStream,
+}
+
+/// The posts of a topic, the first ones with their content and the ids of all of them.
+#[derive(Deserialize, Debug, Clone)]
+struct PostStream {
+    #[serde(default)]
+    posts: Vec<ApiPost>,
+    #[serde(default)]
+    stream: Vec<u64>,
+}
+
+/// A post as returned by the forum.
+#[derive(Deserialize, Debug, Clone)]
+struct ApiPost {
+    id: u64,
+    #[serde(default)]
+    cooked: String,
+    created_at: Option<String>,
+    #[serde(default)]
+    post_type: Option<u64>,
+    #[serde(default)]
+    accepted_answer: bool,
+    #[serde(default)]
+    actions_summary: Vec<ActionSummary>,
+}
+
+impl ApiPost {
+    /// How many likes the post got.
+    fn likes(&self) -> i64 {
+        self.actions_summary
+            .iter()
+            .filter(|action| action.id == LIKE_ACTION)
+            .map(|action| action.count)
+            .sum()
+    }
+}
+
+/// How many times an action, like a like, was done on a post.
+#[derive(Deserialize, Debug, Clone)]
+struct ActionSummary {
+    id: u64,
+    #[serde(default)]
+    count: i64,
+}
+
+/// The posts of a topic got by their ids.
+#[derive(Deserialize, Debug, Clone)]
+struct ApiPosts {
+    post_stream: PostStream,
+}
+
+/// Search topics on Discourse forums, like the Rust users forum, and get them as threads.
+#[derive(std::fmt::Debug)]
+pub struct Discourse {
+    client: reqwest::Client,
+    ddg: ddg::Ddg,
+    forums: Vec<Forum>,
+}
+
+impl Discourse {
+    /// Create a new Discourse instance with a custom client that generates UA (user-agent in
+    /// order to avoid getting rate limited by DuckDuckGO), searching the Rust users and internals
+    /// forums.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::discourse;
+    ///
+    /// let discourse = discourse::Discourse::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::with_client(util::client_with_special_settings())
+    }
+
+    /// Create a new Discourse instance with a provided client, searching the Rust users and
+    /// internals forums.
+    /// Note: DuckDuckGO will limit your requests if you don't provide a user-agent.
+    ///
+    /// ```
+    /// use falion::search::discourse;
+    ///
+    /// let discourse = discourse::Discourse::with_client(reqwest::Client::new());
+    /// ```
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client: client.clone(),
+            ddg: ddg::Ddg::with_client(client),
+            forums: DEFAULT_FORUMS
+                .iter()
+                .filter_map(|forum| Forum::parse(forum).ok())
+                .collect(),
+        }
+    }
+
+    /// Search the given forums instead of the Rust users and internals forums.
+    ///
+    /// # Arguments
+    ///
+    /// * `forums` - The forums to search, the default ones are kept if empty.
+    ///
+    /// ```
+    /// use falion::search::discourse::{self, Forum};
+    ///
+    /// let forums = vec![Forum::parse("https://discourse.nixos.org").unwrap()];
+    /// let discourse = discourse::Discourse::new().with_forums(forums);
+    /// ```
+    pub fn with_forums(mut self, forums: Vec<Forum>) -> Self {
+        if !forums.is_empty() {
+            self.forums = forums;
+        }
+        self
+    }
+
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Make a get request to a forum and parse the json it responds with.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to the forum.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ErrorCode` - The forum returned an error code.
+    #[tracing::instrument(skip_all)]
+    async fn send<T>(&self, request: reqwest::RequestBuilder) -> Result<T, DiscourseError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response = match request.send().await {
+            Ok(res) => res,
+            Err(error) => {
+                tracing::error!("Failed to make a request to the forum. Error: {}", &error);
+                return Err(DiscourseError::InvalidRequest(error));
+            }
+        };
+        if response.status() != reqwest::StatusCode::OK {
+            tracing::error!(
+                "Get request to {} return status code: {}",
+                response.url(),
+                &response.status()
+            );
+            return Err(DiscourseError::ErrorCode(response.status()));
+        }
+
+        match response.json::<T>().await {
+            Ok(parsed) => Ok(parsed),
+            Err(error) => {
+                tracing::error!(
+                    "The response body recieved from the forum is invalid. Error: {}",
+                    &error
+                );
+                Err(DiscourseError::InvalidResponseBody(error))
+            }
+        }
+    }
+
+    /// Get a topic as a thread: its first post as the question, the other posts as the answers
+    /// (the solution marked as accepted) and its title, tags, likes and date as metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic_url` - The absolute url to the topic, on one of the forums.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use falion::search::discourse;
+    ///
+    /// # async fn run() -> Result<(), discourse::DiscourseError> {
+    /// let discourse = discourse::Discourse::new();
+    /// let thread = discourse
+    ///     .get_topic_content("https://users.rust-lang.org/t/how-to-use-async-in-a-trait/1234")
+    ///     .await?;
+    ///
+    /// assert!(!thread.question.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns discourse::DiscourseError
+    ///
+    /// * `NotTopic` - The given url is not a topic on any of the forums.
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+    /// due to rate limiting, bad internet etc.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+    /// internet.
+    /// * `ErrorCode` - The forum returned an error code.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_topic_content(&self, topic_url: &str) -> TopicThread {
+        tracing::info!("Get the content for the following topic: {}", &topic_url);
+        let (forum, id) = match self
+            .forums
+            .iter()
+            .find_map(|forum| forum.topic_id(topic_url).map(|id| (forum, id)))
+        {
+            Some(topic) => topic,
+            None => {
+                tracing::error!("The given url is not a topic url. Url: {}", &topic_url);
+                return Err(DiscourseError::NotTopic(topic_url.to_string()));
+            }
+        };
+
+        // set term width
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+
+        let url = |template: &str| {
+            template
+                .replace("{FORUM}", &forum.base)
+                .replace("{ID}", &id.to_string())
+        };
+        let mut topic: ApiTopic = self.send(self.client.get(url(TOPIC_URL))).await?;
+
+        // the topic only comes with its first posts, get the others by their ids
+        let missing: Vec<(&str, String)> = topic
+            .post_stream
+            .stream
+            .iter()
+            .filter(|id| !topic.post_stream.posts.iter().any(|post| post.id == **id))
+            .take(MAX_EXTRA_POSTS)
+            .map(|id| ("post_ids[]", id.to_string()))
+            .collect();
+        if !missing.is_empty() {
+            let request = self.client.get(url(POSTS_URL)).query(&missing);
+            match self.send::<ApiPosts>(request).await {
+                Ok(posts) => topic.post_stream.posts.extend(posts.post_stream.posts),
+                Err(error) => tracing::warn!(
+                    "Failed to get the rest of the posts of {}. Error: {}",
+                    &topic_url,
+                    error
+                ),
+            }
+        }
+
+        Ok(topic_thread(forum, id, &topic, term_width))
+    }
+
+    /// Search for topics on the forums and get their content. Each topic is got in its own
+    /// future, associated with its name and returned inside a Vec for preserved order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, the most topics to return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because it fails github code action
+    /// use falion::search::discourse;
+    ///
+    /// # async fn run() -> Result<(), discourse::DiscourseError> {
+    /// let discourse = discourse::Discourse::new();
+    /// let topics_content = discourse
+    ///     .get_multiple_topics_content("async closure lifetime", Some(5))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for t in topics_content {
+    ///    assert!(!t.1.await.unwrap().unwrap().question.is_empty())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns discourse::DiscourseError;
+    ///
+    /// * `NoResults` - None of the results are topics.
+    /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+    ///
+    /// First error is for duckduckgo, second is for the future hanle, third is for the actual
+    /// topic content
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_topics_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<TopicThread>)>, DiscourseError> {
+        tracing::info!(
+            "Get multiple Discourse topics and their content for search query: {} with a results limit of: {:#?}",
+            &query,
+            &limit
+        );
+        let sites: Vec<&str> = self
+            .forums
+            .iter()
+            .map(|forum| forum.site.as_str())
+            .collect();
+        let links = match self
+            .ddg
+            .get_links_on_sites(query, &sites, Some(&FORUM_INVALID), limit.map(|l| l * 3))
+            .await
+        {
+            Ok(res) => res,
+            Err(err) => return Err(DiscourseError::DdgError(err)),
+        };
+
+        let mut topics: Vec<(u64, SearchHit)> = Vec::with_capacity(links.len());
+        for hit in links {
+            let id = match self
+                .forums
+                .iter()
+                .find_map(|forum| forum.topic_id(&hit.url))
+            {
+                Some(id) => id,
+                None => continue,
+            };
+            // the posts of a topic have links of their own
+            if !topics.iter().any(|(other, _)| *other == id) {
+                topics.push((id, hit));
+            }
+        }
+        topics.truncate(limit.unwrap_or(usize::MAX));
+        if topics.is_empty() {
+            tracing::error!("No Discourse topics were found for {}", &query);
+            return Err(DiscourseError::NoResults(query.to_string()));
+        }
+
+        // get every topic in its own future, associated with its name
+        let mut topics_content = Vec::with_capacity(topics.len());
+        for (rank, (id, hit)) in topics.into_iter().enumerate() {
+            let link = hit.url.clone();
+            let client = self.client.clone();
+            let forums = self.forums.clone();
+            topics_content.push((
+                SearchHit {
+                    title: hit.title_or(topic_name(&link, id)),
+                    rank: rank + 1,
+                    ..hit
+                },
+                tokio::task::spawn(async move {
+                    Self::with_client(client)
+                        .with_forums(forums)
+                        .get_topic_content(&link)
+                        .await
+                }),
+            ));
+        }
+
+        Ok(topics_content)
+    }
+}
+
+impl Default for Discourse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the name a topic is listed under until it's loaded, from its slug if its url has one.
+/// (ex: https://users.rust-lang.org/t/async-in-traits/1234 -> async in traits)
+///
+/// # Arguments
+///
+/// * `topic_url` - The absolute url to the topic.
+/// * `id` - The id of the topic.
+fn topic_name(topic_url: &str, id: u64) -> String {
+    let id = id.to_string();
+    let mut segments = topic_url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(topic_url)
+        .split('/');
+    segments
+        .by_ref()
+        .find(|segment| *segment == TOPIC_PATH)
+        .and_then(|_| segments.next())
+        .filter(|slug| *slug != id && !slug.is_empty())
+        .map(|slug| slug.replace('-', " "))
+        .unwrap_or_else(|| format!("topic {}", id))
+}
+
+/// Create a thread out of a topic, its first post is the question and the other posts written by
+/// users are the answers.
+///
+/// # Arguments
+///
+/// * `forum` - The forum the topic is on.
+/// * `id` - The id of the topic.
+/// * `topic` - The topic with its posts.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn topic_thread(forum: &Forum, id: u64, topic: &ApiTopic, term_width: usize) -> Thread {
+    let mut posts = topic
+        .post_stream
+        .posts
+        .iter()
+        .filter(|post| post.post_type.unwrap_or(REGULAR_POST) == REGULAR_POST);
+    let first = posts.next();
+
+    Thread {
+        id: Some(id),
+        question: first
+            .map(|post| util::html_to_text(&post.cooked, term_width))
+            .unwrap_or_default(),
+        comments: Vec::new(),
+        answers: posts
+            .map(|post| Answer {
+                id: Some(post.id),
+                content: util::html_to_text(&post.cooked, term_width),
+                score: Some(post.likes()),
+                is_accepted: post.accepted_answer,
+                date: post.created_at.as_deref().and_then(metadata::parse_date),
+                comments: Vec::new(),
+            })
+            .collect(),
+        metadata: Metadata {
+            title: Some(topic.title.clone()),
+            site: Some(forum.site.clone()),
+            score: first.map(ApiPost::likes),
+            answer_count: Some(topic.posts_count.saturating_sub(1)),
+            is_answered: topic.has_accepted_answer
+                || topic
+                    .post_stream
+                    .posts
+                    .iter()
+                    .any(|post| post.accepted_answer),
+            tags: topic
+                .tags
+                .iter()
+                .map(|tag| tag.name().to_string())
+                .collect(),
+            date: topic.created_at.as_deref().and_then(metadata::parse_date),
+            updated: topic
+                .last_posted_at
+                .as_deref()
+                .and_then(metadata::parse_date),
+            ..Default::default()
+        },
+        hidden_comments: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forum() {
+        let forum = Forum::parse("https://users.rust-lang.org").unwrap();
+
+        assert_eq!(
+            forum.topic_id("https://users.rust-lang.org/t/async-in-traits/1234"),
+            Some(1234)
+        );
+        assert_eq!(
+            forum.topic_id("https://users.rust-lang.org/t/async-in-traits/1234/5?u=someone"),
+            Some(1234)
+        );
+        assert_eq!(
+            forum.topic_id("https://users.rust-lang.org/t/1234/5"),
+            Some(1234)
+        );
+        assert_eq!(forum.topic_id("https://users.rust-lang.org/c/help/5"), None);
+        assert_eq!(
+            forum.topic_id("https://internals.rust-lang.org/t/async-in-traits/1234"),
+            None
+        );
+
+        let forum = Forum::parse("https://example.com/forum/").unwrap();
+        assert_eq!(forum.site, "example.com/forum");
+        assert_eq!(
+            forum.topic_id("https://example.com/forum/t/some-topic/7"),
+            Some(7)
+        );
+        assert!(Forum::parse("users.rust-lang.org").is_err());
+    }
+
+    #[test]
+    fn test_topic_name() {
+        assert_eq!(
+            topic_name("https://users.rust-lang.org/t/async-in-traits/1234/5", 1234),
+            "async in traits"
+        );
+        assert_eq!(
+            topic_name("https://users.rust-lang.org/t/1234", 1234),
+            "topic 1234"
+        );
+    }
+
+    #[test]
+    fn test_topic_thread() {
+        let topic: ApiTopic = serde_json::from_str(
+            r#"{"title":"Async in traits","posts_count":3,"created_at":"2023-01-02T10:00:00.000Z","last_posted_at":"2023-01-03T10:00:00.000Z","tags":["async",{"id":1,"name":"traits","slug":"traits"}],"has_accepted_answer":true,"post_stream":{"posts":[{"id":1,"cooked":"<p>How?</p>","created_at":"2023-01-02T10:00:00.000Z","post_type":1,"actions_summary":[{"id":2,"count":3}]},{"id":2,"cooked":"<p>Like this.</p>","created_at":"2023-01-02T11:00:00.000Z","post_type":1,"accepted_answer":true,"actions_summary":[{"id":2,"count":5}]},{"id":3,"cooked":"","post_type":3}],"stream":[1,2,3]}}"#,
+        )
+        .unwrap();
+        let forum = Forum::parse("https://users.rust-lang.org").unwrap();
+        let thread = topic_thread(&forum, 1234, &topic, 50);
+
+        assert_eq!(thread.question, "How?\n");
+        assert_eq!(thread.answers.len(), 1);
+        assert_eq!(thread.answers[0].content, "Like this.\n");
+        assert_eq!(thread.answers[0].score, Some(5));
+        assert!(thread.answers[0].is_accepted);
+        assert_eq!(thread.metadata.score, Some(3));
+        assert_eq!(thread.metadata.answer_count, Some(2));
+        assert!(thread.metadata.is_answered);
+        assert_eq!(thread.metadata.tags, ["async", "traits"]);
+        assert_eq!(thread.metadata.date, Some(1672653600));
+    }
+
+    #[tokio::test]
+    async fn test_get_topic_content() {
+        let discourse = Discourse::with_client(util::client_with_special_settings());
+
+        let thread = discourse
+            .get_topic_content("https://users.rust-lang.org/t/rust-2018-is-here/22625")
+            .await
+            .unwrap();
+
+        assert!(!thread.question.is_empty())
+    }
+}
@@ -0,0 +1,961 @@
+use super::ddg::{DdgError, DdgQuery, SafeSearch, TimeRange};
+use futures::future::BoxFuture;
+use html5ever::tendril::StrTendril;
+use html5ever::tokenizer::{
+    BufferQueue, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use reqwest::header;
+use serde::Deserialize;
+use std::sync::Arc;
+
+const DDG_ADDRESS: &str = "https://duckduckgo.com/";
+const DDG_HTML_ADDRESS: &str = "https://html.duckduckgo.com/html/";
+const DDG_LITE_ADDRESS: &str = "https://lite.duckduckgo.com/lite/";
+const MOJEEK_ADDRESS: &str = "https://www.mojeek.com/search";
+const BRAVE_ADDRESS: &str = "https://api.search.brave.com/res/v1/web/search";
+const BRAVE_TOKEN_HEADER: &str = "X-Subscription-Token";
+const SEARXNG_PATH: &str = "/search";
+const LINKS_URL_SPLIT1: &str = "id=\"deep_preload_link\" rel=\"preload\" as=\"script\" href=\"";
+const LINKS_URL_SPLIT2: &str = "\"><script async id=\"deep_preload_script\"";
+const LINKS_SPLIT1: &str = "{\"en\":[\"";
+const LINKS_SPLIT2: &str = "\"]});";
+const LINKS_SEP: &str = "\",\"";
+// how the results are laid out on every html page
+const DDG_HTML_LAYOUT: Layout = Layout {
+    link: "result__a",
+    snippet: "result__snippet",
+    no_results: "no-results",
+};
+const DDG_LITE_LAYOUT: Layout = Layout {
+    link: "result-link",
+    snippet: "result-snippet",
+    no_results: "No results.",
+};
+const MOJEEK_LAYOUT: Layout = Layout {
+    link: "title",
+    snippet: "s",
+    no_results: "No pages found matching",
+};
+// the html endpoints of duckduckgo link to a redirect with the result in this query parameter
+const DDG_REDIRECT_PARAM: &str = "uddg";
+// the ads of the html endpoints link back to duckduckgo
+const DDG_HOST: &str = "duckduckgo.com";
+
+/// The search engines tried in order until one of them returns results.
+pub type Engines = Vec<Arc<dyn SearchEngine>>;
+
+/// A result of a search, the title and snippet are empty when the search doesn't give them.
+///
+/// * `url` - where the result is.
+/// * `title` - the title of the result.
+/// * `snippet` - a preview of the content of the result.
+/// * `rank` - the position of the result among the results of its search, starting from 1.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchHit {
+    pub url: String,
+    pub title: String,
+    pub snippet: String,
+    pub rank: usize,
+}
+
+impl SearchHit {
+    /// A hit with only its url, for the engines that give nothing else.
+    fn from_url(url: String) -> Self {
+        Self {
+            url,
+            ..Default::default()
+        }
+    }
+
+    /// A hit of a source that finds its results without a search engine, with what it knows of
+    /// them.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - where the result is, empty for local results.
+    /// * `title` - the title of the result.
+    /// * `rank` - the position of the result among the results of the source, starting from 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::engine::SearchHit;
+    ///
+    /// let hit = SearchHit::titled("https://news.ycombinator.com/item?id=1", "Show HN", 1);
+    ///
+    /// assert_eq!(hit.title, "Show HN");
+    /// assert!(hit.snippet.is_empty());
+    /// ```
+    pub fn titled(url: &str, title: &str, rank: usize) -> Self {
+        Self {
+            url: url.to_string(),
+            title: title.to_string(),
+            snippet: String::new(),
+            rank,
+        }
+    }
+
+    /// Get the title of the hit, or the given one if the search didn't give any.
+    ///
+    /// # Arguments
+    ///
+    /// * `fallback` - the title to use instead, like one made out of the url.
+    pub fn title_or(&self, fallback: String) -> String {
+        if self.title.is_empty() {
+            fallback
+        } else {
+            self.title.clone()
+        }
+    }
+}
+
+/// A search engine the sources can find their results with. Every engine takes the whole query,
+/// with the `site:` operators in it, and returns its results in the order the engine ranks them.
+/// The region, safe search and time range of the options are given to the engines that support
+/// them and ignored by the others.
+///
+/// The sources that search the web take their engines with `with_engines`, they're tried in order
+/// and the next one is used when one fails or doesn't find anything. The default ones
+/// (`default_engines`) are kept when none are given.
+pub trait SearchEngine: std::fmt::Debug + Send + Sync {
+    /// The name of the engine, the one used in the config file. (ex: duckduckgo-html)
+    fn name(&self) -> &'static str;
+
+    /// Search for the query and get the results.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to make the requests with.
+    /// * `query` - What to search for, with the `site:` operators in it.
+    /// * `options` - The options of the search, only the region, safe search and time range are
+    /// left to the engine.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `LayoutChanged` - The page isn't laid out the way it used to be, so the results can't
+    /// be found in it.
+    /// * `ErrorCode` - The search returned an error code.
+    fn search<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        query: &'a str,
+        options: &'a DdgQuery,
+    ) -> BoxFuture<'a, Result<Vec<SearchHit>, DdgError>>;
+}
+
+/// The engines used when none are configured, duckduckgo and its html endpoint if the first
+/// one fails, like when its markup changes.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::engine;
+///
+/// let names: Vec<&str> = engine::default_engines().iter().map(|e| e.name()).collect();
+///
+/// assert_eq!(names, ["duckduckgo", "duckduckgo-html"]);
+/// ```
+pub fn default_engines() -> Engines {
+    vec![Arc::new(DuckDuckGo), Arc::new(DuckDuckGoHtml::html())]
+}
+
+/// Make a get request and get the response body.
+///
+/// # Arguments
+///
+/// * `request` - The request to make.
+///
+/// # Errors
+///
+/// * `InvalidRequest` - Reqwest returned an error when processing the request.
+/// * `InvalidResponseBody` - The response content you got back is corrupted.
+/// * `ErrorCode` - The search returned an error code.
+#[tracing::instrument(skip_all)]
+async fn send(request: reqwest::RequestBuilder) -> Result<reqwest::Response, DdgError> {
+    let response = match request.send().await {
+        Ok(res) => res,
+        Err(error) => {
+            tracing::error!("Failed to make a search request. Error: {}", &error);
+            return Err(DdgError::InvalidRequest(error));
+        }
+    };
+    if response.status() != reqwest::StatusCode::OK {
+        tracing::error!(
+            "Get request to {} returned status code: {}",
+            response.url(),
+            &response.status()
+        );
+        return Err(DdgError::ErrorCode(response.status()));
+    }
+
+    Ok(response)
+}
+
+/// Make a get request and get the response body as text.
+///
+/// # Arguments
+///
+/// * `request` - The request to make.
+///
+/// # Errors
+///
+/// * `InvalidRequest` - Reqwest returned an error when processing the request.
+/// * `InvalidResponseBody` - The response content you got back is corrupted.
+/// * `ErrorCode` - The search returned an error code.
+async fn get_text(request: reqwest::RequestBuilder) -> Result<String, DdgError> {
+    match send(request).await?.text().await {
+        Ok(body) => Ok(body),
+        Err(error) => {
+            tracing::error!(
+                "The response body recieved from the search is invalid. Error: {}",
+                &error
+            );
+            Err(DdgError::InvalidResponseBody(error))
+        }
+    }
+}
+
+/// Make a get request and parse the json it responds with.
+///
+/// # Arguments
+///
+/// * `request` - The request to make.
+///
+/// # Errors
+///
+/// * `InvalidRequest` - Reqwest returned an error when processing the request.
+/// * `InvalidResponseBody` - The response content isn't the expected json.
+/// * `ErrorCode` - The search returned an error code.
+async fn get_json<T>(request: reqwest::RequestBuilder) -> Result<T, DdgError>
+where
+    T: serde::de::DeserializeOwned,
+{
+    match send(request).await?.json::<T>().await {
+        Ok(parsed) => Ok(parsed),
+        Err(error) => {
+            tracing::error!(
+                "The response body recieved from the search is invalid. Error: {}",
+                &error
+            );
+            Err(DdgError::InvalidResponseBody(error))
+        }
+    }
+}
+
+/// Get the parameters of the duckduckgo endpoints for the options: kl for the region, kp for the
+/// safe search and df for the time range.
+///
+/// # Arguments
+///
+/// * `options` - The options of the search.
+fn ddg_params(options: &DdgQuery) -> Vec<(&'static str, String)> {
+    let mut params = Vec::new();
+    if let Some(region) = &options.region {
+        params.push(("kl", region.clone()));
+    }
+    if let Some(safe_search) = options.safe_search {
+        let kp = match safe_search {
+            SafeSearch::Strict => "1",
+            SafeSearch::Moderate => "-1",
+            SafeSearch::Off => "-2",
+        };
+        params.push(("kp", kp.to_string()));
+    }
+    if let Some(time_range) = options.time_range {
+        let df = match time_range {
+            TimeRange::Day => "d",
+            TimeRange::Week => "w",
+            TimeRange::Month => "m",
+            TimeRange::Year => "y",
+        };
+        params.push(("df", df.to_string()));
+    }
+    params
+}
+
+/// Get the country and the language of a duckduckgo region, if it's an actual region.
+/// (ex: us-en to us and en, wt-wt is every region)
+///
+/// # Arguments
+///
+/// * `region` - The duckduckgo `kl` code of the region.
+fn region_parts(region: &str) -> Option<(&str, &str)> {
+    region
+        .split_once('-')
+        .filter(|(country, language)| *country != "wt" && !language.is_empty())
+}
+
+/// Where the results are on a page of results, by the classes of their elements.
+///
+/// * `link` - the class of the links to the results, their text is the title.
+/// * `snippet` - the class of the element with the preview of a result, after its link.
+/// * `no_results` - a class or a piece of text only on the page when nothing was found.
+#[derive(Debug, Clone, Copy)]
+struct Layout {
+    link: &'static str,
+    snippet: &'static str,
+    no_results: &'static str,
+}
+
+/// Which text of a hit is being collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Snippet,
+}
+
+/// Collects the hits of a page of results while it's tokenized.
+struct HitsSink {
+    layout: Layout,
+    hits: Vec<SearchHit>,
+    // the field being collected, the tag it's in and how many of those tags are open
+    collecting: Option<(Field, String, usize)>,
+    no_results: bool,
+}
+
+impl HitsSink {
+    /// Add some text to the field being collected, if any.
+    fn push_text(&mut self, text: &str) {
+        let field = match &self.collecting {
+            Some((field, _, _)) => *field,
+            None => return,
+        };
+        if let Some(hit) = self.hits.last_mut() {
+            match field {
+                Field::Title => hit.title.push_str(text),
+                Field::Snippet => hit.snippet.push_str(text),
+            }
+        }
+    }
+}
+
+impl TokenSink for HitsSink {
+    type Handle = ();
+
+    fn process_token(&mut self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        match token {
+            Token::TagToken(tag) => {
+                let name = tag.name.to_string();
+                if let Some((_, open_tag, depth)) = &mut self.collecting {
+                    if *open_tag == name {
+                        match tag.kind {
+                            TagKind::StartTag if !tag.self_closing => *depth += 1,
+                            TagKind::EndTag => *depth -= 1,
+                            _ => (),
+                        }
+                        if *depth == 0 {
+                            self.collecting = None;
+                        }
+                    }
+                    return TokenSinkResult::Continue;
+                }
+                if tag.kind != TagKind::StartTag {
+                    return TokenSinkResult::Continue;
+                }
+
+                let attribute = |attr_name: &str| {
+                    tag.attrs
+                        .iter()
+                        .find(|attr| &*attr.name.local == attr_name)
+                        .map(|attr| attr.value.to_string())
+                };
+                let classes = attribute("class").unwrap_or_default();
+                let has_class = |class: &str| classes.split_whitespace().any(|c| c == class);
+
+                if has_class(self.layout.no_results) {
+                    self.no_results = true;
+                } else if name == "a" && has_class(self.layout.link) {
+                    if let Some(href) = attribute("href") {
+                        self.hits.push(SearchHit::from_url(href));
+                        self.collecting = Some((Field::Title, name, 1));
+                    }
+                } else if has_class(self.layout.snippet)
+                    && self.hits.last().is_some_and(|hit| hit.snippet.is_empty())
+                {
+                    self.collecting = Some((Field::Snippet, name, 1));
+                }
+            }
+            Token::CharacterTokens(text) => {
+                if text.contains(self.layout.no_results) {
+                    self.no_results = true;
+                }
+                self.push_text(&text);
+            }
+            _ => (),
+        }
+
+        TokenSinkResult::Continue
+    }
+}
+
+/// Collapse the whitespace of some text, like a browser shows it.
+///
+/// # Arguments
+///
+/// * `text` - The text to collapse.
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Tokenize the html of a page of results and get its hits, in the order of the page, with their
+/// urls as the page has them.
+///
+/// # Arguments
+///
+/// * `html` - The html of the page.
+/// * `layout` - Where the results are on the page.
+/// * `engine` - The name of the engine the page is from, for the errors.
+///
+/// # Errors
+///
+/// * `LayoutChanged` - There are no results on the page and it doesn't say nothing was found
+/// either, so it isn't laid out the way it used to be.
+fn parse_hits(html: &str, layout: Layout, engine: &str) -> Result<Vec<SearchHit>, DdgError> {
+    let sink = HitsSink {
+        layout,
+        hits: Vec::new(),
+        collecting: None,
+        no_results: false,
+    };
+    let mut queue = BufferQueue::default();
+    queue.push_back(StrTendril::from(html));
+    let mut tokenizer = Tokenizer::new(sink, TokenizerOpts::default());
+    let _ = tokenizer.feed(&mut queue);
+    tokenizer.end();
+    let sink = tokenizer.sink;
+
+    if sink.hits.is_empty() && !sink.no_results {
+        tracing::warn!("Found no results on the {} page.", engine);
+        tracing::debug!("The {} page: {}", engine, html);
+        return Err(DdgError::LayoutChanged {
+            engine: engine.to_string(),
+            at: format!("Links with the class {}", layout.link),
+        });
+    }
+
+    Ok(sink
+        .hits
+        .into_iter()
+        .map(|hit| SearchHit {
+            url: hit.url,
+            title: collapse_whitespace(&hit.title),
+            snippet: collapse_whitespace(&hit.snippet),
+            ..Default::default()
+        })
+        .collect())
+}
+
+/// Get the text of some html, without its tags. (ex: the <strong> in the descriptions of brave)
+///
+/// # Arguments
+///
+/// * `html` - The html to get the text of.
+fn text_of(html: &str) -> String {
+    /// Collects every piece of text.
+    struct TextSink(String);
+
+    impl TokenSink for TextSink {
+        type Handle = ();
+
+        fn process_token(&mut self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+            if let Token::CharacterTokens(text) = token {
+                self.0.push_str(&text);
+            }
+            TokenSinkResult::Continue
+        }
+    }
+
+    let mut queue = BufferQueue::default();
+    queue.push_back(StrTendril::from(html));
+    let mut tokenizer = Tokenizer::new(TextSink(String::new()), TokenizerOpts::default());
+    let _ = tokenizer.feed(&mut queue);
+    tokenizer.end();
+
+    collapse_whitespace(&tokenizer.sink.0)
+}
+
+/// Get the result a link of the html endpoints of duckduckgo redirects to, leaving out the ads.
+/// (ex: //duckduckgo.com/l/?uddg=https%3A%2F%2Fdoc.rust-lang.org&rut=1 -> https://doc.rust-lang.org)
+///
+/// # Arguments
+///
+/// * `href` - The link as the page has it.
+fn ddg_redirect_target(href: &str) -> Option<String> {
+    let absolute = match href.strip_prefix("//") {
+        Some(rest) => format!("https://{}", rest),
+        None => href.to_string(),
+    };
+    let url = url::Url::parse(&absolute).ok()?;
+    let host = url.host_str().unwrap_or_default();
+    if host != DDG_HOST && !host.ends_with(&format!(".{}", DDG_HOST)) {
+        return Some(absolute);
+    }
+
+    url.query_pairs()
+        .find(|(key, _)| key == DDG_REDIRECT_PARAM)
+        .map(|(_, target)| target.into_owned())
+}
+
+/// The duckduckgo search page, its results are in a script the page preloads. Only their links
+/// are there, without titles or snippets.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DuckDuckGo;
+
+impl SearchEngine for DuckDuckGo {
+    fn name(&self) -> &'static str {
+        "duckduckgo"
+    }
+
+    fn search<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        query: &'a str,
+        options: &'a DdgQuery,
+    ) -> BoxFuture<'a, Result<Vec<SearchHit>, DdgError>> {
+        Box::pin(async move {
+            let request = client
+                .get(DDG_ADDRESS)
+                .query(&[("q", query), ("ia", "web")])
+                .query(&ddg_params(options));
+            let response_body = get_text(request).await?;
+
+            // get links url
+            let links_url = match response_body
+                .split_once(LINKS_URL_SPLIT1)
+                .and_then(|start| start.1.split_once(LINKS_URL_SPLIT2))
+            {
+                Some(full) => full.0,
+                None => {
+                    tracing::warn!("Failed to find the links url in the ddg search page.");
+                    tracing::debug!("The ddg search page: {}", &response_body);
+                    return Err(DdgError::LayoutChanged {
+                        engine: self.name().to_string(),
+                        at: String::from("The links url of the search page"),
+                    });
+                }
+            };
+
+            tracing::debug!(
+                "Making get request to ddg links url: {} in order to get results.",
+                &links_url
+            );
+            let links_response_body = get_text(client.get(links_url)).await?;
+
+            // get links
+            match links_response_body
+                .split_once(LINKS_SPLIT1)
+                .and_then(|start| start.1.split_once(LINKS_SPLIT2))
+            {
+                Some(full) => Ok(full
+                    .0
+                    .split(LINKS_SEP)
+                    .map(|link| SearchHit::from_url(link.to_string()))
+                    .collect()),
+                None => {
+                    tracing::warn!("Failed to find the links in the ddg links script.");
+                    tracing::debug!("The ddg links script: {}", &links_response_body);
+                    Err(DdgError::LayoutChanged {
+                        engine: self.name().to_string(),
+                        at: String::from("The links of the links script"),
+                    })
+                }
+            }
+        })
+    }
+}
+
+/// The html only endpoints of duckduckgo, made for browsers without javascript, either the html
+/// one or the even lighter lite one. Their results come with titles and snippets.
+#[derive(Debug, Clone, Copy)]
+pub struct DuckDuckGoHtml {
+    lite: bool,
+}
+
+impl DuckDuckGoHtml {
+    /// The html endpoint. (html.duckduckgo.com)
+    pub fn html() -> Self {
+        Self { lite: false }
+    }
+
+    /// The lite endpoint. (lite.duckduckgo.com)
+    pub fn lite() -> Self {
+        Self { lite: true }
+    }
+
+    /// Get the results of a page of the endpoint, leaving out the ads.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The html of the page.
+    ///
+    /// # Errors
+    ///
+    /// * `LayoutChanged` - The results can't be found on the page.
+    fn parse(&self, html: &str) -> Result<Vec<SearchHit>, DdgError> {
+        let layout = if self.lite {
+            DDG_LITE_LAYOUT
+        } else {
+            DDG_HTML_LAYOUT
+        };
+
+        Ok(parse_hits(html, layout, self.name())?
+            .into_iter()
+            .filter_map(|hit| ddg_redirect_target(&hit.url).map(|url| SearchHit { url, ..hit }))
+            .collect())
+    }
+}
+
+impl SearchEngine for DuckDuckGoHtml {
+    fn name(&self) -> &'static str {
+        if self.lite {
+            "duckduckgo-lite"
+        } else {
+            "duckduckgo-html"
+        }
+    }
+
+    fn search<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        query: &'a str,
+        options: &'a DdgQuery,
+    ) -> BoxFuture<'a, Result<Vec<SearchHit>, DdgError>> {
+        Box::pin(async move {
+            let address = if self.lite {
+                DDG_LITE_ADDRESS
+            } else {
+                DDG_HTML_ADDRESS
+            };
+            let request = client
+                .get(address)
+                .query(&[("q", query)])
+                .query(&ddg_params(options));
+            let body = get_text(request).await?;
+
+            self.parse(&body)
+        })
+    }
+}
+
+/// The results of a SearXNG instance.
+#[derive(Deserialize, Debug)]
+struct SearxngResults {
+    #[serde(default)]
+    results: Vec<SearxngResult>,
+}
+
+/// A result of a SearXNG instance.
+#[derive(Deserialize, Debug)]
+struct SearxngResult {
+    url: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    content: String,
+}
+
+/// A self-hosted (or public) SearXNG instance, through its json api which has to be enabled in
+/// the settings of the instance.
+#[derive(Debug, Clone)]
+pub struct Searxng {
+    instance: String,
+}
+
+impl Searxng {
+    /// Search with the instance at the given address.
+    ///
+    /// # Arguments
+    ///
+    /// * `instance` - The address of the instance. (ex: https://searx.example.org)
+    pub fn new(instance: &str) -> Self {
+        Self {
+            instance: instance.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl SearchEngine for Searxng {
+    fn name(&self) -> &'static str {
+        "searxng"
+    }
+
+    fn search<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        query: &'a str,
+        options: &'a DdgQuery,
+    ) -> BoxFuture<'a, Result<Vec<SearchHit>, DdgError>> {
+        Box::pin(async move {
+            let request = client
+                .get(format!("{}{}", self.instance, SEARXNG_PATH))
+                .header(header::ACCEPT, "application/json")
+                .query(&[("q", query), ("format", "json")]);
+            let mut params = Vec::new();
+            if let Some((country, language)) = options.region.as_deref().and_then(region_parts) {
+                params.push((
+                    "language",
+                    format!("{}-{}", language, country.to_uppercase()),
+                ));
+            }
+            if let Some(safe_search) = options.safe_search {
+                let level = match safe_search {
+                    SafeSearch::Strict => "2",
+                    SafeSearch::Moderate => "1",
+                    SafeSearch::Off => "0",
+                };
+                params.push(("safesearch", level.to_string()));
+            }
+            if let Some(time_range) = options.time_range {
+                params.push(("time_range", time_range.name().to_string()));
+            }
+            let request = request.query(&params);
+            let results: SearxngResults = get_json(request).await?;
+
+            Ok(results
+                .results
+                .into_iter()
+                .map(|result| SearchHit {
+                    url: result.url,
+                    title: collapse_whitespace(&result.title),
+                    snippet: collapse_whitespace(&result.content),
+                    ..Default::default()
+                })
+                .collect())
+        })
+    }
+}
+
+/// The results of the brave search api.
+#[derive(Deserialize, Debug)]
+struct BraveResults {
+    web: Option<BraveWeb>,
+}
+
+/// The web results of the brave search api.
+#[derive(Deserialize, Debug)]
+struct BraveWeb {
+    #[serde(default)]
+    results: Vec<BraveResult>,
+}
+
+/// A web result of the brave search api, its title and description have html in them.
+#[derive(Deserialize, Debug)]
+struct BraveResult {
+    url: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// The brave search api, which needs a (free) subscription token.
+#[derive(Debug, Clone)]
+pub struct Brave {
+    token: String,
+}
+
+impl Brave {
+    /// Search with the given subscription token.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - The subscription token of the api.
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+}
+
+impl SearchEngine for Brave {
+    fn name(&self) -> &'static str {
+        "brave"
+    }
+
+    fn search<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        query: &'a str,
+        options: &'a DdgQuery,
+    ) -> BoxFuture<'a, Result<Vec<SearchHit>, DdgError>> {
+        Box::pin(async move {
+            let request = client
+                .get(BRAVE_ADDRESS)
+                .header(header::ACCEPT, "application/json")
+                .header(BRAVE_TOKEN_HEADER, &self.token)
+                .query(&[("q", query)]);
+            let mut params = Vec::new();
+            if let Some((country, language)) = options.region.as_deref().and_then(region_parts) {
+                params.push(("country", country.to_uppercase()));
+                params.push(("search_lang", language.to_string()));
+            }
+            if let Some(safe_search) = options.safe_search {
+                params.push(("safesearch", safe_search.name().to_string()));
+            }
+            if let Some(time_range) = options.time_range {
+                let freshness = match time_range {
+                    TimeRange::Day => "pd",
+                    TimeRange::Week => "pw",
+                    TimeRange::Month => "pm",
+                    TimeRange::Year => "py",
+                };
+                params.push(("freshness", freshness.to_string()));
+            }
+            let request = request.query(&params);
+            let results: BraveResults = get_json(request).await?;
+
+            Ok(results
+                .web
+                .map(|web| {
+                    web.results
+                        .into_iter()
+                        .map(|result| SearchHit {
+                            url: result.url,
+                            title: text_of(&result.title),
+                            snippet: text_of(&result.description),
+                            ..Default::default()
+                        })
+                        .collect()
+                })
+                .unwrap_or_default())
+        })
+    }
+}
+
+/// The mojeek search page, an independent engine with its own index. It only gets the operators
+/// of the options.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mojeek;
+
+impl SearchEngine for Mojeek {
+    fn name(&self) -> &'static str {
+        "mojeek"
+    }
+
+    fn search<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        query: &'a str,
+        _options: &'a DdgQuery,
+    ) -> BoxFuture<'a, Result<Vec<SearchHit>, DdgError>> {
+        Box::pin(async move {
+            let body = get_text(client.get(MOJEEK_ADDRESS).query(&[("q", query)])).await?;
+
+            parse_hits(&body, MOJEEK_LAYOUT, self.name())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ddg_html_hits() {
+        let html = r#"
+            <div class="result results_links results_links_deep web-result">
+              <h2 class="result__title">
+                <a rel="nofollow" class="result__a" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fdoc.rust-lang.org%2Fstd%2Fvec&amp;rut=abc">Vec in <b>std</b>::vec - Rust</a>
+              </h2>
+              <a class="result__url" href="//duckduckgo.com/l/?uddg=https%3A%2F%2Fdoc.rust-lang.org">doc.rust-lang.org</a>
+              <a class="result__snippet" href="//duckduckgo.com/l/?uddg=x">A contiguous
+                growable array type, written as <b>Vec&lt;T&gt;</b>.</a>
+            </div>
+            <div class="result result--ad">
+              <a rel="nofollow" class="result__a" href="https://duckduckgo.com/y.js?ad_domain=example.com&amp;u3=x">Ad</a>
+            </div>
+            <div class="result">
+              <a rel="nofollow" class='result__a' href=https://users.rust-lang.org/t/vec/1>Direct</a>
+            </div>
+        "#;
+        let hits = DuckDuckGoHtml::html().parse(html).unwrap();
+
+        assert_eq!(
+            hits,
+            [
+                SearchHit {
+                    url: String::from("https://doc.rust-lang.org/std/vec"),
+                    title: String::from("Vec in std::vec - Rust"),
+                    snippet: String::from("A contiguous growable array type, written as Vec<T>."),
+                    rank: 0,
+                },
+                SearchHit {
+                    url: String::from("https://users.rust-lang.org/t/vec/1"),
+                    title: String::from("Direct"),
+                    snippet: String::new(),
+                    rank: 0,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ddg_lite_hits() {
+        let html = r#"
+            <table>
+              <tr><td valign="top">1.&nbsp;</td>
+                <td><a rel="nofollow" href="https://www.rust-lang.org/" class='result-link'>Rust Programming Language</a></td></tr>
+              <tr><td>&nbsp;</td><td class='result-snippet'>A language empowering everyone.</td></tr>
+            </table>
+        "#;
+        let hits = DuckDuckGoHtml::lite().parse(html).unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].url, "https://www.rust-lang.org/");
+        assert_eq!(hits[0].title, "Rust Programming Language");
+        assert_eq!(hits[0].snippet, "A language empowering everyone.");
+    }
+
+    #[test]
+    fn test_layout_changed() {
+        let no_results = r#"<div class="no-results">No results found for <b>x</b>.</div>"#;
+        assert_eq!(DuckDuckGoHtml::html().parse(no_results).unwrap(), []);
+
+        let changed =
+            r#"<div class="web-result"><a class="result-title" href="https://a.com">A</a></div>"#;
+        assert!(matches!(
+            DuckDuckGoHtml::html().parse(changed),
+            Err(DdgError::LayoutChanged { .. })
+        ));
+    }
+
+    #[test]
+    fn test_options_params() {
+        let options = DdgQuery::new()
+            .with_region("US-EN")
+            .with_safe_search(SafeSearch::Off)
+            .with_time_range(TimeRange::Year);
+
+        assert_eq!(
+            ddg_params(&options),
+            [
+                ("kl", String::from("us-en")),
+                ("kp", String::from("-2")),
+                ("df", String::from("y"))
+            ]
+        );
+        assert!(ddg_params(&DdgQuery::new()).is_empty());
+        assert_eq!(region_parts("de-de"), Some(("de", "de")));
+        assert_eq!(region_parts("wt-wt"), None);
+    }
+
+    #[test]
+    fn test_json_results() {
+        let searxng: SearxngResults = serde_json::from_str(
+            r#"{"query":"rust","results":[{"url":"https://a.com","title":"A","content":"a"}]}"#,
+        )
+        .unwrap();
+        let brave: BraveResults =
+            serde_json::from_str(r#"{"web":{"results":[{"url":"https://b.com","title":"B"}]}}"#)
+                .unwrap();
+
+        assert_eq!(searxng.results[0].url, "https://a.com");
+        assert_eq!(brave.web.unwrap().results[0].url, "https://b.com");
+        assert_eq!(
+            text_of("A <strong>growable</strong>\n array"),
+            "A growable array"
+        );
+    }
+}
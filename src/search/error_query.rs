@@ -0,0 +1,347 @@
+const PYTHON_TRACEBACK: &str = "Traceback (most recent call last):";
+const PYTHON_EXCEPTION_SUFFIXES: [&str; 5] = ["Error", "Exception", "Warning", "Interrupt", "Exit"];
+const RUSTC_NOISE: [&str; 4] = [
+    "error: could not compile",
+    "error: aborting due to",
+    "error: Could not compile",
+    "error: process didn't exit successfully",
+];
+const RUST_PANIC: &str = "panicked at ";
+const GO_PANIC: &str = "panic: ";
+const GO_FATAL: &str = "fatal error: ";
+// quoted identifiers longer than this are more likely generated or specific to the user
+const MAX_IDENTIFIER_LEN: usize = 40;
+const FILE_EXTENSIONS: [&str; 16] = [
+    "rs", "py", "go", "js", "ts", "jsx", "tsx", "c", "h", "cpp", "hpp", "java", "toml", "json",
+    "yaml", "lock",
+];
+
+/// Extract a search query out of the output of a compiler or a program that crashed. The most
+/// relevant error line is picked: the first rustc error (with its code), a rust panic's message,
+/// a go panic, or the last line of a python traceback. If none of those are found the first line
+/// mentioning an error is used, output without any error isn't searched at all.
+///
+/// Paths, line numbers, addresses and quoted expressions are stripped from the line, since they're
+/// specific to the user's code, hurt the results and could leak information about the user.
+///
+/// # Arguments
+///
+/// * `output` - The output of the compiler or program, colored or not.
+///
+/// # Returns
+///
+/// The query, or None if no error line with anything left after stripping was found.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::error_query;
+///
+/// let output = "error[E0599]: no method named `push_str` found for struct `Vec<Config>`\n --> src/main.rs:2:5";
+/// assert_eq!(
+///     error_query::extract_error_query(output).as_deref(),
+///     Some("error[E0599]: no method named push_str found for struct")
+/// );
+/// ```
+pub fn extract_error_query(output: &str) -> Option<String> {
+    let output = strip_ansi(output);
+    let lines: Vec<&str> = output.lines().collect();
+
+    let line = find_rustc_error(&lines)
+        .or_else(|| find_rust_panic(&lines))
+        .or_else(|| find_go_panic(&lines))
+        .or_else(|| find_python_error(&lines))
+        .or_else(|| {
+            lines
+                .iter()
+                .find(|line| line.to_lowercase().contains("error"))
+                .copied()
+        })?;
+
+    let query = strip_specifics(line);
+    if query.is_empty() {
+        None
+    } else {
+        Some(query)
+    }
+}
+
+/// Strip everything specific to the user out of an error line: paths, file locations, numbers,
+/// addresses, emails and quoted expressions. Quoted single identifiers (like `tokio`) and paths
+/// with `::` (like `Option::unwrap()`) are kept without the quotes, since they're usually what the
+/// error is about.
+///
+/// # Arguments
+///
+/// * `line` - The error line to strip.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::error_query;
+///
+/// assert_eq!(
+///     error_query::strip_specifics("panic: open /home/me/app.json: no such file or directory"),
+///     "panic: open no such file or directory"
+/// );
+/// ```
+pub fn strip_specifics(line: &str) -> String {
+    let unquoted = strip_quoted(line);
+
+    let query = unquoted
+        .split_whitespace()
+        .filter(|token| !is_specific(token))
+        .collect::<Vec<&str>>()
+        .join(" ");
+
+    query
+        .trim_end_matches(|c: char| c == ':' || c == ',' || c.is_whitespace())
+        .to_string()
+}
+
+/// Remove the ANSI escape sequences used for coloring the output.
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip the whole escape sequence, they end with a char from '@' to '~'
+            if chars.next() == Some('[') {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+/// Remove the quoted parts of a line: everything between backticks and between single or double
+/// quotes that start a word, so apostrophes (like in "doesn't") are left alone. Quoted single
+/// identifiers and paths with `::` are kept without the quotes.
+fn strip_quoted(line: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut stripped = String::with_capacity(line.len());
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        let starts_word = index == 0 || matches!(chars[index - 1], ' ' | '(' | '[' | '=' | ',');
+        let is_quote = c == '`' || ((c == '\'' || c == '"') && starts_word);
+        let closing = chars[index + 1..].iter().position(|other| *other == c);
+
+        match closing {
+            Some(length) if is_quote => {
+                let quoted: String = chars[index + 1..index + 1 + length].iter().collect();
+                if is_identifier(&quoted) || quoted.contains("::") {
+                    stripped.push_str(&quoted);
+                }
+                index += length + 2;
+            }
+            _ => {
+                stripped.push(c);
+                index += 1;
+            }
+        }
+    }
+
+    stripped
+}
+
+/// Check if a quoted part of an error line is a single identifier, like the name of a crate, a
+/// module or a method, and not something specific to the user like a path, a sentence or a type
+/// with its generics. (ex: tokio, push_str, serde-json)
+fn is_identifier(quoted: &str) -> bool {
+    !quoted.is_empty()
+        && quoted.len() <= MAX_IDENTIFIER_LEN
+        && quoted.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && quoted
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Check if a word of an error line is specific to the user, like a path or a line number.
+fn is_specific(token: &str) -> bool {
+    let trimmed = token.trim_matches(|c: char| matches!(c, ':' | ',' | ';' | '.' | '(' | ')'));
+
+    // paths and emails
+    if trimmed.contains('/') || trimmed.contains('\\') || trimmed.contains('@') {
+        return true;
+    }
+    // numbers, addresses and indexes (ex: 42, 0x1f, [5])
+    let unbracketed = trimmed.trim_start_matches('[').trim_end_matches(']');
+    if !unbracketed.is_empty() && unbracketed.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+    if unbracketed.starts_with("0x") {
+        return true;
+    }
+    // file locations (ex: main.go:10:2) and file names (ex: config.toml)
+    let name = trimmed.split(':').next().unwrap_or(trimmed);
+    match name.rsplit_once('.') {
+        Some((stem, extension)) => !stem.is_empty() && FILE_EXTENSIONS.contains(&extension),
+        None => false,
+    }
+}
+
+/// Find the first rustc error, the ones with a code come first.
+fn find_rustc_error<'a>(lines: &[&'a str]) -> Option<&'a str> {
+    lines
+        .iter()
+        .find(|line| line.starts_with("error["))
+        .or_else(|| {
+            lines.iter().find(|line| {
+                line.starts_with("error: ") && !RUSTC_NOISE.iter().any(|n| line.starts_with(n))
+            })
+        })
+        .copied()
+}
+
+/// Find the message of a rust panic, it's either on the same line in quotes (older versions) or
+/// on the line after the location.
+fn find_rust_panic<'a>(lines: &[&'a str]) -> Option<&'a str> {
+    let index = lines.iter().position(|line| line.contains(RUST_PANIC))?;
+    let line = lines[index];
+    let after = &line[line.find(RUST_PANIC)? + RUST_PANIC.len()..];
+
+    match after.strip_prefix('\'') {
+        Some(message) => Some(message.rsplit_once("', ").map_or(message, |(m, _)| m)),
+        None => lines.get(index + 1).copied(),
+    }
+}
+
+/// Find a go panic or fatal error.
+fn find_go_panic<'a>(lines: &[&'a str]) -> Option<&'a str> {
+    lines
+        .iter()
+        .find(|line| line.starts_with(GO_PANIC) || line.starts_with(GO_FATAL))
+        .copied()
+}
+
+/// Find the exception of a python traceback, it's the last line that starts with the exception's
+/// name (ex: KeyError: 'name').
+fn find_python_error<'a>(lines: &[&'a str]) -> Option<&'a str> {
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with(PYTHON_TRACEBACK))
+        .unwrap_or(0);
+
+    lines[start..]
+        .iter()
+        .rev()
+        .find(|line| {
+            let name = line.split(':').next().unwrap_or_default();
+            !line.starts_with(char::is_whitespace)
+                && !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                && PYTHON_EXCEPTION_SUFFIXES
+                    .iter()
+                    .any(|suffix| name.ends_with(suffix))
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_rustc_error() {
+        let output = "   Compiling app v0.1.0 (/home/me/app)\n\
+                      error[E0308]: mismatched types\n  --> src/main.rs:4:18\n\
+                      error: could not compile `app` (bin \"app\") due to 1 previous error";
+
+        assert_eq!(
+            extract_error_query(output).as_deref(),
+            Some("error[E0308]: mismatched types")
+        );
+    }
+
+    #[test]
+    fn test_extract_rust_panic() {
+        let output = "thread 'main' panicked at src/main.rs:3:31:\n\
+                      called `Option::unwrap()` on a `None` value\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace";
+
+        assert_eq!(
+            extract_error_query(output).as_deref(),
+            Some("called Option::unwrap() on a None value")
+        );
+    }
+
+    #[test]
+    fn test_extract_python_traceback() {
+        let output = "Traceback (most recent call last):\n  \
+                      File \"/home/me/app.py\", line 3, in <module>\n    \
+                      print(users['bob'])\n\
+                      KeyError: 'bob'";
+
+        assert_eq!(
+            extract_error_query(output).as_deref(),
+            Some("KeyError: bob")
+        );
+    }
+
+    #[test]
+    fn test_extract_go_panic() {
+        let output = "panic: runtime error: index out of range [5] with length 3\n\n\
+                      goroutine 1 [running]:\nmain.main()\n\t/home/me/main.go:8 +0x1d";
+
+        assert_eq!(
+            extract_error_query(output).as_deref(),
+            Some("panic: runtime error: index out of range with length")
+        );
+    }
+
+    #[test]
+    fn test_extract_no_error() {
+        let output = "   Compiling secret-app v0.1.0 (/home/me/secret-app)\n    \
+                      Finished `dev` profile [unoptimized + debuginfo] target(s) in 1.20s";
+
+        assert_eq!(extract_error_query(output), None);
+        assert_eq!(extract_error_query(""), None);
+    }
+
+    #[test]
+    fn test_strip_specifics() {
+        assert_eq!(
+            strip_specifics("main.go:10:2: undefined: http.Handle"),
+            "undefined: http.Handle"
+        );
+        assert_eq!(
+            strip_specifics("user me@example.com can't open 0x7ffd config.toml"),
+            "user can't open"
+        );
+        assert_eq!(strip_ansi("\x1b[1m\x1b[31merror\x1b[0m"), "error");
+    }
+
+    #[test]
+    fn test_strip_quoted() {
+        assert_eq!(
+            strip_specifics("error[E0432]: unresolved import `tokio`"),
+            "error[E0432]: unresolved import tokio"
+        );
+        assert_eq!(
+            strip_specifics("ModuleNotFoundError: No module named 'requests'"),
+            "ModuleNotFoundError: No module named requests"
+        );
+        assert_eq!(
+            strip_specifics("expected `Result<Vec<Config>, Error>`, found `&str`"),
+            "expected , found"
+        );
+        assert_eq!(
+            strip_specifics("error: couldn't read `/home/me/app/src/secret.rs`"),
+            "error: couldn't read"
+        );
+        assert_eq!(
+            strip_specifics("no field `a very long sentence` on type `MyStruct`"),
+            "no field on type MyStruct"
+        );
+    }
+}
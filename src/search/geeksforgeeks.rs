@@ -1,4 +1,6 @@
 use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::metadata::{self, Metadata};
 use super::util;
 use thiserror::Error;
 
@@ -15,7 +17,25 @@ const GEEKSFORGEEKS_INVALID: [&str; 7] = [
     "https://www.geeksforgeeks.org/expert/",
 ];
 
-type GfgPage = Result<String, GfgError>;
+const ARTICLE_TITLE: &str = "<h1";
+const ARTICLE_UPDATED: &str = "Last Updated";
+const UPDATED_FORMAT: &str = "%d %b, %Y";
+// how many text nodes after the last updated label can hold the date
+const UPDATED_NODES: usize = 3;
+// wide enough for a title to never wrap
+const TITLE_WIDTH: usize = 1000;
+
+type GfgPage = Result<Article, GfgError>;
+
+/// A GeeksForGeeks article together with its metadata.
+///
+/// * `content` - the article as text.
+/// * `metadata` - the title of the article and when it was last updated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Article {
+    pub content: String,
+    pub metadata: Metadata,
+}
 
 /// These are the errors the functions associated with GeeksForGeeks will return.
 ///
@@ -84,7 +104,20 @@ impl GeeksForGeeks {
         }
     }
 
-    /// Get the contents of a GeeksForGeeks page inside a String.
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Get the contents of a GeeksForGeeks page inside a String, with its title and when it was
+    /// last updated.
     ///
     /// # Arguments
     ///
@@ -110,7 +143,7 @@ impl GeeksForGeeks {
     /// # async fn run() -> Result<(), geeksforgeeks::GfgError> {
     /// let ddg = ddg::Ddg::new();
     /// let gfg = geeksforgeeks::GeeksForGeeks::new();
-    /// let link = &ddg.get_links("Rust basics", Some("www.geeksforgeeks.org"), None, Some(&GEEKSFORGEEKS_INVALID), Some(1)).await.unwrap()[0];
+    /// let link = &ddg.get_links("Rust basics", Some("www.geeksforgeeks.org"), None, Some(&GEEKSFORGEEKS_INVALID), Some(1)).await.unwrap()[0].url;
     ///
     /// let page_content = gfg.get_page_content(&link).await.unwrap();
     /// # Ok(())
@@ -207,7 +240,10 @@ impl GeeksForGeeks {
         };
 
         // return article
-        Ok(util::html_to_text(article, term_width))
+        Ok(Article {
+            content: util::html_to_text(article, term_width),
+            metadata: article_metadata(&response_body),
+        })
     }
 
     /// Search for GeeksForGeeks results using duckduckgo and a provided query. This function will
@@ -238,7 +274,7 @@ impl GeeksForGeeks {
     ///     .unwrap();
     ///
     /// for p in page_content {
-    ///    assert!(!p.1.await.unwrap().unwrap().is_empty())
+    ///    assert!(!p.1.await.unwrap().unwrap().content.is_empty())
     /// }
     /// # Ok(())
     /// # }
@@ -257,7 +293,7 @@ impl GeeksForGeeks {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<(String, tokio::task::JoinHandle<GfgPage>)>, GfgError> {
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<GfgPage>)>, GfgError> {
         tracing::info!("Get multiple geeksforgeeks pages and their content for search query: {} with a results limit of: {:#?}", &query, &limit);
         // get the links from duckduckgo
         let links = match self
@@ -281,13 +317,14 @@ impl GeeksForGeeks {
         // start looping through the links associating the page title and the joinhandle for
         // the future the scrapes the content of the page by inserting them togheter in the
         // Vec inside tuples
-        for link in links {
+        for hit in links {
             // unwrap is safe here since ddg does all the checks
-            let name = link.split('/').last().unwrap().replace('-', " ");
+            let name = hit.title_or(hit.url.split('/').last().unwrap().replace('-', " "));
+            let link = hit.url.clone();
             // insert page content
             let client = self.client.clone();
             pages_content.push((
-                name,
+                SearchHit { title: name, ..hit },
                 tokio::task::spawn(async move {
                     Self::with_client(client).get_page_content(&link).await
                 }),
@@ -299,6 +336,31 @@ impl GeeksForGeeks {
     }
 }
 
+/// Get the metadata of an article out of its page: the title in its heading and the date after
+/// the last updated label.
+///
+/// # Arguments
+///
+/// * `page` - The html of the article's page.
+fn article_metadata(page: &str) -> Metadata {
+    Metadata {
+        title: page
+            .split_once(ARTICLE_TITLE)
+            .and_then(|(_, heading)| heading.split_once('>'))
+            .and_then(|(_, title)| title.split_once("</h1>"))
+            .map(|(title, _)| util::html_to_text(title, TITLE_WIDTH).trim().to_string())
+            .filter(|title| !title.is_empty()),
+        updated: page.split_once(ARTICLE_UPDATED).and_then(|(_, updated)| {
+            metadata::text_nodes(updated)
+                .take(UPDATED_NODES)
+                .find_map(|date| chrono::NaiveDate::parse_from_str(date, UPDATED_FORMAT).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc().timestamp())
+        }),
+        ..Default::default()
+    }
+}
+
 impl Default for GeeksForGeeks {
     fn default() -> Self {
         GeeksForGeeks::new()
@@ -322,7 +384,24 @@ mod tests {
 
         let page_content = gfg.get_page_content(link).await.unwrap();
 
-        assert!(!page_content.is_empty())
+        assert!(!page_content.content.is_empty())
+    }
+
+    #[test]
+    fn test_article_metadata() {
+        let page = r#"<div class="article-title"><h1>Rust &amp; Basics</h1></div>
+            <div class="article-meta"><span>Last Updated : </span>
+            <span>05 Jul, 2013</span></div>"#;
+
+        assert_eq!(
+            article_metadata(page),
+            Metadata {
+                title: Some("Rust & Basics".to_string()),
+                updated: Some(1372982400),
+                ..Default::default()
+            }
+        );
+        assert_eq!(article_metadata("<p>article</p>"), Metadata::default());
     }
 
     #[ignore] // ignore to pass github code actions, it work on local machine
@@ -342,7 +421,7 @@ mod tests {
                 .unwrap();
 
             for p in page_content {
-                assert!(!p.1.await.unwrap().unwrap().is_empty())
+                assert!(!p.1.await.unwrap().unwrap().content.is_empty())
             }
         };
 
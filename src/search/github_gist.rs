@@ -1,4 +1,7 @@
 use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::lang::Lang;
+use super::metadata::{self, Metadata};
 use super::util;
 use futures::StreamExt;
 use rayon::prelude::*;
@@ -9,8 +12,22 @@ const GIST_URI: &str = "https://gist.github.com";
 const GIST_SITE: &str = "gist.github.com";
 const GIST_RAW_URL_SPLIT: &str = "<a href=\"/{GIST_LOCATION}/raw/";
 const GIST_RAW_URL: &str = "https://gist.github.com/{GIST_LOCATION}/raw/{FILE_URL}";
+const GIST_STARS: &str = "/stargazers\"";
+const GIST_DATE: &str = "<relative-time datetime=\"";
+// how many text nodes after the stargazers link can hold the count of stars
+const GIST_STARS_NODES: usize = 5;
 
-type GistContent = Result<Vec<String>, GithubGistError>;
+type GistContent = Result<Gist, GithubGistError>;
+
+/// The files of a gist together with its metadata.
+///
+/// * `files` - the content of the files that could be retrieved.
+/// * `metadata` - the stars, the number of files and the date of the gist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Gist {
+    pub files: Vec<String>,
+    pub metadata: Metadata,
+}
 
 /// These are the errors the functions associated with GithubGist will return.
 ///
@@ -23,6 +40,8 @@ type GistContent = Result<Vec<String>, GithubGistError>;
 /// corrupted because it did return 200 OK.
 /// * `NoGistFileGot` - This means the gist might contain files, but the function couldn't get any
 /// of them.
+/// * `NotInLang` - None of the files of the gist are written in the language the gists are
+/// filtered by.
 /// * `ErrorCode` - The website returned an error code
 /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
 #[derive(Error, Debug)]
@@ -37,6 +56,8 @@ pub enum GithubGistError {
     InvalidPageContent,
     #[error("None of the gist's files could be retrieved.")]
     NoGistFileGot,
+    #[error("None of the gist's files are written in {0}.")]
+    NotInLang(String),
     #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
     ErrorCode(reqwest::StatusCode),
     #[error("There was an error retrieving search results from duckduckgo: {0}")]
@@ -48,6 +69,7 @@ pub enum GithubGistError {
 pub struct GithubGist {
     client: reqwest::Client,
     ddg: ddg::Ddg,
+    lang: Option<&'static Lang>,
 }
 
 impl GithubGist {
@@ -65,6 +87,7 @@ impl GithubGist {
         Self {
             client: util::client_with_special_settings(),
             ddg: ddg::Ddg::new(),
+            lang: None,
         }
     }
 
@@ -80,10 +103,41 @@ impl GithubGist {
         Self {
             client: client.clone(),
             ddg: ddg::Ddg::with_client(client),
+            lang: None,
         }
     }
 
-    /// Get the contents of a gist inside a String.
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Only get the files of the gists written in a language, by their extension, and look for
+    /// gists about it.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language of the files, every file is kept if None.
+    ///
+    /// ```
+    /// use falion::search::github_gist;
+    /// use falion::search::lang;
+    ///
+    /// let source = github_gist::GithubGist::new().with_lang(lang::find("python"));
+    /// ```
+    pub fn with_lang(mut self, lang: Option<&'static Lang>) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Get the contents of a gist, every file inside a String, with its stars and date.
     /// Note: the content returned could be partial. Meaning if the gist has multiple files and one
     /// or multiple of them can't be read, but at if least one has been it will return only the
     /// one/ones that have been successfully read.
@@ -101,7 +155,7 @@ impl GithubGist {
     /// # async fn run() -> Result<(), github_gist::GithubGistError> {
     /// let ddg = ddg::Ddg::new();
     /// let github_gist = github_gist::GithubGist::new();
-    /// let link = &ddg.get_links("Rust basics", Some("gist.github.com"), None, None, Some(1)).await.unwrap()[0];
+    /// let link = &ddg.get_links("Rust basics", Some("gist.github.com"), None, None, Some(1)).await.unwrap()[0].url;
     ///
     /// let gist_content = github_gist.get_gist_content(&link).await.unwrap();
     /// # Ok(())
@@ -121,6 +175,7 @@ impl GithubGist {
     /// corrupted because it did return 200 OK.
     /// * `NoGistFileGot` - This means the gist might contain files, but the function couldn't get any
     /// of them.
+    /// * `NotInLang` - None of the files of the gist are written in the language of with_lang.
     /// * `ErrorCode` - The website returned an error code
     #[tracing::instrument(skip_all)]
     pub async fn get_gist_content(&self, gist_url: &str) -> GistContent {
@@ -205,7 +260,7 @@ impl GithubGist {
         }
 
         // get raw gist urls
-        let raw_gist_urls: Vec<String> = response_body
+        let mut raw_gist_urls: Vec<String> = response_body
             .split(&GIST_RAW_URL_SPLIT.replace("{GIST_LOCATION}", gist_location))
             .skip(1)
             .filter_map(|s| s.split_once("\" ").map(|s_split| s_split.0))
@@ -225,6 +280,19 @@ impl GithubGist {
             return Err(GithubGistError::InvalidPageContent);
         }
 
+        // only keep the files written in the language, if any
+        if let Some(lang) = self.lang {
+            raw_gist_urls.retain(|url| lang.is_file_of(url));
+            if raw_gist_urls.is_empty() {
+                tracing::warn!(
+                    "None of the files of {} are written in {}.",
+                    &gist_url,
+                    lang
+                );
+                return Err(GithubGistError::NotInLang(lang.to_string()));
+            }
+        }
+
         tracing::debug!(
             "Request all github gist files (from {}) {:#?} parallel as a future.",
             &gist_url,
@@ -293,7 +361,11 @@ impl GithubGist {
         }
 
         // return gist files
-        Ok(gist_files)
+        let metadata = gist_metadata(&response_body, gist_files.len());
+        Ok(Gist {
+            files: gist_files,
+            metadata,
+        })
     }
 
     /// Search for results using duckduckgo and a provided query on GitHub gists. This function will
@@ -324,7 +396,7 @@ impl GithubGist {
     ///     .unwrap();
     ///
     /// for p in gist_content {
-    ///    assert!(!p.1.await.unwrap().unwrap().is_empty())
+    ///    assert!(!p.1.await.unwrap().unwrap().files.is_empty())
     /// }
     /// # Ok(())
     /// # }
@@ -343,12 +415,18 @@ impl GithubGist {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<(String, tokio::task::JoinHandle<GistContent>)>, GithubGistError> {
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<GistContent>)>, GithubGistError> {
         tracing::info!("Get multiple GitHub gists and their content for search query: {} with a results limit of: {:#?}", &query, &limit);
+        // look for gists about the language, if any
+        let query = match self.lang {
+            Some(lang) => lang.boosted(query),
+            None => query.to_string(),
+        };
+
         // get the links from duckduckgo
         let links = match self
             .ddg
-            .get_links(query, Some(GIST_SITE), Some(false), None, limit)
+            .get_links(&query, Some(GIST_SITE), Some(false), None, limit)
             .await
         {
             Ok(res) => res,
@@ -361,7 +439,8 @@ impl GithubGist {
         // start looping through the links associating the page title and the joinhandle for
         // the future the scrapes the content of the page by inserting them togheter in the
         // Vec inside a tuple
-        for link in links {
+        for hit in links {
+            let link = hit.url.clone();
             // unwrap is safe here since ddg & GithubGist do all the checks
             let name = match link.split_once(GIST_URL) {
                 Some(s) => match s.1.split_once('/') {
@@ -377,10 +456,17 @@ impl GithubGist {
             full_name.push_str(&id);
             // insert page content
             let client = self.client.clone();
+            let lang = self.lang;
             gists_content.push((
-                full_name,
+                SearchHit {
+                    title: hit.title_or(full_name),
+                    ..hit
+                },
                 tokio::task::spawn(async move {
-                    Self::with_client(client).get_gist_content(&link).await
+                    Self::with_client(client)
+                        .with_lang(lang)
+                        .get_gist_content(&link)
+                        .await
                 }),
             ));
         }
@@ -390,6 +476,29 @@ impl GithubGist {
     }
 }
 
+/// Get the metadata of a gist out of its page: the count of stars that follows the link to its
+/// stargazers and the first date on the page, when the gist was created.
+///
+/// # Arguments
+///
+/// * `page` - The html of the gist's page.
+/// * `files` - How many files of the gist were retrieved.
+fn gist_metadata(page: &str, files: usize) -> Metadata {
+    Metadata {
+        stars: page.split_once(GIST_STARS).and_then(|(_, stars)| {
+            metadata::text_nodes(stars)
+                .take(GIST_STARS_NODES)
+                .find_map(metadata::parse_count)
+        }),
+        files: Some(files),
+        date: page
+            .split_once(GIST_DATE)
+            .and_then(|(_, date)| date.split_once('"'))
+            .and_then(|(date, _)| metadata::parse_date(date)),
+        ..Default::default()
+    }
+}
+
 impl Default for GithubGist {
     fn default() -> Self {
         GithubGist::new()
@@ -413,7 +522,24 @@ mod tests {
 
         let gist_content = github_gist.get_gist_content(link).await.unwrap();
 
-        assert!(!gist_content.is_empty())
+        assert!(!gist_content.files.is_empty())
+    }
+
+    #[test]
+    fn test_gist_metadata() {
+        let page = r#"<a href="/someone/1a2b/stargazers" class="btn">
+            <span>Star</span> <span class="Counter">1.2k</span></a>
+            created <relative-time datetime="2013-07-05T21:12:43Z">Jul 5, 2013</relative-time>"#;
+
+        assert_eq!(
+            gist_metadata(page, 2),
+            Metadata {
+                stars: Some(1200),
+                files: Some(2),
+                date: Some(1373058763),
+                ..Default::default()
+            }
+        );
     }
 
     #[ignore] // ignore to pass github code actions, it work on local machine
@@ -433,7 +559,7 @@ mod tests {
                 .unwrap();
 
             for p in gist_content {
-                assert!(!p.1.await.unwrap().unwrap().is_empty())
+                assert!(!p.1.await.unwrap().unwrap().files.is_empty())
             }
         };
 
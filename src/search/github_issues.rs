@@ -0,0 +1,901 @@
+use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::metadata::{self, Metadata};
+use super::thread::{Answer, Comment, Thread};
+use super::util;
+use reqwest::header;
+use serde::Deserialize;
+use thiserror::Error;
+
+const GITHUB_SITE: &str = "github.com";
+const GITHUB_INVALID: [&str; 8] = [
+    "/blob/",
+    "/tree/",
+    "/pull/",
+    "/commit/",
+    "/releases",
+    "/wiki",
+    "/actions",
+    "/security",
+];
+const ISSUES_PATH: &str = "issues";
+const DISCUSSIONS_PATH: &str = "discussions";
+const API_ADDRESS: &str = "https://api.github.com";
+const API_GRAPHQL: &str = "https://api.github.com/graphql";
+const ISSUE_URL: &str = "/repos/{OWNER}/{REPO}/issues/{NUMBER}";
+const COMMENTS_URL: &str = "/repos/{OWNER}/{REPO}/issues/{NUMBER}/comments";
+// the bodies come rendered as html, like the other sources, instead of markdown
+const API_ACCEPT: &str = "application/vnd.github.html+json";
+const API_VERSION_HEADER: &str = "X-GitHub-Api-Version";
+const API_VERSION: &str = "2022-11-28";
+// the api allows at most 100 comments per page
+const MAX_PAGE_SIZE: usize = 100;
+// how many links to ask duckduckgo for per result, since many of github's aren't issues
+const LINKS_PER_RESULT: usize = 3;
+const CLOSED_STATE: &str = "closed";
+const EMPTY_BODY: &str = "No description provided.";
+const DISCUSSION_QUERY: &str = "query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    discussion(number: $number) {
+      title bodyHTML createdAt updatedAt upvoteCount
+      category { name }
+      answer { databaseId }
+      labels(first: 20) { nodes { name } }
+      comments(first: 100) {
+        totalCount
+        nodes {
+          databaseId bodyHTML createdAt upvoteCount isAnswer
+          replies(first: 50) { nodes { bodyHTML upvoteCount } }
+        }
+      }
+    }
+  }
+}";
+
+type IssueThread = Result<Thread, GithubIssuesError>;
+
+/// These are the errors the functions associated with GithubIssues will return.
+///
+/// * `NotIssue` - The given url does not correspond to a GitHub issue or discussion.
+/// * `NeedsToken` - The given url is a discussion, which can only be got with a GitHub token.
+/// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+/// due to rate limiting, bad internet etc.
+/// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+/// internet.
+/// * `ApiError` - The GitHub api returned an error, like the discussion not existing.
+/// * `ErrorCode` - The api returned an error code, like when the rate limit is exceeded.
+/// * `NoResults` - None of the links duckduckgo found are issues or discussions.
+/// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+#[derive(Error, Debug)]
+pub enum GithubIssuesError {
+    #[error("The given page: {0} is not a GitHub issue or discussion.")]
+    NotIssue(String),
+    #[error("The given page: {0} is a GitHub discussion, which needs a GitHub token to be read.")]
+    NeedsToken(String),
+    #[error("Failed to make a request to the GitHub api: {0}")]
+    InvalidRequest(reqwest::Error),
+    #[error("A request has been successfully made, but there was an error getting the response body: {0}")]
+    InvalidResponseBody(reqwest::Error),
+    #[error("The GitHub api returned an error: {0}")]
+    ApiError(String),
+    #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
+    ErrorCode(reqwest::StatusCode),
+    #[error("None of the results for the query: {0} are GitHub issues or discussions.")]
+    NoResults(String),
+    #[error("There was an error retrieving search results from duckduckgo: {0}")]
+    DdgError(ddg::DdgError),
+}
+
+/// If a GitHub thread is an issue (or a pull request) or a discussion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    Issue,
+    Discussion,
+}
+
+/// Where an issue or a discussion is, as found in its url.
+///
+/// * `owner` - the user or organization owning the repository.
+/// * `repo` - the name of the repository.
+/// * `kind` - if it's an issue or a discussion.
+/// * `number` - the number of the issue or discussion in the repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    pub owner: String,
+    pub repo: String,
+    pub kind: IssueKind,
+    pub number: u64,
+}
+
+impl IssueRef {
+    /// The name an issue is listed under until it's loaded. (ex: rust-lang/rust#1234)
+    pub fn name(&self) -> String {
+        format!("{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
+
+/// The reactions of an issue or a comment, only the votes count toward the score.
+#[derive(Deserialize, Debug, Clone, Default)]
+struct ApiReactions {
+    #[serde(rename = "+1", default)]
+    plus_one: i64,
+    #[serde(rename = "-1", default)]
+    minus_one: i64,
+}
+
+/// A label as returned by the api.
+#[derive(Deserialize, Debug, Clone)]
+struct ApiLabel {
+    name: String,
+}
+
+/// An issue as returned by the rest api.
+#[derive(Deserialize, Debug, Clone)]
+struct ApiIssue {
+    title: String,
+    #[serde(default)]
+    body_html: Option<String>,
+    state: String,
+    comments: usize,
+    #[serde(default)]
+    labels: Vec<ApiLabel>,
+    #[serde(default)]
+    reactions: ApiReactions,
+    created_at: String,
+    updated_at: Option<String>,
+}
+
+/// A comment on an issue as returned by the rest api.
+#[derive(Deserialize, Debug, Clone)]
+struct ApiComment {
+    id: u64,
+    #[serde(default)]
+    body_html: Option<String>,
+    #[serde(default)]
+    reactions: ApiReactions,
+    created_at: String,
+}
+
+/// The wrapper every response of the graphql api comes in.
+#[derive(Deserialize, Debug)]
+struct GraphqlWrapper {
+    data: Option<GraphqlData>,
+    #[serde(default)]
+    errors: Vec<GraphqlError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GraphqlError {
+    message: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct GraphqlData {
+    repository: Option<GraphqlRepository>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GraphqlRepository {
+    discussion: Option<ApiDiscussion>,
+}
+
+/// The nodes of a connection of the graphql api.
+#[derive(Deserialize, Debug, Clone)]
+struct Nodes<T> {
+    #[serde(default = "Vec::new")]
+    nodes: Vec<T>,
+}
+
+/// The comments of a discussion, with how many there are in total.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DiscussionComments {
+    total_count: usize,
+    #[serde(default)]
+    nodes: Vec<DiscussionComment>,
+}
+
+/// A discussion as returned by the graphql api.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ApiDiscussion {
+    title: String,
+    #[serde(rename = "bodyHTML")]
+    body_html: String,
+    created_at: String,
+    updated_at: Option<String>,
+    upvote_count: i64,
+    category: Option<ApiLabel>,
+    answer: Option<AnswerId>,
+    labels: Option<Nodes<ApiLabel>>,
+    comments: DiscussionComments,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct AnswerId {
+    database_id: Option<u64>,
+}
+
+/// A comment on a discussion as returned by the graphql api, with its replies.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DiscussionComment {
+    database_id: Option<u64>,
+    #[serde(rename = "bodyHTML")]
+    body_html: String,
+    created_at: String,
+    upvote_count: i64,
+    is_answer: bool,
+    replies: Nodes<DiscussionReply>,
+}
+
+/// A reply to a comment on a discussion as returned by the graphql api.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct DiscussionReply {
+    #[serde(rename = "bodyHTML")]
+    body_html: String,
+    upvote_count: i64,
+}
+
+/// Get the issue or discussion an url points to.
+/// (ex: https://github.com/rust-lang/rust/issues/1234 -> rust-lang, rust, Issue, 1234)
+///
+/// # Arguments
+///
+/// * `issue_url` - The absolute url to the issue or discussion.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::github_issues::{self, IssueKind};
+///
+/// let issue = github_issues::issue_from_url("https://github.com/tokio-rs/tokio/discussions/3858").unwrap();
+///
+/// assert_eq!(issue.name(), "tokio-rs/tokio#3858");
+/// assert_eq!(issue.kind, IssueKind::Discussion);
+/// ```
+pub fn issue_from_url(issue_url: &str) -> Option<IssueRef> {
+    let url = url::Url::parse(issue_url).ok()?;
+    if url.host_str()? != GITHUB_SITE {
+        return None;
+    }
+    let mut segments = url.path_segments()?;
+    let owner = segments.next()?;
+    let repo = segments.next()?;
+    let kind = match segments.next()? {
+        ISSUES_PATH => IssueKind::Issue,
+        DISCUSSIONS_PATH => IssueKind::Discussion,
+        _ => return None,
+    };
+    let number = segments.next()?.parse().ok()?;
+
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+
+    Some(IssueRef {
+        owner: owner.to_string(),
+        repo: repo.to_string(),
+        kind,
+        number,
+    })
+}
+
+/// Search GitHub issues and discussions and get them as threads, the issue as the question and
+/// its comments as the answers.
+#[derive(std::fmt::Debug)]
+pub struct GithubIssues {
+    client: reqwest::Client,
+    ddg: ddg::Ddg,
+    token: Option<String>,
+}
+
+impl GithubIssues {
+    /// Create a new GithubIssues instance with a custom client that generates UA (user-agent in
+    /// order to avoid getting rate limited by DuckDuckGO) and without a GitHub token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::github_issues;
+    ///
+    /// let github_issues = github_issues::GithubIssues::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::with_client(util::client_with_special_settings())
+    }
+
+    /// Create a new GithubIssues instance with a provided client and without a GitHub token.
+    /// Note: DuckDuckGO will limit your requests if you don't provide a user-agent.
+    ///
+    /// ```
+    /// use falion::search::github_issues;
+    ///
+    /// let github_issues = github_issues::GithubIssues::with_client(reqwest::Client::new());
+    /// ```
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self::with_token(client, None)
+    }
+
+    /// Create a new GithubIssues instance with a provided client and an optional GitHub token.
+    /// The token raises the rate limit of the api from 60 to 5,000 requests an hour and is needed
+    /// for discussions, a token without any scope is enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to make the requests with.
+    /// * `token` - Optional, the GitHub token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::github_issues;
+    ///
+    /// let github_issues = github_issues::GithubIssues::with_token(reqwest::Client::new(), Some("token".to_string()));
+    /// ```
+    pub fn with_token(client: reqwest::Client, token: Option<String>) -> Self {
+        Self {
+            client: client.clone(),
+            ddg: ddg::Ddg::with_client(client),
+            token,
+        }
+    }
+
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Add the headers of the api to a request, and the token if there's one.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to the api.
+    fn api_request(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        let request = request
+            .header(header::ACCEPT, API_ACCEPT)
+            .header(API_VERSION_HEADER, API_VERSION);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// Send a request to the api and parse the json it responds with.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to the api, without its headers.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ErrorCode` - The api returned an error code.
+    #[tracing::instrument(skip_all)]
+    async fn send<T>(&self, request: reqwest::RequestBuilder) -> Result<T, GithubIssuesError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response = match self.api_request(request).send().await {
+            Ok(res) => res,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to make a request to the GitHub api. Error: {}",
+                    &error
+                );
+                return Err(GithubIssuesError::InvalidRequest(error));
+            }
+        };
+        if response.status() != reqwest::StatusCode::OK {
+            tracing::error!(
+                "Request to {} return status code: {}",
+                response.url(),
+                &response.status()
+            );
+            return Err(GithubIssuesError::ErrorCode(response.status()));
+        }
+
+        match response.json::<T>().await {
+            Ok(parsed) => Ok(parsed),
+            Err(error) => {
+                tracing::error!(
+                    "The response body recieved from the GitHub api is invalid. Error: {}",
+                    &error
+                );
+                Err(GithubIssuesError::InvalidResponseBody(error))
+            }
+        }
+    }
+
+    /// Get an issue (or a pull request) or a discussion as a thread: its body as the question,
+    /// its comments as the answers (the answer of a discussion marked as accepted and the
+    /// replies as the comments of the answers) and its title, labels, votes and date as metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue_url` - The absolute url to the issue or discussion.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use falion::search::github_issues;
+    ///
+    /// # async fn run() -> Result<(), github_issues::GithubIssuesError> {
+    /// let github_issues = github_issues::GithubIssues::new();
+    /// let thread = github_issues
+    ///     .get_issue_content("https://github.com/rust-lang/rust/issues/1")
+    ///     .await?;
+    ///
+    /// assert!(!thread.question.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns github_issues::GithubIssuesError
+    ///
+    /// * `NotIssue` - The given url does not correspond to a GitHub issue or discussion.
+    /// * `NeedsToken` - The url is a discussion and there's no token.
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+    /// due to rate limiting, bad internet etc.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+    /// internet.
+    /// * `ApiError` - The graphql api returned an error, like the discussion not existing.
+    /// * `ErrorCode` - The api returned an error code.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_issue_content(&self, issue_url: &str) -> IssueThread {
+        tracing::info!(
+            "Get the content for the following github issue: {}",
+            &issue_url
+        );
+        let issue = match issue_from_url(issue_url) {
+            Some(issue) => issue,
+            None => {
+                tracing::error!(
+                    "The given url is not a github issue url. Url: {}",
+                    &issue_url
+                );
+                return Err(GithubIssuesError::NotIssue(issue_url.to_string()));
+            }
+        };
+
+        // set term width
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+
+        match issue.kind {
+            IssueKind::Issue => self.get_issue(&issue, term_width).await,
+            IssueKind::Discussion => {
+                if self.token.is_none() {
+                    tracing::error!("Can't get the discussion {} without a token.", &issue_url);
+                    return Err(GithubIssuesError::NeedsToken(issue_url.to_string()));
+                }
+                self.get_discussion(&issue, term_width).await
+            }
+        }
+    }
+
+    /// Get an issue and its comments from the rest api, both at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - Where the issue is.
+    /// * `term_width` - The width of your terminal in order to properly display.
+    #[tracing::instrument(skip_all)]
+    async fn get_issue(&self, issue: &IssueRef, term_width: usize) -> IssueThread {
+        let path = |template: &str| {
+            template
+                .replace("{OWNER}", &issue.owner)
+                .replace("{REPO}", &issue.repo)
+                .replace("{NUMBER}", &issue.number.to_string())
+        };
+        let issue_request = self.client.get(API_ADDRESS.to_string() + &path(ISSUE_URL));
+        let comments_request = self
+            .client
+            .get(API_ADDRESS.to_string() + &path(COMMENTS_URL))
+            .query(&[("per_page", MAX_PAGE_SIZE)]);
+
+        let (api_issue, comments) = futures::join!(
+            self.send::<ApiIssue>(issue_request),
+            self.send::<Vec<ApiComment>>(comments_request)
+        );
+        let api_issue = api_issue?;
+        // the issue is still worth showing without its comments
+        let comments = comments.unwrap_or_else(|error| {
+            tracing::warn!(
+                "Failed to get the comments of {}. Error: {}",
+                issue.name(),
+                error
+            );
+            Vec::new()
+        });
+
+        Ok(issue_thread(issue, &api_issue, &comments, term_width))
+    }
+
+    /// Get a discussion and its comments from the graphql api.
+    ///
+    /// # Arguments
+    ///
+    /// * `issue` - Where the discussion is.
+    /// * `term_width` - The width of your terminal in order to properly display.
+    #[tracing::instrument(skip_all)]
+    async fn get_discussion(&self, issue: &IssueRef, term_width: usize) -> IssueThread {
+        let body = serde_json::json!({
+            "query": DISCUSSION_QUERY,
+            "variables": {
+                "owner": issue.owner,
+                "repo": issue.repo,
+                "number": issue.number,
+            },
+        });
+        let wrapper: GraphqlWrapper = self.send(self.client.post(API_GRAPHQL).json(&body)).await?;
+
+        if let Some(error) = wrapper.errors.into_iter().next() {
+            tracing::error!("The GitHub graphql api returned: {}", &error.message);
+            return Err(GithubIssuesError::ApiError(error.message));
+        }
+        match wrapper
+            .data
+            .and_then(|data| data.repository)
+            .and_then(|repository| repository.discussion)
+        {
+            Some(discussion) => Ok(discussion_thread(issue, &discussion, term_width)),
+            None => {
+                tracing::error!("The discussion {} wasn't returned.", issue.name());
+                Err(GithubIssuesError::ApiError(format!(
+                    "The discussion {} doesn't exist.",
+                    issue.name()
+                )))
+            }
+        }
+    }
+
+    /// Search for GitHub issues and discussions and get their content. Each thread is got in its
+    /// own future, associated with its name and returned inside a Vec for preserved order. The
+    /// discussions are left out when there's no token, since they can't be got without one.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, the most issues and discussions to return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because it fails github code action
+    /// use falion::search::github_issues;
+    ///
+    /// # async fn run() -> Result<(), github_issues::GithubIssuesError> {
+    /// let github_issues = github_issues::GithubIssues::new();
+    /// let issues_content = github_issues
+    ///     .get_multiple_issues_content("tokio runtime panic", Some(5))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for i in issues_content {
+    ///    assert!(!i.1.await.unwrap().unwrap().question.is_empty())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns github_issues::GithubIssuesError;
+    ///
+    /// * `NoResults` - None of the results are issues or discussions.
+    /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+    ///
+    /// First error is for duckduckgo, second is for the future hanle, third is for the actual
+    /// thread content
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_issues_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<IssueThread>)>, GithubIssuesError> {
+        tracing::info!(
+            "Get multiple GitHub issues and their content for search query: {} with a results limit of: {:#?}",
+            &query,
+            &limit
+        );
+        let limit = limit.unwrap_or(100);
+        let links = match self
+            .ddg
+            .get_links(
+                query,
+                Some(GITHUB_SITE),
+                Some(false),
+                Some(&GITHUB_INVALID),
+                Some(limit * LINKS_PER_RESULT),
+            )
+            .await
+        {
+            Ok(res) => res,
+            Err(err) => return Err(GithubIssuesError::DdgError(err)),
+        };
+
+        let mut issues: Vec<(IssueRef, SearchHit)> = Vec::with_capacity(limit);
+        for hit in links {
+            let issue = match issue_from_url(&hit.url) {
+                Some(issue) => issue,
+                None => continue,
+            };
+            // the comments of an issue have links of their own
+            if issues.iter().any(|(other, _)| *other == issue)
+                || (issue.kind == IssueKind::Discussion && self.token.is_none())
+            {
+                continue;
+            }
+            issues.push((issue, hit));
+            if issues.len() == limit {
+                break;
+            }
+        }
+        if issues.is_empty() {
+            tracing::error!("No GitHub issues or discussions were found for {}", &query);
+            return Err(GithubIssuesError::NoResults(query.to_string()));
+        }
+
+        // get every thread in its own future, associated with its name
+        let mut issues_content = Vec::with_capacity(issues.len());
+        for (rank, (issue, hit)) in issues.into_iter().enumerate() {
+            let link = hit.url.clone();
+            let client = self.client.clone();
+            let token = self.token.clone();
+            issues_content.push((
+                SearchHit {
+                    title: hit.title_or(issue.name()),
+                    rank: rank + 1,
+                    ..hit
+                },
+                tokio::task::spawn(async move {
+                    Self::with_token(client, token)
+                        .get_issue_content(&link)
+                        .await
+                }),
+            ));
+        }
+
+        Ok(issues_content)
+    }
+}
+
+impl Default for GithubIssues {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render the html body of an issue or a comment as text, GitHub leaves it empty when nothing
+/// was written.
+///
+/// # Arguments
+///
+/// * `body_html` - The body as returned by the api.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn body_to_text(body_html: Option<&str>, term_width: usize) -> String {
+    match body_html {
+        Some(body) if !body.trim().is_empty() => util::html_to_text(body, term_width),
+        _ => EMPTY_BODY.to_string(),
+    }
+}
+
+/// Create a thread out of an issue and its comments, a closed issue counts as answered.
+///
+/// # Arguments
+///
+/// * `issue` - Where the issue is.
+/// * `api_issue` - The issue.
+/// * `comments` - The comments on the issue.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn issue_thread(
+    issue: &IssueRef,
+    api_issue: &ApiIssue,
+    comments: &[ApiComment],
+    term_width: usize,
+) -> Thread {
+    Thread {
+        id: Some(issue.number),
+        question: body_to_text(api_issue.body_html.as_deref(), term_width),
+        comments: Vec::new(),
+        answers: comments
+            .iter()
+            .map(|comment| Answer {
+                id: Some(comment.id),
+                content: body_to_text(comment.body_html.as_deref(), term_width),
+                score: Some(comment.reactions.plus_one - comment.reactions.minus_one),
+                is_accepted: false,
+                date: metadata::parse_date(&comment.created_at),
+                comments: Vec::new(),
+            })
+            .collect(),
+        metadata: Metadata {
+            title: Some(api_issue.title.clone()),
+            site: Some(format!("{}/{}", issue.owner, issue.repo)),
+            score: Some(api_issue.reactions.plus_one - api_issue.reactions.minus_one),
+            answer_count: Some(api_issue.comments),
+            is_answered: api_issue.state == CLOSED_STATE,
+            tags: api_issue
+                .labels
+                .iter()
+                .map(|label| label.name.clone())
+                .collect(),
+            date: metadata::parse_date(&api_issue.created_at),
+            updated: api_issue
+                .updated_at
+                .as_deref()
+                .and_then(metadata::parse_date),
+            ..Default::default()
+        },
+        hidden_comments: Vec::new(),
+    }
+}
+
+/// Create a thread out of a discussion, its comments are the answers and their replies the
+/// comments of the answers. The category of the discussion is its first tag.
+///
+/// # Arguments
+///
+/// * `issue` - Where the discussion is.
+/// * `discussion` - The discussion.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn discussion_thread(issue: &IssueRef, discussion: &ApiDiscussion, term_width: usize) -> Thread {
+    let mut tags: Vec<String> = discussion
+        .category
+        .iter()
+        .map(|category| category.name.clone())
+        .collect();
+    if let Some(labels) = &discussion.labels {
+        tags.extend(labels.nodes.iter().map(|label| label.name.clone()));
+    }
+
+    Thread {
+        id: Some(issue.number),
+        question: body_to_text(Some(&discussion.body_html), term_width),
+        comments: Vec::new(),
+        answers: discussion
+            .comments
+            .nodes
+            .iter()
+            .map(|comment| Answer {
+                id: comment.database_id,
+                content: body_to_text(Some(&comment.body_html), term_width),
+                score: Some(comment.upvote_count),
+                is_accepted: comment.is_answer,
+                date: metadata::parse_date(&comment.created_at),
+                comments: comment
+                    .replies
+                    .nodes
+                    .iter()
+                    .map(|reply| Comment {
+                        content: util::html_to_text(&reply.body_html, term_width)
+                            .trim()
+                            .to_string(),
+                        score: Some(reply.upvote_count),
+                    })
+                    .collect(),
+            })
+            .collect(),
+        metadata: Metadata {
+            title: Some(discussion.title.clone()),
+            site: Some(format!("{}/{}", issue.owner, issue.repo)),
+            score: Some(discussion.upvote_count),
+            answer_count: Some(discussion.comments.total_count),
+            is_answered: discussion
+                .answer
+                .as_ref()
+                .is_some_and(|answer| answer.database_id.is_some()),
+            tags,
+            date: metadata::parse_date(&discussion.created_at),
+            updated: discussion
+                .updated_at
+                .as_deref()
+                .and_then(metadata::parse_date),
+            ..Default::default()
+        },
+        hidden_comments: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_ref(kind: IssueKind) -> IssueRef {
+        IssueRef {
+            owner: "tokio-rs".to_string(),
+            repo: "tokio".to_string(),
+            kind,
+            number: 3858,
+        }
+    }
+
+    #[test]
+    fn test_issue_from_url() {
+        assert_eq!(
+            issue_from_url("https://github.com/tokio-rs/tokio/issues/3858#issuecomment-1"),
+            Some(issue_ref(IssueKind::Issue))
+        );
+        assert_eq!(
+            issue_from_url("https://github.com/tokio-rs/tokio/discussions/3858"),
+            Some(issue_ref(IssueKind::Discussion))
+        );
+        assert_eq!(
+            issue_from_url("https://github.com/tokio-rs/tokio/issues"),
+            None
+        );
+        assert_eq!(
+            issue_from_url("https://github.com/tokio-rs/tokio/pull/3858"),
+            None
+        );
+        assert_eq!(
+            issue_from_url("https://gist.github.com/tokio-rs/tokio/issues/3858"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_issue_thread() {
+        let api_issue: ApiIssue = serde_json::from_str(
+            r#"{"title":"Runtime panics on shutdown","body_html":"<p>It panics.</p>","state":"closed","comments":1,"labels":[{"name":"C-bug"}],"reactions":{"+1":5,"-1":1,"heart":2},"created_at":"2021-06-10T12:00:00Z","updated_at":"2021-06-12T12:00:00Z"}"#,
+        )
+        .unwrap();
+        let comments: Vec<ApiComment> = serde_json::from_str(
+            r#"[{"id":7,"body_html":"","reactions":{"+1":2},"created_at":"2021-06-11T12:00:00Z"}]"#,
+        )
+        .unwrap();
+        let thread = issue_thread(&issue_ref(IssueKind::Issue), &api_issue, &comments, 50);
+
+        assert_eq!(thread.question, "It panics.\n");
+        assert_eq!(thread.answers.len(), 1);
+        assert_eq!(thread.answers[0].content, EMPTY_BODY);
+        assert_eq!(thread.answers[0].score, Some(2));
+        assert_eq!(thread.metadata.score, Some(4));
+        assert!(thread.metadata.is_answered);
+        assert_eq!(thread.metadata.tags, ["C-bug"]);
+        assert_eq!(thread.metadata.site.as_deref(), Some("tokio-rs/tokio"));
+        assert_eq!(thread.metadata.date, Some(1623326400));
+    }
+
+    #[test]
+    fn test_discussion_thread() {
+        let discussion: ApiDiscussion = serde_json::from_str(
+            r#"{"title":"How to block on a future?","bodyHTML":"<p>Inside sync code.</p>","createdAt":"2021-06-10T12:00:00Z","updatedAt":null,"upvoteCount":3,"category":{"name":"Q&A"},"answer":{"databaseId":9},"labels":{"nodes":[]},"comments":{"totalCount":1,"nodes":[{"databaseId":9,"bodyHTML":"<p>Use block_on.</p>","createdAt":"2021-06-11T12:00:00Z","upvoteCount":4,"isAnswer":true,"replies":{"nodes":[{"bodyHTML":"<p>Thanks!</p>","upvoteCount":0}]}}]}}"#,
+        )
+        .unwrap();
+        let thread = discussion_thread(&issue_ref(IssueKind::Discussion), &discussion, 50);
+
+        assert_eq!(thread.question, "Inside sync code.\n");
+        assert!(thread.answers[0].is_accepted);
+        assert_eq!(thread.answers[0].comments[0].content, "Thanks!");
+        assert!(thread.metadata.is_answered);
+        assert_eq!(thread.metadata.tags, ["Q&A"]);
+        assert_eq!(thread.metadata.answer_count, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_get_issue_content() {
+        let github_issues = GithubIssues::with_client(util::client_with_special_settings());
+
+        let thread = github_issues
+            .get_issue_content("https://github.com/rust-lang/rust/issues/1")
+            .await
+            .unwrap();
+
+        assert!(!thread.question.is_empty())
+    }
+}
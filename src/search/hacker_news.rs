@@ -0,0 +1,526 @@
+use super::engine::SearchHit;
+use super::metadata::Metadata;
+use super::thread::{Answer, Comment, Thread};
+use super::util;
+use serde::Deserialize;
+use thiserror::Error;
+
+const DEFAULT_ENDPOINT: &str = "https://hn.algolia.com/api/v1";
+const SEARCH_PATH: &str = "/search";
+const ITEM_PATH: &str = "/items/";
+const HN_SITE: &str = "news.ycombinator.com";
+const HN_ITEM_URL: &str = "https://news.ycombinator.com/item?id=";
+// both the stories and the comments are searched, the comments lead to their story
+const SEARCH_TAGS: &str = "(story,comment)";
+// how many hits to ask for per result, since many comments can be of the same story
+const HITS_PER_RESULT: usize = 3;
+const COMMENT_TYPE: &str = "comment";
+
+type StoryThread = Result<Thread, HackerNewsError>;
+
+/// These are the errors the functions associated with HackerNews will return.
+///
+/// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+/// due to rate limiting, bad internet etc.
+/// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+/// internet.
+/// * `ErrorCode` - The search api returned an error code, like when the story doesn't exist.
+/// * `NoResults` - No stories or comments matched the query.
+#[derive(Error, Debug)]
+pub enum HackerNewsError {
+    #[error("Failed to make a request to the hacker news search api: {0}")]
+    InvalidRequest(reqwest::Error),
+    #[error("A request has been successfully made, but there was an error getting the response body: {0}")]
+    InvalidResponseBody(reqwest::Error),
+    #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
+    ErrorCode(reqwest::StatusCode),
+    #[error("No hacker news stories or comments were found for the query: {0}")]
+    NoResults(String),
+}
+
+/// The results of a search.
+#[derive(Deserialize, Debug, Clone)]
+struct SearchResults {
+    #[serde(default)]
+    hits: Vec<Hit>,
+}
+
+/// A story or a comment matching a search.
+#[derive(Deserialize, Debug, Clone)]
+struct Hit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+    title: Option<String>,
+    story_id: Option<u64>,
+    story_title: Option<String>,
+}
+
+impl Hit {
+    /// The id and the title of the story the hit is, or is a comment of.
+    fn story(&self) -> Option<(u64, String)> {
+        match (&self.title, self.story_id, &self.story_title) {
+            (Some(title), _, _) => Some((self.object_id.parse().ok()?, title.clone())),
+            (None, Some(id), Some(title)) => Some((id, title.clone())),
+            _ => None,
+        }
+    }
+}
+
+/// A story or a comment with all of its replies.
+#[derive(Deserialize, Debug, Clone)]
+struct Item {
+    id: u64,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    title: Option<String>,
+    url: Option<String>,
+    text: Option<String>,
+    points: Option<i64>,
+    created_at_i: Option<i64>,
+    #[serde(default)]
+    children: Vec<Item>,
+}
+
+impl Item {
+    /// If the item is a comment that wasn't deleted.
+    fn is_comment(&self) -> bool {
+        self.kind.as_deref() == Some(COMMENT_TYPE) && self.text.is_some()
+    }
+
+    /// How many comments are under the item, including the replies to them.
+    fn comment_count(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| usize::from(child.is_comment()) + child.comment_count())
+            .sum()
+    }
+}
+
+/// Search hacker news stories and comments through its Algolia search api and get their
+/// discussions as threads.
+#[derive(std::fmt::Debug)]
+pub struct HackerNews {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl HackerNews {
+    /// Create a new HackerNews instance with a custom client that generates UA (user-agent in
+    /// order to avoid getting rate limited).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::hacker_news;
+    ///
+    /// let hacker_news = hacker_news::HackerNews::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::with_client(util::client_with_special_settings())
+    }
+
+    /// Create a new HackerNews instance with a provided client.
+    ///
+    /// ```
+    /// use falion::search::hacker_news;
+    ///
+    /// let hacker_news = hacker_news::HackerNews::with_client(reqwest::Client::new());
+    /// ```
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+        }
+    }
+
+    /// Use another endpoint for the search api than hn.algolia.com, like a mirror or a local
+    /// server for testing.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - The address of the api, without a trailing slash.
+    /// (ex: https://hn.algolia.com/api/v1)
+    ///
+    /// ```
+    /// use falion::search::hacker_news;
+    ///
+    /// let hacker_news =
+    ///     hacker_news::HackerNews::new().with_endpoint("http://localhost:8080/api/v1");
+    /// ```
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Make a get request to the search api and parse the json it responds with.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to the api.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ErrorCode` - The api returned an error code.
+    #[tracing::instrument(skip_all)]
+    async fn send<T>(&self, request: reqwest::RequestBuilder) -> Result<T, HackerNewsError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let response = match request.send().await {
+            Ok(res) => res,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to make a request to the hacker news api. Error: {}",
+                    &error
+                );
+                return Err(HackerNewsError::InvalidRequest(error));
+            }
+        };
+        if response.status() != reqwest::StatusCode::OK {
+            tracing::error!(
+                "Get request to {} return status code: {}",
+                response.url(),
+                &response.status()
+            );
+            return Err(HackerNewsError::ErrorCode(response.status()));
+        }
+
+        match response.json::<T>().await {
+            Ok(parsed) => Ok(parsed),
+            Err(error) => {
+                tracing::error!(
+                    "The response body recieved from the hacker news api is invalid. Error: {}",
+                    &error
+                );
+                Err(HackerNewsError::InvalidResponseBody(error))
+            }
+        }
+    }
+
+    /// Get a story with its discussion as a thread: the story is the question, its top comments
+    /// are the answers and the replies to every comment are flattened under it, indented by how
+    /// nested they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `story_id` - The id of the story on hacker news.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use falion::search::hacker_news;
+    ///
+    /// # async fn run() -> Result<(), hacker_news::HackerNewsError> {
+    /// let hacker_news = hacker_news::HackerNews::new();
+    /// let thread = hacker_news.get_story_content(8863).await?;
+    ///
+    /// assert!(!thread.answers.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns hacker_news::HackerNewsError
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+    /// due to rate limiting, bad internet etc.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+    /// internet.
+    /// * `ErrorCode` - The api returned an error code.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_story_content(&self, story_id: u64) -> StoryThread {
+        tracing::info!("Get the content for the following story: {}", &story_id);
+
+        // set term width
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+
+        let request_url = format!("{}{}{}", self.endpoint, ITEM_PATH, story_id);
+        let story: Item = self.send(self.client.get(request_url)).await?;
+
+        Ok(story_thread(&story, term_width))
+    }
+
+    /// Search for stories and comments on hacker news and get the discussions of their stories.
+    /// Each story is got in its own future, associated with its title and returned inside a Vec
+    /// for preserved order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, the most stories to return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because it fails github code action
+    /// use falion::search::hacker_news;
+    ///
+    /// # async fn run() -> Result<(), hacker_news::HackerNewsError> {
+    /// let hacker_news = hacker_news::HackerNews::new();
+    /// let stories_content = hacker_news
+    ///     .get_multiple_stories_content("monorepo build system", Some(5))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for s in stories_content {
+    ///    assert!(s.1.await.unwrap().unwrap().metadata.title.is_some())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns hacker_news::HackerNewsError;
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ErrorCode` - The api returned an error code.
+    /// * `NoResults` - No stories or comments matched the query.
+    ///
+    /// First error is for the search, second is for the future hanle, third is for the actual
+    /// story content
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_stories_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<StoryThread>)>, HackerNewsError> {
+        tracing::info!(
+            "Get multiple hacker news stories and their content for search query: {} with a results limit of: {:#?}",
+            &query,
+            &limit
+        );
+        let hits_per_page = limit.unwrap_or(10) * HITS_PER_RESULT;
+        let request = self
+            .client
+            .get(format!("{}{}", self.endpoint, SEARCH_PATH))
+            .query(&[
+                ("query", query),
+                ("tags", SEARCH_TAGS),
+                ("hitsPerPage", &hits_per_page.to_string()),
+            ]);
+        let results: SearchResults = self.send(request).await?;
+
+        // the comments lead to their story, which can already be in the results
+        let mut stories: Vec<(u64, String)> = Vec::with_capacity(results.hits.len());
+        for (id, title) in results.hits.iter().filter_map(Hit::story) {
+            if !stories.iter().any(|(other, _)| *other == id) {
+                stories.push((id, title));
+            }
+        }
+        stories.truncate(limit.unwrap_or(usize::MAX));
+        if stories.is_empty() {
+            tracing::error!("No hacker news stories were found for {}", &query);
+            return Err(HackerNewsError::NoResults(query.to_string()));
+        }
+
+        // get every story in its own future, associated with its title
+        let mut stories_content = Vec::with_capacity(stories.len());
+        for (rank, (id, title)) in stories.into_iter().enumerate() {
+            let client = self.client.clone();
+            let endpoint = self.endpoint.clone();
+            stories_content.push((
+                SearchHit::titled(&format!("{}{}", HN_ITEM_URL, id), &title, rank + 1),
+                tokio::task::spawn(async move {
+                    Self::with_client(client)
+                        .with_endpoint(&endpoint)
+                        .get_story_content(id)
+                        .await
+                }),
+            ));
+        }
+
+        Ok(stories_content)
+    }
+}
+
+impl Default for HackerNews {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create a thread out of a story and its discussion, the story is the question, the top
+/// comments are the answers and their replies are flattened under them.
+///
+/// # Arguments
+///
+/// * `story` - The story with all of its comments.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn story_thread(story: &Item, term_width: usize) -> Thread {
+    // the link of stories that aren't text is shown above their text, if any
+    let mut question = String::new();
+    if let Some(url) = &story.url {
+        question.push_str(url);
+        question.push_str("\n\n");
+    }
+    if let Some(html) = &story.text {
+        question.push_str(&util::html_to_text(html, term_width));
+    }
+
+    Thread {
+        id: Some(story.id),
+        question,
+        comments: Vec::new(),
+        answers: story
+            .children
+            .iter()
+            .filter(|child| child.is_comment())
+            .map(|child| {
+                let mut replies = Vec::new();
+                flatten_replies(&child.children, 0, term_width, &mut replies);
+                Answer {
+                    id: Some(child.id),
+                    content: util::html_to_text(
+                        child.text.as_deref().unwrap_or_default(),
+                        term_width,
+                    ),
+                    score: child.points,
+                    is_accepted: false,
+                    date: child.created_at_i,
+                    comments: replies,
+                }
+            })
+            .collect(),
+        metadata: Metadata {
+            title: story.title.clone(),
+            site: Some(HN_SITE.to_string()),
+            score: story.points,
+            answer_count: Some(story.comment_count()),
+            date: story.created_at_i,
+            ..Default::default()
+        },
+        hidden_comments: Vec::new(),
+    }
+}
+
+/// Flatten a tree of replies depth first, every reply indented by how deep it is in the tree.
+///
+/// # Arguments
+///
+/// * `replies` - The replies on this level of the tree.
+/// * `depth` - How nested the replies are, 0 for the replies to a top comment.
+/// * `term_width` - The width of your terminal in order to properly display.
+/// * `flattened` - Where the replies are added to.
+fn flatten_replies(
+    replies: &[Item],
+    depth: usize,
+    term_width: usize,
+    flattened: &mut Vec<Comment>,
+) {
+    for reply in replies {
+        if !reply.is_comment() {
+            continue;
+        }
+
+        flattened.push(Comment::nested(
+            reply.text.as_deref().unwrap_or_default(),
+            reply.points,
+            depth,
+            term_width,
+        ));
+        flatten_replies(&reply.children, depth + 1, term_width, flattened);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const SEARCH_RESPONSE: &str = r#"{"hits":[
+        {"objectID":"1","title":"Monorepos at scale","story_id":null},
+        {"objectID":"5","title":null,"story_id":1,"story_title":"Monorepos at scale"},
+        {"objectID":"7","title":null,"story_id":6,"story_title":"Bazel vs Buck"}
+    ]}"#;
+    const ITEM_RESPONSE: &str = r#"{"id":1,"type":"story","title":"Monorepos at scale","url":"https://example.com/monorepos","text":null,"points":120,"created_at_i":1672653600,"children":[
+        {"id":2,"type":"comment","text":"<p>We use one.</p>","points":null,"created_at_i":1672657200,"children":[
+            {"id":3,"type":"comment","text":"<p>How big?</p>","children":[
+                {"id":4,"type":"comment","text":"<p>Huge.</p>","children":[]}
+            ]},
+            {"id":5,"type":"comment","text":null,"children":[]}
+        ]}
+    ]}"#;
+
+    /// Serve the responses of the api on a local port, every request gets the response of the
+    /// first path its path starts with.
+    async fn serve(routes: Vec<(&'static str, &'static str)>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = vec![0; 4096];
+                let read = stream.read(&mut buffer).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let body = routes
+                    .iter()
+                    .find(|(route, _)| path.starts_with(route))
+                    .map(|(_, body)| *body)
+                    .unwrap_or("{}");
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        format!("http://{}/api/v1", address)
+    }
+
+    #[test]
+    fn test_story_thread() {
+        let story: Item = serde_json::from_str(ITEM_RESPONSE).unwrap();
+        let thread = story_thread(&story, 50);
+
+        assert_eq!(thread.question, "https://example.com/monorepos\n\n");
+        assert_eq!(thread.answers.len(), 1);
+        assert_eq!(thread.answers[0].content, "We use one.\n");
+        assert_eq!(
+            thread.answers[0].comments,
+            [
+                Comment {
+                    content: String::from("How big?"),
+                    score: None,
+                },
+                Comment {
+                    content: String::from("  Huge."),
+                    score: None,
+                },
+            ]
+        );
+        assert_eq!(thread.metadata.score, Some(120));
+        assert_eq!(thread.metadata.answer_count, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_get_multiple_stories_content() {
+        let endpoint = serve(vec![
+            ("/api/v1/search", SEARCH_RESPONSE),
+            ("/api/v1/items/1", ITEM_RESPONSE),
+        ])
+        .await;
+        let hacker_news = HackerNews::with_client(reqwest::Client::new()).with_endpoint(&endpoint);
+
+        let stories = hacker_news
+            .get_multiple_stories_content("monorepo", Some(5))
+            .await
+            .unwrap();
+        let titles: Vec<&str> = stories.iter().map(|(hit, _)| hit.title.as_str()).collect();
+        assert_eq!(titles, ["Monorepos at scale", "Bazel vs Buck"]);
+        assert_eq!(stories[0].0.url, "https://news.ycombinator.com/item?id=1");
+        assert_eq!(stories[1].0.rank, 2);
+
+        let thread = hacker_news.get_story_content(1).await.unwrap();
+        assert_eq!(thread.metadata.title.as_deref(), Some("Monorepos at scale"));
+        assert_eq!(thread.answers.len(), 1);
+    }
+}
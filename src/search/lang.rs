@@ -0,0 +1,385 @@
+use super::engine::SearchHit;
+use serde::Deserialize;
+use std::path::Path;
+
+// The files that mark the root of a project and the language it's written in, checked in order
+// in every directory. A package.json is javascript unless it depends on typescript.
+const PROJECT_FILES: [(&str, &str); 12] = [
+    ("Cargo.toml", "rust"),
+    ("go.mod", "go"),
+    ("package.json", "javascript"),
+    ("pyproject.toml", "python"),
+    ("setup.py", "python"),
+    ("pom.xml", "java"),
+    ("build.gradle.kts", "kotlin"),
+    ("build.gradle", "java"),
+    ("Gemfile", "ruby"),
+    ("composer.json", "php"),
+    ("mix.exs", "elixir"),
+    ("pubspec.yaml", "dart"),
+];
+const PACKAGE_JSON: &str = "package.json";
+const TYPESCRIPT_DEPENDENCY: &str = "\"typescript\"";
+
+/// A programming language the searches can be narrowed down to.
+///
+/// * `name` - the name the language is selected by, the same StackOverflow tags it with.
+/// (ex: c++)
+/// * `title` - the name of the language, added to the web searches. (ex: C++)
+/// * `aliases` - other names the language is selected by and mentioned as. (ex: cpp)
+/// * `extensions` - the extensions of its files, the files of gists are filtered by them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lang {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub aliases: &'static [&'static str],
+    pub extensions: &'static [&'static str],
+}
+
+const fn lang(
+    name: &'static str,
+    title: &'static str,
+    aliases: &'static [&'static str],
+    extensions: &'static [&'static str],
+) -> Lang {
+    Lang {
+        name,
+        title,
+        aliases,
+        extensions,
+    }
+}
+
+/// Every language the searches can be narrowed down to, sorted by name.
+pub const LANGS: [Lang; 20] = [
+    lang(
+        "bash",
+        "Bash",
+        &["sh", "shell", "zsh"],
+        &["sh", "bash", "zsh"],
+    ),
+    lang("c", "C", &[], &["c", "h"]),
+    lang("c#", "C#", &["csharp", "dotnet"], &["cs", "csx"]),
+    lang(
+        "c++",
+        "C++",
+        &["cpp", "cxx"],
+        &["cpp", "cc", "cxx", "hpp", "hh", "hxx", "h"],
+    ),
+    lang("dart", "Dart", &["flutter"], &["dart"]),
+    lang("elixir", "Elixir", &[], &["ex", "exs"]),
+    lang("go", "Go", &["golang"], &["go"]),
+    lang("haskell", "Haskell", &["hs", "ghc"], &["hs", "lhs"]),
+    lang("java", "Java", &["jdk"], &["java"]),
+    lang(
+        "javascript",
+        "JavaScript",
+        &["js", "node", "nodejs"],
+        &["js", "mjs", "cjs", "jsx"],
+    ),
+    lang("kotlin", "Kotlin", &["kt"], &["kt", "kts"]),
+    lang("lua", "Lua", &[], &["lua"]),
+    lang("php", "PHP", &[], &["php"]),
+    lang(
+        "python",
+        "Python",
+        &["py", "python3"],
+        &["py", "pyw", "ipynb"],
+    ),
+    lang("ruby", "Ruby", &["rb", "rails"], &["rb"]),
+    lang("rust", "Rust", &["rs", "rustlang"], &["rs"]),
+    lang("scala", "Scala", &[], &["scala", "sc"]),
+    lang("swift", "Swift", &[], &["swift"]),
+    lang(
+        "typescript",
+        "TypeScript",
+        &["ts"],
+        &["ts", "tsx", "mts", "cts"],
+    ),
+    lang("zig", "Zig", &[], &["zig"]),
+];
+
+/// How the web searches are narrowed down to the language.
+///
+/// * `Boost` - the language is added to the query, the results that don't mention it are kept.
+/// * `Filter` - the language is added to the query and only the results mentioning it in their
+/// title, snippet or url are kept.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LangMode {
+    #[default]
+    Boost,
+    Filter,
+}
+
+impl LangMode {
+    /// Every way of narrowing down the web searches, from the loosest one.
+    pub const ALL: [LangMode; 2] = [LangMode::Boost, LangMode::Filter];
+
+    /// Get the name of the mode, as accepted by from_str. (ex: filter)
+    pub fn name(&self) -> &'static str {
+        match self {
+            LangMode::Boost => "boost",
+            LangMode::Filter => "filter",
+        }
+    }
+}
+
+impl std::str::FromStr for LangMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        LangMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == name)
+            .ok_or_else(|| format!("Unknown language mode: {}", name))
+    }
+}
+
+impl std::fmt::Display for LangMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Lang {
+    /// Get the query with the tag of the language in front of it, the way StackExchange searches
+    /// take tags. (ex: [rust] sort a vec)
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - What to search for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::lang;
+    ///
+    /// assert_eq!(lang::find("rs").unwrap().tagged("sort a vec"), "[rust] sort a vec");
+    /// ```
+    pub fn tagged(&self, query: &str) -> String {
+        format!("[{}] {}", self.name, query)
+    }
+
+    /// Get the query with the language added to it, unless it already mentions it. (ex: sort a
+    /// vec Rust)
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - What to search for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::lang;
+    ///
+    /// let rust = lang::find("rust").unwrap();
+    ///
+    /// assert_eq!(rust.boosted("sort a vec"), "sort a vec Rust");
+    /// assert_eq!(rust.boosted("sort a vec in rust"), "sort a vec in rust");
+    /// ```
+    pub fn boosted(&self, query: &str) -> String {
+        if self.mentioned_in(query) {
+            query.to_string()
+        } else {
+            format!("{} {}", query, self.title)
+        }
+    }
+
+    /// Checks if a text mentions the language, by its name or one of its aliases as a word of
+    /// its own, in any case.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to look in, like the title of a result or its url.
+    pub fn mentioned_in(&self, text: &str) -> bool {
+        text.to_lowercase()
+            .split(|c: char| !(c.is_alphanumeric() || c == '+' || c == '#'))
+            .any(|word| word == self.name || self.aliases.contains(&word))
+    }
+
+    /// Checks if a search result mentions the language in its title, snippet or url.
+    ///
+    /// # Arguments
+    ///
+    /// * `hit` - The search result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::engine::SearchHit;
+    /// use falion::search::lang;
+    ///
+    /// let go = lang::find("golang").unwrap();
+    /// let hit = SearchHit::titled("https://go.dev/blog/slices", "Arrays, slices", 1);
+    ///
+    /// assert!(go.mentions(&hit));
+    /// ```
+    pub fn mentions(&self, hit: &SearchHit) -> bool {
+        [&hit.title, &hit.snippet, &hit.url]
+            .into_iter()
+            .any(|text| self.mentioned_in(text))
+    }
+
+    /// Checks if a file is written in the language by its extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_name` - The name of the file, or its url. (ex: main.rs)
+    pub fn is_file_of(&self, file_name: &str) -> bool {
+        let file_name = file_name.rsplit('/').next().unwrap_or(file_name);
+        match file_name.rsplit_once('.') {
+            Some((_, extension)) => self.extensions.contains(&extension.to_lowercase().as_str()),
+            None => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.title)
+    }
+}
+
+/// Find a language by its name or one of its aliases.
+///
+/// # Arguments
+///
+/// * `name` - the name or an alias of the language, in any case.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::lang;
+///
+/// assert_eq!(lang::find("C++").unwrap().title, "C++");
+/// assert_eq!(lang::find("golang").unwrap().name, "go");
+/// assert!(lang::find("english").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static Lang> {
+    let name = name.trim().to_lowercase();
+    LANGS
+        .iter()
+        .find(|lang| lang.name == name || lang.aliases.contains(&name.as_str()))
+}
+
+/// Detect the language of the project a directory is in from the files at its root, like
+/// Cargo.toml, package.json or go.mod. The directory and then its parents are checked, the
+/// closest one with such a file decides.
+///
+/// # Arguments
+///
+/// * `dir` - the directory to start from, usually the current one.
+///
+/// # Examples
+///
+/// ```no_run
+/// use falion::search::lang;
+///
+/// let lang = lang::detect(&std::env::current_dir().unwrap());
+/// ```
+#[tracing::instrument(skip_all)]
+pub fn detect(dir: &Path) -> Option<&'static Lang> {
+    for dir in dir.ancestors() {
+        let found = PROJECT_FILES
+            .iter()
+            .find(|(file, _)| dir.join(file).is_file());
+        if let Some((file, name)) = found {
+            let name = if *file == PACKAGE_JSON && depends_on_typescript(&dir.join(file)) {
+                "typescript"
+            } else {
+                name
+            };
+            tracing::info!(
+                "Detected the language: {} from {}",
+                name,
+                dir.join(file).display()
+            );
+            return find(name);
+        }
+    }
+
+    None
+}
+
+/// Checks if a package.json has typescript in its dependencies.
+///
+/// # Arguments
+///
+/// * `path` - the path to the package.json.
+fn depends_on_typescript(path: &Path) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|package| package.contains(TYPESCRIPT_DEPENDENCY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_langs_are_sorted_and_unique() {
+        assert!(LANGS.windows(2).all(|pair| pair[0].name < pair[1].name));
+        for lang in LANGS {
+            for alias in lang.aliases {
+                assert_eq!(
+                    find(alias),
+                    Some(&lang),
+                    "{} is the alias of another language",
+                    alias
+                );
+            }
+        }
+        assert!(PROJECT_FILES
+            .iter()
+            .all(|(_, name)| LANGS.iter().any(|lang| lang.name == *name)));
+    }
+
+    #[test]
+    fn test_mentions() {
+        let cpp = find("cpp").unwrap();
+        let rust = find("rust").unwrap();
+
+        assert!(cpp.mentioned_in("How to sort a vector in C++?"));
+        assert!(!cpp.mentioned_in("How to sort a list in C?"));
+        assert!(find("c").unwrap().mentioned_in("How to sort a list in C?"));
+        assert!(rust.mentioned_in("https://doc.rust-lang.org/std/vec/struct.Vec.html"));
+        assert!(!rust.mentioned_in("Trusty sorting"));
+        assert!(find("node").unwrap().mentioned_in("Streams in Node.js."));
+        assert!(!rust.mentions(&SearchHit::titled(
+            "https://docs.python.org/3/howto/sorting.html",
+            "Sorting Techniques",
+            1
+        )));
+    }
+
+    #[test]
+    fn test_is_file_of() {
+        let rust = find("rust").unwrap();
+
+        assert!(rust.is_file_of("main.rs"));
+        assert!(rust.is_file_of("https://gist.github.com/a/1/raw/2/LIB.RS"));
+        assert!(!rust.is_file_of("main.py"));
+        assert!(!rust.is_file_of("Makefile"));
+    }
+
+    #[test]
+    fn test_detect() {
+        let dir = std::env::temp_dir().join("falion-test-lang");
+        let nested = dir.join("web").join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"test\"\n").unwrap();
+
+        assert_eq!(detect(&nested).map(|lang| lang.name), Some("rust"));
+
+        std::fs::write(
+            dir.join("web").join("package.json"),
+            "{\"devDependencies\": {\"typescript\": \"^5.0.0\"}}",
+        )
+        .unwrap();
+        assert_eq!(detect(&nested).map(|lang| lang.name), Some("typescript"));
+
+        std::fs::write(dir.join("web").join("package.json"), "{}").unwrap();
+        assert_eq!(detect(&nested).map(|lang| lang.name), Some("javascript"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
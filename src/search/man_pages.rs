@@ -0,0 +1,722 @@
+use super::engine::SearchHit;
+use super::metadata::Metadata;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+// words that are part of how a question is asked and never the name of a page
+const STOP_WORDS: [&str; 34] = [
+    "a", "an", "and", "are", "can", "command", "do", "does", "for", "from", "get", "how", "i",
+    "in", "into", "is", "it", "linux", "make", "my", "of", "on", "or", "the", "to", "unix", "use",
+    "using", "what", "when", "where", "which", "with", "you",
+];
+// the pages of the platform come first, then the ones that work everywhere
+#[cfg(target_os = "linux")]
+const TLDR_PLATFORMS: [&str; 2] = ["linux", "common"];
+#[cfg(target_os = "macos")]
+const TLDR_PLATFORMS: [&str; 2] = ["osx", "common"];
+#[cfg(target_os = "windows")]
+const TLDR_PLATFORMS: [&str; 2] = ["windows", "common"];
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+const TLDR_PLATFORMS: [&str; 1] = ["common"];
+const TLDR_PAGES: &str = "pages";
+const TLDR_EXTENSION: &str = "md";
+const TLDR_SITE: &str = "tldr";
+const MAN_SITE: &str = "man";
+// apropos exits with this code when nothing matched, which isn't an error for us
+const APROPOS_NOTHING: i32 = 16;
+
+type LocalPage = Result<ManPage, ManError>;
+
+/// A man page or a tldr page rendered as text, together with its metadata.
+///
+/// * `content` - the page as text.
+/// * `metadata` - the name and the description of the page, and if it's a man or a tldr page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManPage {
+    pub content: String,
+    pub metadata: Metadata,
+}
+
+/// These are the errors the functions associated with the man and tldr pages will return.
+///
+/// * `NoResults` - No man or tldr page matched the query.
+/// * `CommandFailed` - The man or apropos command couldn't be run, usually because it's not
+/// installed.
+/// * `ErrorCode` - The man or apropos command exited with an error code.
+/// * `InvalidTldrPage` - The tldr page exists but couldn't be read.
+#[derive(Error, Debug)]
+pub enum ManError {
+    #[error("No man or tldr page matched the query: {0}")]
+    NoResults(String),
+    #[error("Failed to run the man pages command, is man installed? Error: {0}")]
+    CommandFailed(std::io::Error),
+    #[error("The man pages command didn't exit successfully, it exited with: {0}")]
+    ErrorCode(std::process::ExitStatus),
+    #[error("Failed to read the tldr page at: {path}. Error: {error}")]
+    InvalidTldrPage {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+/// A page found for a query, before it's rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Found {
+    Man {
+        name: String,
+        section: String,
+        description: String,
+    },
+    Tldr {
+        name: String,
+        path: PathBuf,
+    },
+}
+
+impl Found {
+    /// The title the page is listed under until it's rendered. (ex: tar(1), tar (tldr))
+    fn title(&self) -> String {
+        match self {
+            Found::Man { name, section, .. } => format!("{}({})", name, section),
+            Found::Tldr { name, .. } => format!("{} ({})", name, TLDR_SITE),
+        }
+    }
+}
+
+/// Search the man pages installed on this machine and a local copy of the tldr pages, without
+/// any network request.
+#[derive(std::fmt::Debug)]
+pub struct ManPages {
+    tldr_dir: Option<PathBuf>,
+}
+
+impl ManPages {
+    /// Create a new ManPages instance that reads the tldr pages from where the tldr clients cache
+    /// them (tealdeer, the python and the node clients), if any of them did.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::man_pages;
+    ///
+    /// let man = man_pages::ManPages::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            tldr_dir: default_tldr_dir(),
+        }
+    }
+
+    /// Create a new ManPages instance that reads the tldr pages from the given directory, either
+    /// the tldr-pages repository (or its extracted archive) or its pages directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::man_pages;
+    ///
+    /// let man = man_pages::ManPages::with_tldr_dir("/home/user/tldr".into());
+    /// ```
+    pub fn with_tldr_dir(tldr_dir: PathBuf) -> Self {
+        Self {
+            tldr_dir: Some(tldr_dir),
+        }
+    }
+
+    /// Render a man page as text, as wide as the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the page. (ex: tar)
+    /// * `section` - Optional, the section of the page. (ex: 1)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because man isn't installed everywhere
+    /// use falion::search::man_pages;
+    ///
+    /// # async fn run() -> Result<(), man_pages::ManError> {
+    /// let man = man_pages::ManPages::new();
+    /// let page = man.get_man_page("tar", Some("1")).await.unwrap();
+    ///
+    /// assert!(!page.content.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns man_pages::ManError
+    ///
+    /// * `CommandFailed` - The man command couldn't be run, usually because it's not installed.
+    /// * `ErrorCode` - The man command exited with an error code, like when there's no such page.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_man_page(&self, name: &str, section: Option<&str>) -> LocalPage {
+        tracing::info!("Render the man page {} in section {:#?}", &name, &section);
+        // set term width
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+
+        let mut command = tokio::process::Command::new("man");
+        if let Some(section) = section {
+            command.arg(section);
+        }
+        let output = match command
+            .arg(name)
+            .env("MANWIDTH", term_width.to_string())
+            .env("MANPAGER", "cat")
+            .env("PAGER", "cat")
+            .env_remove("MAN_KEEP_FORMATTING")
+            .output()
+            .await
+        {
+            Ok(output) => output,
+            Err(err) => {
+                tracing::error!("Failed to run man for {}. Error: {}", &name, &err);
+                return Err(ManError::CommandFailed(err));
+            }
+        };
+        if !output.status.success() {
+            tracing::error!("man for {} exited with: {}", &name, &output.status);
+            return Err(ManError::ErrorCode(output.status));
+        }
+
+        Ok(ManPage {
+            content: strip_formatting(&String::from_utf8_lossy(&output.stdout)),
+            metadata: Metadata {
+                title: Some(match section {
+                    Some(section) => format!("{}({})", name, section),
+                    None => name.to_string(),
+                }),
+                site: Some(MAN_SITE.to_string()),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Render a tldr page as text.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the markdown file of the page.
+    ///
+    /// # Errors
+    ///
+    /// returns man_pages::ManError
+    ///
+    /// * `InvalidTldrPage` - The page couldn't be read.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_tldr_page(&self, path: &Path) -> LocalPage {
+        tracing::info!("Render the tldr page at {}", path.display());
+        let markdown = match tokio::fs::read_to_string(path).await {
+            Ok(markdown) => markdown,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to read the tldr page at {}. Error: {}",
+                    path.display(),
+                    &error
+                );
+                return Err(ManError::InvalidTldrPage {
+                    path: path.to_path_buf(),
+                    error,
+                });
+            }
+        };
+
+        Ok(ManPage {
+            content: tldr_to_text(&markdown),
+            metadata: Metadata {
+                title: tldr_title(&markdown),
+                site: Some(TLDR_SITE.to_string()),
+                ..Default::default()
+            },
+        })
+    }
+
+    /// Search for man and tldr pages matching a query, like the name of a command or a question
+    /// about it (ex: how do I use git bisect). The pages named after the query come first, tldr
+    /// page before man page, followed by the man pages apropos finds. Each page is rendered in its
+    /// own future, associated with its name and returned inside a Vec for preserved order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, the most pages to return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because man isn't installed everywhere
+    /// use falion::search::man_pages;
+    ///
+    /// # async fn run() -> Result<(), man_pages::ManError> {
+    /// let man = man_pages::ManPages::new();
+    /// let pages_content = man
+    ///     .get_multiple_pages_content("how do I use rsync", Some(5))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for p in pages_content {
+    ///    assert!(!p.1.await.unwrap().unwrap().content.is_empty())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns man_pages::ManError;
+    ///
+    /// * `NoResults` - No man or tldr page matched the query.
+    /// * `CommandFailed` - Apropos couldn't be run and there's no tldr page for the query.
+    /// * `ErrorCode` - Apropos exited with an error code and there's no tldr page for the query.
+    ///
+    /// First error is for the search, second is for the future hanle, third is for the actual
+    /// page content
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_pages_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<LocalPage>)>, ManError> {
+        tracing::info!(
+            "Get multiple man and tldr pages for search query: {} with a results limit of: {:#?}",
+            &query,
+            &limit
+        );
+        let names = candidate_names(query);
+        if names.is_empty() {
+            tracing::error!("The query {} has no words that could name a page.", &query);
+            return Err(ManError::NoResults(query.to_string()));
+        }
+
+        let tldr: Vec<Found> = match &self.tldr_dir {
+            Some(dir) => names
+                .iter()
+                .filter_map(|name| {
+                    tldr_path(dir, name).map(|path| Found::Tldr {
+                        name: name.clone(),
+                        path,
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let man = match apropos(&names).await {
+            Ok(man) => man,
+            Err(error) if !tldr.is_empty() => {
+                tracing::warn!(
+                    "Failed to search the man pages, showing only tldr pages. Error: {}",
+                    error
+                );
+                Vec::new()
+            }
+            Err(error) => return Err(error),
+        };
+
+        let found = arrange(&names, tldr, man, limit.unwrap_or(100));
+        if found.is_empty() {
+            tracing::error!("No man or tldr page matched the query {}", &query);
+            return Err(ManError::NoResults(query.to_string()));
+        }
+
+        // render every page in its own future, associated with its name
+        let mut pages_content = Vec::with_capacity(found.len());
+        for (rank, page) in found.into_iter().enumerate() {
+            let tldr_dir = self.tldr_dir.clone();
+            let snippet = match &page {
+                Found::Man { description, .. } => description.clone(),
+                Found::Tldr { .. } => String::new(),
+            };
+            pages_content.push((
+                SearchHit {
+                    title: page.title(),
+                    snippet,
+                    rank: rank + 1,
+                    ..Default::default()
+                },
+                tokio::task::spawn(async move {
+                    let man_pages = ManPages { tldr_dir };
+                    match page {
+                        Found::Man {
+                            name,
+                            section,
+                            description,
+                        } => {
+                            let mut rendered =
+                                man_pages.get_man_page(&name, Some(&section)).await?;
+                            rendered.metadata.title =
+                                Some(format!("{}({}) - {}", name, section, description));
+                            Ok(rendered)
+                        }
+                        Found::Tldr { path, .. } => man_pages.get_tldr_page(&path).await,
+                    }
+                }),
+            ));
+        }
+
+        Ok(pages_content)
+    }
+}
+
+impl Default for ManPages {
+    fn default() -> Self {
+        ManPages::new()
+    }
+}
+
+/// Get the words of a query that could be the name of a page, most specific first: two words
+/// joined by a dash (ex: git bisect -> git-bisect), then the words themselves.
+///
+/// # Arguments
+///
+/// * `query` - The query to search for.
+fn candidate_names(query: &str) -> Vec<String> {
+    let words: Vec<String> = query
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| {
+            !word.is_empty()
+                && !STOP_WORDS.contains(&word.as_str())
+                && word
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '+'))
+        })
+        .collect();
+
+    let mut names: Vec<String> = words
+        .windows(2)
+        .map(|pair| format!("{}-{}", pair[0], pair[1]))
+        .collect();
+    for word in words {
+        if !names.contains(&word) {
+            names.push(word);
+        }
+    }
+
+    names
+}
+
+/// Search the descriptions of the installed man pages for any of the names, with apropos.
+///
+/// # Arguments
+///
+/// * `names` - The names to search for.
+///
+/// # Errors
+///
+/// * `CommandFailed` - Apropos couldn't be run, usually because it's not installed.
+/// * `ErrorCode` - Apropos exited with an error code other than the one for no matches.
+#[tracing::instrument(skip_all)]
+async fn apropos(names: &[String]) -> Result<Vec<Found>, ManError> {
+    let output = match tokio::process::Command::new("apropos")
+        .arg("--")
+        .args(names)
+        .output()
+        .await
+    {
+        Ok(output) => output,
+        Err(err) => {
+            tracing::error!("Failed to run apropos. Error: {}", &err);
+            return Err(ManError::CommandFailed(err));
+        }
+    };
+    if !output.status.success() && output.status.code() != Some(APROPOS_NOTHING) {
+        tracing::error!("apropos exited with: {}", &output.status);
+        return Err(ManError::ErrorCode(output.status));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_apropos_line)
+        .collect())
+}
+
+/// Parse a line of the output of apropos, of man-db (tar (1) - an archiving utility) or of
+/// mandoc (gzip, gunzip(1) - compression/decompression tool).
+///
+/// # Arguments
+///
+/// * `line` - The line to parse.
+fn parse_apropos_line(line: &str) -> Option<Found> {
+    let (page, description) = line.split_once(" - ")?;
+    let (names, section) = page.trim().rsplit_once('(')?;
+    let section = section.strip_suffix(')')?.trim();
+    let name = names.split(',').next()?.trim();
+
+    if name.is_empty() || section.is_empty() {
+        return None;
+    }
+
+    Some(Found::Man {
+        name: name.to_string(),
+        section: section.to_string(),
+        description: description.trim().to_string(),
+    })
+}
+
+/// Put the found pages in the order they're shown: the pages named after the query first, the
+/// tldr page before the man page, then the other man pages, the ones whose name and description
+/// have more of the names first.
+///
+/// # Arguments
+///
+/// * `names` - The names searched for, most specific first.
+/// * `tldr` - The tldr pages found.
+/// * `man` - The man pages found, in the order apropos listed them.
+/// * `limit` - The most pages to return.
+fn arrange(names: &[String], tldr: Vec<Found>, man: Vec<Found>, limit: usize) -> Vec<Found> {
+    let mut found = Vec::with_capacity(tldr.len() + man.len());
+
+    for name in names {
+        found.extend(
+            tldr.iter()
+                .filter(|page| matches!(page, Found::Tldr { name: other, .. } if other == name))
+                .cloned(),
+        );
+        found.extend(
+            man.iter()
+                .filter(|page| matches!(page, Found::Man { name: other, .. } if other == name))
+                .cloned(),
+        );
+    }
+
+    let relevance = |page: &Found| match page {
+        Found::Man {
+            name, description, ..
+        } => {
+            let text = format!("{} {}", name, description).to_lowercase();
+            names.iter().filter(|other| text.contains(*other)).count()
+        }
+        Found::Tldr { .. } => 0,
+    };
+    let mut rest: Vec<Found> = man
+        .into_iter()
+        .filter(|page| !found.contains(page))
+        .collect();
+    // stable, so pages as relevant keep the order apropos listed them in
+    rest.sort_by_key(|page| std::cmp::Reverse(relevance(page)));
+    found.extend(rest);
+
+    found.truncate(limit);
+    found
+}
+
+/// Get the directory the tldr clients cache the pages in, the first one that exists. (tealdeer,
+/// the python client, the node client)
+fn default_tldr_dir() -> Option<PathBuf> {
+    let cache = dirs::cache_dir();
+    let home = dirs::home_dir();
+    [
+        cache
+            .as_ref()
+            .map(|cache| cache.join("tealdeer").join("tldr-pages")),
+        cache.as_ref().map(|cache| cache.join("tldr")),
+        home.as_ref().map(|home| home.join(".tldr").join("cache")),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|dir| dir.is_dir())
+}
+
+/// Get the path of the tldr page with the given name, if there is one for this platform or for
+/// every platform. The directory can be the tldr-pages repository or its pages directory.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the tldr pages.
+/// * `name` - The name of the page. (ex: tar)
+fn tldr_path(dir: &Path, name: &str) -> Option<PathBuf> {
+    let file = format!("{}.{}", name, TLDR_EXTENSION);
+    let file = &file;
+    [dir.join(TLDR_PAGES), dir.to_path_buf()]
+        .iter()
+        .flat_map(|pages| {
+            TLDR_PLATFORMS
+                .iter()
+                .map(move |platform| pages.join(platform).join(file))
+        })
+        .find(|path| path.is_file())
+}
+
+/// Get the title of a tldr page, its name followed by the first line of its description.
+/// (ex: tar - Archiving utility.)
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown of the page.
+fn tldr_title(markdown: &str) -> Option<String> {
+    let name = markdown
+        .lines()
+        .find_map(|line| line.strip_prefix("# "))?
+        .trim();
+    match markdown.lines().find_map(|line| line.strip_prefix("> ")) {
+        Some(description) => Some(format!("{} - {}", name, description.trim())),
+        None => Some(name.to_string()),
+    }
+}
+
+/// Render the markdown of a tldr page as text: the commands are indented under what they do and
+/// the braces around the placeholders are removed.
+///
+/// # Arguments
+///
+/// * `markdown` - The markdown of the page.
+fn tldr_to_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        if let Some(title) = line.strip_prefix("# ") {
+            text.push_str(title);
+        } else if let Some(description) = line.strip_prefix("> ") {
+            text.push_str(&description.replace(['<', '>'], ""));
+        } else if let Some(command) = line
+            .strip_prefix('`')
+            .and_then(|line| line.strip_suffix('`'))
+        {
+            text.push_str("    ");
+            text.push_str(&command.replace("{{", "").replace("}}", ""));
+        } else {
+            text.push_str(line);
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+/// Remove the formatting man can leave in its output: the overstrikes used for bold and
+/// underline (a character, a backspace and a character) and the terminal escape sequences.
+///
+/// # Arguments
+///
+/// * `text` - The output of man.
+fn strip_formatting(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // the backspace erases the character before it, the one after it stays
+            '\u{8}' => {
+                stripped.pop();
+            }
+            // escape sequences end with a letter (ex: \x1b[1m)
+            '\u{1b}' => {
+                if chars.peek() == Some(&'[') {
+                    chars.next();
+                }
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            _ => stripped.push(c),
+        }
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidate_names() {
+        assert_eq!(
+            candidate_names("How do I use `git bisect`?"),
+            ["git-bisect", "git", "bisect"]
+        );
+        assert_eq!(candidate_names("rsync"), ["rsync"]);
+        assert!(candidate_names("how do I use it").is_empty());
+    }
+
+    #[test]
+    fn test_parse_apropos_line() {
+        assert_eq!(
+            parse_apropos_line("tar (1)              - an archiving utility"),
+            Some(Found::Man {
+                name: "tar".to_string(),
+                section: "1".to_string(),
+                description: "an archiving utility".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_apropos_line("gzip, gunzip(1) - compression/decompression tool"),
+            Some(Found::Man {
+                name: "gzip".to_string(),
+                section: "1".to_string(),
+                description: "compression/decompression tool".to_string(),
+            })
+        );
+        assert_eq!(parse_apropos_line("tar: nothing appropriate."), None);
+    }
+
+    #[test]
+    fn test_arrange() {
+        let man = |name: &str, description: &str| Found::Man {
+            name: name.to_string(),
+            section: "1".to_string(),
+            description: description.to_string(),
+        };
+        let names = candidate_names("git bisect");
+        let tldr = vec![Found::Tldr {
+            name: "git-bisect".to_string(),
+            path: PathBuf::from("git-bisect.md"),
+        }];
+        let found = arrange(
+            &names,
+            tldr,
+            vec![
+                man("git-log", "Show commit logs"),
+                man("git-bisect-lk2009", "Fighting regressions with git bisect"),
+                man("git-bisect", "Use binary search to find the commit"),
+            ],
+            3,
+        );
+        let titles: Vec<String> = found.iter().map(Found::title).collect();
+
+        assert_eq!(
+            titles,
+            ["git-bisect (tldr)", "git-bisect(1)", "git-bisect-lk2009(1)"]
+        );
+    }
+
+    #[test]
+    fn test_tldr_page() {
+        let dir = std::env::temp_dir().join("falion-test-tldr");
+        let common = dir.join(TLDR_PAGES).join("common");
+        std::fs::create_dir_all(&common).unwrap();
+        let markdown = "# tar\n\n> Archiving utility.\n> More information: <https://www.gnu.org/software/tar>.\n\n- Create an archive from files:\n\n`tar cf {{path/to/target.tar}} {{path/to/file}}`\n";
+        std::fs::write(common.join("tar.md"), markdown).unwrap();
+
+        assert_eq!(tldr_path(&dir, "tar"), Some(common.join("tar.md")));
+        assert_eq!(
+            tldr_path(&dir.join(TLDR_PAGES), "tar"),
+            Some(common.join("tar.md"))
+        );
+        assert_eq!(tldr_path(&dir, "rsync"), None);
+        assert_eq!(
+            tldr_title(markdown).as_deref(),
+            Some("tar - Archiving utility.")
+        );
+        assert_eq!(
+            tldr_to_text(markdown),
+            "tar\n\nArchiving utility.\nMore information: https://www.gnu.org/software/tar.\n\n- Create an archive from files:\n\n    tar cf path/to/target.tar path/to/file\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_strip_formatting() {
+        assert_eq!(strip_formatting("N\u{8}NA\u{8}AM\u{8}ME\u{8}E"), "NAME");
+        assert_eq!(strip_formatting("_\u{8}f_\u{8}i_\u{8}l_\u{8}e"), "file");
+        assert_eq!(strip_formatting("\u{1b}[1mtar\u{1b}[0m"), "tar");
+    }
+}
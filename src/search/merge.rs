@@ -0,0 +1,499 @@
+use super::ddg;
+use super::engine::SearchHit;
+use std::collections::{HashMap, HashSet};
+
+// How much the votes of a result count next to its rank in the merged ranking
+const VOTES_FACTOR: f64 = 0.1;
+
+/// The sources falion gets results from, in the order they're listed in the cli and gui.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Source {
+    StackOverflow,
+    StackExchange,
+    GithubGist,
+    GeeksForGeeks,
+    DdgSearch,
+    RustDocs,
+    ManPages,
+    OfflineDocs,
+    GithubIssues,
+    Discourse,
+    Reddit,
+    HackerNews,
+}
+
+impl Source {
+    /// Every source, in the order they're listed in the cli and gui.
+    pub const ALL: [Source; 12] = [
+        Source::StackOverflow,
+        Source::StackExchange,
+        Source::GithubGist,
+        Source::GeeksForGeeks,
+        Source::DdgSearch,
+        Source::RustDocs,
+        Source::ManPages,
+        Source::OfflineDocs,
+        Source::GithubIssues,
+        Source::Discourse,
+        Source::Reddit,
+        Source::HackerNews,
+    ];
+
+    /// Every source, in the order duplicates are kept in: a result found by more than one source
+    /// stays with the first of them. The web search comes last since it finds the pages of every
+    /// other source, which show them better.
+    pub const DEDUP_ORDER: [Source; 12] = [
+        Source::StackOverflow,
+        Source::StackExchange,
+        Source::GithubGist,
+        Source::GeeksForGeeks,
+        Source::RustDocs,
+        Source::ManPages,
+        Source::OfflineDocs,
+        Source::GithubIssues,
+        Source::Discourse,
+        Source::Reddit,
+        Source::HackerNews,
+        Source::DdgSearch,
+    ];
+
+    /// Get the name of the source, as accepted by from_str. (ex: github-gist)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Source::StackOverflow => "stackoverflow",
+            Source::StackExchange => "stackexchange",
+            Source::GithubGist => "github-gist",
+            Source::GeeksForGeeks => "geeksforgeeks",
+            Source::DdgSearch => "ddg-search",
+            Source::RustDocs => "rust-docs",
+            Source::ManPages => "man-pages",
+            Source::OfflineDocs => "offline-docs",
+            Source::GithubIssues => "github-issues",
+            Source::Discourse => "discourse",
+            Source::Reddit => "reddit",
+            Source::HackerNews => "hacker-news",
+        }
+    }
+
+    /// Get the short label the results of the source are tagged with in the merged view.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Source::StackOverflow => "SO",
+            Source::StackExchange => "SE",
+            Source::GithubGist => "Gist",
+            Source::GeeksForGeeks => "GfG",
+            Source::DdgSearch => "Web",
+            Source::RustDocs => "Docs",
+            Source::ManPages => "Man",
+            Source::OfflineDocs => "Offline",
+            Source::GithubIssues => "Issue",
+            Source::Discourse => "Forum",
+            Source::Reddit => "Reddit",
+            Source::HackerNews => "HN",
+        }
+    }
+
+    /// Get how much the results of the source count in the merged ranking when it isn't
+    /// configured, the sources with reviewed answers and official docs count the most.
+    pub fn default_weight(&self) -> f64 {
+        match self {
+            Source::StackOverflow => 1.2,
+            Source::RustDocs | Source::OfflineDocs => 1.1,
+            Source::StackExchange | Source::GithubIssues => 1.0,
+            Source::DdgSearch | Source::Discourse | Source::ManPages => 0.9,
+            Source::GithubGist => 0.8,
+            Source::GeeksForGeeks | Source::Reddit => 0.7,
+            Source::HackerNews => 0.6,
+        }
+    }
+}
+
+impl std::str::FromStr for Source {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Source::ALL
+            .into_iter()
+            .find(|source| source.name() == name)
+            .ok_or_else(|| format!("Unknown source: {}", name))
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How much the results of every source count in the merged ranking, the default weight of a
+/// source is used unless it's given another one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Weights {
+    weights: HashMap<Source, f64>,
+}
+
+impl Weights {
+    /// Create the weights with the default weight of every source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::merge::{Source, Weights};
+    ///
+    /// let weights = Weights::new();
+    /// assert_eq!(weights.of(Source::Reddit), Source::Reddit.default_weight());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Give a source another weight, 0 keeps its results out of the merged view.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the source to weigh.
+    /// * `weight` - how much its results count, negative weights count as 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::merge::{Source, Weights};
+    ///
+    /// let weights = Weights::new().with_weight(Source::Reddit, 2.0);
+    /// assert_eq!(weights.of(Source::Reddit), 2.0);
+    /// ```
+    pub fn with_weight(mut self, source: Source, weight: f64) -> Self {
+        self.weights.insert(source, weight.max(0.0));
+        self
+    }
+
+    /// Get how much the results of a source count.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the source to get the weight of.
+    pub fn of(&self, source: Source) -> f64 {
+        self.weights
+            .get(&source)
+            .copied()
+            .unwrap_or_else(|| source.default_weight())
+    }
+}
+
+/// A result in the merged view, pointing at where it is in the results of its source.
+///
+/// * `source` - the source the result is from.
+/// * `index` - the index of the result in the results of its source.
+/// * `title` - the title the result is listed under.
+/// * `rank` - the position of the result among the results of its source, starting from 1.
+/// * `votes` - the votes of the result, if its source has any and its content loaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedResult {
+    pub source: Source,
+    pub index: usize,
+    pub title: String,
+    pub rank: usize,
+    pub votes: Option<i64>,
+}
+
+impl MergedResult {
+    /// Get how high the result ranks in the merged view, the higher the better: the weight of
+    /// its source times how high its source ranked it, plus a bonus growing with the log of its
+    /// votes so a well received answer climbs a few places without burying everything else.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - how much the results of every source count.
+    pub fn score(&self, weights: &Weights) -> f64 {
+        let rank = self.rank.max(1) as f64;
+        let votes = self.votes.unwrap_or(0).max(0) as f64;
+
+        weights.of(self.source) * (1.0 / rank + VOTES_FACTOR * votes.ln_1p())
+    }
+}
+
+/// Get the results of a source as results of the merged view.
+///
+/// # Arguments
+///
+/// * `source` - the source the results are from.
+/// * `results` - the results of the source, none if getting them failed.
+/// * `votes` - gets the votes of a result, if they're known.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::engine::SearchHit;
+/// use falion::search::merge::{self, Source};
+///
+/// let results: Result<Vec<(SearchHit, ())>, String> =
+///     Ok(vec![(SearchHit::titled("https://example.com", "Example", 1), ())]);
+/// let merged = merge::merged_results(Source::DdgSearch, &results, |_| None);
+///
+/// assert_eq!(merged[0].title, "Example");
+/// ```
+pub fn merged_results<T, E>(
+    source: Source,
+    results: &Result<Vec<(SearchHit, T)>, E>,
+    votes: impl Fn(&SearchHit) -> Option<i64>,
+) -> Vec<MergedResult> {
+    match results {
+        Ok(results) => results
+            .iter()
+            .enumerate()
+            .map(|(index, (hit, _))| MergedResult {
+                source,
+                index,
+                title: hit.title.clone(),
+                rank: if hit.rank == 0 { index + 1 } else { hit.rank },
+                votes: votes(hit),
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Interleave the results of every source into one list, from the highest score to the lowest.
+/// Results with the same score keep the order they're given in, and the results of the sources
+/// weighing 0 are left out.
+///
+/// # Arguments
+///
+/// * `results` - the results of every source.
+/// * `weights` - how much the results of every source count.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::merge::{self, MergedResult, Source, Weights};
+///
+/// let result = |source, rank, votes| MergedResult {
+///     source,
+///     index: rank - 1,
+///     title: String::new(),
+///     rank,
+///     votes,
+/// };
+/// let merged = merge::rank(
+///     vec![
+///         result(Source::Reddit, 1, None),
+///         result(Source::StackOverflow, 1, Some(120)),
+///     ],
+///     &Weights::new(),
+/// );
+///
+/// assert_eq!(merged[0].source, Source::StackOverflow);
+/// ```
+pub fn rank(results: Vec<MergedResult>, weights: &Weights) -> Vec<MergedResult> {
+    let mut scored: Vec<(f64, MergedResult)> = results
+        .into_iter()
+        .filter(|result| weights.of(result.source) > 0.0)
+        .map(|result| (result.score(weights), result))
+        .collect();
+    // stable, so ties keep their order
+    scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+
+    scored.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Remove the results whose page was already found, comparing the canonical form of their links,
+/// and remember the pages of the ones that are kept. Results without a link (ex: man pages) are
+/// always kept.
+///
+/// # Arguments
+///
+/// * `results` - the results of a source.
+/// * `seen` - the canonical links of the results kept so far, shared across sources.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::engine::SearchHit;
+/// use falion::search::merge;
+/// use std::collections::HashSet;
+///
+/// let mut seen = HashSet::new();
+/// let mut first = vec![(SearchHit::titled("https://stackoverflow.com/questions/1/slug", "", 1), ())];
+/// let mut second = vec![(SearchHit::titled("https://stackoverflow.com/questions/1", "", 1), ())];
+///
+/// assert_eq!(merge::dedup(&mut first, &mut seen), 0);
+/// assert_eq!(merge::dedup(&mut second, &mut seen), 1);
+/// assert!(second.is_empty());
+/// ```
+///
+/// # Returns
+///
+/// How many results were removed.
+pub fn dedup<T>(results: &mut Vec<(SearchHit, T)>, seen: &mut HashSet<String>) -> usize {
+    let before = results.len();
+    results.retain(|(hit, _)| {
+        if hit.url.is_empty() {
+            return true;
+        }
+        let link = ddg::canonical_url(&hit.url).unwrap_or_else(|| hit.url.clone());
+        seen.insert(link)
+    });
+
+    before - results.len()
+}
+
+/// Remove the results of every source that were already found by a source before it, see dedup.
+/// A source left without results gets the error made by `emptied` instead, since callers expect
+/// results to never be empty.
+///
+/// # Arguments
+///
+/// * `results` - the results of a source, untouched if getting them failed.
+/// * `seen` - the canonical links of the results kept so far, shared across sources.
+/// * `emptied` - makes the error of a source whose results were all found before.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::engine::SearchHit;
+/// use falion::search::merge;
+/// use std::collections::HashSet;
+///
+/// let mut seen = HashSet::from([String::from("https://example.com")]);
+/// let mut results: Result<Vec<(SearchHit, ())>, String> =
+///     Ok(vec![(SearchHit::titled("https://example.com/", "Example", 1), ())]);
+/// merge::dedup_results(&mut results, &mut seen, || String::from("duplicates"));
+///
+/// assert_eq!(results, Err(String::from("duplicates")));
+/// ```
+#[tracing::instrument(skip_all)]
+pub fn dedup_results<T, E>(
+    results: &mut Result<Vec<(SearchHit, T)>, E>,
+    seen: &mut HashSet<String>,
+    emptied: impl FnOnce() -> E,
+) {
+    if let Ok(hits) = results {
+        let removed = dedup(hits, seen);
+        if removed > 0 {
+            tracing::info!(
+                "Removed {} results already found by another source.",
+                removed
+            );
+        }
+        if hits.is_empty() {
+            *results = Err(emptied());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merged(source: Source, rank: usize, votes: Option<i64>) -> MergedResult {
+        MergedResult {
+            source,
+            index: rank - 1,
+            title: format!("{} {}", source, rank),
+            rank,
+            votes,
+        }
+    }
+
+    #[test]
+    fn test_source_names() {
+        for source in Source::ALL {
+            assert_eq!(source.name().parse::<Source>(), Ok(source));
+            assert!(Source::DEDUP_ORDER.contains(&source));
+        }
+        assert!("stack-overflow".parse::<Source>().is_err());
+        assert_eq!(Source::DEDUP_ORDER.last(), Some(&Source::DdgSearch));
+    }
+
+    #[test]
+    fn test_dedup() {
+        let hit = |url: &str| (SearchHit::titled(url, "title", 1), ());
+        let mut seen = HashSet::new();
+        let mut stackoverflow = vec![
+            hit("https://stackoverflow.com/questions/1/how-to"),
+            hit("https://stackoverflow.com/questions/2"),
+        ];
+        let mut man_pages = vec![hit(""), hit("")];
+        let mut web = vec![
+            hit("https://stackoverflow.com/questions/2/other-slug?utm_source=x"),
+            hit("https://stackoverflow.com/q/1#answer-3"),
+            hit("https://doc.rust-lang.org/book/"),
+            hit("https://doc.rust-lang.org/book"),
+        ];
+
+        assert_eq!(dedup(&mut stackoverflow, &mut seen), 0);
+        assert_eq!(dedup(&mut man_pages, &mut seen), 0);
+        assert_eq!(dedup(&mut web, &mut seen), 3);
+        assert_eq!(web[0].0.url, "https://doc.rust-lang.org/book/");
+
+        let mut emptied: Result<Vec<(SearchHit, ())>, &str> =
+            Ok(vec![hit("https://stackoverflow.com/questions/2")]);
+        dedup_results(&mut emptied, &mut seen, || "emptied");
+        assert_eq!(emptied, Err("emptied"));
+    }
+
+    #[test]
+    fn test_rank() {
+        let weights = Weights::new();
+
+        // the first result of a source beats the second one of a source weighing the same
+        let ranked = rank(
+            vec![
+                merged(Source::StackExchange, 2, None),
+                merged(Source::GithubIssues, 1, None),
+            ],
+            &weights,
+        );
+        assert_eq!(ranked[0].source, Source::GithubIssues);
+
+        // votes lift a result, but not over everything
+        let ranked = rank(
+            vec![
+                merged(Source::StackExchange, 1, Some(0)),
+                merged(Source::StackExchange, 2, Some(500)),
+                merged(Source::StackExchange, 5, Some(3)),
+            ],
+            &weights,
+        );
+        let ranks: Vec<usize> = ranked.iter().map(|result| result.rank).collect();
+        assert_eq!(ranks, [2, 1, 5]);
+
+        // ties keep their order and sources weighing 0 are left out
+        let weights = Weights::new()
+            .with_weight(Source::Reddit, 1.0)
+            .with_weight(Source::Discourse, 1.0)
+            .with_weight(Source::HackerNews, 0.0);
+        let ranked = rank(
+            vec![
+                merged(Source::Reddit, 1, None),
+                merged(Source::HackerNews, 1, None),
+                merged(Source::Discourse, 1, None),
+            ],
+            &weights,
+        );
+        let sources: Vec<Source> = ranked.iter().map(|result| result.source).collect();
+        assert_eq!(sources, [Source::Reddit, Source::Discourse]);
+    }
+
+    #[test]
+    fn test_merged_results() {
+        let results: Result<Vec<(SearchHit, ())>, ()> = Ok(vec![
+            (SearchHit::titled("https://a.com", "a", 0), ()),
+            (SearchHit::titled("https://b.com", "b", 4), ()),
+        ]);
+        let merged = merged_results(Source::Reddit, &results, |hit| {
+            (hit.title == "b").then_some(7)
+        });
+
+        assert_eq!(merged[0].rank, 1);
+        assert_eq!(merged[1].rank, 4);
+        assert_eq!(merged[1].index, 1);
+        assert_eq!(merged[1].votes, Some(7));
+        assert!(
+            merged_results(Source::Reddit, &Err::<Vec<(SearchHit, ())>, ()>(()), |_| {
+                None
+            })
+            .is_empty()
+        );
+    }
+}
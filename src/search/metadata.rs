@@ -0,0 +1,227 @@
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+/// What's known about a result besides its content, every field is optional since each source
+/// has different metadata.
+///
+/// * `title` - the real title of the result, better than the one made out of the url.
+/// * `site` - the site the result is on, for sources spanning many sites.
+/// * `score` - the votes of a question.
+/// * `answer_count` - how many answers a question has.
+/// * `is_answered` - if a question has an accepted answer.
+/// * `tags` - the tags of a question.
+/// * `stars` - the stars of a gist.
+/// * `files` - how many files a gist has.
+/// * `date` - when the result was posted, as a unix timestamp.
+/// * `updated` - when the result was last updated, as a unix timestamp.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub title: Option<String>,
+    pub site: Option<String>,
+    pub score: Option<i64>,
+    pub answer_count: Option<usize>,
+    pub is_answered: bool,
+    pub tags: Vec<String>,
+    pub stars: Option<u64>,
+    pub files: Option<usize>,
+    pub date: Option<i64>,
+    pub updated: Option<i64>,
+}
+
+impl Metadata {
+    /// Summarize the metadata in one line, leaving out what's unknown.
+    /// (ex: Unix & Linux · ▲ 12 · 3 answers ✔ · rust, lifetimes · 10 years ago)
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - the current time as a unix timestamp, the dates are shown relative to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::metadata::Metadata;
+    ///
+    /// let metadata = Metadata {
+    ///     score: Some(12),
+    ///     answer_count: Some(3),
+    ///     is_answered: true,
+    ///     tags: vec!["rust".to_string()],
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(metadata.summary(0), "▲ 12 · 3 answers ✔ · rust");
+    /// ```
+    pub fn summary(&self, now: i64) -> String {
+        let mut parts: Vec<String> = Vec::new();
+
+        if let Some(site) = &self.site {
+            parts.push(site.clone());
+        }
+        if let Some(score) = self.score {
+            parts.push(format!("▲ {}", score));
+        }
+        if let Some(stars) = self.stars {
+            parts.push(format!("★ {}", stars));
+        }
+        if let Some(count) = self.answer_count {
+            let mut answers = plural(count, "answer");
+            if self.is_answered {
+                answers.push_str(" ✔");
+            }
+            parts.push(answers);
+        }
+        if let Some(files) = self.files {
+            parts.push(plural(files, "file"));
+        }
+        if !self.tags.is_empty() {
+            parts.push(self.tags.join(", "));
+        }
+        if let Some(date) = self.date {
+            parts.push(age(now - date));
+        }
+        if let Some(updated) = self.updated {
+            parts.push(format!("updated {}", age(now - updated)));
+        }
+
+        parts.join(" · ")
+    }
+}
+
+/// Describe how long ago something happened in the biggest fitting unit. (ex: 3 days ago)
+///
+/// # Arguments
+///
+/// * `seconds` - how many seconds ago it happened.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::metadata;
+///
+/// assert_eq!(metadata::age(2 * 24 * 60 * 60), "2 days ago");
+/// ```
+pub fn age(seconds: i64) -> String {
+    let (count, unit) = match seconds {
+        seconds if seconds < MINUTE => return "just now".to_string(),
+        seconds if seconds < HOUR => (seconds / MINUTE, "minute"),
+        seconds if seconds < DAY => (seconds / HOUR, "hour"),
+        seconds if seconds < MONTH => (seconds / DAY, "day"),
+        seconds if seconds < YEAR => (seconds / MONTH, "month"),
+        seconds => (seconds / YEAR, "year"),
+    };
+
+    plural(count as usize, unit) + " ago"
+}
+
+/// Parse a count the way sites show it, with an optional k suffix. (ex: 12, 1,024, 1.2k)
+///
+/// # Arguments
+///
+/// * `text` - the count as shown on the page.
+pub fn parse_count(text: &str) -> Option<u64> {
+    let text = text.trim().replace(',', "");
+    match text.strip_suffix(['k', 'K']) {
+        Some(thousands) => thousands
+            .parse::<f64>()
+            .ok()
+            .map(|count| (count * 1000.0) as u64),
+        None => text.parse().ok(),
+    }
+}
+
+/// Parse a date the way pages put it in their markup, with optional fractions of a second and an
+/// optional Z for utc, into a unix timestamp. (ex: 2013-07-05T21:12:43.123Z)
+///
+/// # Arguments
+///
+/// * `date` - the date as found in the page.
+pub fn parse_date(date: &str) -> Option<i64> {
+    chrono::NaiveDateTime::parse_from_str(date.trim_end_matches('Z'), DATE_FORMAT)
+        .ok()
+        .map(|date| date.and_utc().timestamp())
+}
+
+/// Get the text between the tags of some html, skipping the blank parts.
+/// (ex: <a>Star</a> <span>12</span> -> Star, 12)
+///
+/// # Arguments
+///
+/// * `html` - the html to go through.
+pub fn text_nodes(html: &str) -> impl Iterator<Item = &str> {
+    html.split('>')
+        .skip(1)
+        .filter_map(|node| node.split_once('<').map(|(text, _)| text.trim()))
+        .filter(|text| !text.is_empty())
+}
+
+/// Get a count followed by a unit, in plural if needed. (ex: 1 file, 2 files)
+fn plural(count: usize, unit: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let metadata = Metadata {
+            stars: Some(4),
+            files: Some(1),
+            date: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(metadata.summary(3 * YEAR), "★ 4 · 1 file · 3 years ago");
+
+        let metadata = Metadata {
+            updated: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(metadata.summary(100 + 2 * HOUR), "updated 2 hours ago");
+
+        let metadata = Metadata {
+            site: Some("Super User".to_string()),
+            score: Some(-1),
+            ..Default::default()
+        };
+        assert_eq!(metadata.summary(0), "Super User · ▲ -1");
+        assert_eq!(Metadata::default().summary(0), "");
+    }
+
+    #[test]
+    fn test_age() {
+        assert_eq!(age(5), "just now");
+        assert_eq!(age(MINUTE), "1 minute ago");
+        assert_eq!(age(40 * DAY), "1 month ago");
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count(" 12 "), Some(12));
+        assert_eq!(parse_count("1,024"), Some(1024));
+        assert_eq!(parse_count("1.2k"), Some(1200));
+        assert_eq!(parse_count("Star"), None);
+    }
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!(parse_date("2013-07-05T21:12:43"), Some(1373058763));
+        assert_eq!(parse_date("2013-07-05T21:12:43Z"), Some(1373058763));
+        assert_eq!(parse_date("2013-07-05T21:12:43.123Z"), Some(1373058763));
+        assert_eq!(parse_date("yesterday"), None);
+    }
+
+    #[test]
+    fn test_text_nodes() {
+        let html = "<a>Star</a> <span class=\"Counter\"> 12 </span>";
+        assert_eq!(text_nodes(html).collect::<Vec<&str>>(), ["Star", "12"]);
+    }
+}
@@ -1,7 +1,23 @@
+pub mod cargo_lock;
 pub mod ddg;
 pub mod ddg_search;
+pub mod discourse;
+pub mod engine;
+pub mod error_query;
 pub mod geeksforgeeks;
 pub mod github_gist;
+pub mod github_issues;
+pub mod hacker_news;
+pub mod lang;
+pub mod man_pages;
+pub mod merge;
+pub mod metadata;
+pub mod offline_docs;
+pub mod reddit;
+pub mod rust_docs;
+pub mod se_sites;
 pub mod stackexchange;
+pub mod stackexchange_api;
 pub mod stackoverflow;
+pub mod thread;
 pub mod util;
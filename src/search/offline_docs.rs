@@ -0,0 +1,875 @@
+use super::engine::SearchHit;
+use super::metadata::Metadata;
+use super::util;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::OnceCell;
+
+// words that are part of how a question is asked and never part of the name of an entry
+const STOP_WORDS: [&str; 22] = [
+    "a", "an", "and", "are", "do", "does", "doc", "docs", "for", "get", "how", "i", "in", "is",
+    "of", "on", "or", "the", "to", "use", "what", "with",
+];
+// a devdocs bundle is a directory with these files, as downloaded from downloads.devdocs.io
+const DEVDOCS_INDEX: &str = "index.json";
+const DEVDOCS_DB: &str = "db.json";
+// both devdocs bundles and zeal docsets can describe themselves in this file
+const META: &str = "meta.json";
+// a dash or zeal docset is a directory with this extension, with its index and its pages inside
+const DOCSET_EXTENSION: &str = "docset";
+const DOCSET_RESOURCES: &str = "Contents/Resources";
+const DOCSET_INDEX: &str = "docSet.dsidx";
+const DOCSET_DOCUMENTS: &str = "Documents";
+const DOCSET_QUERY: &str = "SELECT name, type, path FROM searchIndex;";
+// dash can put annotations in front of the path of an entry (ex: <dash_entry_name=push>)
+const DASH_ANNOTATION: &str = "<dash_entry_";
+const FIELD_SEPARATOR: char = '\t';
+
+type DocPage = Result<OfflineDoc, OfflineDocsError>;
+type Pages = HashMap<String, String>;
+
+/// An entry of a documentation bundle or docset rendered as text, together with its metadata.
+///
+/// * `content` - the page of the entry as text, starting at the entry.
+/// * `metadata` - the name of the entry and the documentation it's from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OfflineDoc {
+    pub content: String,
+    pub metadata: Metadata,
+}
+
+/// These are the errors the functions associated with the offline documentation will return.
+///
+/// * `NoDocsDir` - No directory with documentation was configured and none of the default ones
+/// exist.
+/// * `NoDocs` - There are no devdocs bundles or docsets in the directory.
+/// * `NoResults` - No entry of any documentation matched the query.
+/// * `ReadFailed` - A file of a bundle or docset couldn't be read.
+/// * `InvalidBundle` - A file of a devdocs bundle isn't the json it should be.
+/// * `CommandFailed` - The sqlite3 command couldn't be run to read the index of a docset,
+/// usually because it's not installed.
+/// * `ErrorCode` - The sqlite3 command exited with an error code.
+/// * `MissingPage` - The page of an entry isn't part of its bundle or docset.
+#[derive(Error, Debug)]
+pub enum OfflineDocsError {
+    #[error(
+        "No offline documentation directory was configured and none of the default ones exist."
+    )]
+    NoDocsDir,
+    #[error("There are no devdocs bundles or docsets in: {0}")]
+    NoDocs(PathBuf),
+    #[error("No entry of the offline documentation matched the query: {0}")]
+    NoResults(String),
+    #[error("Failed to read: {path}. Error: {error}")]
+    ReadFailed {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("The devdocs bundle file: {path} is invalid. Error: {error}")]
+    InvalidBundle {
+        path: PathBuf,
+        error: serde_json::Error,
+    },
+    #[error("Failed to run sqlite3 to read a docset index, is sqlite3 installed? Error: {0}")]
+    CommandFailed(std::io::Error),
+    #[error("The sqlite3 command didn't exit successfully, it exited with: {0}")]
+    ErrorCode(std::process::ExitStatus),
+    #[error("The page of the entry: {0} isn't part of its documentation.")]
+    MissingPage(String),
+}
+
+/// The kind of documentation found in the directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    DevDocs,
+    Docset,
+}
+
+/// A devdocs bundle or a docset, with the pages of a devdocs bundle loaded once for all of its
+/// entries.
+#[derive(Debug)]
+struct Bundle {
+    name: String,
+    dir: PathBuf,
+    format: Format,
+    pages: OnceCell<Pages>,
+}
+
+/// An entry of the index of a bundle, the same fields in devdocs and in docsets.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct Entry {
+    name: String,
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// The index of a devdocs bundle.
+#[derive(Debug, serde::Deserialize)]
+struct DevDocsIndex {
+    entries: Vec<Entry>,
+}
+
+/// What a bundle says about itself, devdocs bundles have a name and a release while zeal docsets
+/// have a title.
+#[derive(Debug, Default, serde::Deserialize)]
+struct Meta {
+    name: Option<String>,
+    title: Option<String>,
+    release: Option<String>,
+}
+
+/// Search documentation downloaded for offline use, devdocs bundles and dash or zeal docsets,
+/// without any network request.
+#[derive(std::fmt::Debug)]
+pub struct OfflineDocs {
+    docs_dir: Option<PathBuf>,
+}
+
+impl OfflineDocs {
+    /// Create a new OfflineDocs instance that reads the documentation from the falion data
+    /// directory, or from where zeal or dash keep their docsets, the first one that exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::offline_docs;
+    ///
+    /// let docs = offline_docs::OfflineDocs::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            docs_dir: default_docs_dir(),
+        }
+    }
+
+    /// Create a new OfflineDocs instance that reads the documentation from the given directory,
+    /// which holds devdocs bundles (each extracted in its own directory) and docsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::offline_docs;
+    ///
+    /// let docs = offline_docs::OfflineDocs::with_docs_dir("/home/user/docs".into());
+    /// ```
+    pub fn with_docs_dir(docs_dir: PathBuf) -> Self {
+        Self {
+            docs_dir: Some(docs_dir),
+        }
+    }
+
+    /// Search the entries of every devdocs bundle and docset for a query (ex: python list sort,
+    /// Array.prototype.push). The words naming a documentation only pick it, the others have to
+    /// be part of the name of the entry, the entries named exactly after them first. Each entry
+    /// is rendered in its own future, associated with its name and returned inside a Vec for
+    /// preserved order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, the most entries to return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because there may be no documentation downloaded
+    /// use falion::search::offline_docs;
+    ///
+    /// # async fn run() -> Result<(), offline_docs::OfflineDocsError> {
+    /// let docs = offline_docs::OfflineDocs::new();
+    /// let docs_content = docs
+    ///     .get_multiple_docs_content("javascript array push", Some(5))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for d in docs_content {
+    ///    assert!(!d.1.await.unwrap().unwrap().content.is_empty())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns offline_docs::OfflineDocsError;
+    ///
+    /// * `NoDocsDir` - No directory with documentation was configured or found.
+    /// * `NoDocs` - There are no bundles or docsets in the directory.
+    /// * `NoResults` - No entry matched the query.
+    /// * `ReadFailed` - The directory couldn't be read.
+    ///
+    /// First error is for the search, second is for the future hanle, third is for the actual
+    /// entry content
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_docs_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<DocPage>)>, OfflineDocsError> {
+        tracing::info!(
+            "Get multiple offline docs entries for search query: {} with a results limit of: {:#?}",
+            &query,
+            &limit
+        );
+        let docs_dir = match &self.docs_dir {
+            Some(docs_dir) => docs_dir,
+            None => {
+                tracing::error!("There is no offline documentation directory.");
+                return Err(OfflineDocsError::NoDocsDir);
+            }
+        };
+        let bundles = find_bundles(docs_dir).await?;
+        if bundles.is_empty() {
+            tracing::error!("There is no documentation in {}", docs_dir.display());
+            return Err(OfflineDocsError::NoDocs(docs_dir.clone()));
+        }
+
+        let words = query_words(query);
+        if words.is_empty() {
+            tracing::error!("The query {} has no words to search for.", &query);
+            return Err(OfflineDocsError::NoResults(query.to_string()));
+        }
+
+        // a bundle that can't be read shouldn't hide the results of the others
+        let indexes = futures::future::join_all(bundles.iter().map(load_entries)).await;
+        let mut found: Vec<(usize, usize, Entry)> = Vec::new();
+        for (index, (bundle, entries)) in bundles.iter().zip(indexes).enumerate() {
+            match entries {
+                Ok(entries) => found.extend(
+                    rank(&bundle.name, &words, entries)
+                        .into_iter()
+                        .map(|(score, entry)| (score, index, entry)),
+                ),
+                Err(error) => tracing::warn!(
+                    "Failed to read the index of {}. Error: {}",
+                    bundle.dir.display(),
+                    error
+                ),
+            }
+        }
+        // stable, so entries as relevant keep the order of their bundle
+        found.sort_by_key(|(score, _, entry)| (std::cmp::Reverse(*score), entry.name.len()));
+        found.truncate(limit.unwrap_or(100));
+        if found.is_empty() {
+            tracing::error!("No offline docs entry matched the query {}", &query);
+            return Err(OfflineDocsError::NoResults(query.to_string()));
+        }
+
+        // render every entry in its own future, associated with its name
+        let bundles: Vec<Arc<Bundle>> = bundles.into_iter().map(Arc::new).collect();
+        let mut docs_content = Vec::with_capacity(found.len());
+        for (rank, (_, index, entry)) in found.into_iter().enumerate() {
+            let bundle = Arc::clone(&bundles[index]);
+            docs_content.push((
+                SearchHit {
+                    title: format!("{} ({})", entry.name, bundle.name),
+                    snippet: entry.kind.clone(),
+                    rank: rank + 1,
+                    ..Default::default()
+                },
+                tokio::task::spawn(async move { render_entry(&bundle, entry).await }),
+            ));
+        }
+
+        Ok(docs_content)
+    }
+}
+
+impl Default for OfflineDocs {
+    fn default() -> Self {
+        OfflineDocs::new()
+    }
+}
+
+/// Get the directory with the offline documentation, the first one that exists: the one of
+/// falion, then the ones zeal and dash keep their docsets in.
+fn default_docs_dir() -> Option<PathBuf> {
+    let data = dirs::data_dir();
+    let local = dirs::data_local_dir();
+    [
+        data.as_ref().map(|data| data.join("falion").join("docs")),
+        local
+            .as_ref()
+            .map(|local| local.join("Zeal").join("Zeal").join("docsets")),
+        data.as_ref().map(|data| data.join("Dash").join("DocSets")),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|dir| dir.is_dir())
+}
+
+/// Find the devdocs bundles and the docsets in a directory, or in its subdirectories like dash
+/// keeps them.
+///
+/// # Arguments
+///
+/// * `docs_dir` - The directory with the documentation.
+///
+/// # Errors
+///
+/// * `ReadFailed` - The directory couldn't be read.
+async fn find_bundles(docs_dir: &Path) -> Result<Vec<Bundle>, OfflineDocsError> {
+    let mut bundles = Vec::new();
+    let mut to_read = vec![(docs_dir.to_path_buf(), true)];
+    while let Some((dir, look_inside)) = to_read.pop() {
+        let mut read_dir = match tokio::fs::read_dir(&dir).await {
+            Ok(read_dir) => read_dir,
+            Err(error) => {
+                tracing::error!("Failed to read {}. Error: {}", dir.display(), &error);
+                return Err(OfflineDocsError::ReadFailed { path: dir, error });
+            }
+        };
+        let mut subdirs = Vec::new();
+        while let Ok(Some(child)) = read_dir.next_entry().await {
+            let path = child.path();
+            if path.is_dir() {
+                subdirs.push(path);
+            }
+        }
+        // sorted, so the results are in the same order every time
+        subdirs.sort();
+
+        for path in subdirs {
+            match bundle_format(&path) {
+                Some(format) => bundles.push(Bundle {
+                    name: bundle_name(&path, format).await,
+                    dir: path,
+                    format,
+                    pages: OnceCell::new(),
+                }),
+                None if look_inside => to_read.push((path, false)),
+                None => (),
+            }
+        }
+    }
+
+    Ok(bundles)
+}
+
+/// Get the format of the documentation in a directory, if it's a devdocs bundle or a docset.
+///
+/// # Arguments
+///
+/// * `dir` - The directory to check.
+fn bundle_format(dir: &Path) -> Option<Format> {
+    if dir.extension().is_some_and(|ext| ext == DOCSET_EXTENSION) {
+        Some(Format::Docset)
+    } else if dir.join(DEVDOCS_INDEX).is_file() {
+        Some(Format::DevDocs)
+    } else {
+        None
+    }
+}
+
+/// Get the name of a bundle, from what it says about itself if it does, or else from the name of
+/// its directory. (ex: Python 3.12, JavaScript)
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the bundle.
+/// * `format` - The format of the bundle.
+async fn bundle_name(dir: &Path, format: Format) -> String {
+    let meta = tokio::fs::read(dir.join(META))
+        .await
+        .ok()
+        .and_then(|meta| serde_json::from_slice::<Meta>(&meta).ok())
+        .unwrap_or_default();
+    // the version of a devdocs slug has dots, only the extension of a docset is left out
+    let stem = match format {
+        Format::DevDocs => dir.file_name(),
+        Format::Docset => dir.file_stem(),
+    }
+    .map(|stem| stem.to_string_lossy().to_string())
+    .unwrap_or_default();
+
+    match (format, meta) {
+        (
+            Format::DevDocs,
+            Meta {
+                name: Some(name),
+                release: Some(release),
+                ..
+            },
+        ) => format!("{} {}", name, release),
+        (
+            Format::DevDocs,
+            Meta {
+                name: Some(name), ..
+            },
+        ) => name,
+        // the slug of a devdocs bundle has the version after a tilde (ex: python~3.12)
+        (Format::DevDocs, _) => stem.replace('~', " "),
+        (
+            Format::Docset,
+            Meta {
+                title: Some(title), ..
+            },
+        ) => title,
+        (Format::Docset, _) => stem.replace('_', " "),
+    }
+}
+
+/// Load the entries of the index of a bundle, from the json of a devdocs bundle or with sqlite3
+/// from the database of a docset.
+///
+/// # Arguments
+///
+/// * `bundle` - The bundle to load the index of.
+///
+/// # Errors
+///
+/// * `ReadFailed` - The index of the devdocs bundle couldn't be read.
+/// * `InvalidBundle` - The index of the devdocs bundle isn't valid json.
+/// * `CommandFailed` - Sqlite3 couldn't be run, usually because it's not installed.
+/// * `ErrorCode` - Sqlite3 exited with an error code, like when the docset has no index.
+#[tracing::instrument(skip_all)]
+async fn load_entries(bundle: &Bundle) -> Result<Vec<Entry>, OfflineDocsError> {
+    match bundle.format {
+        Format::DevDocs => {
+            let index: DevDocsIndex = read_json(&bundle.dir.join(DEVDOCS_INDEX)).await?;
+            Ok(index.entries)
+        }
+        Format::Docset => {
+            let index = bundle.dir.join(DOCSET_RESOURCES).join(DOCSET_INDEX);
+            let output = match tokio::process::Command::new("sqlite3")
+                .arg("-readonly")
+                .arg("-separator")
+                .arg(FIELD_SEPARATOR.to_string())
+                .arg(&index)
+                .arg(DOCSET_QUERY)
+                .output()
+                .await
+            {
+                Ok(output) => output,
+                Err(err) => {
+                    tracing::error!(
+                        "Failed to run sqlite3 on {}. Error: {}",
+                        index.display(),
+                        &err
+                    );
+                    return Err(OfflineDocsError::CommandFailed(err));
+                }
+            };
+            if !output.status.success() {
+                tracing::error!(
+                    "sqlite3 on {} exited with: {}",
+                    index.display(),
+                    &output.status
+                );
+                return Err(OfflineDocsError::ErrorCode(output.status));
+            }
+
+            Ok(String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(parse_docset_line)
+                .collect())
+        }
+    }
+}
+
+/// Read and parse a json file of a devdocs bundle.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file.
+///
+/// # Errors
+///
+/// * `ReadFailed` - The file couldn't be read.
+/// * `InvalidBundle` - The file isn't the expected json.
+async fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, OfflineDocsError> {
+    let json = match tokio::fs::read(path).await {
+        Ok(json) => json,
+        Err(error) => {
+            tracing::error!("Failed to read {}. Error: {}", path.display(), &error);
+            return Err(OfflineDocsError::ReadFailed {
+                path: path.to_path_buf(),
+                error,
+            });
+        }
+    };
+
+    match serde_json::from_slice(&json) {
+        Ok(parsed) => Ok(parsed),
+        Err(error) => {
+            tracing::error!("Failed to parse {}. Error: {}", path.display(), &error);
+            Err(OfflineDocsError::InvalidBundle {
+                path: path.to_path_buf(),
+                error,
+            })
+        }
+    }
+}
+
+/// Parse a line of the output of sqlite3 for the index of a docset. (name, type and path
+/// separated by tabs)
+///
+/// # Arguments
+///
+/// * `line` - The line to parse.
+fn parse_docset_line(line: &str) -> Option<Entry> {
+    let mut fields = line.splitn(3, FIELD_SEPARATOR);
+    let name = fields.next()?.trim();
+    let kind = fields.next()?.trim();
+    let path = fields.next()?.trim();
+
+    if name.is_empty() || path.is_empty() {
+        return None;
+    }
+
+    Some(Entry {
+        name: name.to_string(),
+        path: path.to_string(),
+        kind: kind.to_string(),
+    })
+}
+
+/// Get the words of a query to look for in the names of the entries, lowercased.
+///
+/// # Arguments
+///
+/// * `query` - The query to search for.
+fn query_words(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric() && c != '_')
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty() && !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Score the entries of a bundle against the words of a query, leaving out the ones that don't
+/// match. The words naming the bundle (ex: python) only raise the score of all of its entries,
+/// the others have to be in the name of the entry and count more when they're a whole part of it
+/// (ex: sort in list.sort) or the whole name.
+///
+/// # Arguments
+///
+/// * `bundle_name` - The name of the bundle the entries are from.
+/// * `words` - The words of the query, lowercased.
+/// * `entries` - The entries of the bundle.
+fn rank(bundle_name: &str, words: &[String], entries: Vec<Entry>) -> Vec<(usize, Entry)> {
+    let bundle_name = bundle_name.to_lowercase();
+    let (naming, terms): (Vec<&String>, Vec<&String>) = words
+        .iter()
+        .partition(|word| bundle_name.split_whitespace().any(|part| part == *word));
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let bonus = naming.len() * 5;
+
+    let mut ranked: Vec<(usize, Entry)> = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let name = entry.name.to_lowercase();
+            let parts: Vec<&str> = name
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .filter(|part| !part.is_empty())
+                .collect();
+            let mut score = 0;
+            for term in &terms {
+                if name == **term {
+                    score += 30;
+                } else if parts.contains(&term.as_str()) {
+                    score += 20;
+                } else if name.contains(term.as_str()) {
+                    score += 10;
+                }
+            }
+
+            (score > 0).then_some((score + bonus, entry))
+        })
+        .collect();
+    // the same page can be indexed more than once (ex: under two types)
+    let mut seen = std::collections::HashSet::new();
+    ranked.retain(|(_, entry)| seen.insert(entry.path.clone()));
+
+    ranked
+}
+
+/// Render an entry of a bundle as text, as wide as the terminal.
+///
+/// # Arguments
+///
+/// * `bundle` - The bundle the entry is from.
+/// * `entry` - The entry to render.
+///
+/// # Errors
+///
+/// * `ReadFailed` - The pages of the bundle couldn't be read.
+/// * `InvalidBundle` - The pages of the devdocs bundle aren't valid json.
+/// * `MissingPage` - The page of the entry isn't part of the bundle.
+#[tracing::instrument(skip_all)]
+async fn render_entry(bundle: &Bundle, entry: Entry) -> DocPage {
+    tracing::info!("Render the entry {} of {}", &entry.name, &bundle.name);
+    // set term width
+    let term_width: usize = match crossterm::terminal::size() {
+        Ok(size) => size.0.into(),
+        Err(_) => 100,
+    };
+
+    let html = match bundle.format {
+        Format::DevDocs => {
+            let db = bundle.dir.join(DEVDOCS_DB);
+            let pages = bundle
+                .pages
+                .get_or_try_init(|| read_json::<Pages>(&db))
+                .await?;
+            let (page, fragment) = split_fragment(&entry.path);
+            match pages.get(page) {
+                Some(html) => from_fragment(html, fragment).to_string(),
+                None => {
+                    tracing::error!("The page {} isn't in {}", page, &bundle.name);
+                    return Err(OfflineDocsError::MissingPage(entry.path));
+                }
+            }
+        }
+        Format::Docset => {
+            let path = strip_annotations(&entry.path);
+            let (page, fragment) = split_fragment(&path);
+            // some docsets link to online pages, which are never fetched
+            if page.starts_with("http://") || page.starts_with("https://") {
+                tracing::error!("The page {} of {} is online", page, &bundle.name);
+                return Err(OfflineDocsError::MissingPage(entry.path));
+            }
+            let page = urlencoding::decode(page)
+                .map(|page| page.to_string())
+                .unwrap_or_else(|_| page.to_string());
+            // the index comes from the docset, a page outside of it is never read
+            if !is_relative_page(&page) {
+                tracing::error!("The page {} is outside of {}", page, &bundle.name);
+                return Err(OfflineDocsError::MissingPage(entry.path));
+            }
+            let file = bundle
+                .dir
+                .join(DOCSET_RESOURCES)
+                .join(DOCSET_DOCUMENTS)
+                .join(page);
+            let html = match tokio::fs::read(&file).await {
+                Ok(html) => html,
+                Err(error) => {
+                    tracing::error!("Failed to read {}. Error: {}", file.display(), &error);
+                    return Err(OfflineDocsError::ReadFailed { path: file, error });
+                }
+            };
+            from_fragment(&String::from_utf8_lossy(&html), fragment).to_string()
+        }
+    };
+
+    Ok(OfflineDoc {
+        content: util::html_to_text(&html, term_width),
+        metadata: Metadata {
+            title: Some(format!("{} ({})", entry.name, entry.kind)),
+            site: Some(bundle.name.clone()),
+            ..Default::default()
+        },
+    })
+}
+
+/// Remove the annotations dash can put in the path of an entry.
+/// (ex: <dash_entry_name=push>array.html -> array.html)
+///
+/// # Arguments
+///
+/// * `path` - The path of the entry.
+fn strip_annotations(path: &str) -> String {
+    let mut stripped = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find(DASH_ANNOTATION) {
+        stripped.push_str(&rest[..start]);
+        rest = match rest[start..].find('>') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        };
+    }
+    stripped.push_str(rest);
+
+    stripped
+}
+
+/// Check if the page of a docset entry stays inside the documents of the docset: a relative path
+/// without any `..`, so a crafted or corrupt index can't make falion read other files.
+///
+/// # Arguments
+///
+/// * `page` - The path of the page, decoded and without its fragment.
+fn is_relative_page(page: &str) -> bool {
+    Path::new(page).components().all(|component| {
+        matches!(
+            component,
+            std::path::Component::Normal(_) | std::path::Component::CurDir
+        )
+    })
+}
+
+/// Split the path of an entry into its page and its fragment, if it has one.
+/// (ex: library/stdtypes#list.sort -> library/stdtypes, list.sort)
+///
+/// # Arguments
+///
+/// * `path` - The path of the entry.
+fn split_fragment(path: &str) -> (&str, Option<&str>) {
+    match path.split_once('#') {
+        Some((page, fragment)) => (page, Some(fragment)),
+        None => (path, None),
+    }
+}
+
+/// Get the html of a page starting at the element of the fragment, so the page of a member shows
+/// the member first. The whole page is returned when there's no such element.
+///
+/// # Arguments
+///
+/// * `html` - The html of the page.
+/// * `fragment` - Optional, the id or the anchor name of the element.
+fn from_fragment<'a>(html: &'a str, fragment: Option<&str>) -> &'a str {
+    let fragment = match fragment {
+        Some(fragment) if !fragment.is_empty() => fragment,
+        _ => return html,
+    };
+
+    ["id", "name"]
+        .iter()
+        .flat_map(|attribute| {
+            [
+                format!("{}=\"{}\"", attribute, fragment),
+                format!("{}='{}'", attribute, fragment),
+            ]
+        })
+        .filter_map(|anchor| html.find(&anchor))
+        .min()
+        .and_then(|position| html[..position].rfind('<'))
+        .map_or(html, |start| &html[start..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, path: &str) -> Entry {
+        Entry {
+            name: name.to_string(),
+            path: path.to_string(),
+            kind: "Method".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_query_words() {
+        assert_eq!(
+            query_words("How do I use `list.sort` in Python?"),
+            ["list.sort", "python"]
+        );
+        assert!(query_words("how to use the docs").is_empty());
+    }
+
+    #[test]
+    fn test_rank() {
+        let entries = vec![
+            entry("sorted", "library/functions#sorted"),
+            entry("list.sort", "library/stdtypes#list.sort"),
+            entry("sort", "library/sort"),
+            entry("list.append", "library/stdtypes#list.append"),
+            entry("sort", "library/sort"),
+        ];
+        let words = query_words("python sort");
+        let ranked = rank("Python 3.12", &words, entries.clone());
+
+        assert_eq!(
+            ranked,
+            [
+                (10 + 5, entry("sorted", "library/functions#sorted")),
+                (20 + 5, entry("list.sort", "library/stdtypes#list.sort")),
+                (30 + 5, entry("sort", "library/sort")),
+            ]
+        );
+        // naming the documentation alone matches nothing
+        assert!(rank("Python 3.12", &query_words("python"), entries).is_empty());
+    }
+
+    #[test]
+    fn test_docset_paths() {
+        assert_eq!(
+            parse_docset_line("push\tMethod\t<dash_entry_name=push>array.html#push"),
+            Some(Entry {
+                name: "push".to_string(),
+                path: "<dash_entry_name=push>array.html#push".to_string(),
+                kind: "Method".to_string(),
+            })
+        );
+        assert_eq!(parse_docset_line("push"), None);
+        assert_eq!(
+            strip_annotations(
+                "<dash_entry_name=push><dash_entry_menuDescription=Array>array.html#push"
+            ),
+            "array.html#push"
+        );
+        assert_eq!(
+            split_fragment("array.html#push"),
+            ("array.html", Some("push"))
+        );
+        assert_eq!(split_fragment("array.html"), ("array.html", None));
+        assert!(is_relative_page(
+            "developer.mozilla.org/en-US/docs/array.html"
+        ));
+        assert!(is_relative_page("./array.html"));
+        assert!(!is_relative_page("../../../../.ssh/id_rsa"));
+        assert!(!is_relative_page("docs/../../secret.html"));
+        assert!(!is_relative_page("/etc/passwd"));
+    }
+
+    #[test]
+    fn test_from_fragment() {
+        let html = "<h1>Array</h1><p>Intro</p><section><h2 id=\"push\">push()</h2></section>";
+
+        assert_eq!(
+            from_fragment(html, Some("push")),
+            "<h2 id=\"push\">push()</h2></section>"
+        );
+        assert_eq!(from_fragment(html, Some("pop")), html);
+        assert_eq!(from_fragment(html, None), html);
+    }
+
+    #[tokio::test]
+    async fn test_devdocs_bundle() {
+        let dir = std::env::temp_dir().join("falion-test-offline-docs");
+        let bundle = dir.join("python~3.12");
+        std::fs::create_dir_all(&bundle).unwrap();
+        std::fs::write(
+            bundle.join(DEVDOCS_INDEX),
+            r#"{"entries":[{"name":"list.sort","path":"library/stdtypes#list.sort","type":"Built-in Types"}],"types":[]}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            bundle.join(DEVDOCS_DB),
+            r#"{"library/stdtypes":"<h1>Built-in Types</h1><dl><dt id=\"list.sort\">sort(*, key=None, reverse=False)</dt><dd><p>This method sorts the list in place.</p></dd></dl>"}"#,
+        )
+        .unwrap();
+
+        let docs = OfflineDocs::with_docs_dir(dir.clone());
+        let docs_content = docs
+            .get_multiple_docs_content("python sort", Some(5))
+            .await
+            .unwrap();
+        assert_eq!(docs_content.len(), 1);
+        assert_eq!(docs_content[0].0.title, "list.sort (python 3.12)");
+
+        let doc = docs_content
+            .into_iter()
+            .next()
+            .unwrap()
+            .1
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(doc.content.starts_with("sort(*, key=None, reverse=False)"));
+        assert!(doc.content.contains("This method sorts the list in place."));
+        assert_eq!(doc.metadata.site.as_deref(), Some("python 3.12"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,667 @@
+use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::metadata::Metadata;
+use super::thread::{Answer, Comment, Thread};
+use super::util;
+use serde::Deserialize;
+use thiserror::Error;
+
+const DEFAULT_SUBREDDITS: [&str; 2] = ["rust", "learnprogramming"];
+const REDDIT_SITE: &str = "reddit.com/r/";
+const COMMENTS_PATH: &str = "comments";
+// old reddit serves the same json without the login walls of the new one, raw_json keeps the html
+// of the posts from being escaped a second time
+const THREAD_URL: &str =
+    "https://old.reddit.com/r/{SUB}/comments/{ID}.json?raw_json=1&sort=top&limit={LIMIT}&depth={DEPTH}";
+// how many of the top comments to get, with how many levels of replies
+const COMMENTS_LIMIT: usize = 50;
+const REPLIES_DEPTH: usize = 6;
+const POST_KIND: &str = "t3";
+const COMMENT_KIND: &str = "t1";
+const REMOVED_BODIES: [&str; 2] = ["[deleted]", "[removed]"];
+// how many links to ask duckduckgo for per result, since many of them are comments of the same post
+const LINKS_PER_RESULT: usize = 3;
+
+type RedditThread = Result<Thread, RedditError>;
+
+/// These are the errors the functions associated with Reddit will return.
+///
+/// * `InvalidSubreddit` - The given name isn't a valid subreddit name.
+/// * `NotThread` - The given url is not a post of one of the subreddits.
+/// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+/// due to rate limiting, bad internet etc.
+/// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+/// internet.
+/// * `ErrorCode` - Reddit returned an error code, like when the post was removed or too many
+/// requests were made.
+/// * `NoResults` - None of the links duckduckgo found are posts.
+/// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+#[derive(Error, Debug)]
+pub enum RedditError {
+    #[error("The given subreddit: {0} isn't a valid subreddit name.")]
+    InvalidSubreddit(String),
+    #[error("The given page: {0} is not a post of any of the subreddits.")]
+    NotThread(String),
+    #[error("Failed to make a request to reddit: {0}")]
+    InvalidRequest(reqwest::Error),
+    #[error("A request has been successfully made, but there was an error getting the response body: {0}")]
+    InvalidResponseBody(reqwest::Error),
+    #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
+    ErrorCode(reqwest::StatusCode),
+    #[error("None of the results for the query: {0} are posts of the subreddits.")]
+    NoResults(String),
+    #[error("There was an error retrieving search results from duckduckgo: {0}")]
+    DdgError(ddg::DdgError),
+}
+
+/// Get the name of a subreddit the way reddit's urls have it, with or without its r/ prefix.
+/// (ex: r/Rust -> rust)
+///
+/// # Arguments
+///
+/// * `name` - The name of the subreddit.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::reddit;
+///
+/// assert_eq!(reddit::subreddit("r/Rust").unwrap(), "rust");
+/// assert!(reddit::subreddit("not a subreddit").is_err());
+/// ```
+///
+/// # Errors
+///
+/// * `InvalidSubreddit` - The name is empty or has characters other than letters, digits and
+/// underscores.
+pub fn subreddit(name: &str) -> Result<String, RedditError> {
+    let trimmed = name.trim().trim_start_matches('/');
+    let trimmed = trimmed.strip_prefix("r/").unwrap_or(trimmed);
+
+    if trimmed.is_empty()
+        || !trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(RedditError::InvalidSubreddit(name.to_string()));
+    }
+
+    Ok(trimmed.to_lowercase())
+}
+
+/// Get the subreddit and the id of the post an url points to, if it's a post.
+/// (ex: https://www.reddit.com/r/rust/comments/abc123/some_title/ -> (rust, abc123))
+///
+/// # Arguments
+///
+/// * `url` - The absolute url to the post, or to one of its comments.
+pub fn post_from_url(url: &str) -> Option<(String, String)> {
+    let url = url::Url::parse(url).ok()?;
+    let mut segments = url.path_segments()?;
+
+    if segments.next()? != "r" {
+        return None;
+    }
+    let sub = subreddit(segments.next()?).ok()?;
+    if segments.next()? != COMMENTS_PATH {
+        return None;
+    }
+    let id = segments.next()?.to_lowercase();
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    Some((sub, id))
+}
+
+/// A listing reddit returns, of posts or comments.
+#[derive(Deserialize, Debug, Clone)]
+struct Listing {
+    data: ListingData,
+}
+
+/// The content of a listing.
+#[derive(Deserialize, Debug, Clone)]
+struct ListingData {
+    #[serde(default)]
+    children: Vec<Child>,
+}
+
+/// A child of a listing, a post, a comment or a link to more comments.
+#[derive(Deserialize, Debug, Clone)]
+struct Child {
+    kind: String,
+    data: ChildData,
+}
+
+/// What reddit returns for a post or a comment, the fields of only one of them are optional.
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+struct ChildData {
+    id: String,
+    title: Option<String>,
+    selftext_html: Option<String>,
+    body_html: Option<String>,
+    body: Option<String>,
+    url: Option<String>,
+    is_self: bool,
+    score: i64,
+    num_comments: Option<usize>,
+    created_utc: Option<f64>,
+    edited: serde_json::Value,
+    subreddit: Option<String>,
+    link_flair_text: Option<String>,
+    #[serde(deserialize_with = "listing_or_empty")]
+    replies: Option<Listing>,
+}
+
+/// Deserialize the replies of a comment, which are an empty string instead of a listing when it
+/// has none.
+fn listing_or_empty<'de, D>(deserializer: D) -> Result<Option<Listing>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value).ok())
+}
+
+impl ChildData {
+    /// The id of the post or comment as a number, reddit has them in base 36.
+    fn numeric_id(&self) -> Option<u64> {
+        u64::from_str_radix(&self.id, 36).ok()
+    }
+
+    /// If the comment was deleted or removed by the moderators.
+    fn is_removed(&self) -> bool {
+        self.body
+            .as_deref()
+            .is_some_and(|body| REMOVED_BODIES.contains(&body.trim()))
+    }
+
+    /// The comments replying to this one, if any.
+    fn replies(&self) -> &[Child] {
+        match &self.replies {
+            Some(listing) => &listing.data.children,
+            None => &[],
+        }
+    }
+}
+
+/// Search posts of programming subreddits, like r/rust and r/learnprogramming, and get them as
+/// threads.
+#[derive(std::fmt::Debug)]
+pub struct Reddit {
+    client: reqwest::Client,
+    ddg: ddg::Ddg,
+    subreddits: Vec<String>,
+}
+
+impl Reddit {
+    /// Create a new Reddit instance with a custom client that generates UA (user-agent in order
+    /// to avoid getting rate limited by DuckDuckGO), searching r/rust and r/learnprogramming.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::reddit;
+    ///
+    /// let reddit = reddit::Reddit::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::with_client(util::client_with_special_settings())
+    }
+
+    /// Create a new Reddit instance with a provided client, searching r/rust and
+    /// r/learnprogramming.
+    /// Note: DuckDuckGO will limit your requests if you don't provide a user-agent.
+    ///
+    /// ```
+    /// use falion::search::reddit;
+    ///
+    /// let reddit = reddit::Reddit::with_client(reqwest::Client::new());
+    /// ```
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client: client.clone(),
+            ddg: ddg::Ddg::with_client(client),
+            subreddits: DEFAULT_SUBREDDITS
+                .iter()
+                .map(|sub| sub.to_string())
+                .collect(),
+        }
+    }
+
+    /// Search the given subreddits instead of r/rust and r/learnprogramming.
+    ///
+    /// # Arguments
+    ///
+    /// * `subreddits` - The names of the subreddits to search, as given by `reddit::subreddit`,
+    /// the default ones are kept if empty.
+    ///
+    /// ```
+    /// use falion::search::reddit;
+    ///
+    /// let subreddits = vec![reddit::subreddit("r/golang").unwrap()];
+    /// let reddit = reddit::Reddit::new().with_subreddits(subreddits);
+    /// ```
+    pub fn with_subreddits(mut self, subreddits: Vec<String>) -> Self {
+        if !subreddits.is_empty() {
+            self.subreddits = subreddits;
+        }
+        self
+    }
+
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Get a post as a thread: the post is the question, its top comments are the answers and the
+    /// replies to every comment are flattened under it, indented by how nested they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `post_url` - The absolute url to the post, on one of the subreddits.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use falion::search::reddit;
+    ///
+    /// # async fn run() -> Result<(), reddit::RedditError> {
+    /// let reddit = reddit::Reddit::new();
+    /// let thread = reddit
+    ///     .get_post_content("https://www.reddit.com/r/rust/comments/abc123/some_title/")
+    ///     .await?;
+    ///
+    /// assert!(!thread.question.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns reddit::RedditError
+    ///
+    /// * `NotThread` - The given url is not a post of any of the subreddits.
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+    /// due to rate limiting, bad internet etc.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+    /// internet.
+    /// * `ErrorCode` - Reddit returned an error code.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_post_content(&self, post_url: &str) -> RedditThread {
+        tracing::info!("Get the content for the following post: {}", &post_url);
+        let (sub, id) = match post_from_url(post_url) {
+            Some(post) if self.subreddits.contains(&post.0) => post,
+            _ => {
+                tracing::error!("The given url is not a post url. Url: {}", &post_url);
+                return Err(RedditError::NotThread(post_url.to_string()));
+            }
+        };
+
+        // set term width
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+
+        let request_url = THREAD_URL
+            .replace("{SUB}", &sub)
+            .replace("{ID}", &id)
+            .replace("{LIMIT}", &COMMENTS_LIMIT.to_string())
+            .replace("{DEPTH}", &REPLIES_DEPTH.to_string());
+        let response = match self.client.get(&request_url).send().await {
+            Ok(res) => res,
+            Err(error) => {
+                tracing::error!("Failed to make a request to reddit. Error: {}", &error);
+                return Err(RedditError::InvalidRequest(error));
+            }
+        };
+        if response.status() != reqwest::StatusCode::OK {
+            tracing::error!(
+                "Get request to {} return status code: {}",
+                &request_url,
+                &response.status()
+            );
+            return Err(RedditError::ErrorCode(response.status()));
+        }
+
+        // the post and its comments come as two listings
+        let listings = match response.json::<Vec<Listing>>().await {
+            Ok(listings) => listings,
+            Err(error) => {
+                tracing::error!(
+                    "The response body recieved from reddit is invalid. Error: {}",
+                    &error
+                );
+                return Err(RedditError::InvalidResponseBody(error));
+            }
+        };
+
+        match post_thread(&listings, term_width) {
+            Some(thread) => Ok(thread),
+            None => {
+                tracing::error!("Reddit returned no post for: {}", &post_url);
+                Err(RedditError::NotThread(post_url.to_string()))
+            }
+        }
+    }
+
+    /// Search for posts on the subreddits and get their content. Each post is got in its own
+    /// future, associated with its name and returned inside a Vec for preserved order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, the most posts to return.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because it fails github code action
+    /// use falion::search::reddit;
+    ///
+    /// # async fn run() -> Result<(), reddit::RedditError> {
+    /// let reddit = reddit::Reddit::new();
+    /// let posts_content = reddit
+    ///     .get_multiple_posts_content("borrow checker fighting", Some(5))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for p in posts_content {
+    ///    assert!(!p.1.await.unwrap().unwrap().question.is_empty())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns reddit::RedditError;
+    ///
+    /// * `NoResults` - None of the results are posts.
+    /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+    ///
+    /// First error is for duckduckgo, second is for the future hanle, third is for the actual
+    /// post content
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_posts_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<RedditThread>)>, RedditError> {
+        tracing::info!(
+            "Get multiple reddit posts and their content for search query: {} with a results limit of: {:#?}",
+            &query,
+            &limit
+        );
+        let sites: Vec<String> = self
+            .subreddits
+            .iter()
+            .map(|sub| format!("{}{}", REDDIT_SITE, sub))
+            .collect();
+        let sites: Vec<&str> = sites.iter().map(String::as_str).collect();
+        let links = match self
+            .ddg
+            .get_links_on_sites(query, &sites, None, limit.map(|l| l * LINKS_PER_RESULT))
+            .await
+        {
+            Ok(res) => res,
+            Err(err) => return Err(RedditError::DdgError(err)),
+        };
+
+        // the comments of a post have links of their own
+        let mut posts: Vec<(String, SearchHit)> = Vec::with_capacity(links.len());
+        for hit in links {
+            if let Some((_, id)) = post_from_url(&hit.url) {
+                if !posts.iter().any(|(other, _)| *other == id) {
+                    posts.push((id, hit));
+                }
+            }
+        }
+        posts.truncate(limit.unwrap_or(usize::MAX));
+        if posts.is_empty() {
+            tracing::error!("No reddit posts were found for {}", &query);
+            return Err(RedditError::NoResults(query.to_string()));
+        }
+
+        // get every post in its own future, associated with its name
+        let mut posts_content = Vec::with_capacity(posts.len());
+        for (rank, (_, hit)) in posts.into_iter().enumerate() {
+            let link = hit.url.clone();
+            let client = self.client.clone();
+            let subreddits = self.subreddits.clone();
+            posts_content.push((
+                SearchHit {
+                    title: hit.title_or(post_name(&link)),
+                    rank: rank + 1,
+                    ..hit
+                },
+                tokio::task::spawn(async move {
+                    Self::with_client(client)
+                        .with_subreddits(subreddits)
+                        .get_post_content(&link)
+                        .await
+                }),
+            ));
+        }
+
+        Ok(posts_content)
+    }
+}
+
+impl Default for Reddit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the name a post is listed under until it's loaded, from its url.
+/// (ex: https://www.reddit.com/r/rust/comments/abc123/how_to_use_rc/ -> r/rust how to use rc)
+///
+/// # Arguments
+///
+/// * `post_url` - The absolute url to the post.
+fn post_name(post_url: &str) -> String {
+    let (sub, id) = post_from_url(post_url).unwrap_or_default();
+    let slug = post_url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(post_url)
+        .split('/')
+        .skip_while(|segment| !segment.eq_ignore_ascii_case(&id))
+        .nth(1)
+        .filter(|slug| !slug.is_empty())
+        .map(|slug| slug.replace('_', " "))
+        .unwrap_or(id);
+
+    format!("r/{} {}", sub, slug)
+}
+
+/// Create a thread out of the listings reddit returns for a post, the post is the question, the
+/// top comments are the answers and their replies are flattened under them.
+///
+/// # Arguments
+///
+/// * `listings` - The listing with the post and the one with its comments.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn post_thread(listings: &[Listing], term_width: usize) -> Option<Thread> {
+    let post = listings
+        .first()?
+        .data
+        .children
+        .iter()
+        .find(|child| child.kind == POST_KIND)
+        .map(|child| &child.data)?;
+    let comments = listings
+        .get(1)
+        .map(|listing| listing.data.children.as_slice())
+        .unwrap_or_default();
+
+    // the link of posts that aren't text is shown above their text, if any
+    let mut question = String::new();
+    if !post.is_self {
+        if let Some(url) = &post.url {
+            question.push_str(url);
+            question.push_str("\n\n");
+        }
+    }
+    if let Some(html) = &post.selftext_html {
+        question.push_str(&util::html_to_text(html, term_width));
+    }
+
+    let date = post.created_utc.map(|date| date as i64);
+    let updated = post.edited.as_f64().map(|date| date as i64);
+
+    Some(Thread {
+        id: post.numeric_id(),
+        question,
+        comments: Vec::new(),
+        answers: comments
+            .iter()
+            .filter(|child| child.kind == COMMENT_KIND && !child.data.is_removed())
+            .map(|child| {
+                let mut replies = Vec::new();
+                flatten_replies(child.data.replies(), 0, term_width, &mut replies);
+                Answer {
+                    id: child.data.numeric_id(),
+                    content: util::html_to_text(
+                        child.data.body_html.as_deref().unwrap_or_default(),
+                        term_width,
+                    ),
+                    score: Some(child.data.score),
+                    is_accepted: false,
+                    date: child.data.created_utc.map(|date| date as i64),
+                    comments: replies,
+                }
+            })
+            .collect(),
+        metadata: Metadata {
+            title: post.title.clone(),
+            site: post.subreddit.as_ref().map(|sub| format!("r/{}", sub)),
+            score: Some(post.score),
+            answer_count: post.num_comments,
+            tags: post.link_flair_text.clone().into_iter().collect(),
+            date,
+            updated,
+            ..Default::default()
+        },
+        hidden_comments: Vec::new(),
+    })
+}
+
+/// Flatten a tree of replies depth first, every reply indented by how deep it is in the tree
+/// so it's still clear what it replies to.
+///
+/// # Arguments
+///
+/// * `replies` - The replies on this level of the tree.
+/// * `depth` - How nested the replies are, 0 for the replies to a top comment.
+/// * `term_width` - The width of your terminal in order to properly display.
+/// * `flattened` - Where the replies are added to.
+fn flatten_replies(
+    replies: &[Child],
+    depth: usize,
+    term_width: usize,
+    flattened: &mut Vec<Comment>,
+) {
+    for reply in replies {
+        if reply.kind != COMMENT_KIND || reply.data.is_removed() {
+            continue;
+        }
+
+        flattened.push(Comment::nested(
+            reply.data.body_html.as_deref().unwrap_or_default(),
+            Some(reply.data.score),
+            depth,
+            term_width,
+        ));
+        flatten_replies(reply.data.replies(), depth + 1, term_width, flattened);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_from_url() {
+        assert_eq!(
+            post_from_url("https://www.reddit.com/r/Rust/comments/Abc123/some_title/"),
+            Some((String::from("rust"), String::from("abc123")))
+        );
+        assert_eq!(
+            post_from_url("https://old.reddit.com/r/rust/comments/abc123/some_title/def456/"),
+            Some((String::from("rust"), String::from("abc123")))
+        );
+        assert_eq!(post_from_url("https://www.reddit.com/r/rust/"), None);
+        assert_eq!(post_from_url("https://www.reddit.com/user/someone/"), None);
+        assert_eq!(
+            post_name("https://www.reddit.com/r/rust/comments/abc123/how_to_use_rc/"),
+            "r/rust how to use rc"
+        );
+    }
+
+    #[test]
+    fn test_post_thread() {
+        let listings: Vec<Listing> = serde_json::from_str(
+            r#"[
+                {"kind":"Listing","data":{"children":[{"kind":"t3","data":{"id":"abc","title":"How to use Rc?","selftext_html":"<p>Question</p>","is_self":true,"score":42,"num_comments":4,"created_utc":1672653600.0,"edited":false,"subreddit":"rust","link_flair_text":"help"}}]}},
+                {"kind":"Listing","data":{"children":[
+                    {"kind":"t1","data":{"id":"c1","body":"Answer","body_html":"<p>Answer</p>","score":10,"created_utc":1672657200.0,"replies":{"kind":"Listing","data":{"children":[
+                        {"kind":"t1","data":{"id":"c2","body":"Reply","body_html":"<p>Reply</p>","score":3,"replies":{"kind":"Listing","data":{"children":[
+                            {"kind":"t1","data":{"id":"c3","body":"Nested","body_html":"<p>Nested</p>","score":1,"replies":""}}
+                        ]}}}},
+                        {"kind":"more","data":{"count":2,"children":["c5","c6"]}}
+                    ]}}}},
+                    {"kind":"t1","data":{"id":"c4","body":"[deleted]","body_html":"<p>[deleted]</p>","score":0,"replies":""}}
+                ]}}
+            ]"#,
+        )
+        .unwrap();
+        let thread = post_thread(&listings, 50).unwrap();
+
+        assert_eq!(thread.question, "Question\n");
+        assert_eq!(thread.id, Some(13368));
+        assert_eq!(thread.answers.len(), 1);
+        assert_eq!(thread.answers[0].content, "Answer\n");
+        assert_eq!(thread.answers[0].score, Some(10));
+        assert_eq!(
+            thread.answers[0].comments,
+            [
+                Comment {
+                    content: String::from("Reply"),
+                    score: Some(3),
+                },
+                Comment {
+                    content: String::from("  Nested"),
+                    score: Some(1),
+                },
+            ]
+        );
+        assert_eq!(thread.metadata.title.as_deref(), Some("How to use Rc?"));
+        assert_eq!(thread.metadata.site.as_deref(), Some("r/rust"));
+        assert_eq!(thread.metadata.tags, ["help"]);
+        assert_eq!(thread.metadata.date, Some(1672653600));
+        assert_eq!(thread.metadata.updated, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_post_content() {
+        let reddit = Reddit::with_client(util::client_with_special_settings());
+
+        let thread = reddit
+            .get_post_content("https://www.reddit.com/r/rust/comments/9ppc6n/rust_2018_is_here/")
+            .await
+            .unwrap();
+
+        assert!(!thread.question.is_empty() || thread.metadata.title.is_some())
+    }
+}
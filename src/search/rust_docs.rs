@@ -0,0 +1,634 @@
+use super::ddg;
+use super::engine::{Engines, SearchHit};
+use super::metadata::Metadata;
+use super::util;
+use thiserror::Error;
+
+const DOCS_SITES: [&str; 2] = ["doc.rust-lang.org", "docs.rs"];
+const DOCS_INVALID: [&str; 14] = [
+    "/src/",
+    "/crate/",
+    "/book/",
+    "/reference/",
+    "/rust-by-example/",
+    "/nomicon/",
+    "/cargo/",
+    "/rustc/",
+    "/rustdoc/",
+    "/edition-guide/",
+    "/error_codes/",
+    "/unstable-book/",
+    "/releases",
+    "/all.html",
+];
+// the first segments of the path that aren't part of the item path, the release channel on the
+// std docs, the crate and its version on docs.rs
+const STD_CHANNELS: [&str; 3] = ["stable", "beta", "nightly"];
+const DOCS_RS_PREFIX: usize = 2;
+// the kinds of items inside another item that have their own id on its page
+const MEMBER_KINDS: [&str; 6] = [
+    "method",
+    "tymethod",
+    "variant",
+    "structfield",
+    "associatedconstant",
+    "associatedtype",
+];
+
+const ITEM_TITLE: &str = "<h1";
+const ITEM_DECL: &str = "item-decl";
+const TOP_DOC: &str = "toggle top-doc";
+const CODE_HEADER: &str = "class=\"code-header\">";
+const SECTION: &str = "<section id=\"";
+const DOCBLOCK: &str = "<div class=\"docblock\">";
+const DOCBLOCK_END: &str = "</details>";
+const EXAMPLE: &str = "<pre class=\"rust rust-example-rendered\">";
+const PRE_END: &str = "</pre>";
+// wide enough for a title or a signature to never wrap
+const TITLE_WIDTH: usize = 1000;
+
+type DocPage = Result<Doc, RustDocsError>;
+
+/// The documentation of a Rust item, like a struct, a function or a macro, or of one of its
+/// members, like a method.
+///
+/// * `signature` - the declaration of the item. (ex: pub fn retain<F>(&mut self, f: F))
+/// * `description` - the documentation of the item, without the examples.
+/// * `examples` - the code examples of the documentation, in order.
+/// * `metadata` - the path of the item and the site it's documented on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Doc {
+    pub signature: String,
+    pub description: String,
+    pub examples: Vec<String>,
+    pub metadata: Metadata,
+}
+
+/// These are the errors the functions associated with the Rust documentation will return.
+///
+/// * `NotDocsPage` - The given url does not correspond to a std docs or docs.rs page.
+/// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+/// due to rate limiting, bad internet etc.
+/// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+/// internet.
+/// * `InvalidPageContent` - The page isn't the documentation of an item, even though it did
+/// return 200 OK.
+/// * `ErrorCode` - The website returned an error code
+/// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+#[derive(Error, Debug)]
+pub enum RustDocsError {
+    #[error("The given page: {0} is not a std docs or docs.rs page this function can scrape.")]
+    NotDocsPage(String),
+    #[error("Failed to make a request with the provided query/url: {0}")]
+    InvalidRequest(reqwest::Error),
+    #[error("A request has been successfully made, but there was an error getting the response body: {0}")]
+    InvalidResponseBody(reqwest::Error),
+    #[error("Couldn't find the documentation of an item in the page even though it was successfully retrieved with 200 OK.")]
+    InvalidPageContent,
+    #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
+    ErrorCode(reqwest::StatusCode),
+    #[error("There was an error retrieving search results from duckduckgo: {0}")]
+    DdgError(ddg::DdgError),
+}
+
+/// Scrape the documentation of Rust items from the std docs and docs.rs
+#[derive(std::fmt::Debug)]
+pub struct RustDocs {
+    client: reqwest::Client,
+    ddg: ddg::Ddg,
+}
+
+impl RustDocs {
+    /// Create a new RustDocs instance with a custom client that generates UA (user-agent in
+    /// order to avoid getting rate limited by DuckDuckGO).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::rust_docs;
+    ///
+    /// let docs = rust_docs::RustDocs::new();
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            client: util::client_with_special_settings(),
+            ddg: ddg::Ddg::new(),
+        }
+    }
+
+    /// Create a new RustDocs instance with a provided client.
+    /// Note: DuckDuckGO will limit your requests if you don't provide a user-agent.
+    ///
+    /// ```
+    /// use falion::search::rust_docs;
+    ///
+    /// let docs = rust_docs::RustDocs::with_client(reqwest::Client::new());
+    /// ```
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client: client.clone(),
+            ddg: ddg::Ddg::with_client(client),
+        }
+    }
+
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Get the documentation of the item on a std docs or docs.rs page. If a member of the item
+    /// is given, like a method, its documentation is got instead, falling back to the item if the
+    /// page doesn't have it.
+    ///
+    /// # Arguments
+    ///
+    /// * `page_url` - The absolute url to the page, on https://doc.rust-lang.org/* or
+    /// https://docs.rs/*.
+    /// * `member` - Optional, the name of the member of the item to document. (ex: retain)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because it fails github code action
+    /// use falion::search::rust_docs;
+    ///
+    /// # async fn run() -> Result<(), rust_docs::RustDocsError> {
+    /// let docs = rust_docs::RustDocs::new();
+    /// let doc = docs
+    ///     .get_doc_content("https://doc.rust-lang.org/std/vec/struct.Vec.html", Some("retain"))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// assert!(doc.signature.contains("retain"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns rust_docs::RustDocsError
+    ///
+    /// * `NotDocsPage` - The given url does not correspond to a std docs or docs.rs page.
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+    /// due to rate limiting, bad internet etc.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+    /// internet.
+    /// * `InvalidPageContent` - The page isn't the documentation of an item, even though it did
+    /// return 200 OK.
+    /// * `ErrorCode` - The website returned an error code
+    #[tracing::instrument(skip_all)]
+    pub async fn get_doc_content(&self, page_url: &str, member: Option<&str>) -> DocPage {
+        tracing::info!(
+            "Get the documentation of member {:#?} on the following page: {}",
+            &member,
+            &page_url
+        );
+        // set term width
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+
+        // check if page URL is valid
+        let site = match docs_site(page_url) {
+            Some(site) => site,
+            None => {
+                tracing::error!("The given page is not a documentation page: {}", &page_url);
+                return Err(RustDocsError::NotDocsPage(page_url.to_string()));
+            }
+        };
+
+        // get the documentation page
+        let response_body = match self.client.get(page_url).send().await {
+            Ok(res) => {
+                if res.status() != reqwest::StatusCode::OK {
+                    tracing::error!(
+                        "Get request to {} return status code: {}",
+                        &page_url,
+                        &res.status()
+                    );
+                    return Err(RustDocsError::ErrorCode(res.status()));
+                }
+
+                match res.text().await {
+                    Ok(body) => body,
+                    Err(err) => {
+                        tracing::error!(
+                            "The response body recieved from {} is invalid. Error: {}",
+                            &page_url,
+                            &err
+                        );
+                        return Err(RustDocsError::InvalidResponseBody(err));
+                    }
+                }
+            }
+            Err(err) => {
+                tracing::error!(
+                    "Failed to make a get request to {}. Error: {}",
+                    &page_url,
+                    &err
+                );
+                return Err(RustDocsError::InvalidRequest(err));
+            }
+        };
+
+        match doc_from_html(&response_body, member, term_width) {
+            Some(mut doc) => {
+                doc.metadata.site = Some(site.to_string());
+                Ok(doc)
+            }
+            None => {
+                tracing::error!(
+                    "Failed to find the documentation of an item on {}. Page: {}",
+                    &page_url,
+                    &response_body
+                );
+                Err(RustDocsError::InvalidPageContent)
+            }
+        }
+    }
+
+    /// Search for documentation pages on the std docs and docs.rs using duckduckgo and a provided
+    /// query, like an item path (ex: Vec::retain, tokio::select). This function will go through
+    /// ALL of those results and crate a future for each one which will start getting the
+    /// documentation asynchronously for ALL of them. Each of this Futures is associated with the
+    /// path of the item and returned inside a Vec for preserved order.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The query to search for. If it's an item path, the documentation of the member
+    /// it ends with is shown when the page has it. (ex: retain for Vec::retain)
+    /// * `limit` - Optional, but doing 100 requests at once will probably get you rate limited. A
+    /// recommended value is something like 10 for enough results and still good results.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run // don't run because it fails github code action
+    /// use falion::search::rust_docs;
+    ///
+    /// # async fn run() -> Result<(), rust_docs::RustDocsError> {
+    /// let docs = rust_docs::RustDocs::new();
+    /// let docs_content = docs
+    ///     .get_multiple_docs_content("Vec::retain", Some(1))
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// for d in docs_content {
+    ///    assert!(!d.1.await.unwrap().unwrap().description.is_empty())
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// returns rust_docs::RustDocsError;
+    ///
+    /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+    ///
+    /// First error is for duckduckgo, second is for the future hanle, third is for the actual
+    /// documentation
+    #[tracing::instrument(skip_all)]
+    pub async fn get_multiple_docs_content(
+        &self,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<DocPage>)>, RustDocsError> {
+        tracing::info!("Get multiple documentation pages and their content for search query: {} with a results limit of: {:#?}", &query, &limit);
+        // get the links from duckduckgo
+        let links = match self
+            .ddg
+            .get_links_on_sites(query, &DOCS_SITES, Some(&DOCS_INVALID), limit)
+            .await
+        {
+            Ok(res) => res,
+            Err(err) => return Err(RustDocsError::DdgError(err)),
+        };
+
+        // create a new Vec
+        let mut docs_content = Vec::with_capacity(links.len());
+        let member = member_name(query);
+
+        // start looping through the links associating the item path and the joinhandle for
+        // the future the scrapes the documentation by inserting them togheter in the
+        // Vec inside tuples
+        for hit in links {
+            let link = hit.url.clone();
+            // the item path says more than the title of the page
+            let name = item_path(&link).unwrap_or_else(|| hit.title_or(link.clone()));
+            // insert documentation content
+            let client = self.client.clone();
+            let member = member.clone();
+            docs_content.push((
+                SearchHit { title: name, ..hit },
+                tokio::task::spawn(async move {
+                    Self::with_client(client)
+                        .get_doc_content(&link, member.as_deref())
+                        .await
+                }),
+            ));
+        }
+
+        // return the Vec
+        Ok(docs_content)
+    }
+}
+
+impl Default for RustDocs {
+    fn default() -> Self {
+        RustDocs::new()
+    }
+}
+
+/// Get the site of a documentation page, if it's on the std docs or docs.rs.
+///
+/// # Arguments
+///
+/// * `page_url` - The absolute url to the page.
+fn docs_site(page_url: &str) -> Option<&'static str> {
+    let url = url::Url::parse(page_url).ok()?;
+    if url.scheme() != "https" {
+        return None;
+    }
+    let host = url.host_str()?;
+
+    DOCS_SITES.into_iter().find(|site| *site == host)
+}
+
+/// Get the path of the item documented on a page out of its url, leaving out the release channel
+/// or the crate version. (ex: https://docs.rs/tokio/latest/tokio/macro.select.html ->
+/// tokio::select)
+///
+/// # Arguments
+///
+/// * `page_url` - The absolute url to the page.
+fn item_path(page_url: &str) -> Option<String> {
+    let url = url::Url::parse(page_url).ok()?;
+    let site = docs_site(page_url)?;
+    let mut segments: Vec<&str> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    if site == "docs.rs" {
+        segments.drain(..DOCS_RS_PREFIX.min(segments.len()));
+    } else if segments.first().is_some_and(|first| {
+        STD_CHANNELS.contains(first) || first.starts_with(|c: char| c.is_ascii_digit())
+    }) {
+        segments.remove(0);
+    }
+
+    let path: Vec<&str> = segments
+        .into_iter()
+        .filter(|segment| *segment != "index.html")
+        .map(|segment| match segment.strip_suffix(".html") {
+            // the file of an item is named after its kind and its name (ex: struct.Vec.html)
+            Some(file) => file.split_once('.').map_or(file, |(_, name)| name),
+            None => segment,
+        })
+        .collect();
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.join("::"))
+    }
+}
+
+/// Get the name of the member an item path query ends with, if the query is one.
+/// (ex: Vec::retain -> retain)
+///
+/// # Arguments
+///
+/// * `query` - The query searched for.
+fn member_name(query: &str) -> Option<String> {
+    let query = query.trim();
+    if query.contains(char::is_whitespace) {
+        return None;
+    }
+
+    query
+        .rsplit_once("::")
+        .map(|(_, member)| member.trim_end_matches("()"))
+        .filter(|member| {
+            !member.is_empty() && member.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+        .map(|member| member.to_string())
+}
+
+/// Get the documentation of the item on a rustdoc page, or of one of its members if given and
+/// the page has it.
+///
+/// # Arguments
+///
+/// * `page` - The html of the page.
+/// * `member` - Optional, the name of the member of the item to document.
+/// * `term_width` - The width of your terminal in order to properly display.
+fn doc_from_html(page: &str, member: Option<&str>, term_width: usize) -> Option<Doc> {
+    let title = item_title(page);
+    let member = member.and_then(|member| {
+        MEMBER_KINDS.iter().find_map(|kind| {
+            let id = format!("id=\"{}.{}\"", kind, member);
+            page.split_once(id.as_str()).map(|(_, rest)| (member, rest))
+        })
+    });
+
+    let (title, signature, docblock) = match member {
+        Some((member, rest)) => {
+            // the member ends where the next one starts
+            let block = rest.split_once(SECTION).map_or(rest, |(block, _)| block);
+            let signature = block
+                .split_once(CODE_HEADER)
+                .and_then(|(_, header)| header.split_once("</h"))
+                .map(|(header, _)| header);
+            let title = title.map(|title| format!("{}::{}", title, member));
+            (title, signature, docblock(block))
+        }
+        None => {
+            let signature = page
+                .split_once(ITEM_DECL)
+                .and_then(|(_, decl)| decl.split_once('>'))
+                .and_then(|(_, decl)| decl.split_once(PRE_END))
+                .map(|(decl, _)| decl);
+            let docblock = page.split_once(TOP_DOC).and_then(|(_, top)| docblock(top));
+            (title, signature, docblock)
+        }
+    };
+
+    if signature.is_none() && docblock.is_none() {
+        return None;
+    }
+
+    let docblock = docblock.unwrap_or_default();
+    let mut examples = Vec::new();
+    let mut description = String::with_capacity(docblock.len());
+    let mut rest = docblock;
+    while let Some((before, example)) = rest.split_once(EXAMPLE) {
+        description.push_str(before);
+        let (code, after) = example.split_once(PRE_END).unwrap_or((example, ""));
+        examples.push(code_to_text(code, term_width));
+        rest = after;
+    }
+    description.push_str(rest);
+
+    Some(Doc {
+        signature: signature
+            .map(|signature| code_to_text(signature, TITLE_WIDTH).trim().to_string())
+            .unwrap_or_default(),
+        description: util::html_to_text(&description, term_width),
+        examples,
+        metadata: Metadata {
+            title,
+            ..Default::default()
+        },
+    })
+}
+
+/// Get the documentation block that starts in the given html, up to where its toggle closes.
+///
+/// # Arguments
+///
+/// * `html` - The html the documentation block is in.
+fn docblock(html: &str) -> Option<&str> {
+    html.split_once(DOCBLOCK).map(|(_, block)| {
+        block
+            .split_once(DOCBLOCK_END)
+            .map_or(block, |(block, _)| block)
+    })
+}
+
+/// Converts html of code into plain text, without the references of the links and the marks of
+/// the formatting that would break it.
+///
+/// # Arguments
+///
+/// * `html` - The html to convert.
+/// * `width` - The width to wrap the text at.
+fn code_to_text(html: &str, width: usize) -> String {
+    html2text::from_read_with_decorator(
+        html.as_bytes(),
+        width,
+        html2text::render::text_renderer::TrivialDecorator::new(),
+    )
+}
+
+/// Get the path of the item documented on a rustdoc page out of its heading, without its kind.
+/// (ex: Struct std::vec::Vec -> std::vec::Vec)
+///
+/// # Arguments
+///
+/// * `page` - The html of the page.
+fn item_title(page: &str) -> Option<String> {
+    let heading = page
+        .split_once(ITEM_TITLE)
+        .and_then(|(_, heading)| heading.split_once('>'))
+        .and_then(|(_, heading)| heading.split_once("</h1>"))
+        .map(|(heading, _)| heading)?;
+    // leave out the copy path button
+    let heading = heading
+        .split_once("<button")
+        .map_or(heading, |(path, _)| path);
+    let heading = code_to_text(heading, TITLE_WIDTH);
+    let heading = heading.trim();
+    let path = heading.split_once(' ').map_or(heading, |(_, path)| path);
+
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r##"<div class="main-heading"><h1>Struct <a href="../index.html">std</a>::<wbr><a href="index.html">vec</a>::<wbr><a class="struct" href="#">Vec</a><button id="copy-path" title="Copy item path to clipboard">Copy item path</button></h1></div>
+        <pre class="rust item-decl"><code>pub struct Vec&lt;T&gt; { /* private fields */ }</code></pre>
+        <details class="toggle top-doc" open><summary class="hideme"><span>Expand description</span></summary><div class="docblock"><p>A contiguous growable array type.</p>
+        <div class="example-wrap"><pre class="rust rust-example-rendered"><code>let v = vec![1];</code></pre></div></div></details>
+        <details class="toggle method-toggle" open><summary><section id="method.retain" class="method"><h4 class="code-header">pub fn <a href="#method.retain" class="fn">retain</a>&lt;F&gt;(&amp;mut self, f: F)</h4></section></summary><div class="docblock"><p>Retains only the elements specified by the predicate.</p></div></details>
+        <section id="method.len" class="method"><h4 class="code-header">pub fn <a href="#method.len" class="fn">len</a>(&amp;self) -&gt; usize</h4></section>"##;
+
+    #[test]
+    fn test_item_path() {
+        assert_eq!(
+            item_path("https://doc.rust-lang.org/std/vec/struct.Vec.html").as_deref(),
+            Some("std::vec::Vec")
+        );
+        assert_eq!(
+            item_path("https://doc.rust-lang.org/nightly/core/option/index.html").as_deref(),
+            Some("core::option")
+        );
+        assert_eq!(
+            item_path("https://docs.rs/tokio/latest/tokio/macro.select.html").as_deref(),
+            Some("tokio::select")
+        );
+        assert_eq!(item_path("https://docs.rs/tokio/latest/"), None);
+        assert_eq!(item_path("https://www.rust-lang.org/std/index.html"), None);
+    }
+
+    #[test]
+    fn test_member_name() {
+        assert_eq!(member_name("Vec::retain").as_deref(), Some("retain"));
+        assert_eq!(member_name("tokio::select").as_deref(), Some("select"));
+        assert_eq!(member_name("Option::take()").as_deref(), Some("take"));
+        assert_eq!(member_name("how to retain in a Vec"), None);
+        assert_eq!(member_name("HashMap"), None);
+    }
+
+    #[test]
+    fn test_doc_from_html() {
+        let doc = doc_from_html(PAGE, None, 100).unwrap();
+
+        assert_eq!(doc.metadata.title.as_deref(), Some("std::vec::Vec"));
+        assert!(doc.signature.contains("pub struct Vec<T>"));
+        assert!(doc
+            .description
+            .contains("A contiguous growable array type."));
+        assert!(!doc.description.contains("vec![1]"));
+        assert_eq!(doc.examples.len(), 1);
+        assert!(doc.examples[0].contains("let v = vec![1];"));
+    }
+
+    #[test]
+    fn test_doc_from_html_member() {
+        let doc = doc_from_html(PAGE, Some("retain"), 100).unwrap();
+
+        assert_eq!(doc.metadata.title.as_deref(), Some("std::vec::Vec::retain"));
+        assert!(doc.signature.contains("pub fn retain<F>(&mut self, f: F)"));
+        assert!(doc.description.contains("Retains only the elements"));
+        assert!(doc.examples.is_empty());
+
+        // undocumented members only have their signature, unknown ones fall back to the item
+        let doc = doc_from_html(PAGE, Some("len"), 100).unwrap();
+        assert!(doc.signature.contains("len(&self) -> usize"));
+        assert!(doc.description.trim().is_empty());
+        let doc = doc_from_html(PAGE, Some("select"), 100).unwrap();
+        assert_eq!(doc.metadata.title.as_deref(), Some("std::vec::Vec"));
+
+        assert_eq!(doc_from_html("<p>page</p>", None, 100), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_doc_content() {
+        let client = util::client_with_special_settings();
+        let docs = RustDocs::with_client(client);
+
+        let doc = docs
+            .get_doc_content(
+                "https://doc.rust-lang.org/std/vec/struct.Vec.html",
+                Some("retain"),
+            )
+            .await
+            .unwrap();
+
+        assert!(doc.signature.contains("retain"))
+    }
+}
@@ -0,0 +1,551 @@
+/// The domains every site of the StackExchange network is on, or a subdomain of, besides
+/// StackOverflow which is a source of its own.
+pub const NETWORK_DOMAINS: [&str; 6] = [
+    "stackexchange.com",
+    "superuser.com",
+    "serverfault.com",
+    "askubuntu.com",
+    "mathoverflow.net",
+    "stackapps.com",
+];
+
+/// A site of the StackExchange network.
+///
+/// * `name` - the short name the site is selected by, the same the StackExchange api uses.
+/// (ex: unix)
+/// * `host` - where the site is. (ex: unix.stackexchange.com)
+/// * `title` - the name of the site, shown next to its results. (ex: Unix & Linux)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeSite {
+    pub name: &'static str,
+    pub host: &'static str,
+    pub title: &'static str,
+}
+
+const fn site(name: &'static str, host: &'static str, title: &'static str) -> SeSite {
+    SeSite { name, host, title }
+}
+
+/// Every site of the StackExchange network, sorted by name. Meta sites aren't listed, except for
+/// the one of the whole network.
+pub const SITES: [SeSite; 176] = [
+    site("academia", "academia.stackexchange.com", "Academia"),
+    site("ai", "ai.stackexchange.com", "Artificial Intelligence"),
+    site(
+        "android",
+        "android.stackexchange.com",
+        "Android Enthusiasts",
+    ),
+    site("anime", "anime.stackexchange.com", "Anime & Manga"),
+    site("apple", "apple.stackexchange.com", "Ask Different"),
+    site("arduino", "arduino.stackexchange.com", "Arduino"),
+    site("askubuntu", "askubuntu.com", "Ask Ubuntu"),
+    site("astronomy", "astronomy.stackexchange.com", "Astronomy"),
+    site("aviation", "aviation.stackexchange.com", "Aviation"),
+    site("bicycles", "bicycles.stackexchange.com", "Bicycles"),
+    site(
+        "bioinformatics",
+        "bioinformatics.stackexchange.com",
+        "Bioinformatics",
+    ),
+    site("biology", "biology.stackexchange.com", "Biology"),
+    site("bitcoin", "bitcoin.stackexchange.com", "Bitcoin"),
+    site("blender", "blender.stackexchange.com", "Blender"),
+    site(
+        "boardgames",
+        "boardgames.stackexchange.com",
+        "Board & Card Games",
+    ),
+    site("bricks", "bricks.stackexchange.com", "LEGO Answers"),
+    site("buddhism", "buddhism.stackexchange.com", "Buddhism"),
+    site("cardano", "cardano.stackexchange.com", "Cardano"),
+    site("chemistry", "chemistry.stackexchange.com", "Chemistry"),
+    site("chess", "chess.stackexchange.com", "Chess"),
+    site("chinese", "chinese.stackexchange.com", "Chinese Language"),
+    site(
+        "christianity",
+        "christianity.stackexchange.com",
+        "Christianity",
+    ),
+    site("civicrm", "civicrm.stackexchange.com", "CiviCRM"),
+    site("codegolf", "codegolf.stackexchange.com", "Code Golf"),
+    site("codereview", "codereview.stackexchange.com", "Code Review"),
+    site("coffee", "coffee.stackexchange.com", "Coffee"),
+    site(
+        "cogsci",
+        "cogsci.stackexchange.com",
+        "Psychology & Neuroscience",
+    ),
+    site(
+        "communitybuilding",
+        "communitybuilding.stackexchange.com",
+        "Community Building",
+    ),
+    site(
+        "computergraphics",
+        "computergraphics.stackexchange.com",
+        "Computer Graphics",
+    ),
+    site(
+        "conlang",
+        "conlang.stackexchange.com",
+        "Constructed Languages",
+    ),
+    site("cooking", "cooking.stackexchange.com", "Seasoned Advice"),
+    site("craftcms", "craftcms.stackexchange.com", "Craft CMS"),
+    site("crafts", "crafts.stackexchange.com", "Arts & Crafts"),
+    site("crypto", "crypto.stackexchange.com", "Cryptography"),
+    site("cs", "cs.stackexchange.com", "Computer Science"),
+    site(
+        "cseducators",
+        "cseducators.stackexchange.com",
+        "Computer Science Educators",
+    ),
+    site(
+        "cstheory",
+        "cstheory.stackexchange.com",
+        "Theoretical Computer Science",
+    ),
+    site(
+        "datascience",
+        "datascience.stackexchange.com",
+        "Data Science",
+    ),
+    site("dba", "dba.stackexchange.com", "Database Administrators"),
+    site("devops", "devops.stackexchange.com", "DevOps"),
+    site("diy", "diy.stackexchange.com", "Home Improvement"),
+    site(
+        "drones",
+        "drones.stackexchange.com",
+        "Drones and Model Aircraft",
+    ),
+    site("drupal", "drupal.stackexchange.com", "Drupal Answers"),
+    site("dsp", "dsp.stackexchange.com", "Signal Processing"),
+    site(
+        "earthscience",
+        "earthscience.stackexchange.com",
+        "Earth Science",
+    ),
+    site("ebooks", "ebooks.stackexchange.com", "Ebooks"),
+    site("economics", "economics.stackexchange.com", "Economics"),
+    site(
+        "electronics",
+        "electronics.stackexchange.com",
+        "Electrical Engineering",
+    ),
+    site(
+        "elementaryos",
+        "elementaryos.stackexchange.com",
+        "elementary OS",
+    ),
+    site("ell", "ell.stackexchange.com", "English Language Learners"),
+    site("emacs", "emacs.stackexchange.com", "Emacs"),
+    site(
+        "engineering",
+        "engineering.stackexchange.com",
+        "Engineering",
+    ),
+    site(
+        "english",
+        "english.stackexchange.com",
+        "English Language & Usage",
+    ),
+    site("eosio", "eosio.stackexchange.com", "EOS.IO"),
+    site(
+        "es.stackoverflow",
+        "es.stackoverflow.com",
+        "Stack Overflow en español",
+    ),
+    site(
+        "esperanto",
+        "esperanto.stackexchange.com",
+        "Esperanto Language",
+    ),
+    site("ethereum", "ethereum.stackexchange.com", "Ethereum"),
+    site(
+        "expatriates",
+        "expatriates.stackexchange.com",
+        "Expatriates",
+    ),
+    site(
+        "expressionengine",
+        "expressionengine.stackexchange.com",
+        "ExpressionEngine Answers",
+    ),
+    site("fitness", "fitness.stackexchange.com", "Physical Fitness"),
+    site(
+        "freelancing",
+        "freelancing.stackexchange.com",
+        "Freelancing",
+    ),
+    site("french", "french.stackexchange.com", "French Language"),
+    site("gamedev", "gamedev.stackexchange.com", "Game Development"),
+    site("gaming", "gaming.stackexchange.com", "Arqade"),
+    site(
+        "gardening",
+        "gardening.stackexchange.com",
+        "Gardening & Landscaping",
+    ),
+    site("genai", "genai.stackexchange.com", "GenAI"),
+    site(
+        "genealogy",
+        "genealogy.stackexchange.com",
+        "Genealogy & Family History",
+    ),
+    site("german", "german.stackexchange.com", "German Language"),
+    site(
+        "gis",
+        "gis.stackexchange.com",
+        "Geographic Information Systems",
+    ),
+    site(
+        "graphicdesign",
+        "graphicdesign.stackexchange.com",
+        "Graphic Design",
+    ),
+    site("ham", "ham.stackexchange.com", "Amateur Radio"),
+    site(
+        "hardwarerecs",
+        "hardwarerecs.stackexchange.com",
+        "Hardware Recommendations",
+    ),
+    site(
+        "hermeneutics",
+        "hermeneutics.stackexchange.com",
+        "Biblical Hermeneutics",
+    ),
+    site("hinduism", "hinduism.stackexchange.com", "Hinduism"),
+    site("history", "history.stackexchange.com", "History"),
+    site("homebrew", "homebrew.stackexchange.com", "Homebrewing"),
+    site(
+        "hsm",
+        "hsm.stackexchange.com",
+        "History of Science and Mathematics",
+    ),
+    site(
+        "interpersonal",
+        "interpersonal.stackexchange.com",
+        "Interpersonal Skills",
+    ),
+    site("iot", "iot.stackexchange.com", "Internet of Things"),
+    site("iota", "iota.stackexchange.com", "IOTA"),
+    site("islam", "islam.stackexchange.com", "Islam"),
+    site("italian", "italian.stackexchange.com", "Italian Language"),
+    site(
+        "ja.stackoverflow",
+        "ja.stackoverflow.com",
+        "スタック・オーバーフロー",
+    ),
+    site(
+        "japanese",
+        "japanese.stackexchange.com",
+        "Japanese Language",
+    ),
+    site("joomla", "joomla.stackexchange.com", "Joomla"),
+    site("judaism", "judaism.stackexchange.com", "Mi Yodeya"),
+    site("korean", "korean.stackexchange.com", "Korean Language"),
+    site(
+        "languagelearning",
+        "languagelearning.stackexchange.com",
+        "Language Learning",
+    ),
+    site("latin", "latin.stackexchange.com", "Latin Language"),
+    site("law", "law.stackexchange.com", "Law"),
+    site("lifehacks", "lifehacks.stackexchange.com", "Lifehacks"),
+    site(
+        "linguistics",
+        "linguistics.stackexchange.com",
+        "Linguistics",
+    ),
+    site("literature", "literature.stackexchange.com", "Literature"),
+    site("magento", "magento.stackexchange.com", "Magento"),
+    site(
+        "martialarts",
+        "martialarts.stackexchange.com",
+        "Martial Arts",
+    ),
+    site(
+        "materials",
+        "materials.stackexchange.com",
+        "Materials Modeling",
+    ),
+    site("math", "math.stackexchange.com", "Mathematics"),
+    site(
+        "matheducators",
+        "matheducators.stackexchange.com",
+        "Mathematics Educators",
+    ),
+    site(
+        "mathematica",
+        "mathematica.stackexchange.com",
+        "Mathematica",
+    ),
+    site("mathoverflow", "mathoverflow.net", "MathOverflow"),
+    site(
+        "mechanics",
+        "mechanics.stackexchange.com",
+        "Motor Vehicle Maintenance & Repair",
+    ),
+    site(
+        "medicalsciences",
+        "medicalsciences.stackexchange.com",
+        "Medical Sciences",
+    ),
+    site("meta", "meta.stackexchange.com", "Meta Stack Exchange"),
+    site("monero", "monero.stackexchange.com", "Monero"),
+    site(
+        "money",
+        "money.stackexchange.com",
+        "Personal Finance & Money",
+    ),
+    site("movies", "movies.stackexchange.com", "Movies & TV"),
+    site(
+        "music",
+        "music.stackexchange.com",
+        "Music: Practice & Theory",
+    ),
+    site("musicfans", "musicfans.stackexchange.com", "Music Fans"),
+    site(
+        "mythology",
+        "mythology.stackexchange.com",
+        "Mythology & Folklore",
+    ),
+    site(
+        "networkengineering",
+        "networkengineering.stackexchange.com",
+        "Network Engineering",
+    ),
+    site("opendata", "opendata.stackexchange.com", "Open Data"),
+    site("opensource", "opensource.stackexchange.com", "Open Source"),
+    site("or", "or.stackexchange.com", "Operations Research"),
+    site(
+        "outdoors",
+        "outdoors.stackexchange.com",
+        "The Great Outdoors",
+    ),
+    site("parenting", "parenting.stackexchange.com", "Parenting"),
+    site("pets", "pets.stackexchange.com", "Pets"),
+    site("philosophy", "philosophy.stackexchange.com", "Philosophy"),
+    site("photo", "photo.stackexchange.com", "Photography"),
+    site("physics", "physics.stackexchange.com", "Physics"),
+    site("pm", "pm.stackexchange.com", "Project Management"),
+    site("poker", "poker.stackexchange.com", "Poker"),
+    site("politics", "politics.stackexchange.com", "Politics"),
+    site(
+        "portuguese",
+        "portuguese.stackexchange.com",
+        "Portuguese Language",
+    ),
+    site(
+        "proofassistants",
+        "proofassistants.stackexchange.com",
+        "Proof Assistants",
+    ),
+    site(
+        "pt.stackoverflow",
+        "pt.stackoverflow.com",
+        "Stack Overflow em Português",
+    ),
+    site("puzzling", "puzzling.stackexchange.com", "Puzzling"),
+    site("quant", "quant.stackexchange.com", "Quantitative Finance"),
+    site(
+        "quantumcomputing",
+        "quantumcomputing.stackexchange.com",
+        "Quantum Computing",
+    ),
+    site(
+        "raspberrypi",
+        "raspberrypi.stackexchange.com",
+        "Raspberry Pi",
+    ),
+    site(
+        "retrocomputing",
+        "retrocomputing.stackexchange.com",
+        "Retrocomputing",
+    ),
+    site(
+        "reverseengineering",
+        "reverseengineering.stackexchange.com",
+        "Reverse Engineering",
+    ),
+    site("robotics", "robotics.stackexchange.com", "Robotics"),
+    site("rpg", "rpg.stackexchange.com", "Role-playing Games"),
+    site(
+        "ru.stackoverflow",
+        "ru.stackoverflow.com",
+        "Stack Overflow на русском",
+    ),
+    site("rus", "rus.stackexchange.com", "Russian Language"),
+    site("salesforce", "salesforce.stackexchange.com", "Salesforce"),
+    site(
+        "scicomp",
+        "scicomp.stackexchange.com",
+        "Computational Science",
+    ),
+    site(
+        "scifi",
+        "scifi.stackexchange.com",
+        "Science Fiction & Fantasy",
+    ),
+    site(
+        "security",
+        "security.stackexchange.com",
+        "Information Security",
+    ),
+    site("serverfault", "serverfault.com", "Server Fault"),
+    site("sharepoint", "sharepoint.stackexchange.com", "SharePoint"),
+    site("sitecore", "sitecore.stackexchange.com", "Sitecore"),
+    site("skeptics", "skeptics.stackexchange.com", "Skeptics"),
+    site(
+        "softwareengineering",
+        "softwareengineering.stackexchange.com",
+        "Software Engineering",
+    ),
+    site(
+        "softwarerecs",
+        "softwarerecs.stackexchange.com",
+        "Software Recommendations",
+    ),
+    site("solana", "solana.stackexchange.com", "Solana"),
+    site("sound", "sound.stackexchange.com", "Sound Design"),
+    site("space", "space.stackexchange.com", "Space Exploration"),
+    site("spanish", "spanish.stackexchange.com", "Spanish Language"),
+    site("sports", "sports.stackexchange.com", "Sports"),
+    site(
+        "sqa",
+        "sqa.stackexchange.com",
+        "Software Quality Assurance & Testing",
+    ),
+    site("stackapps", "stackapps.com", "Stack Apps"),
+    site("stackoverflow", "stackoverflow.com", "Stack Overflow"),
+    site("stats", "stats.stackexchange.com", "Cross Validated"),
+    site("stellar", "stellar.stackexchange.com", "Stellar"),
+    site(
+        "substrate",
+        "substrate.stackexchange.com",
+        "Substrate and Polkadot",
+    ),
+    site("superuser", "superuser.com", "Super User"),
+    site(
+        "sustainability",
+        "sustainability.stackexchange.com",
+        "Sustainable Living",
+    ),
+    site("tex", "tex.stackexchange.com", "TeX - LaTeX"),
+    site("tezos", "tezos.stackexchange.com", "Tezos"),
+    site("tor", "tor.stackexchange.com", "Tor"),
+    site("travel", "travel.stackexchange.com", "Travel"),
+    site("tridion", "tridion.stackexchange.com", "Tridion"),
+    site(
+        "ukrainian",
+        "ukrainian.stackexchange.com",
+        "Ukrainian Language",
+    ),
+    site("unix", "unix.stackexchange.com", "Unix & Linux"),
+    site("ux", "ux.stackexchange.com", "User Experience"),
+    site(
+        "vegetarianism",
+        "vegetarianism.stackexchange.com",
+        "Vegetarianism",
+    ),
+    site("vi", "vi.stackexchange.com", "Vi and Vim"),
+    site("webapps", "webapps.stackexchange.com", "Web Applications"),
+    site("webmasters", "webmasters.stackexchange.com", "Webmasters"),
+    site(
+        "windowsphone",
+        "windowsphone.stackexchange.com",
+        "Windows Phone",
+    ),
+    site(
+        "woodworking",
+        "woodworking.stackexchange.com",
+        "Woodworking",
+    ),
+    site(
+        "wordpress",
+        "wordpress.stackexchange.com",
+        "WordPress Development",
+    ),
+    site("workplace", "workplace.stackexchange.com", "The Workplace"),
+    site(
+        "worldbuilding",
+        "worldbuilding.stackexchange.com",
+        "Worldbuilding",
+    ),
+    site("writing", "writing.stackexchange.com", "Writing"),
+];
+
+/// Find a site by its short name or its host. (ex: unix or unix.stackexchange.com)
+///
+/// # Arguments
+///
+/// * `name` - the short name or the host of the site, in any case.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::se_sites;
+///
+/// assert_eq!(se_sites::find("Superuser").unwrap().host, "superuser.com");
+/// assert_eq!(se_sites::find("unix.stackexchange.com").unwrap().title, "Unix & Linux");
+/// assert!(se_sites::find("superusers").is_none());
+/// ```
+pub fn find(name: &str) -> Option<&'static SeSite> {
+    let name = name.trim().to_lowercase();
+    SITES
+        .iter()
+        .find(|site| site.name == name || site.host == name)
+}
+
+/// Find the site an url is on, if it's on one of the network. (ex:
+/// https://superuser.com/questions/1/title -> Super User)
+///
+/// # Arguments
+///
+/// * `url` - the absolute url.
+pub fn from_url(url: &str) -> Option<&'static SeSite> {
+    let url = url::Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    SITES.iter().find(|site| site.host == host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sites_are_sorted_and_unique() {
+        assert!(SITES.windows(2).all(|pair| pair[0].name < pair[1].name));
+    }
+
+    #[test]
+    fn test_every_site_is_on_the_network() {
+        for site in SITES
+            .iter()
+            .filter(|site| !site.host.ends_with("stackoverflow.com"))
+        {
+            assert!(
+                NETWORK_DOMAINS
+                    .iter()
+                    .any(|domain| site.host == *domain
+                        || site.host.ends_with(&format!(".{}", domain))),
+                "{} isn't on a network domain",
+                site.host
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_url() {
+        assert_eq!(
+            from_url("https://mathoverflow.net/questions/1/title").map(|site| site.name),
+            Some("mathoverflow")
+        );
+        assert_eq!(
+            from_url("https://unix.stackexchange.com/questions/1/title").map(|site| site.title),
+            Some("Unix & Linux")
+        );
+        assert!(from_url("https://meta.unix.stackexchange.com/questions/1").is_none());
+        assert!(from_url("not an url").is_none());
+    }
+}
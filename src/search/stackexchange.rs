@@ -1,15 +1,16 @@
+use super::engine::{Engines, SearchHit};
+use super::lang::Lang;
+use super::se_sites::{self, SeSite};
+use super::stackexchange_api::{self, StackExchangeApi};
+use super::thread::Thread;
 use super::{ddg, util};
 use thiserror::Error;
 
-const QUESTION_SEP: &str = "<div class=\"s-prose js-post-body\" itemprop=\"text\">";
-const QUESTION_END: &str = "</div>";
-const STACKEXCHANGE_QUESTION_URL: &str = "stackexchange.com/questions/";
-const STACKEXCHANGE_INVALID: [&str; 2] = [
-    "stackexchange.com/questions/tagged",
-    "stackexchange.com/tag",
-];
+const STACKEXCHANGE_INVALID: [&str; 1] = ["/questions/tagged"];
 
-type SeQuestion = Result<Vec<String>, SeError>;
+type SeQuestion = Result<Thread, SeError>;
+type SiteQuestion = (String, u64);
+type TitledThread = (String, Thread);
 
 /// These are the errors the functions associated with StackExchange will return.
 ///
@@ -22,6 +23,8 @@ type SeQuestion = Result<Vec<String>, SeError>;
 /// corrupted because it did return 200 OK.
 /// * `ErrorCode` - The website returned an error code
 /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+/// * `ApiError` - error with getting results from the StackExchange api.
+/// (stackexchange_api::SeApiError)
 #[derive(Error, Debug)]
 pub enum SeError {
     #[error("The given page: {0} is not a valid StackExchange page this function can scrape.")]
@@ -36,13 +39,51 @@ pub enum SeError {
     ErrorCode(reqwest::StatusCode),
     #[error("There was an error retrieving search results from duckduckgo: {0}")]
     DdgError(ddg::DdgError),
+    #[error("There was an error retrieving results from the StackExchange api: {0}")]
+    ApiError(stackexchange_api::SeApiError),
 }
 
-/// Scrape questions from StackExchange
+/// Get the site of the StackExchange network a question is on, if the url is the one of a
+/// question.
+///
+/// # Arguments
+///
+/// * `question_url` - The absolute url to the question.
+fn question_site(question_url: &str) -> Option<&'static SeSite> {
+    if STACKEXCHANGE_INVALID
+        .iter()
+        .any(|invalid| question_url.contains(invalid))
+    {
+        return None;
+    }
+    let (host, _) = stackexchange_api::question_from_url(question_url)?;
+
+    se_sites::find(&host)
+}
+
+/// Get the name of a result before its content is loaded, the slug of the question followed by
+/// the site it's on. (ex: how to list files [Unix & Linux])
+///
+/// # Arguments
+///
+/// * `slug` - The slug of the question with spaces instead of dashes.
+/// * `question_url` - The absolute url to the question.
+fn site_name(slug: &str, question_url: &str) -> String {
+    match se_sites::from_url(question_url) {
+        Some(site) => format!("{} [{}]", slug, site.title),
+        None => slug.to_string(),
+    }
+}
+
+/// Scrape questions from the sites of the StackExchange network, by default from all of them
+/// besides StackOverflow.
 #[derive(std::fmt::Debug)]
 pub struct StackExchange {
     client: reqwest::Client,
     ddg: ddg::Ddg,
+    api: Option<StackExchangeApi>,
+    sites: Vec<&'static SeSite>,
+    lang: Option<&'static Lang>,
 }
 
 impl StackExchange {
@@ -60,6 +101,9 @@ impl StackExchange {
         Self {
             client: util::client_with_special_settings(),
             ddg: ddg::Ddg::new(),
+            api: None,
+            sites: Vec::new(),
+            lang: None,
         }
     }
 
@@ -75,32 +119,105 @@ impl StackExchange {
         Self {
             client: client.clone(),
             ddg: ddg::Ddg::with_client(client),
+            api: None,
+            sites: Vec::new(),
+            lang: None,
+        }
+    }
+
+    /// Create a new StackExchange instance that gets the questions from the official StackExchange
+    /// api instead of scraping them, this doesn't break when the site's markup changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to make the requests with.
+    /// * `api` - The StackExchange api client, it holds the app key and the quota.
+    ///
+    /// ```
+    /// use falion::search::stackexchange;
+    /// use falion::search::stackexchange_api;
+    ///
+    /// let client = reqwest::Client::new();
+    /// let api = stackexchange_api::StackExchangeApi::with_client(client.clone(), None);
+    /// let se = stackexchange::StackExchange::with_api(client, api);
+    /// ```
+    pub fn with_api(client: reqwest::Client, api: StackExchangeApi) -> Self {
+        Self {
+            client: client.clone(),
+            ddg: ddg::Ddg::with_client(client),
+            api: Some(api),
+            sites: Vec::new(),
+            lang: None,
         }
     }
 
-    /// Get the contents of a StackExchange question inside a vector, the first item being the
-    /// question itself and the rest the answers.
+    /// Only search the given sites of the StackExchange network instead of all of them.
     ///
     /// # Arguments
     ///
-    /// * `question_url` - The StackExchange absolute url, specifically like this
-    /// https://stackexchange.com/questions/[0-9]*/the-question, to the question
+    /// * `sites` - The sites to search, every site is searched if empty.
+    ///
+    /// ```
+    /// use falion::search::se_sites;
+    /// use falion::search::stackexchange;
+    ///
+    /// let sites = ["unix", "superuser"].iter().filter_map(|name| se_sites::find(name)).collect();
+    /// let se = stackexchange::StackExchange::new().with_sites(sites);
+    /// ```
+    pub fn with_sites(mut self, sites: Vec<&'static SeSite>) -> Self {
+        self.sites = sites;
+        self
+    }
+
+    /// Only search for the questions tagged with a language.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language the questions have to be tagged with, they can be about any
+    /// language if None.
+    ///
+    /// ```
+    /// use falion::search::lang;
+    /// use falion::search::stackexchange;
+    ///
+    /// let source = stackexchange::StackExchange::new().with_lang(lang::find("bash"));
+    /// ```
+    pub fn with_lang(mut self, lang: Option<&'static Lang>) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Get the contents of a StackExchange question as a thread: the question itself and its
+    /// answers, with their score, date and if they're accepted.
+    ///
+    /// # Arguments
+    ///
+    /// * `question_url` - The absolute url to the question on any site of the StackExchange
+    /// network, specifically like this https://superuser.com/questions/[0-9]*/the-question
     ///
     /// # Examples
     ///
     /// ```
     /// use falion::search::ddg;
+    /// use falion::search::se_sites;
     /// use falion::search::stackexchange;
     ///
-    /// const STACKEXCHANGE_INVALID: [&str; 2] = [
-    ///     "stackexchange.com/questions/tagged",
-    ///     "stackexchange.com/tag",
-    /// ];
-    ///
     /// # async fn run() -> Result<(), stackexchange::SeError> {
     /// let ddg = ddg::Ddg::new();
     /// let se = stackexchange::StackExchange::new();
-    /// let link = &ddg.get_links("Rust threading", Some("stackexchange.com/questions/"), Some(true), Some(&STACKEXCHANGE_INVALID), Some(1)).await.unwrap()[0];
+    /// let link = &ddg.get_links_on_sites("Rust threading", &se_sites::NETWORK_DOMAINS, Some(&["/questions/tagged"]), Some(1)).await.unwrap()[0].url;
     ///
     /// let question_content = se.get_question_content(&link).await.unwrap();
     /// # Ok(())
@@ -142,23 +259,26 @@ impl StackExchange {
             }
         }
 
-        match question_url.split_once(STACKEXCHANGE_QUESTION_URL) {
-            Some(split) => {
-                if split.0.is_empty() {
-                    tracing::error!(
-                        "The given url is not a stackexchange url (second check, second split). Url: {}",
-                        &question_url
-                    );
-                    return Err(SeError::NotSeQuestion(question_url.to_string()));
-                }
-            }
+        let site = match question_site(question_url) {
+            Some(site) => site,
             None => {
                 tracing::error!(
-                    "The given url is not a stackexchange url (second check, first split). Url: {}",
+                    "The given url is not a question on a stackexchange site (second check). Url: {}",
                     &question_url
                 );
                 return Err(SeError::NotSeQuestion(question_url.to_string()));
             }
+        };
+
+        // get the question from the api if it's used instead of scraping
+        if let Some(api) = &self.api {
+            return match api.get_question_content(question_url).await {
+                Ok(mut content) => {
+                    content.metadata.site = Some(site.title.to_string());
+                    Ok(content)
+                }
+                Err(error) => Err(SeError::ApiError(error)),
+            };
         }
 
         // get stackexchange page
@@ -195,27 +315,26 @@ impl StackExchange {
             }
         };
 
-        // parse the page to get the question and answers
-        let question_content = response_body
-            .split(QUESTION_SEP)
-            .skip(1)
-            .filter_map(|q| {
-                q.split_once(QUESTION_END).map(|q_sep| {
-                    let html = q_sep.0;
-                    util::html_to_text(html, term_width)
-                })
-            })
-            .collect::<Vec<String>>();
-
-        // check if page data was valid and we parsed something
-        if question_content.is_empty() {
-            tracing::error!(
-                "The stackexchange question ({}) content is empty. Response body: {}",
-                &question_url,
-                &response_body
-            );
-            return Err(SeError::InvalidQuestionContent);
-        }
+        // parse the page to get the question and answers, check if page data was valid and we
+        // parsed something
+        let mut question_content = match Thread::from_stackexchange_page(&response_body, term_width)
+        {
+            Some(thread) => thread,
+            None => {
+                tracing::error!(
+                    "The stackexchange question ({}) content is empty. Response body: {}",
+                    &question_url,
+                    &response_body
+                );
+                return Err(SeError::InvalidQuestionContent);
+            }
+        };
+
+        // the page only has the first few comments of each post, get the rest like the page does
+        question_content
+            .load_hidden_comments(&self.client, question_url, term_width)
+            .await;
+        question_content.metadata.site = Some(site.title.to_string());
 
         // return question and aswers content
         Ok(question_content)
@@ -249,7 +368,7 @@ impl StackExchange {
     ///     .unwrap();
     ///
     /// for q in question_content {
-    ///    assert!(!q.1.await.unwrap().unwrap().is_empty())
+    ///    assert!(!q.1.await.unwrap().unwrap().question.is_empty())
     /// }
     /// # Ok(())
     /// # }
@@ -268,37 +387,51 @@ impl StackExchange {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<(String, tokio::task::JoinHandle<SeQuestion>)>, SeError> {
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<SeQuestion>)>, SeError> {
         tracing::info!("Get multiple Stackexchange questions and their content for search query: {} with a results limit of: {:#?}", &query, &limit);
-        // get the links from duckduckgo
-        let links = match self
+        // get the links from duckduckgo, on the whole network unless sites were selected
+        let domains: Vec<&str> = if self.sites.is_empty() {
+            se_sites::NETWORK_DOMAINS.to_vec()
+        } else {
+            self.sites.iter().map(|site| site.host).collect()
+        };
+        // tag the query with the language, if any
+        let query = match self.lang {
+            Some(lang) => lang.tagged(query),
+            None => query.to_string(),
+        };
+        let links: Vec<SearchHit> = match self
             .ddg
-            .get_links(
-                query,
-                Some(STACKEXCHANGE_QUESTION_URL),
-                Some(true),
-                Some(&STACKEXCHANGE_INVALID),
-                limit,
-            )
+            .get_links_on_sites(&query, &domains, Some(&STACKEXCHANGE_INVALID), limit)
             .await
         {
-            Ok(res) => res,
+            Ok(res) => res
+                .into_iter()
+                .filter(|hit| question_site(&hit.url).is_some())
+                .collect(),
             Err(err) => return Err(SeError::DdgError(err)),
         };
 
+        // get the content from the api if it's used instead of scraping
+        if let Some(api) = &self.api {
+            return Self::get_multiple_questions_content_api(api, &links).await;
+        }
+
         // create a new Vec
         let mut questions_content = Vec::with_capacity(links.len());
 
         // start looping through the links associating the question title and the joinhandle for
         // the future the scrapes the content of the question by inserting them togheter in the
         // Vec inside a tuple
-        for link in links {
+        for hit in links {
             // unwrap is safe here since ddg does all the checks
-            let name = link.split('/').last().unwrap().replace('-', " ");
+            let slug = hit.url.split('/').last().unwrap().replace('-', " ");
+            let name = site_name(&hit.title_or(slug), &hit.url);
+            let link = hit.url.clone();
             // insert question content
             let client = self.client.clone();
             questions_content.push((
-                name,
+                SearchHit { title: name, ..hit },
                 tokio::task::spawn(async move {
                     Self::with_client(client).get_question_content(&link).await
                 }),
@@ -308,6 +441,74 @@ impl StackExchange {
         // return the Vec
         Ok(questions_content)
     }
+
+    /// Get the content of StackExchange questions found on duckduckgo with the StackExchange
+    /// api, this only takes two requests per site: one for the questions and one for all of their
+    /// answers. The content is already there so the returned handles finish right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The StackExchange api client.
+    /// * `links` - The search hits of the questions, in the order they should be returned.
+    ///
+    /// # Errors
+    ///
+    /// * `ApiError` - error with getting results from the StackExchange api.
+    #[tracing::instrument(skip_all)]
+    async fn get_multiple_questions_content_api(
+        api: &StackExchangeApi,
+        links: &[SearchHit],
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<SeQuestion>)>, SeError> {
+        // group the questions by their site, keeping the order of the sites
+        let questions: Vec<(SiteQuestion, &SearchHit)> = links
+            .iter()
+            .filter_map(|hit| Some((stackexchange_api::question_from_url(&hit.url)?, hit)))
+            .collect();
+        let mut sites: Vec<(&str, Vec<u64>)> = Vec::new();
+        for ((site, id), _) in &questions {
+            match sites.iter_mut().find(|(other, _)| other == site) {
+                Some((_, ids)) => ids.push(*id),
+                None => sites.push((site, vec![*id])),
+            }
+        }
+
+        // get the threads of every site
+        let mut threads: Vec<(SiteQuestion, TitledThread)> = Vec::with_capacity(questions.len());
+        for (site, ids) in sites {
+            let site_questions = match api.get_questions(site, &ids).await {
+                Ok(questions) => questions,
+                Err(error) => return Err(SeError::ApiError(error)),
+            };
+            let site_threads = match api.get_threads(site, &site_questions).await {
+                Ok(threads) => threads,
+                Err(error) => return Err(SeError::ApiError(error)),
+            };
+            threads.extend(
+                site_questions
+                    .iter()
+                    .map(|question| (site.to_string(), question.question_id))
+                    .zip(site_threads),
+            );
+        }
+
+        // return them in the order of the links
+        Ok(questions
+            .iter()
+            .filter_map(|(question, hit)| {
+                let index = threads.iter().position(|(other, _)| other == question)?;
+                let (title, mut content) = threads.swap_remove(index).1;
+                content.metadata.site =
+                    se_sites::find(&question.0).map(|site| site.title.to_string());
+                Some((
+                    SearchHit {
+                        title,
+                        ..(*hit).clone()
+                    },
+                    tokio::task::spawn(async move { Ok(content) }),
+                ))
+            })
+            .collect())
+    }
 }
 
 impl Default for StackExchange {
@@ -333,7 +534,7 @@ mod tests {
         let link =
             "https://codereview.stackexchange.com/questions/256345/n-dimensional-array-in-rust";
 
-        let question_content = &se.get_question_content(link).await.unwrap()[0];
+        let question_content = &se.get_question_content(link).await.unwrap().question;
 
         assert!(!question_content.is_empty())
     }
@@ -354,7 +555,7 @@ mod tests {
                 .unwrap();
 
             for q in question_content {
-                assert!(!q.1.await.unwrap().unwrap().is_empty())
+                assert!(!q.1.await.unwrap().unwrap().question.is_empty())
             }
         };
 
@@ -364,4 +565,32 @@ mod tests {
             .unwrap()
             .block_on(test)
     }
+
+    #[test]
+    fn test_question_site() {
+        let site = |url: &str| question_site(url).map(|site| site.name);
+
+        assert_eq!(
+            site("https://superuser.com/questions/1/title"),
+            Some("superuser")
+        );
+        assert_eq!(
+            site("https://unix.stackexchange.com/questions/2/title"),
+            Some("unix")
+        );
+        assert_eq!(
+            site("https://unix.stackexchange.com/questions/tagged/bash"),
+            None
+        );
+        assert_eq!(site("https://unix.stackexchange.com/users/3/name"), None);
+        assert_eq!(site("https://example.com/questions/4/title"), None);
+    }
+
+    #[test]
+    fn test_site_name() {
+        assert_eq!(
+            site_name("list files", "https://askubuntu.com/questions/1/list-files"),
+            "list files [Ask Ubuntu]"
+        );
+    }
 }
@@ -0,0 +1,944 @@
+use super::metadata::Metadata;
+use super::thread::{Answer, Comment, Thread};
+use super::util;
+use serde::Deserialize;
+use std::sync::{Arc, RwLock};
+use thiserror::Error;
+
+const API_ADDRESS: &str = "https://api.stackexchange.com/2.3";
+const API_FILTER: &str = "withbody";
+const QUESTION_PATH: &str = "/questions/";
+const POSTS_PATH: &str = "/posts/";
+// the api allows at most 100 ids per request and 100 items per page
+const MAX_PAGE_SIZE: usize = 100;
+// every page costs a request of the quota, so stop paging after this many
+const MAX_PAGES: usize = 10;
+// warn when less than this percentage of the daily quota is left
+const LOW_QUOTA_PERCENT: u32 = 10;
+// wide enough for a title to never wrap
+const TITLE_WIDTH: usize = 1000;
+
+/// The type of errors the StackExchange api can return.
+///
+/// * `NotQuestionUrl` - The given url is not the url of a question on a StackExchange site.
+/// * `InvalidRequest` - Reqwest returned an error when processing the request. This can be
+/// due to rate limiting, bad internet etc.
+/// * `InvalidResponseBody` - The response content you got back is corrupted, usually bad
+/// internet.
+/// * `ApiError` - The api returned an error, like an invalid key or the quota being exceeded.
+/// * `ErrorCode` - The api returned an error code without saying why.
+/// * `NoResults` - The api didn't return anything for the given query or question.
+#[derive(Error, Debug)]
+pub enum SeApiError {
+    #[error("The given url: {0} is not a StackExchange question.")]
+    NotQuestionUrl(String),
+    #[error("Failed to make a request to the StackExchange api: {0}")]
+    InvalidRequest(reqwest::Error),
+    #[error("A request has been successfully made, but there was an error getting the response body: {0}")]
+    InvalidResponseBody(reqwest::Error),
+    #[error("The StackExchange api returned the error {id} ({name}): {message}")]
+    ApiError {
+        id: u32,
+        name: String,
+        message: String,
+    },
+    #[error("The request was successful, but the response wasn't 200 OK, it was: {0}")]
+    ErrorCode(reqwest::StatusCode),
+    #[error("The StackExchange api didn't return any results.")]
+    NoResults,
+}
+
+/// How much of the daily quota of requests to the api is left. The quota is per ip, 300 requests
+/// a day without an app key and 10,000 with one.
+///
+/// * `remaining` - how many requests are left for today.
+/// * `max` - how many requests are allowed in a day.
+/// * `backoff` - if set, how many seconds to wait before making the same request again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+    pub remaining: u32,
+    pub max: u32,
+    pub backoff: Option<u32>,
+}
+
+/// A question as returned by the api.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApiQuestion {
+    pub question_id: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub answer_count: u64,
+    pub score: i64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub accepted_answer_id: Option<u64>,
+    pub creation_date: i64,
+}
+
+/// An answer as returned by the api.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApiAnswer {
+    pub answer_id: u64,
+    pub question_id: u64,
+    #[serde(default)]
+    pub body: String,
+    pub score: i64,
+    pub is_accepted: bool,
+    pub creation_date: i64,
+}
+
+/// A comment on a question or an answer as returned by the api.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ApiComment {
+    pub post_id: u64,
+    #[serde(default)]
+    pub body: String,
+    pub score: i64,
+}
+
+/// The wrapper every response of the api comes in.
+#[derive(Deserialize, Debug)]
+struct ApiWrapper<T> {
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+    #[serde(default)]
+    has_more: bool,
+    quota_remaining: Option<u32>,
+    quota_max: Option<u32>,
+    backoff: Option<u32>,
+    error_id: Option<u32>,
+    error_name: Option<String>,
+    error_message: Option<String>,
+}
+
+/// Get questions and answers from the official StackExchange api instead of scraping the sites.
+/// Every StackExchange site is supported, including StackOverflow.
+#[derive(std::fmt::Debug, Clone)]
+pub struct StackExchangeApi {
+    client: reqwest::Client,
+    key: Option<String>,
+    endpoint: String,
+    quota: Arc<RwLock<Option<Quota>>>,
+}
+
+/// Get the site and the id of a question out of its url.
+/// (ex: https://unix.stackexchange.com/questions/123/title -> ("unix.stackexchange.com", 123))
+///
+/// # Arguments
+///
+/// * `question_url` - The absolute url to the question.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::stackexchange_api;
+///
+/// assert_eq!(
+///     stackexchange_api::question_from_url("https://stackoverflow.com/questions/17490716/lifetimes-in-rust"),
+///     Some(("stackoverflow.com".to_string(), 17490716))
+/// );
+/// ```
+pub fn question_from_url(question_url: &str) -> Option<(String, u64)> {
+    let url = url::Url::parse(question_url).ok()?;
+    let site = url.host_str()?.to_string();
+    let id = url
+        .path()
+        .strip_prefix(QUESTION_PATH)?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()?;
+
+    Some((site, id))
+}
+
+/// Create a thread out of a question and its answers, the same way the scrapers return it: the
+/// question and the answers as text, with the score, date, accepted mark and comments of the
+/// answers, and the metadata of the question.
+///
+/// # Arguments
+///
+/// * `question` - The question.
+/// * `answers` - The answers, any answer that's not for this question is ignored.
+/// * `comments` - The comments, any comment that's not on this question or its answers is ignored.
+/// * `term_width` - The width of your terminal in order to properly display.
+pub fn thread_content(
+    question: &ApiQuestion,
+    answers: &[ApiAnswer],
+    comments: &[ApiComment],
+    term_width: usize,
+) -> Thread {
+    let post_comments = |post_id: u64| -> Vec<Comment> {
+        comments
+            .iter()
+            .filter(|comment| comment.post_id == post_id)
+            .map(|comment| Comment {
+                content: util::html_to_text(&comment.body, term_width)
+                    .trim()
+                    .to_string(),
+                score: Some(comment.score),
+            })
+            .collect()
+    };
+
+    let mut thread_answers = Vec::with_capacity(question.answer_count as usize);
+    thread_answers.extend(
+        answers
+            .iter()
+            .filter(|answer| answer.question_id == question.question_id)
+            .map(|answer| Answer {
+                id: Some(answer.answer_id),
+                content: util::html_to_text(&answer.body, term_width),
+                score: Some(answer.score),
+                is_accepted: answer.is_accepted,
+                date: Some(answer.creation_date),
+                comments: post_comments(answer.answer_id),
+            }),
+    );
+
+    Thread {
+        id: Some(question.question_id),
+        question: util::html_to_text(&question.body, term_width),
+        comments: post_comments(question.question_id),
+        answers: thread_answers,
+        metadata: Metadata {
+            title: Some(decode_title(&question.title)),
+            score: Some(question.score),
+            answer_count: Some(question.answer_count as usize),
+            is_answered: question.accepted_answer_id.is_some(),
+            tags: question.tags.clone(),
+            date: Some(question.creation_date),
+            ..Default::default()
+        },
+        hidden_comments: Vec::new(),
+    }
+}
+
+/// Decode the html entities the api leaves in the titles. (ex: &#39; -> ')
+///
+/// # Arguments
+///
+/// * `title` - The title as returned by the api.
+pub fn decode_title(title: &str) -> String {
+    util::html_to_text(title, TITLE_WIDTH).trim().to_string()
+}
+
+impl StackExchangeApi {
+    /// Create a new StackExchangeApi instance with a custom client that generates UA and without
+    /// an app key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::stackexchange_api;
+    ///
+    /// let api = stackexchange_api::StackExchangeApi::new();
+    /// ```
+    pub fn new() -> Self {
+        Self::with_client(util::client_with_special_settings(), None)
+    }
+
+    /// Create a new StackExchangeApi instance with a provided client and an optional app key.
+    /// The key isn't secret, it only raises the daily quota from 300 to 10,000 requests, you can
+    /// get one by registering an app on stackapps.com.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to make the requests with.
+    /// * `key` - Optional, the app key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::stackexchange_api;
+    ///
+    /// let api = stackexchange_api::StackExchangeApi::with_client(reqwest::Client::new(), Some("key".to_string()));
+    /// ```
+    pub fn with_client(client: reqwest::Client, key: Option<String>) -> Self {
+        Self {
+            client,
+            key,
+            endpoint: API_ADDRESS.to_string(),
+            quota: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Get the quota reported by the latest response of the api, it's shared between the clones
+    /// of this instance. None if no request has been made yet.
+    pub fn quota(&self) -> Option<Quota> {
+        match self.quota.read() {
+            Ok(quota) => *quota,
+            Err(_) => None,
+        }
+    }
+
+    /// Make a get request to the api and return the items of the first page of the response, the
+    /// quota is updated from the response.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the api method. (ex: /search/advanced)
+    /// * `params` - The parameters of the method, the site, filter and key are added to them.
+    /// * `site` - The site to make the request for.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    async fn get<T>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+        site: &str,
+    ) -> Result<Vec<T>, SeApiError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        Ok(self.get_page(path, params, site).await?.items)
+    }
+
+    /// Make get requests to the api for every page of the response, until the api says it has
+    /// no more items or `MAX_PAGES` pages were requested, and return the items of all of them.
+    /// The back off the api asks for is waited between the pages.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the api method. (ex: /questions/1;2/answers)
+    /// * `params` - The parameters of the method, the page, site, filter and key are added to
+    /// them.
+    /// * `site` - The site to make the requests for.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    #[tracing::instrument(skip_all)]
+    async fn get_all<T>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+        site: &str,
+    ) -> Result<Vec<T>, SeApiError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut items = Vec::new();
+        for page in 1..=MAX_PAGES {
+            let mut page_params = params.to_vec();
+            page_params.push(("page", page.to_string()));
+
+            let wrapper = self.get_page::<T>(path, &page_params, site).await?;
+            items.extend(wrapper.items);
+            if !wrapper.has_more {
+                return Ok(items);
+            }
+            if let Some(backoff) = wrapper.backoff {
+                tokio::time::sleep(std::time::Duration::from_secs(backoff.into())).await;
+            }
+        }
+
+        tracing::warn!(
+            "Stopped getting {} after {} pages, the api still has more.",
+            path,
+            MAX_PAGES
+        );
+        Ok(items)
+    }
+
+    /// Make a get request to the api and return the response, the quota is updated from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the api method. (ex: /search/advanced)
+    /// * `params` - The parameters of the method, the site, filter and key are added to them.
+    /// * `site` - The site to make the request for.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    #[tracing::instrument(skip_all)]
+    async fn get_page<T>(
+        &self,
+        path: &str,
+        params: &[(&str, String)],
+        site: &str,
+    ) -> Result<ApiWrapper<T>, SeApiError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let mut query: Vec<(&str, String)> = params.to_vec();
+        query.push(("site", site.to_string()));
+        query.push(("filter", API_FILTER.to_string()));
+        if let Some(key) = &self.key {
+            query.push(("key", key.clone()));
+        }
+
+        let url = self.endpoint.clone() + path;
+        let response = match self.client.get(&url).query(&query).send().await {
+            Ok(res) => res,
+            Err(error) => {
+                tracing::error!(
+                    "Failed to make a get request to {}. Error: {}",
+                    &url,
+                    &error
+                );
+                return Err(SeApiError::InvalidRequest(error));
+            }
+        };
+
+        // the api also describes its errors in json, so parse the body no matter the status
+        let status = response.status();
+        let wrapper = match response.json::<ApiWrapper<T>>().await {
+            Ok(wrapper) => wrapper,
+            Err(error) => {
+                if status != reqwest::StatusCode::OK {
+                    tracing::error!("Get request to {} return status code: {}", &url, &status);
+                    return Err(SeApiError::ErrorCode(status));
+                }
+                tracing::error!(
+                    "The response body recieved from {} is invalid. Error: {}",
+                    &url,
+                    &error
+                );
+                return Err(SeApiError::InvalidResponseBody(error));
+            }
+        };
+
+        self.update_quota(&wrapper);
+
+        if let Some(id) = wrapper.error_id {
+            let name = wrapper.error_name.unwrap_or_default();
+            let message = wrapper.error_message.unwrap_or_default();
+            tracing::error!(
+                "The StackExchange api returned the error {} ({}): {}",
+                id,
+                &name,
+                &message
+            );
+            return Err(SeApiError::ApiError { id, name, message });
+        }
+        if status != reqwest::StatusCode::OK {
+            tracing::error!("Get request to {} return status code: {}", &url, &status);
+            return Err(SeApiError::ErrorCode(status));
+        }
+
+        Ok(wrapper)
+    }
+
+    /// Update the shared quota with the one reported in a response and warn if it's running low.
+    fn update_quota<T>(&self, wrapper: &ApiWrapper<T>) {
+        let (remaining, max) = match (wrapper.quota_remaining, wrapper.quota_max) {
+            (Some(remaining), Some(max)) => (remaining, max),
+            _ => return,
+        };
+        let quota = Quota {
+            remaining,
+            max,
+            backoff: wrapper.backoff,
+        };
+
+        tracing::info!("StackExchange api quota: {}/{}", remaining, max);
+        if remaining.saturating_mul(100) < max.saturating_mul(LOW_QUOTA_PERCENT) {
+            tracing::warn!(
+                "The StackExchange api quota is running low: {}/{}",
+                remaining,
+                max
+            );
+        }
+        if let Some(backoff) = wrapper.backoff {
+            tracing::warn!(
+                "The StackExchange api asked to back off for {} seconds.",
+                backoff
+            );
+        }
+
+        if let Ok(mut current) = self.quota.write() {
+            *current = Some(quota);
+        }
+    }
+
+    /// Search for questions on a site, ordered by relevance. The questions come with their body.
+    ///
+    /// # Arguments
+    ///
+    /// * `site` - The site to search on. (ex: stackoverflow or unix.stackexchange.com)
+    /// * `query` - The query to search for, the tags in brackets in it are the tags the questions
+    /// have to have. (ex: [rust] sort a vec)
+    /// * `limit` - Optional, how many questions to get, at most 100.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use falion::search::stackexchange_api;
+    ///
+    /// # async fn run() -> Result<(), stackexchange_api::SeApiError> {
+    /// let api = stackexchange_api::StackExchangeApi::new();
+    /// let questions = api.search("stackoverflow", "Rust threading", Some(5)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    /// * `NoResults` - No questions were found.
+    #[tracing::instrument(skip_all)]
+    pub async fn search(
+        &self,
+        site: &str,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<ApiQuestion>, SeApiError> {
+        tracing::info!(
+            "Search the StackExchange api on {} for: {} with a results limit of: {:#?}",
+            site,
+            query,
+            limit
+        );
+        let page_size = limit.unwrap_or(MAX_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let (query, tags) = split_tags(query);
+        let mut params = vec![
+            ("q", query),
+            ("order", "desc".to_string()),
+            ("sort", "relevance".to_string()),
+            ("pagesize", page_size.to_string()),
+        ];
+        if !tags.is_empty() {
+            params.push(("tagged", tags.join(";")));
+        }
+
+        let questions = self.get("/search/advanced", &params, site).await?;
+        if questions.is_empty() {
+            return Err(SeApiError::NoResults);
+        }
+
+        Ok(questions)
+    }
+
+    /// Get questions of a site by their ids, they come with their body.
+    ///
+    /// # Arguments
+    ///
+    /// * `site` - The site the questions are on. (ex: stackoverflow or unix.stackexchange.com)
+    /// * `ids` - The ids of the questions, at most 100.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    /// * `NoResults` - None of the questions were found.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_questions(
+        &self,
+        site: &str,
+        ids: &[u64],
+    ) -> Result<Vec<ApiQuestion>, SeApiError> {
+        let path = QUESTION_PATH.to_string() + &join_ids(ids);
+        let params = [("pagesize", MAX_PAGE_SIZE.to_string())];
+
+        let questions = self.get(&path, &params, site).await?;
+        if questions.is_empty() {
+            return Err(SeApiError::NoResults);
+        }
+
+        Ok(questions)
+    }
+
+    /// Get the answers to questions of a site by the ids of the questions, ordered by votes. The
+    /// answers come with their body, every page of them is requested so no question loses its
+    /// answers to the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `site` - The site the questions are on. (ex: stackoverflow or unix.stackexchange.com)
+    /// * `ids` - The ids of the questions, at most 100.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_answers(&self, site: &str, ids: &[u64]) -> Result<Vec<ApiAnswer>, SeApiError> {
+        let path = QUESTION_PATH.to_string() + &join_ids(ids) + "/answers";
+        let params = [
+            ("order", "desc".to_string()),
+            ("sort", "votes".to_string()),
+            ("pagesize", MAX_PAGE_SIZE.to_string()),
+        ];
+
+        self.get_all(&path, &params, site).await
+    }
+
+    /// Get the comments on the given posts, questions or answers, oldest first like the sites
+    /// show them. The ids are split in requests of 100, the most the api takes at once, and every
+    /// page of comments is requested for each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `site` - The site the posts are on. (ex: stackoverflow or unix.stackexchange.com)
+    /// * `ids` - The ids of the posts.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_comments(
+        &self,
+        site: &str,
+        ids: &[u64],
+    ) -> Result<Vec<ApiComment>, SeApiError> {
+        let params = [
+            ("order", "asc".to_string()),
+            ("sort", "creation".to_string()),
+            ("pagesize", MAX_PAGE_SIZE.to_string()),
+        ];
+        let requests = ids.chunks(MAX_PAGE_SIZE).map(|chunk| {
+            let path = POSTS_PATH.to_string() + &join_ids(chunk) + "/comments";
+            let params = &params;
+            async move { self.get_all::<ApiComment>(&path, params, site).await }
+        });
+
+        let mut comments = Vec::new();
+        for chunk in futures::future::join_all(requests).await {
+            comments.extend(chunk?);
+        }
+
+        Ok(comments)
+    }
+
+    /// Get the comments on a question and its answers, comments being extra an error only gets
+    /// logged and no comments are returned.
+    async fn get_thread_comments(
+        &self,
+        site: &str,
+        questions: &[u64],
+        answers: &[ApiAnswer],
+    ) -> Vec<ApiComment> {
+        let mut ids = questions.to_vec();
+        ids.extend(answers.iter().map(|answer| answer.answer_id));
+
+        match self.get_comments(site, &ids).await {
+            Ok(comments) => comments,
+            Err(error) => {
+                tracing::warn!("Failed to get the comments from the api. Error: {}", error);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Get the answers of the given questions, every page of them in the same requests, and
+    /// create their threads: the question and its answers, as text.
+    ///
+    /// # Arguments
+    ///
+    /// * `site` - The site the questions are on. (ex: stackoverflow or unix.stackexchange.com)
+    /// * `questions` - The questions, with their body, at most 100.
+    ///
+    /// # Returns
+    ///
+    /// The decoded title and the thread of every question, in the given order.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_threads(
+        &self,
+        site: &str,
+        questions: &[ApiQuestion],
+    ) -> Result<Vec<(String, Thread)>, SeApiError> {
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+        let ids: Vec<u64> = questions
+            .iter()
+            .map(|question| question.question_id)
+            .collect();
+
+        let answers = self.get_answers(site, &ids).await?;
+        let comments = self.get_thread_comments(site, &ids, &answers).await;
+
+        Ok(questions
+            .iter()
+            .map(|question| {
+                (
+                    decode_title(&question.title),
+                    thread_content(question, &answers, &comments, term_width),
+                )
+            })
+            .collect())
+    }
+
+    /// Get the thread of a question by its url: the question itself and its answers.
+    ///
+    /// # Arguments
+    ///
+    /// * `question_url` - The absolute url to the question on any StackExchange site.
+    ///
+    /// # Errors
+    ///
+    /// * `NotQuestionUrl` - The given url is not the url of a question.
+    /// * `InvalidRequest` - Reqwest returned an error when processing the request.
+    /// * `InvalidResponseBody` - The response content you got back is corrupted.
+    /// * `ApiError` - The api returned an error.
+    /// * `ErrorCode` - The api returned an error code without saying why.
+    /// * `NoResults` - The question wasn't found.
+    #[tracing::instrument(skip_all)]
+    pub async fn get_question_content(&self, question_url: &str) -> Result<Thread, SeApiError> {
+        let term_width: usize = match crossterm::terminal::size() {
+            Ok(size) => size.0.into(),
+            Err(_) => 100,
+        };
+        let (site, id) = match question_from_url(question_url) {
+            Some(question) => question,
+            None => return Err(SeApiError::NotQuestionUrl(question_url.to_string())),
+        };
+
+        let questions = self.get_questions(&site, &[id]).await?;
+        let answers = self.get_answers(&site, &[id]).await?;
+        let comments = self.get_thread_comments(&site, &[id], &answers).await;
+
+        Ok(thread_content(
+            &questions[0],
+            &answers,
+            &comments,
+            term_width,
+        ))
+    }
+}
+
+impl Default for StackExchangeApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split the tags in brackets out of a query, the api takes them on their own.
+/// (ex: [rust] sort a vec -> sort a vec and rust)
+///
+/// # Arguments
+///
+/// * `query` - The query, with the tags anywhere in it.
+fn split_tags(query: &str) -> (String, Vec<&str>) {
+    let (tags, words): (Vec<&str>, Vec<&str>) = query
+        .split_whitespace()
+        .partition(|word| word.len() > 2 && word.starts_with('[') && word.ends_with(']'));
+
+    (
+        words.join(" "),
+        tags.iter()
+            .map(|tag| tag.trim_start_matches('[').trim_end_matches(']'))
+            .collect(),
+    )
+}
+
+/// Join ids the way the api expects them in a path. (ex: 1;2;3)
+fn join_ids(ids: &[u64]) -> String {
+    ids.iter()
+        .take(MAX_PAGE_SIZE)
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    const ANSWERS_PAGE_1: &str = r#"{"items":[{"answer_id":2,"question_id":1,"body":"<p>top</p>","score":9,"is_accepted":true,"creation_date":0}],"has_more":true,"quota_max":300,"quota_remaining":298}"#;
+    const ANSWERS_PAGE_2: &str = r#"{"items":[{"answer_id":3,"question_id":4,"body":"<p>other</p>","score":1,"is_accepted":false,"creation_date":0}],"has_more":false,"quota_max":300,"quota_remaining":297}"#;
+    const COMMENTS_PAGE_1: &str =
+        r#"{"items":[{"post_id":1,"body":"first","score":0}],"has_more":true}"#;
+    const COMMENTS_PAGE_2: &str =
+        r#"{"items":[{"post_id":2,"body":"last","score":2}],"has_more":false}"#;
+
+    /// Serve the responses of the api on a local port, every request gets the response of the
+    /// first route its path and query contain. The paths and queries of the requests are kept.
+    async fn serve(
+        routes: Vec<(&'static str, &'static str)>,
+    ) -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let requests_clone = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = vec![0; 4096];
+                let read = stream.read(&mut buffer).await.unwrap_or_default();
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                requests_clone.lock().unwrap().push(path.to_string());
+                let body = routes
+                    .iter()
+                    .find(|(route, _)| path.contains(route))
+                    .map(|(_, body)| *body)
+                    .unwrap_or("{}");
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        (format!("http://{}/2.3", address), requests)
+    }
+
+    #[test]
+    fn test_question_from_url() {
+        assert_eq!(
+            question_from_url("https://unix.stackexchange.com/questions/123/some-title"),
+            Some(("unix.stackexchange.com".to_string(), 123))
+        );
+        assert_eq!(
+            question_from_url("https://stackoverflow.com/questions/tagged/rust"),
+            None
+        );
+        assert_eq!(question_from_url("not a url"), None);
+    }
+
+    #[test]
+    fn test_split_tags() {
+        assert_eq!(
+            split_tags("[rust] sort a  vec [vec]"),
+            ("sort a vec".to_string(), vec!["rust", "vec"])
+        );
+        assert_eq!(
+            split_tags("array [] index"),
+            ("array [] index".to_string(), Vec::<&str>::new())
+        );
+    }
+
+    #[test]
+    fn test_parse_api_response() {
+        let body = r#"{"items":[{"tags":["rust"],"owner":{"display_name":"someone"},"is_answered":true,"view_count":10,"accepted_answer_id":2,"answer_count":1,"score":5,"last_activity_date":1700000000,"creation_date":1600000000,"question_id":1,"link":"https://stackoverflow.com/questions/1/title","title":"Isn&#39;t it","body":"<p>question</p>"}],"has_more":false,"quota_max":300,"quota_remaining":299}"#;
+        let wrapper: ApiWrapper<ApiQuestion> = serde_json::from_str(body).unwrap();
+        let api = StackExchangeApi::with_client(reqwest::Client::new(), None);
+        api.update_quota(&wrapper);
+
+        assert_eq!(wrapper.items[0].question_id, 1);
+        assert_eq!(decode_title(&wrapper.items[0].title), "Isn't it");
+        assert_eq!(
+            api.quota(),
+            Some(Quota {
+                remaining: 299,
+                max: 300,
+                backoff: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_api_error() {
+        let body = r#"{"error_id":502,"error_message":"too many requests from this IP","error_name":"throttle_violation"}"#;
+        let wrapper: ApiWrapper<ApiAnswer> = serde_json::from_str(body).unwrap();
+
+        assert!(wrapper.items.is_empty());
+        assert_eq!(wrapper.error_id, Some(502));
+    }
+
+    #[tokio::test]
+    async fn test_get_answers_pages() {
+        let page: ApiWrapper<ApiAnswer> = serde_json::from_str(ANSWERS_PAGE_1).unwrap();
+        assert!(page.has_more);
+
+        let (endpoint, requests) =
+            serve(vec![("page=1", ANSWERS_PAGE_1), ("page=2", ANSWERS_PAGE_2)]).await;
+        let mut api = StackExchangeApi::with_client(reqwest::Client::new(), None);
+        api.endpoint = endpoint;
+
+        let answers = api.get_answers("stackoverflow", &[1, 4]).await.unwrap();
+        let ids: Vec<u64> = answers.iter().map(|answer| answer.answer_id).collect();
+        assert_eq!(ids, [2, 3]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with("/2.3/questions/1;4/answers?"));
+        assert!(requests[1].contains("page=2"));
+        assert_eq!(api.quota().map(|quota| quota.remaining), Some(297));
+    }
+
+    #[tokio::test]
+    async fn test_get_comments_pages() {
+        let (endpoint, requests) = serve(vec![
+            ("page=1", COMMENTS_PAGE_1),
+            ("page=2", COMMENTS_PAGE_2),
+        ])
+        .await;
+        let mut api = StackExchangeApi::with_client(reqwest::Client::new(), None);
+        api.endpoint = endpoint;
+
+        let comments = api.get_comments("stackoverflow", &[1, 2]).await.unwrap();
+        let posts: Vec<u64> = comments.iter().map(|comment| comment.post_id).collect();
+        assert_eq!(posts, [1, 2]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1].starts_with("/2.3/posts/1;2/comments?"));
+        assert!(requests[1].contains("page=2"));
+    }
+
+    #[test]
+    fn test_thread_content() {
+        let question: ApiQuestion = serde_json::from_str(r#"{"question_id":1,"title":"t","body":"<p>question</p>","score":1,"tags":["rust"],"answer_count":2,"accepted_answer_id":null,"creation_date":0,"link":"l"}"#).unwrap();
+        let answers: Vec<ApiAnswer> = serde_json::from_str(r#"[{"answer_id":2,"question_id":1,"body":"<p>answer</p>","score":3,"is_accepted":false,"creation_date":0},{"answer_id":3,"question_id":9,"body":"<p>other</p>","score":3,"is_accepted":false,"creation_date":0}]"#).unwrap();
+
+        let comments: Vec<ApiComment> = serde_json::from_str(r#"[{"post_id":1,"body":"<code>Rc</code>?","score":0},{"post_id":2,"body":"thanks","score":4},{"post_id":3,"body":"other","score":1}]"#).unwrap();
+
+        assert_eq!(
+            thread_content(&question, &answers, &comments, 50),
+            Thread {
+                id: Some(1),
+                question: "question\n".to_string(),
+                comments: vec![Comment {
+                    content: "`Rc`?".to_string(),
+                    score: Some(0),
+                }],
+                answers: vec![Answer {
+                    id: Some(2),
+                    content: "answer\n".to_string(),
+                    score: Some(3),
+                    is_accepted: false,
+                    date: Some(0),
+                    comments: vec![Comment {
+                        content: "thanks".to_string(),
+                        score: Some(4),
+                    }],
+                }],
+                metadata: Metadata {
+                    title: Some("t".to_string()),
+                    score: Some(1),
+                    answer_count: Some(2),
+                    tags: vec!["rust".to_string()],
+                    date: Some(0),
+                    ..Default::default()
+                },
+                hidden_comments: Vec::new(),
+            }
+        );
+    }
+}
@@ -1,16 +1,19 @@
+use super::engine::{Engines, SearchHit};
+use super::lang::Lang;
+use super::stackexchange_api::{self, StackExchangeApi};
+use super::thread::Thread;
 use super::{ddg, util};
 use thiserror::Error;
 
-const QUESTION_SEP: &str = "<div class=\"s-prose js-post-body\" itemprop=\"text\">";
-const QUESTION_END: &str = "</div>";
 const STACKOVERFLOW_QUESTION_URL: &str = "https://stackoverflow.com/questions/";
 const STACKOVERFLOW_SITE: &str = "stackoverflow.com/questions/";
+const STACKOVERFLOW_API_SITE: &str = "stackoverflow";
 const STACKOVERFLOW_INVALID: [&str; 2] = [
     "https://stackoverflow.com/questions/tagged",
     "https://stackoverflow.com/questions/tagged",
 ];
 
-type SofQuestion = Result<Vec<String>, SofError>;
+type SofQuestion = Result<Thread, SofError>;
 
 /// These are the errors the functions associated with StackOverflow will return.
 ///
@@ -23,6 +26,8 @@ type SofQuestion = Result<Vec<String>, SofError>;
 /// corrupted because it did return 200 OK.
 /// * `ErrorCode` - The website returned an error code
 /// * `DdgError` - error with getting results from DuckDuckGO. (ddg::DdgError)
+/// * `ApiError` - error with getting results from the StackExchange api.
+/// (stackexchange_api::SeApiError)
 #[derive(Error, Debug)]
 pub enum SofError {
     #[error("The given page: {0} is not a valid StackOverflow page this function can scrape.")]
@@ -37,6 +42,8 @@ pub enum SofError {
     ErrorCode(reqwest::StatusCode),
     #[error("There was an error retrieving search results from duckduckgo: {0}")]
     DdgError(ddg::DdgError),
+    #[error("There was an error retrieving results from the StackExchange api: {0}")]
+    ApiError(stackexchange_api::SeApiError),
 }
 
 /// Scrape questions from StackOverflow
@@ -44,6 +51,8 @@ pub enum SofError {
 pub struct StackOverflow {
     client: reqwest::Client,
     ddg: ddg::Ddg,
+    api: Option<StackExchangeApi>,
+    lang: Option<&'static Lang>,
 }
 
 impl StackOverflow {
@@ -61,6 +70,8 @@ impl StackOverflow {
         Self {
             client: util::client_with_special_settings(),
             ddg: ddg::Ddg::new(),
+            api: None,
+            lang: None,
         }
     }
 
@@ -76,11 +87,68 @@ impl StackOverflow {
         Self {
             client: client.clone(),
             ddg: ddg::Ddg::with_client(client),
+            api: None,
+            lang: None,
         }
     }
 
-    /// Get the contents of a StackOverflow question inside a vector, the first item being the
-    /// question itself and the rest the answers.
+    /// Create a new StackOverflow instance that gets the questions from the official StackExchange
+    /// api instead of scraping them, this doesn't break when the site's markup changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to make the requests with.
+    /// * `api` - The StackExchange api client, it holds the app key and the quota.
+    ///
+    /// ```
+    /// use falion::search::stackoverflow;
+    /// use falion::search::stackexchange_api;
+    ///
+    /// let client = reqwest::Client::new();
+    /// let api = stackexchange_api::StackExchangeApi::with_client(client.clone(), None);
+    /// let sof = stackoverflow::StackOverflow::with_api(client, api);
+    /// ```
+    pub fn with_api(client: reqwest::Client, api: StackExchangeApi) -> Self {
+        Self {
+            client: client.clone(),
+            ddg: ddg::Ddg::with_client(client),
+            api: Some(api),
+            lang: None,
+        }
+    }
+
+    /// Find the results with the given search engines instead of the default ones.
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.ddg = self.ddg.with_engines(engines);
+        self
+    }
+
+    /// Search with the given options, like only getting results from the last year.
+    pub fn with_options(mut self, options: ddg::DdgQuery) -> Self {
+        self.ddg = self.ddg.with_options(options);
+        self
+    }
+
+    /// Only search for the questions tagged with a language.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language the questions have to be tagged with, they can be about any
+    /// language if None.
+    ///
+    /// ```
+    /// use falion::search::lang;
+    /// use falion::search::stackoverflow;
+    ///
+    /// let source = stackoverflow::StackOverflow::new().with_lang(lang::find("rust"));
+    /// ```
+    pub fn with_lang(mut self, lang: Option<&'static Lang>) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    /// Get the contents of a StackOverflow question as a thread: the question itself and its
+    /// answers, with their score, date and if they're accepted.
     ///
     /// # Arguments
     ///
@@ -101,7 +169,7 @@ impl StackOverflow {
     /// # async fn run() -> Result<(), stackoverflow::SofError> {
     /// let ddg = ddg::Ddg::new();
     /// let sof = stackoverflow::StackOverflow::new();
-    /// let link = &ddg.get_links("Rust threading", Some("stackoverflow.com/questions/"), Some(false), Some(&STACKOVERFLOW_INVALID), Some(1)).await.unwrap()[0];
+    /// let link = &ddg.get_links("Rust threading", Some("stackoverflow.com/questions/"), Some(false), Some(&STACKOVERFLOW_INVALID), Some(1)).await.unwrap()[0].url;
     ///
     /// let question_content = sof.get_question_content(&link).await.unwrap();
     /// # Ok(())
@@ -162,6 +230,14 @@ impl StackOverflow {
             }
         }
 
+        // get the question from the api if it's used instead of scraping
+        if let Some(api) = &self.api {
+            return match api.get_question_content(question_url).await {
+                Ok(content) => Ok(content),
+                Err(error) => Err(SofError::ApiError(error)),
+            };
+        }
+
         // get stackoverflow page
         let response_body = match self.client.get(question_url).send().await {
             Ok(res) => {
@@ -196,27 +272,25 @@ impl StackOverflow {
             }
         };
 
-        // parse the page to get the question and answers
-        let question_content = response_body
-            .split(QUESTION_SEP)
-            .skip(1)
-            .filter_map(|q| {
-                q.split_once(QUESTION_END).map(|q_sep| {
-                    let html = q_sep.0;
-                    util::html_to_text(html, term_width)
-                })
-            })
-            .collect::<Vec<String>>();
-
-        // check if page data was valid and we parsed something
-        if question_content.is_empty() {
-            tracing::error!(
-                "The stackoverflow question ({}) content is empty. Response body: {}",
-                &question_url,
-                &response_body
-            );
-            return Err(SofError::InvalidQuestionContent);
-        }
+        // parse the page to get the question and answers, check if page data was valid and we
+        // parsed something
+        let mut question_content = match Thread::from_stackexchange_page(&response_body, term_width)
+        {
+            Some(thread) => thread,
+            None => {
+                tracing::error!(
+                    "The stackoverflow question ({}) content is empty. Response body: {}",
+                    &question_url,
+                    &response_body
+                );
+                return Err(SofError::InvalidQuestionContent);
+            }
+        };
+
+        // the page only has the first few comments of each post, get the rest like the page does
+        question_content
+            .load_hidden_comments(&self.client, question_url, term_width)
+            .await;
 
         // return question and aswers content
         Ok(question_content)
@@ -250,7 +324,7 @@ impl StackOverflow {
     ///     .unwrap();
     ///
     /// for q in question_content {
-    ///    assert!(!q.1.await.unwrap().unwrap().is_empty())
+    ///    assert!(!q.1.await.unwrap().unwrap().question.is_empty())
     /// }
     /// # Ok(())
     /// # }
@@ -269,13 +343,24 @@ impl StackOverflow {
         &self,
         query: &str,
         limit: Option<usize>,
-    ) -> Result<Vec<(String, tokio::task::JoinHandle<SofQuestion>)>, SofError> {
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<SofQuestion>)>, SofError> {
         tracing::info!("Get multiple StackOverflow questions and their content for search query: {} with a results limit of: {:#?}", &query, &limit);
+        // tag the query with the language, if any
+        let query = match self.lang {
+            Some(lang) => lang.tagged(query),
+            None => query.to_string(),
+        };
+
+        // search with the api if it's used instead of duckduckgo and scraping
+        if let Some(api) = &self.api {
+            return Self::get_multiple_questions_content_api(api, &query, limit).await;
+        }
+
         // get the links from duckduckgo
         let links = match self
             .ddg
             .get_links(
-                query,
+                &query,
                 Some(STACKOVERFLOW_SITE),
                 Some(false),
                 Some(&STACKOVERFLOW_INVALID),
@@ -293,13 +378,14 @@ impl StackOverflow {
         // start looping through the links associating the question title and the joinhandle for
         // the future the scrapes the content of the question by inserting them togheter in the
         // Vec inside a tuple
-        for link in links {
+        for hit in links {
             // unwrap is safe here since ddg does all the checks
-            let name = link.split('/').last().unwrap().replace('-', " ");
+            let name = hit.title_or(hit.url.split('/').last().unwrap().replace('-', " "));
+            let link = hit.url.clone();
             // insert content
             let client = self.client.clone();
             questions_content.push((
-                name,
+                SearchHit { title: name, ..hit },
                 tokio::task::spawn(async move {
                     Self::with_client(client).get_question_content(&link).await
                 }),
@@ -309,6 +395,48 @@ impl StackOverflow {
         // return the Vec
         Ok(questions_content)
     }
+
+    /// Search for StackOverflow questions with the StackExchange api and get their content, this
+    /// only takes two requests: one for the questions and one for all of their answers. The
+    /// content is already there so the returned handles finish right away.
+    ///
+    /// # Arguments
+    ///
+    /// * `api` - The StackExchange api client.
+    /// * `query` - The query to search for.
+    /// * `limit` - Optional, how many questions to get, at most 100.
+    ///
+    /// # Errors
+    ///
+    /// * `ApiError` - error with getting results from the StackExchange api.
+    #[tracing::instrument(skip_all)]
+    async fn get_multiple_questions_content_api(
+        api: &StackExchangeApi,
+        query: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(SearchHit, tokio::task::JoinHandle<SofQuestion>)>, SofError> {
+        let questions = match api.search(STACKOVERFLOW_API_SITE, query, limit).await {
+            Ok(questions) => questions,
+            Err(error) => return Err(SofError::ApiError(error)),
+        };
+        let threads = match api.get_threads(STACKOVERFLOW_API_SITE, &questions).await {
+            Ok(threads) => threads,
+            Err(error) => return Err(SofError::ApiError(error)),
+        };
+
+        Ok(questions
+            .iter()
+            .zip(threads)
+            .enumerate()
+            .map(|(index, (question, (title, content)))| {
+                let url = format!("{}{}", STACKOVERFLOW_QUESTION_URL, question.question_id);
+                (
+                    SearchHit::titled(&url, &title, index + 1),
+                    tokio::task::spawn(async move { Ok(content) }),
+                )
+            })
+            .collect())
+    }
 }
 
 impl Default for StackOverflow {
@@ -333,7 +461,7 @@ mod tests {
 
         let link = "https://stackoverflow.com/questions/17490716/lifetimes-in-rust";
 
-        let question_content = &sof.get_question_content(link).await.unwrap()[0];
+        let question_content = &sof.get_question_content(link).await.unwrap().question;
 
         assert!(!question_content.is_empty())
     }
@@ -355,7 +483,7 @@ mod tests {
                 .unwrap();
 
             for q in question_content {
-                assert!(!q.1.await.unwrap().unwrap().is_empty())
+                assert!(!q.1.await.unwrap().unwrap().question.is_empty())
             }
         };
         tokio::runtime::Builder::new_current_thread()
@@ -0,0 +1,641 @@
+use super::cargo_lock::CargoLock;
+use super::metadata::{self, Metadata};
+use super::stackexchange_api;
+use super::util;
+use serde::Deserialize;
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+const POST_SEP: &str = "<div class=\"s-prose js-post-body\" itemprop=\"text\">";
+const POST_END: &str = "</div>";
+const ANSWER_SEP: &str = "<div id=\"answer-";
+const ANSWER_SCORE: &str = "data-score=\"";
+const ANSWER_ACCEPTED: [&str; 2] = ["accepted-answer", "itemprop=\"acceptedAnswer\""];
+const POST_DATE: &str = "itemprop=\"dateCreated\" datetime=\"";
+const QUESTION_TITLE: &str = "class=\"question-hyperlink\">";
+const QUESTION_HEADER: &str = "js-question\"";
+const QUESTION_TAG: &str = "href=\"/questions/tagged/";
+const ANSWER_COUNT: &str = "data-answercount=\"";
+const QUESTION_ID: &str = "data-questionid=\"";
+const ANSWER_ID: &str = "data-answerid=\"";
+const COMMENT_SEP: &str = "class=\"comment js-comment";
+const COMMENT_SCORE: &str = "data-comment-score=\"";
+const COMMENT_BODY: &str = "<span class=\"comment-copy\">";
+const COMMENT_END: &str = "</span>";
+const COMMENTS_POST_ID: &str = "data-post-id=\"";
+const COMMENTS_HIDDEN: &str = "js-show-link comments-link";
+const COMMENTS_URL: &str = "{SITE}/posts/{ID}/comments";
+// how much a reply is indented for every level it's nested in
+const REPLY_INDENT: &str = "  ";
+// the narrowest a deeply nested reply is wrapped at
+const MIN_REPLY_WIDTH: usize = 20;
+
+/// A comment on a question or an answer.
+///
+/// * `content` - the comment as text.
+/// * `score` - the votes the comment got, if known.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Comment {
+    pub content: String,
+    pub score: Option<i64>,
+}
+
+impl Comment {
+    /// Create a comment out of a reply in a tree of replies that's flattened, like the comments
+    /// of reddit and hacker news. The reply is indented by how nested it is so it's still clear
+    /// what it replies to.
+    ///
+    /// # Arguments
+    ///
+    /// * `html` - The reply as html.
+    /// * `score` - The votes the reply got, if known.
+    /// * `depth` - How nested the reply is, 0 for the direct replies.
+    /// * `term_width` - The width of your terminal in order to properly display.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::thread::Comment;
+    ///
+    /// let comment = Comment::nested("<p>Agreed.</p>", Some(3), 1, 50);
+    ///
+    /// assert_eq!(comment.content, "  Agreed.");
+    /// ```
+    pub fn nested(html: &str, score: Option<i64>, depth: usize, term_width: usize) -> Comment {
+        let indent = REPLY_INDENT.repeat(depth);
+        let width = term_width.saturating_sub(indent.len()).max(MIN_REPLY_WIDTH);
+
+        Comment {
+            content: util::html_to_text(html, width)
+                .trim_end()
+                .lines()
+                .map(|line| format!("{}{}", indent, line))
+                .collect::<Vec<String>>()
+                .join("\n"),
+            score,
+        }
+    }
+}
+
+/// An answer to a question together with what's known about it. The score and the date are
+/// optional since not every source has them.
+///
+/// * `id` - the id of the answer on its site.
+/// * `content` - the answer as text.
+/// * `score` - the votes the answer got.
+/// * `is_accepted` - if the author of the question accepted this answer.
+/// * `date` - when the answer was posted, as a unix timestamp.
+/// * `comments` - the comments on the answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answer {
+    pub id: Option<u64>,
+    pub content: String,
+    pub score: Option<i64>,
+    pub is_accepted: bool,
+    pub date: Option<i64>,
+    pub comments: Vec<Comment>,
+}
+
+/// A question with its answers, in the order the page lists them.
+///
+/// * `id` - the id of the question on its site.
+/// * `question` - the question as text.
+/// * `comments` - the comments on the question.
+/// * `answers` - the answers.
+/// * `metadata` - the title, score, tags, date and answer count of the question.
+/// * `hidden_comments` - the ids of the posts that have more comments than the page shows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Thread {
+    pub id: Option<u64>,
+    pub question: String,
+    pub comments: Vec<Comment>,
+    pub answers: Vec<Answer>,
+    pub metadata: Metadata,
+    pub hidden_comments: Vec<u64>,
+}
+
+/// How the answers of a thread are ordered.
+///
+/// * `Page` - the order the page lists them in.
+/// * `Score` - highest score first.
+/// * `Recency` - newest first.
+/// * `AcceptedFirst` - the accepted answer first, then the order of the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AnswerSort {
+    #[default]
+    Page,
+    Score,
+    Recency,
+    AcceptedFirst,
+}
+
+/// How the answers of a thread are ordered and which of them are shown.
+///
+/// * `sort` - how the answers are ordered.
+/// * `min_score` - Optional, hide the answers with a lower score than this, answers without a
+/// score are always shown.
+/// * `limit` - Optional, show at most this many answers.
+/// * `cargo_lock` - Optional, the crates of the project the search is made from, the answers
+/// mentioning versions of them that aren't compatible with the pinned ones are flagged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerOptions {
+    pub sort: AnswerSort,
+    pub min_score: Option<i64>,
+    pub limit: Option<usize>,
+    pub cargo_lock: Option<Arc<CargoLock>>,
+}
+
+impl AnswerSort {
+    /// Every way of ordering the answers, in the order they should be presented to the user.
+    pub const ALL: [AnswerSort; 4] = [
+        AnswerSort::Page,
+        AnswerSort::Score,
+        AnswerSort::Recency,
+        AnswerSort::AcceptedFirst,
+    ];
+
+    /// Get the name of the sort, as accepted by from_str. (ex: accepted-first)
+    pub fn name(&self) -> &'static str {
+        match self {
+            AnswerSort::Page => "page",
+            AnswerSort::Score => "score",
+            AnswerSort::Recency => "recency",
+            AnswerSort::AcceptedFirst => "accepted-first",
+        }
+    }
+}
+
+impl std::str::FromStr for AnswerSort {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        AnswerSort::ALL
+            .into_iter()
+            .find(|sort| sort.name() == name)
+            .ok_or_else(|| format!("Unknown answer sort: {}", name))
+    }
+}
+
+impl std::fmt::Display for AnswerSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Thread {
+    /// Get the answers ordered and filtered by the given options, the thread itself stays
+    /// untouched so it can be arranged again with other options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - how to order the answers and which of them to show.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::thread::{Answer, AnswerOptions, AnswerSort, Thread};
+    ///
+    /// let thread = Thread {
+    ///     question: "question".to_string(),
+    ///     answers: vec![
+    ///         Answer { content: "low".to_string(), score: Some(1), ..Default::default() },
+    ///         Answer { content: "high".to_string(), score: Some(10), ..Default::default() },
+    ///     ],
+    ///     ..Default::default()
+    /// };
+    /// let options = AnswerOptions { sort: AnswerSort::Score, ..Default::default() };
+    ///
+    /// assert_eq!(thread.arranged(&options)[0].content, "high");
+    /// ```
+    pub fn arranged(&self, options: &AnswerOptions) -> Vec<&Answer> {
+        let mut answers: Vec<&Answer> = self
+            .answers
+            .iter()
+            .filter(|answer| match (options.min_score, answer.score) {
+                (Some(min_score), Some(score)) => score >= min_score,
+                _ => true,
+            })
+            .collect();
+
+        // the sorts are stable so equal answers keep the order of the page
+        match options.sort {
+            AnswerSort::Page => (),
+            AnswerSort::Score => answers.sort_by_key(|answer| Reverse(answer.score)),
+            AnswerSort::Recency => answers.sort_by_key(|answer| Reverse(answer.date)),
+            AnswerSort::AcceptedFirst => answers.sort_by_key(|answer| !answer.is_accepted),
+        }
+
+        if let Some(limit) = options.limit {
+            answers.truncate(limit);
+        }
+
+        answers
+    }
+
+    /// Parse the page of a question on StackOverflow or any other StackExchange site, they all
+    /// share the same markup. The score, the accepted mark and the date of the answers, and the
+    /// metadata of the question, are read from the page too, when they're missing the thread is
+    /// kept without them.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The html of the question's page.
+    /// * `term_width` - The width of your terminal in order to properly display.
+    ///
+    /// # Returns
+    ///
+    /// The thread, or None if the question couldn't be found in the page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use falion::search::thread::Thread;
+    ///
+    /// let page = "<div class=\"s-prose js-post-body\" itemprop=\"text\"><p>question</p></div>\
+    ///     <div id=\"answer-1\" class=\"answer js-answer accepted-answer\" data-score=\"7\">\
+    ///     <div class=\"s-prose js-post-body\" itemprop=\"text\"><p>answer</p></div>";
+    /// let thread = Thread::from_stackexchange_page(page, 50).unwrap();
+    ///
+    /// assert_eq!(thread.question, "question\n");
+    /// assert_eq!(thread.answers[0].score, Some(7));
+    /// assert!(thread.answers[0].is_accepted);
+    /// assert!(thread.metadata.is_answered);
+    /// ```
+    pub fn from_stackexchange_page(page: &str, term_width: usize) -> Option<Thread> {
+        let mut posts = page.split(ANSWER_SEP);
+        let question_post = posts.next()?;
+        let question = post_content(question_post, term_width)?;
+
+        let answers = posts
+            .filter_map(|post| {
+                let content = post_content(post, term_width)?;
+                // the attributes of the answer are all in its opening tag
+                let header = post.split_once('>').map_or(post, |(header, _)| header);
+                let score = attribute(header, ANSWER_SCORE).and_then(|score| score.parse().ok());
+                let is_accepted = ANSWER_ACCEPTED.iter().any(|mark| header.contains(mark));
+                let date = attribute(post, POST_DATE).and_then(metadata::parse_date);
+
+                Some(Answer {
+                    id: attribute(header, ANSWER_ID).and_then(|id| id.parse().ok()),
+                    content,
+                    score,
+                    is_accepted,
+                    date,
+                    comments: comments_from_html(post, term_width),
+                })
+            })
+            .collect::<Vec<Answer>>();
+
+        let metadata = Metadata {
+            title: question_title(question_post),
+            score: question_post
+                .split_once(QUESTION_HEADER)
+                .and_then(|(_, header)| attribute(header, ANSWER_SCORE))
+                .and_then(|score| score.parse().ok()),
+            answer_count: attribute(question_post, ANSWER_COUNT)
+                .and_then(|count| count.parse().ok())
+                .or(Some(answers.len())),
+            is_answered: answers.iter().any(|answer| answer.is_accepted),
+            tags: question_tags(question_post),
+            date: question_post
+                .split_once(POST_SEP)
+                .and_then(|(_, post)| attribute(post, POST_DATE))
+                .and_then(metadata::parse_date),
+            ..Default::default()
+        };
+
+        // the page only shows the first few comments of every post
+        let hidden_comments = page
+            .split(ANSWER_SEP)
+            .filter(|post| post.contains(COMMENTS_HIDDEN))
+            .filter_map(|post| attribute(post, COMMENTS_POST_ID)?.parse().ok())
+            .collect();
+
+        Some(Thread {
+            id: attribute(question_post, QUESTION_ID).and_then(|id| id.parse().ok()),
+            question,
+            comments: comments_from_html(question_post, term_width),
+            answers,
+            metadata,
+            hidden_comments,
+        })
+    }
+
+    /// Get the comments of a post of the thread, the question or one of its answers.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the id of the post.
+    ///
+    /// # Returns
+    ///
+    /// The comments, or None if the thread has no post with that id.
+    pub fn post_comments_mut(&mut self, id: u64) -> Option<&mut Vec<Comment>> {
+        if self.id == Some(id) {
+            return Some(&mut self.comments);
+        }
+
+        self.answers
+            .iter_mut()
+            .find(|answer| answer.id == Some(id))
+            .map(|answer| &mut answer.comments)
+    }
+
+    /// Get every comment of the posts the page didn't show all the comments of, the way the
+    /// "show more comments" link of the page does. When getting the comments of a post fails the
+    /// ones the page showed are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - The client to make the requests with.
+    /// * `question_url` - The url of the thread, the comments are got from the same site.
+    /// * `term_width` - The width of your terminal in order to properly display.
+    #[tracing::instrument(skip_all)]
+    pub async fn load_hidden_comments(
+        &mut self,
+        client: &reqwest::Client,
+        question_url: &str,
+        term_width: usize,
+    ) {
+        if self.hidden_comments.is_empty() {
+            return;
+        }
+        let site_url = match url::Url::parse(question_url) {
+            Ok(url) => url.origin().ascii_serialization(),
+            Err(error) => {
+                tracing::warn!("Invalid question url {}. Error: {}", question_url, error);
+                return;
+            }
+        };
+
+        let requests = self.hidden_comments.iter().map(|id| {
+            let url = COMMENTS_URL
+                .replace("{SITE}", &site_url)
+                .replace("{ID}", &id.to_string());
+            async move {
+                let response = match client.get(&url).send().await {
+                    Ok(res) if res.status() == reqwest::StatusCode::OK => res.text().await,
+                    Ok(res) => {
+                        tracing::warn!(
+                            "Get request to {} return status code: {}",
+                            &url,
+                            &res.status()
+                        );
+                        return None;
+                    }
+                    Err(error) => Err(error),
+                };
+
+                match response {
+                    Ok(html) => Some((*id, comments_from_html(&html, term_width))),
+                    Err(error) => {
+                        tracing::warn!("Failed to get the comments at {}. Error: {}", &url, &error);
+                        None
+                    }
+                }
+            }
+        });
+        let loaded = futures::future::join_all(requests).await;
+
+        for (id, comments) in loaded.into_iter().flatten() {
+            if comments.is_empty() {
+                continue;
+            }
+            if let Some(post_comments) = self.post_comments_mut(id) {
+                *post_comments = comments;
+            }
+        }
+        self.hidden_comments.clear();
+    }
+}
+
+/// Get the comments in some html of a StackExchange site, like a post of a page or the list of
+/// comments of a post.
+///
+/// # Arguments
+///
+/// * `html` - The html the comments are in.
+/// * `term_width` - The width of your terminal in order to properly display.
+///
+/// # Examples
+///
+/// ```
+/// use falion::search::thread;
+///
+/// let html = "<li class=\"comment js-comment \" data-comment-score=\"5\">\
+///     <span class=\"comment-copy\">Use <code>Rc</code> instead.</span></li>";
+/// let comments = thread::comments_from_html(html, 50);
+///
+/// assert_eq!(comments[0].content, "Use `Rc` instead.");
+/// assert_eq!(comments[0].score, Some(5));
+/// ```
+pub fn comments_from_html(html: &str, term_width: usize) -> Vec<Comment> {
+    html.split(COMMENT_SEP)
+        .skip(1)
+        .filter_map(|comment| {
+            let (_, body) = comment.split_once(COMMENT_BODY)?;
+            let (body, _) = body.split_once(COMMENT_END)?;
+
+            Some(Comment {
+                content: util::html_to_text(body, term_width).trim().to_string(),
+                score: attribute(comment, COMMENT_SCORE).and_then(|score| score.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// Get the body of the first post in the given part of a page, as text.
+fn post_content(post: &str, term_width: usize) -> Option<String> {
+    let (_, body) = post.split_once(POST_SEP)?;
+    let (html, _) = body.split_once(POST_END)?;
+
+    Some(util::html_to_text(html, term_width))
+}
+
+/// Get the title of the question out of the link to itself in the header of the page.
+fn question_title(page: &str) -> Option<String> {
+    let (_, title) = page.split_once(QUESTION_TITLE)?;
+    let (title, _) = title.split_once("</a>")?;
+
+    Some(stackexchange_api::decode_title(title)).filter(|title| !title.is_empty())
+}
+
+/// Get the tags of the question, they're the links to the tags after its body.
+fn question_tags(page: &str) -> Vec<String> {
+    let post = page.split_once(POST_SEP).map_or("", |(_, post)| post);
+    let mut tags: Vec<String> = Vec::new();
+    for tag in post.split(QUESTION_TAG).skip(1) {
+        if let Some((tag, _)) = tag.split_once('"') {
+            if !tags.iter().any(|other| other == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+
+    tags
+}
+
+/// Get the value of the first attribute that starts with the given prefix (ex: data-score=").
+fn attribute<'a>(html: &'a str, prefix: &str) -> Option<&'a str> {
+    let (_, value) = html.split_once(prefix)?;
+    value.split_once('"').map(|(value, _)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(content: &str, score: Option<i64>, is_accepted: bool, date: Option<i64>) -> Answer {
+        Answer {
+            content: content.to_string(),
+            score,
+            is_accepted,
+            date,
+            ..Default::default()
+        }
+    }
+
+    fn thread() -> Thread {
+        Thread {
+            question: "question".to_string(),
+            answers: vec![
+                answer("first", Some(3), false, Some(100)),
+                answer("accepted", Some(1), true, Some(50)),
+                answer("best", Some(20), false, Some(10)),
+                answer("unknown", None, false, None),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn contents(answers: Vec<&Answer>) -> Vec<&str> {
+        answers
+            .into_iter()
+            .map(|answer| answer.content.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_arranged_sorts() {
+        let thread = thread();
+        let arrange = |sort| {
+            contents(thread.arranged(&AnswerOptions {
+                sort,
+                ..Default::default()
+            }))
+        };
+
+        assert_eq!(
+            arrange(AnswerSort::Page),
+            ["first", "accepted", "best", "unknown"]
+        );
+        assert_eq!(
+            arrange(AnswerSort::Score),
+            ["best", "first", "accepted", "unknown"]
+        );
+        assert_eq!(
+            arrange(AnswerSort::Recency),
+            ["first", "accepted", "best", "unknown"]
+        );
+        assert_eq!(
+            arrange(AnswerSort::AcceptedFirst),
+            ["accepted", "first", "best", "unknown"]
+        );
+    }
+
+    #[test]
+    fn test_arranged_filters() {
+        let thread = thread();
+        let options = AnswerOptions {
+            sort: AnswerSort::Score,
+            min_score: Some(2),
+            limit: Some(2),
+            ..Default::default()
+        };
+
+        assert_eq!(contents(thread.arranged(&options)), ["best", "first"]);
+    }
+
+    #[test]
+    fn test_from_stackexchange_page() {
+        let page = r#"<h1><a href="/questions/1/t" class="question-hyperlink">Isn&#39;t it</a></h1>
+            <div class="question js-question" data-score="12">
+            <div class="s-prose js-post-body" itemprop="text"><p>question</p></div>
+            <a href="/questions/tagged/rust" class="post-tag">rust</a>
+            <a href="/questions/tagged/rust" class="post-tag">rust</a>
+            <time itemprop="dateCreated" datetime="2013-07-05T21:12:43"></time></div>
+            <div id="answers-header"><h2 data-answercount="3"></h2></div>
+            <div id="answer-1" class="answer js-answer" data-score="3">
+            <div class="s-prose js-post-body" itemprop="text"><p>first</p></div>
+            <time itemprop="dateCreated" datetime="2013-07-05T21:12:43"></time></div>
+            <div id="answer-2" class="answer js-answer accepted-answer" data-score="-1">
+            <div class="s-prose js-post-body" itemprop="text"><p>second</p></div></div>"#;
+        let thread = Thread::from_stackexchange_page(page, 50).unwrap();
+
+        assert_eq!(thread.question, "question\n");
+        assert_eq!(
+            thread.answers,
+            vec![
+                answer("first\n", Some(3), false, Some(1373058763)),
+                answer("second\n", Some(-1), true, None),
+            ]
+        );
+        assert_eq!(
+            thread.metadata,
+            Metadata {
+                title: Some("Isn't it".to_string()),
+                score: Some(12),
+                answer_count: Some(3),
+                is_answered: true,
+                tags: vec!["rust".to_string()],
+                date: Some(1373058763),
+                ..Default::default()
+            }
+        );
+        assert_eq!(Thread::from_stackexchange_page("<p>empty</p>", 50), None);
+    }
+
+    #[test]
+    fn test_stackexchange_page_comments() {
+        let page = r##"<div class="question js-question" data-questionid="10">
+            <div class="s-prose js-post-body" itemprop="text"><p>question</p></div>
+            <div id="comments-10" class="comments js-comments-container" data-post-id="10">
+            <li id="comment-1" class="comment js-comment " data-comment-score="2">
+            <span class="comment-copy">What did you <i>try</i>?</span></li>
+            <a class="js-show-link comments-link " href="#">Show 3 more comments</a></div></div>
+            <div id="answer-11" class="answer js-answer" data-answerid="11" data-score="1">
+            <div class="s-prose js-post-body" itemprop="text"><p>answer</p></div>
+            <div id="comments-11" class="comments js-comments-container" data-post-id="11">
+            <li id="comment-2" class="comment js-comment " data-comment-score="0">
+            <span class="comment-copy">Works</span></li></div></div>"##;
+        let mut thread = Thread::from_stackexchange_page(page, 50).unwrap();
+
+        assert_eq!(thread.id, Some(10));
+        assert_eq!(
+            thread.comments,
+            vec![Comment {
+                content: "What did you *try*?".to_string(),
+                score: Some(2),
+            }]
+        );
+        assert_eq!(thread.answers[0].id, Some(11));
+        assert_eq!(thread.answers[0].comments[0].content, "Works");
+        assert_eq!(thread.answers[0].comments[0].score, Some(0));
+        assert_eq!(thread.hidden_comments, vec![10]);
+
+        assert_eq!(
+            thread.post_comments_mut(11).map(|comments| comments.len()),
+            Some(1)
+        );
+        assert!(thread.post_comments_mut(12).is_none());
+    }
+
+    #[test]
+    fn test_answer_sort_names() {
+        for sort in AnswerSort::ALL {
+            assert_eq!(sort.name().parse::<AnswerSort>(), Ok(sort));
+        }
+        assert!("votes".parse::<AnswerSort>().is_err());
+    }
+}
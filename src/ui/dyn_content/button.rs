@@ -1,7 +1,9 @@
-use super::super::results::ResultType;
+use super::show_element;
 use super::util;
 use super::MainWindow;
-use super::ResultsDynType;
+use super::ResultsContentType;
+use crate::content::Tagged;
+use crate::search::thread::{AnswerOptions, AnswerSort};
 use dashmap::DashMap;
 use slint::Weak;
 use std::sync::Arc;
@@ -18,21 +20,22 @@ use tokio::sync::RwLock;
 /// function.
 /// * `index` - ARC to the RwLock of the current results index for this particular resource
 /// * `content_index` - the index of the item that should be displayed from the result
-/// * `results_type` - the kind of result this is. Ex: StackOverflow.
+/// * `answer_options` - ARC to the RwLock of how the answers of threads are ordered and filtered.
 ///
 /// # Panics
 ///
 /// If slint couldn't be invoked from the event loop.
 #[tracing::instrument(skip_all)]
-pub fn get_back_content_fn<E, F>(
+pub fn get_back_content_fn<T, E, F>(
     ui: Weak<MainWindow>,
-    results: Arc<RwLock<Option<ResultsDynType<E, F>>>>,
-    results_awaited: Arc<DashMap<String, Vec<String>>>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
     index: Arc<RwLock<usize>>,
     content_index: Arc<RwLock<usize>>,
-    results_type: ResultType,
+    answer_options: Arc<RwLock<AnswerOptions>>,
 ) -> impl Fn()
 where
+    T: Tagged + std::marker::Send + std::marker::Sync + 'static,
     E: std::fmt::Display + std::marker::Send + 'static,
     F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
 {
@@ -42,6 +45,7 @@ where
         let content_index_clone = Arc::clone(&content_index);
         let results_clone = Arc::clone(&results);
         let results_awaited_clone = Arc::clone(&results_awaited);
+        let answer_options_clone = Arc::clone(&answer_options);
         // clone ui weak pointer
         let ui = ui.clone();
 
@@ -50,6 +54,7 @@ where
                 index_clone.read(),
                 content_index_clone.write(),
                 results_clone.read(),
+                answer_options_clone.read(),
             );
             let index_lock = locked.0;
             let mut content_index_lock = locked.1;
@@ -60,41 +65,21 @@ where
                 *content_index_lock = content_index_lock.saturating_sub(1);
             }
             let results_lock = locked.2;
+            let options = locked.3.clone();
 
             match results_lock.as_ref() {
                 Some(results) => match results {
                     Ok(results) => match results.get(*index_lock) {
-                        Some(result) => match results_awaited_clone.get(&result.0) {
+                        Some(result) => match results_awaited_clone.get(&result.0.title) {
                             Some(result) => {
-                                match result.get(*content_index_lock) {
-                                    Some(content) => {
-                                        let ui_clone = ui.clone();
-                                        // make content tag
-                                        let content_tag = if results_type == ResultType::GithubGist
-                                        {
-                                            format!("File {}", *content_index_lock + 1)
-                                        } else if *content_index_lock == 0 {
-                                            "Question".to_string()
-                                        } else {
-                                            format!("Answer {}", *content_index_lock)
-                                        };
-
-                                        // set content
-                                        let content = content.to_owned();
-                                        if let Err(err) = slint::invoke_from_event_loop(move || {
-                                            let ui = util::get_ui(ui_clone);
-
-                                            // set content tag
-                                            ui.set_dyn_content_tag(content_tag.into());
-
-                                            // set dynamic content
-                                            ui.set_dyn_content(content.into());
-
-                                            // log action
-                                            tracing::info!("Successfully displayed previous item in resource result.")
-                                        }) {
-                                            util::slint_event_loop_panic(err);
-                                        };
+                                match result
+                                    .elements(&options)
+                                    .into_iter()
+                                    .nth(*content_index_lock)
+                                {
+                                    Some(element) => {
+                                        show_element(ui.clone(), element);
+                                        tracing::info!("Successfully displayed previous item in resource result.");
                                     }
                                     None => {
                                         tracing::warn!("User tried getting content at a non existent index. Programming error.");
@@ -132,21 +117,22 @@ where
 /// function.
 /// * `index` - ARC to the RwLock of the current results index for this particular resource
 /// * `content_index` - the index of the item that should be displayed from the result
-/// * `results_type` - the kind of result this is. Ex: StackOverflow.
+/// * `answer_options` - ARC to the RwLock of how the answers of threads are ordered and filtered.
 ///
 /// # Panics
 ///
 /// If slint couldn't be invoked from the event loop.
 #[tracing::instrument(skip_all)]
-pub fn get_next_content_fn<E, F>(
+pub fn get_next_content_fn<T, E, F>(
     ui: Weak<MainWindow>,
-    results: Arc<RwLock<Option<ResultsDynType<E, F>>>>,
-    results_awaited: Arc<DashMap<String, Vec<String>>>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
     index: Arc<RwLock<usize>>,
     content_index: Arc<RwLock<usize>>,
-    results_type: ResultType,
+    answer_options: Arc<RwLock<AnswerOptions>>,
 ) -> impl Fn()
 where
+    T: Tagged + std::marker::Send + std::marker::Sync + 'static,
     E: std::fmt::Display + std::marker::Send + 'static,
     F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
 {
@@ -156,6 +142,7 @@ where
         let content_index_clone = Arc::clone(&content_index);
         let results_clone = Arc::clone(&results);
         let results_awaited_clone = Arc::clone(&results_awaited);
+        let answer_options_clone = Arc::clone(&answer_options);
         // clone ui weak pointer
         let ui = ui.clone();
 
@@ -164,49 +151,25 @@ where
                 index_clone.read(),
                 content_index_clone.write(),
                 results_clone.read(),
+                answer_options_clone.read(),
             );
             let index_lock = locked.0;
             let mut content_index_lock = locked.1;
             let results_lock = locked.2;
+            let options = locked.3.clone();
 
             match results_lock.as_ref() {
                 Some(results) => match results {
                     Ok(results) => match results.get(*index_lock) {
-                        Some(result) => match results_awaited_clone.get(&result.0) {
+                        Some(result) => match results_awaited_clone.get(&result.0.title) {
                             Some(result) => {
-                                if *content_index_lock < result.len() - 1 {
+                                let elements = result.elements(&options);
+                                if *content_index_lock + 1 < elements.len() {
                                     *content_index_lock += 1;
-                                    match result.get(*content_index_lock) {
-                                        Some(content) => {
-                                            let ui_clone = ui.clone();
-                                            // make content tag
-                                            let content_tag =
-                                                if results_type == ResultType::GithubGist {
-                                                    format!("File {}", *content_index_lock + 1)
-                                                } else if *content_index_lock == 0 {
-                                                    "Question".to_string()
-                                                } else {
-                                                    format!("Answer {}", *content_index_lock)
-                                                };
-
-                                            // set content
-                                            let content = content.to_owned();
-                                            if let Err(err) = slint::invoke_from_event_loop(
-                                                move || {
-                                                    let ui = util::get_ui(ui_clone);
-
-                                                    // set content tag
-                                                    ui.set_dyn_content_tag(content_tag.into());
-
-                                                    // unwrap is fine since it there is always at least one element
-                                                    ui.set_dyn_content(content.into());
-
-                                                    // log action
-                                                    tracing::info!("Successfully displayed next item in resource result.")
-                                                },
-                                            ) {
-                                                util::slint_event_loop_panic(err);
-                                            };
+                                    match elements.into_iter().nth(*content_index_lock) {
+                                        Some(element) => {
+                                            show_element(ui.clone(), element);
+                                            tracing::info!("Successfully displayed next item in resource result.");
                                         }
                                         None => {
                                             tracing::warn!("User tried getting content at a non existent index. Programming error.");
@@ -233,3 +196,96 @@ where
         });
     }
 }
+
+/// The function that's called when another way of sorting the answers is picked. The answers are
+/// arranged again and the question is shown, since the one being read might have moved.
+///
+/// # Arguments
+///
+/// * `ui` - weak pointer to the slint ui
+/// * `results` - ARC to the RwLock encapsulation of the Option for the results variable, from the main
+/// ui function.
+/// * `results_awaited` - ARC to the RwLock of the awaited results variable, from the main ui
+/// function.
+/// * `index` - ARC to the RwLock of the current results index for this particular resource
+/// * `content_index` - the index of the item that should be displayed from the result
+/// * `answer_options` - ARC to the RwLock of how the answers of threads are ordered and filtered.
+///
+/// # Panics
+///
+/// If slint couldn't be invoked from the event loop.
+#[tracing::instrument(skip_all)]
+pub fn get_sort_changed_fn<T, E, F>(
+    ui: Weak<MainWindow>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
+    index: Arc<RwLock<usize>>,
+    content_index: Arc<RwLock<usize>>,
+    answer_options: Arc<RwLock<AnswerOptions>>,
+) -> impl Fn(slint::SharedString)
+where
+    T: Tagged + std::marker::Send + std::marker::Sync + 'static,
+    E: std::fmt::Display + std::marker::Send + 'static,
+    F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
+{
+    move |sort| {
+        // the sort box only has the names of the sorts
+        let sort = match sort.parse::<AnswerSort>() {
+            Ok(sort) => sort,
+            Err(error) => {
+                tracing::warn!("The sort box gave an unknown sort. Error: {}", error);
+                return;
+            }
+        };
+        // clone necessary ARCs
+        let index_clone = Arc::clone(&index);
+        let content_index_clone = Arc::clone(&content_index);
+        let results_clone = Arc::clone(&results);
+        let results_awaited_clone = Arc::clone(&results_awaited);
+        let answer_options_clone = Arc::clone(&answer_options);
+        // clone ui weak pointer
+        let ui = ui.clone();
+
+        tokio::spawn(async move {
+            let locked = futures::join!(
+                index_clone.read(),
+                content_index_clone.write(),
+                results_clone.read(),
+                answer_options_clone.write(),
+            );
+            let index_lock = locked.0;
+            let mut content_index_lock = locked.1;
+            let results_lock = locked.2;
+            let mut options_lock = locked.3;
+            options_lock.sort = sort;
+            *content_index_lock = 0;
+
+            // keep the sort box on the picked sort when the view is opened again
+            let ui_clone = ui.clone();
+            if let Err(err) = slint::invoke_from_event_loop(move || {
+                let ui = util::get_ui(ui_clone);
+
+                ui.set_dyn_sort(sort.name().into());
+            }) {
+                util::slint_event_loop_panic(err);
+            };
+
+            let element = match results_lock.as_ref() {
+                Some(Ok(results)) => results
+                    .get(*index_lock)
+                    .and_then(|result| results_awaited_clone.get(&result.0.title))
+                    .and_then(|result| result.elements(&options_lock).into_iter().next()),
+                _ => None,
+            };
+            match element {
+                Some(element) => {
+                    show_element(ui, element);
+                    tracing::info!("Sorted the answers by {} successfully.", sort);
+                }
+                None => {
+                    tracing::warn!("User sorted the answers of a result that's not awaited. Programming error.");
+                }
+            }
+        });
+    }
+}
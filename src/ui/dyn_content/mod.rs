@@ -3,8 +3,10 @@ use super::results;
 use super::results::ResultType;
 use super::util;
 use super::MainWindow;
-use super::ResultsDynType;
+use super::ResultsContentType;
 use super::DYN_CONTENT_VIEW;
+use crate::content::{self, Element, Tagged};
+use crate::search::thread::{AnswerOptions, AnswerSort};
 use dashmap::DashMap;
 use slint::Weak;
 use std::sync::Arc;
@@ -21,20 +23,23 @@ use tokio::sync::RwLock;
 /// * `results_awaited` - ARC to the RwLock of the awaited results variable, from the main ui
 /// function.
 /// * `index` - ARC to the RwLock of the current results index for this particular resource
+/// * `answer_options` - ARC to the RwLock of how the answers of threads are ordered and filtered.
 /// * `results_type` - the kind of result this is. Ex: StackOverflow.
 ///
 /// # Panics
 ///
 /// It the results type is not made for this function
 #[tracing::instrument(skip_all)]
-pub fn setup_content_display<E, F>(
+pub fn setup_content_display<T, E, F>(
     ui: Weak<MainWindow>,
-    results: Arc<RwLock<Option<ResultsDynType<E, F>>>>,
-    results_awaited: Arc<DashMap<String, Vec<String>>>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
     index: Arc<RwLock<usize>>,
     content_index: Arc<RwLock<usize>>,
+    answer_options: Arc<RwLock<AnswerOptions>>,
     results_type: ResultType,
 ) where
+    T: Tagged + std::marker::Send + std::marker::Sync + 'static,
     E: std::fmt::Display + std::marker::Send + 'static,
     F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
 {
@@ -51,6 +56,7 @@ pub fn setup_content_display<E, F>(
             Arc::clone(&results_awaited),
             Arc::clone(&index),
             Arc::clone(&content_index),
+            Arc::clone(&answer_options),
             results_type,
         )),
         ResultType::StackExchange => ui_strong.on_se_enter(get_resource_enter_fn(
@@ -59,6 +65,7 @@ pub fn setup_content_display<E, F>(
             Arc::clone(&results_awaited),
             Arc::clone(&index),
             Arc::clone(&content_index),
+            Arc::clone(&answer_options),
             results_type,
         )),
         ResultType::GithubGist => ui_strong.on_gg_enter(get_resource_enter_fn(
@@ -67,6 +74,52 @@ pub fn setup_content_display<E, F>(
             Arc::clone(&results_awaited),
             Arc::clone(&index),
             Arc::clone(&content_index),
+            Arc::clone(&answer_options),
+            results_type,
+        )),
+        ResultType::RustDocs => ui_strong.on_docs_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            Arc::clone(&content_index),
+            Arc::clone(&answer_options),
+            results_type,
+        )),
+        ResultType::GithubIssues => ui_strong.on_issues_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            Arc::clone(&content_index),
+            Arc::clone(&answer_options),
+            results_type,
+        )),
+        ResultType::Discourse => ui_strong.on_discourse_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            Arc::clone(&content_index),
+            Arc::clone(&answer_options),
+            results_type,
+        )),
+        ResultType::Reddit => ui_strong.on_reddit_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            Arc::clone(&content_index),
+            Arc::clone(&answer_options),
+            results_type,
+        )),
+        ResultType::HackerNews => ui_strong.on_hn_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            Arc::clone(&content_index),
+            Arc::clone(&answer_options),
             results_type,
         )),
         _ => {
@@ -87,21 +140,24 @@ pub fn setup_content_display<E, F>(
 /// function.
 /// * `index` - ARC to the RwLock of the current results index for this particular resource
 /// * `content_index` - the index of the item that should be displayed from the result
+/// * `answer_options` - ARC to the RwLock of how the answers of threads are ordered and filtered.
 /// * `results_type` - the kind of result this is. Ex: StackOverflow.
 ///
 /// # Panics
 ///
 /// If slint couldn't be invoked from the event loop.
 #[tracing::instrument(skip_all)]
-fn get_resource_enter_fn<E, F>(
+fn get_resource_enter_fn<T, E, F>(
     ui: Weak<MainWindow>,
-    results: Arc<RwLock<Option<ResultsDynType<E, F>>>>,
-    results_awaited: Arc<DashMap<String, Vec<String>>>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
     index: Arc<RwLock<usize>>,
     content_index: Arc<RwLock<usize>>,
+    answer_options: Arc<RwLock<AnswerOptions>>,
     results_type: ResultType,
 ) -> impl Fn()
 where
+    T: Tagged + std::marker::Send + std::marker::Sync + 'static,
     E: std::fmt::Display + std::marker::Send + 'static,
     F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
 {
@@ -111,6 +167,7 @@ where
         let index_clone = Arc::clone(&index);
         let content_index_clone = Arc::clone(&content_index);
         let results_awaited_clone = Arc::clone(&results_awaited);
+        let answer_options_clone = Arc::clone(&answer_options);
         // clone ui weak pointer
         let ui = ui.clone();
 
@@ -122,6 +179,7 @@ where
             let locked = futures::join!(results_clone.write(), index_clone.read(),);
             let mut results_lock = locked.0;
             let index_lock = locked.1;
+            let options = answer_options_clone.read().await.clone();
 
             let content = match results_lock.as_mut() {
                 Some(results) => match results {
@@ -138,32 +196,23 @@ where
                                 util::slint_event_loop_panic(err);
                             };
                             // get content
-                            match results_awaited_clone.get(&result.0) {
+                            match results_awaited_clone.get(&result.0.title) {
                                 Some(result) => result,
                                 None => {
-                                    let (title, handle) = result;
-                                    let awaited = match handle.await {
-                                        Ok(handled) => match handled {
-                                            Ok(content) => content,
-                                            Err(error) => {
-                                                tracing::error!("There was an error getting the contetn for this a result. Error: {}", error);
-                                                vec![format!("There has been an error getting the content for this result. Error: {}", error)]
-                                            }
-                                        },
-                                        Err(error) => {
-                                            tracing::error!(
-                                                "There was an error handeling the future for a result. Error: {}",
-                                                error
-                                            );
-                                            vec![format!("There has been an error handeling the future for this result. Error: {}", error)]
-                                        }
-                                    };
-
-                                    // save already awaited
-                                    results_awaited_clone.insert(title.to_owned(), awaited);
+                                    let (hit, handle) = result;
+                                    let awaited = content::await_content(handle).await;
+
+                                    // save already awaited, under the title with the metadata
+                                    hit.title = awaited.row_title(&hit.title);
+                                    results_awaited_clone.insert(hit.title.to_owned(), awaited);
+                                    results::display::display_result(
+                                        ui.clone(),
+                                        hit.title.to_owned(),
+                                        results_type,
+                                    );
 
                                     // unwrap is safe since we just inserted this element
-                                    results_awaited_clone.get(title).unwrap()
+                                    results_awaited_clone.get(&hit.title).unwrap()
                                 }
                             }
                         }
@@ -190,23 +239,30 @@ where
             let index_clone = Arc::clone(&index_clone);
             let content_index_clone = Arc::clone(&content_index_clone);
             let results_awaited_clone = Arc::clone(&results_awaited_clone);
-            // get first element
-            let first = content.first().unwrap().to_owned();
+            // get first element, the question or the first file, no matter how the answers are
+            // arranged
+            let first = content.elements(&options).into_iter().next();
             // drop the Mutex locks
+            drop(content);
             drop(results_lock);
             drop(index_lock);
+            if let Some(first) = first {
+                show_element(ui_clone.clone(), first);
+            }
             if let Err(err) = slint::invoke_from_event_loop(move || {
                 let ui_strong = util::get_ui(ui_clone);
 
-                // set dynamic content first tag
-                if results_type == ResultType::GithubGist {
-                    ui_strong.set_dyn_content_tag("File 1".into());
-                } else {
-                    ui_strong.set_dyn_content_tag("Question".into());
-                }
-
-                // set dyn content
-                ui_strong.set_dyn_content(first.into());
+                // only threads have answers to sort
+                ui_strong.set_dyn_content_sortable(matches!(
+                    results_type,
+                    ResultType::StackOverflow
+                        | ResultType::StackExchange
+                        | ResultType::GithubIssues
+                        | ResultType::Discourse
+                        | ResultType::Reddit
+                        | ResultType::HackerNews
+                ));
+                ui_strong.set_dyn_sort(options.sort.name().into());
 
                 // setup back and next buttons
                 // setup back content button
@@ -216,7 +272,7 @@ where
                     Arc::clone(&results_awaited_clone),
                     Arc::clone(&index_clone),
                     Arc::clone(&content_index_clone),
-                    results_type,
+                    Arc::clone(&answer_options_clone),
                 ));
 
                 // setup next content button
@@ -226,7 +282,17 @@ where
                     Arc::clone(&results_awaited_clone),
                     Arc::clone(&index_clone),
                     Arc::clone(&content_index_clone),
-                    results_type,
+                    Arc::clone(&answer_options_clone),
+                ));
+
+                // setup the answers sort
+                ui_strong.on_dyn_sort_changed(button::get_sort_changed_fn(
+                    ui.clone(),
+                    Arc::clone(&results_clone),
+                    Arc::clone(&results_awaited_clone),
+                    Arc::clone(&index_clone),
+                    Arc::clone(&content_index_clone),
+                    Arc::clone(&answer_options_clone),
                 ));
 
                 // enable btns
@@ -237,3 +303,53 @@ where
         });
     }
 }
+
+/// Display an element of a dynamic content: its tag, marked if it's the accepted answer, and the
+/// element itself.
+///
+/// # Arguments
+///
+/// * `ui` - weak pointer to the slint ui
+/// * `element` - the element to display, its comments are collapsed under it.
+///
+/// # Panics
+///
+/// If slint couldn't be invoked from the event loop.
+#[tracing::instrument(skip_all)]
+fn show_element(ui: Weak<MainWindow>, element: Element) {
+    if let Err(err) = slint::invoke_from_event_loop(move || {
+        let ui = util::get_ui(ui);
+
+        ui.set_dyn_content_tag(element.tag.into());
+        ui.set_dyn_content_accepted(element.is_accepted);
+        ui.set_dyn_content(element.content.into());
+        ui.set_dyn_content_comment_count(element.comments.len() as i32);
+        ui.set_dyn_content_comments(
+            element
+                .comments
+                .iter()
+                .map(|comment| format!("• {}", comment))
+                .collect::<Vec<String>>()
+                .join("\n\n")
+                .into(),
+        );
+    }) {
+        util::slint_event_loop_panic(err);
+    };
+}
+
+/// Set the ways the answers can be sorted in the sort box of the dynamic content view.
+///
+/// # Arguments
+///
+/// * `ui` - weak pointer to the slint ui
+#[tracing::instrument(skip_all)]
+pub fn setup_sorts(ui: Weak<MainWindow>) {
+    let ui = util::get_ui(ui);
+    let sorts: Vec<slint::SharedString> = AnswerSort::ALL
+        .iter()
+        .map(|sort| sort.name().into())
+        .collect();
+
+    ui.set_dyn_sorts(std::rc::Rc::new(slint::VecModel::from(sorts)).into());
+}
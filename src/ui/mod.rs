@@ -3,15 +3,29 @@ mod results;
 mod static_content;
 mod util;
 use super::search;
+use super::search::ddg::DdgError;
 use super::search::ddg_search::DdgSearchError;
-use super::search::geeksforgeeks::GfgError;
-use super::search::github_gist::GithubGistError;
+use super::search::discourse::DiscourseError;
+use super::search::engine::SearchHit;
+use super::search::geeksforgeeks::{Article, GfgError};
+use super::search::github_gist::{Gist, GithubGistError};
+use super::search::github_issues::GithubIssuesError;
+use super::search::hacker_news::HackerNewsError;
+use super::search::man_pages::{ManError, ManPage};
+use super::search::merge::{self, Source, Weights};
+use super::search::offline_docs::{OfflineDoc, OfflineDocsError};
+use super::search::reddit::RedditError;
+use super::search::rust_docs::{Doc, RustDocsError};
 use super::search::stackexchange::SeError;
 use super::search::stackoverflow::SofError;
+use super::search::thread::{AnswerOptions, Thread};
+use crate::config::{ApiSource, Config};
 use dashmap::DashMap;
 use results::display;
 use results::helper;
 use results::index;
+use results::loading;
+use results::merged;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::task::JoinHandle;
@@ -23,20 +37,34 @@ const DYN_CONTENT_VIEW: i32 = 1;
 const STATIC_CONTENT_VIEW: i32 = 2;
 
 type StackOverflowResults =
-    Option<Result<Vec<(String, JoinHandle<Result<Vec<String>, SofError>>)>, SofError>>;
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Thread, SofError>>)>, SofError>>;
 type StackExchangeResults =
-    Option<Result<Vec<(String, JoinHandle<Result<Vec<String>, SeError>>)>, SeError>>;
-type GithubGistResults = Option<
-    Result<Vec<(String, JoinHandle<Result<Vec<String>, GithubGistError>>)>, GithubGistError>,
->;
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Thread, SeError>>)>, SeError>>;
+type GithubGistResults =
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Gist, GithubGistError>>)>, GithubGistError>>;
 type GeeksForGeeksResults =
-    Option<Result<Vec<(String, JoinHandle<Result<String, GfgError>>)>, GfgError>>;
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Article, GfgError>>)>, GfgError>>;
 type DdgSearchResults =
-    Option<Result<Vec<(String, JoinHandle<Result<String, DdgSearchError>>)>, DdgSearchError>>;
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<String, DdgSearchError>>)>, DdgSearchError>>;
+type RustDocsResults =
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Doc, RustDocsError>>)>, RustDocsError>>;
+type ManPagesResults =
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<ManPage, ManError>>)>, ManError>>;
+type OfflineDocsResults = Option<
+    Result<Vec<(SearchHit, JoinHandle<Result<OfflineDoc, OfflineDocsError>>)>, OfflineDocsError>,
+>;
+type GithubIssuesResults = Option<
+    Result<Vec<(SearchHit, JoinHandle<Result<Thread, GithubIssuesError>>)>, GithubIssuesError>,
+>;
+type DiscourseResults =
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Thread, DiscourseError>>)>, DiscourseError>>;
+type RedditResults =
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Thread, RedditError>>)>, RedditError>>;
+type HackerNewsResults =
+    Option<Result<Vec<(SearchHit, JoinHandle<Result<Thread, HackerNewsError>>)>, HackerNewsError>>;
 
-type Results<T, E> = Result<Vec<(String, T)>, E>;
-type ResultsStaticType<E, F> = Result<Vec<(String, JoinHandle<Result<String, E>>)>, F>;
-type ResultsDynType<E, F> = Result<Vec<(String, JoinHandle<Result<Vec<String>, E>>)>, F>;
+type Results<T, E> = Result<Vec<(SearchHit, T)>, E>;
+type ResultsContentType<T, E, F> = Result<Vec<(SearchHit, JoinHandle<Result<T, E>>)>, F>;
 
 /// The main ui function that executes the window and sets it up.
 #[tracing::instrument(skip_all)]
@@ -54,19 +82,153 @@ pub fn ui() {
         }
     };
 
+    // load the config, the gui can't show the errors so the default is used instead
+    let config = match Config::load(None) {
+        Ok(config) => config,
+        Err(error) => {
+            tracing::error!(
+                "Failed to load the config, using the default one. Error: {}",
+                error
+            );
+            Config::default()
+        }
+    };
+    let api_sources = config.stackexchange_api.sources;
+    let se_sites = match config.stackexchange.sites() {
+        Ok(sites) => sites,
+        Err(error) => {
+            tracing::error!(
+                "Invalid StackExchange sites, searching all of them. Error: {}",
+                error
+            );
+            Vec::new()
+        }
+    };
+    let forums = match config.discourse.forums() {
+        Ok(forums) => forums,
+        Err(error) => {
+            tracing::error!(
+                "Invalid Discourse forums, searching the default ones. Error: {}",
+                error
+            );
+            Vec::new()
+        }
+    };
+    let subreddits = match config.reddit.subreddits() {
+        Ok(subreddits) => subreddits,
+        Err(error) => {
+            tracing::error!(
+                "Invalid subreddits, searching the default ones. Error: {}",
+                error
+            );
+            Vec::new()
+        }
+    };
+    let engines = match config.search.engines() {
+        Ok(engines) => engines,
+        Err(error) => {
+            tracing::error!(
+                "Invalid search engines, searching with the default ones. Error: {}",
+                error
+            );
+            Vec::new()
+        }
+    };
+    let lang = match config
+        .lang
+        .lang(&std::env::current_dir().unwrap_or_default())
+    {
+        Ok(lang) => lang,
+        Err(error) => {
+            tracing::error!(
+                "Invalid language, the searches aren't narrowed down to one. Error: {}",
+                error
+            );
+            None
+        }
+    };
+    let cargo_lock = match config
+        .cargo_lock
+        .cargo_lock(&std::env::current_dir().unwrap_or_default())
+    {
+        Ok(cargo_lock) => cargo_lock.map(Arc::new),
+        Err(error) => {
+            tracing::error!(
+                "Invalid Cargo.lock, the queries don't get the versions of the crates. Error: {}",
+                error
+            );
+            None
+        }
+    };
+    let answer_options: Arc<RwLock<AnswerOptions>> = Arc::new(RwLock::new(AnswerOptions {
+        cargo_lock: cargo_lock.clone(),
+        ..config.answers.options()
+    }));
+
     // Make source objects
     let client = search::util::client_with_special_settings();
-    let stackoverflow = Arc::new(search::stackoverflow::StackOverflow::with_client(
-        client.clone(),
-    ));
-    let stackexchange = Arc::new(search::stackexchange::StackExchange::with_client(
-        client.clone(),
-    ));
-    let github_gist = Arc::new(search::github_gist::GithubGist::with_client(client.clone()));
-    let geeksforgeeks = Arc::new(search::geeksforgeeks::GeeksForGeeks::with_client(
+    let api = search::stackexchange_api::StackExchangeApi::with_client(
         client.clone(),
-    ));
-    let ddg_search = Arc::new(search::ddg_search::DdgSearch::with_client(client.clone()));
+        config.stackexchange_api.key,
+    );
+    let stackoverflow = Arc::new(
+        if api_sources.contains(&ApiSource::Stackoverflow) {
+            search::stackoverflow::StackOverflow::with_api(client.clone(), api.clone())
+        } else {
+            search::stackoverflow::StackOverflow::with_client(client.clone())
+        }
+        .with_lang(lang)
+        .with_engines(engines.clone()),
+    );
+    let stackexchange = Arc::new(
+        if api_sources.contains(&ApiSource::Stackexchange) {
+            search::stackexchange::StackExchange::with_api(client.clone(), api.clone())
+        } else {
+            search::stackexchange::StackExchange::with_client(client.clone())
+        }
+        .with_sites(se_sites)
+        .with_lang(lang)
+        .with_engines(engines.clone()),
+    );
+    let github_gist = Arc::new(
+        search::github_gist::GithubGist::with_client(client.clone())
+            .with_lang(lang)
+            .with_engines(engines.clone()),
+    );
+    let geeksforgeeks = Arc::new(
+        search::geeksforgeeks::GeeksForGeeks::with_client(client.clone())
+            .with_engines(engines.clone()),
+    );
+    let ddg_search = Arc::new(
+        search::ddg_search::DdgSearch::with_client(client.clone())
+            .with_lang(lang, config.lang.mode)
+            .with_engines(engines.clone()),
+    );
+    let rust_docs = Arc::new(
+        search::rust_docs::RustDocs::with_client(client.clone()).with_engines(engines.clone()),
+    );
+    let man_pages = Arc::new(config.man_pages.man_pages());
+    let offline_docs = Arc::new(config.offline_docs.offline_docs());
+    let github_issues = Arc::new(
+        search::github_issues::GithubIssues::with_token(client.clone(), config.github.token())
+            .with_engines(engines.clone()),
+    );
+    let discourse = Arc::new(
+        search::discourse::Discourse::with_client(client.clone())
+            .with_forums(forums)
+            .with_engines(engines.clone()),
+    );
+    let reddit = Arc::new(
+        search::reddit::Reddit::with_client(client.clone())
+            .with_subreddits(subreddits)
+            .with_engines(engines),
+    );
+    let hacker_news = Arc::new(match &config.hacker_news.endpoint {
+        Some(endpoint) => {
+            search::hacker_news::HackerNews::with_client(client.clone()).with_endpoint(endpoint)
+        }
+        None => search::hacker_news::HackerNews::with_client(client.clone()),
+    });
 
     // make variables to store results
     let stackoverflow_results: Arc<RwLock<StackOverflowResults>> = Arc::new(RwLock::new(None));
@@ -74,19 +236,38 @@ pub fn ui() {
     let github_gist_results: Arc<RwLock<GithubGistResults>> = Arc::new(RwLock::new(None));
     let geeksforgeeks_results: Arc<RwLock<GeeksForGeeksResults>> = Arc::new(RwLock::new(None));
     let ddg_search_results: Arc<RwLock<DdgSearchResults>> = Arc::new(RwLock::new(None));
+    let rust_docs_results: Arc<RwLock<RustDocsResults>> = Arc::new(RwLock::new(None));
+    let man_pages_results: Arc<RwLock<ManPagesResults>> = Arc::new(RwLock::new(None));
+    let offline_docs_results: Arc<RwLock<OfflineDocsResults>> = Arc::new(RwLock::new(None));
+    let github_issues_results: Arc<RwLock<GithubIssuesResults>> = Arc::new(RwLock::new(None));
+    let discourse_results: Arc<RwLock<DiscourseResults>> = Arc::new(RwLock::new(None));
+    let reddit_results: Arc<RwLock<RedditResults>> = Arc::new(RwLock::new(None));
+    let hacker_news_results: Arc<RwLock<HackerNewsResults>> = Arc::new(RwLock::new(None));
 
     // make variables to store awaite results
     // create vars
-    let stackoverflow_results_awaited: Arc<DashMap<String, Vec<String>>> =
+    let stackoverflow_results_awaited: Arc<DashMap<String, Thread>> =
         Arc::new(DashMap::with_capacity(5));
-    let stackexchange_results_awaited: Arc<DashMap<String, Vec<String>>> =
+    let stackexchange_results_awaited: Arc<DashMap<String, Thread>> =
         Arc::new(DashMap::with_capacity(5));
-    let github_gist_results_awaited: Arc<DashMap<String, Vec<String>>> =
+    let github_gist_results_awaited: Arc<DashMap<String, Gist>> =
         Arc::new(DashMap::with_capacity(5));
-    let geeksforgeeks_results_awaited: Arc<DashMap<String, String>> =
+    let geeksforgeeks_results_awaited: Arc<DashMap<String, Article>> =
         Arc::new(DashMap::with_capacity(5));
     let ddg_search_results_awaited: Arc<DashMap<String, String>> =
         Arc::new(DashMap::with_capacity(5));
+    let rust_docs_results_awaited: Arc<DashMap<String, Doc>> = Arc::new(DashMap::with_capacity(5));
+    let man_pages_results_awaited: Arc<DashMap<String, ManPage>> =
+        Arc::new(DashMap::with_capacity(5));
+    let offline_docs_results_awaited: Arc<DashMap<String, OfflineDoc>> =
+        Arc::new(DashMap::with_capacity(5));
+    let github_issues_results_awaited: Arc<DashMap<String, Thread>> =
+        Arc::new(DashMap::with_capacity(5));
+    let discourse_results_awaited: Arc<DashMap<String, Thread>> =
+        Arc::new(DashMap::with_capacity(5));
+    let reddit_results_awaited: Arc<DashMap<String, Thread>> = Arc::new(DashMap::with_capacity(5));
+    let hacker_news_results_awaited: Arc<DashMap<String, Thread>> =
+        Arc::new(DashMap::with_capacity(5));
 
     // make variables to store the current index
     let stackoverflow_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
@@ -94,11 +275,126 @@ pub fn ui() {
     let github_gist_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
     let geeksforgeeks_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
     let ddg_search_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let rust_docs_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let man_pages_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let offline_docs_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let github_issues_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let discourse_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let reddit_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let hacker_news_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
 
     // make variables to store the current content index
     let stackoverflow_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
     let stackexchange_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
     let github_gist_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let rust_docs_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let github_issues_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let discourse_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let reddit_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+    let hacker_news_content_index: Arc<RwLock<usize>> = Arc::new(RwLock::new(0));
+
+    // the view interleaving the results of every resource, if the config shows it
+    let merged_view = if config.merged.show {
+        let weights = match config.merged.weights() {
+            Ok(weights) => weights,
+            Err(error) => {
+                tracing::error!(
+                    "Invalid merged view weights, using the default ones. Error: {}",
+                    error
+                );
+                Weights::new()
+            }
+        };
+        let view = merged::MergedView::new(weights)
+            .with_source(merged::MergedSource::new(
+                Source::StackOverflow,
+                results::ResultType::StackOverflow,
+                Arc::clone(&stackoverflow_results),
+                Arc::clone(&stackoverflow_results_awaited),
+                Arc::clone(&stackoverflow_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::StackExchange,
+                results::ResultType::StackExchange,
+                Arc::clone(&stackexchange_results),
+                Arc::clone(&stackexchange_results_awaited),
+                Arc::clone(&stackexchange_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::GithubGist,
+                results::ResultType::GithubGist,
+                Arc::clone(&github_gist_results),
+                Arc::clone(&github_gist_results_awaited),
+                Arc::clone(&github_gist_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::GeeksForGeeks,
+                results::ResultType::GeeksForGeeks,
+                Arc::clone(&geeksforgeeks_results),
+                Arc::clone(&geeksforgeeks_results_awaited),
+                Arc::clone(&geeksforgeeks_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::DdgSearch,
+                results::ResultType::DdgSearch,
+                Arc::clone(&ddg_search_results),
+                Arc::clone(&ddg_search_results_awaited),
+                Arc::clone(&ddg_search_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::RustDocs,
+                results::ResultType::RustDocs,
+                Arc::clone(&rust_docs_results),
+                Arc::clone(&rust_docs_results_awaited),
+                Arc::clone(&rust_docs_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::ManPages,
+                results::ResultType::ManPages,
+                Arc::clone(&man_pages_results),
+                Arc::clone(&man_pages_results_awaited),
+                Arc::clone(&man_pages_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::OfflineDocs,
+                results::ResultType::OfflineDocs,
+                Arc::clone(&offline_docs_results),
+                Arc::clone(&offline_docs_results_awaited),
+                Arc::clone(&offline_docs_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::GithubIssues,
+                results::ResultType::GithubIssues,
+                Arc::clone(&github_issues_results),
+                Arc::clone(&github_issues_results_awaited),
+                Arc::clone(&github_issues_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::Discourse,
+                results::ResultType::Discourse,
+                Arc::clone(&discourse_results),
+                Arc::clone(&discourse_results_awaited),
+                Arc::clone(&discourse_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::Reddit,
+                results::ResultType::Reddit,
+                Arc::clone(&reddit_results),
+                Arc::clone(&reddit_results_awaited),
+                Arc::clone(&reddit_index),
+            ))
+            .with_source(merged::MergedSource::new(
+                Source::HackerNews,
+                results::ResultType::HackerNews,
+                Arc::clone(&hacker_news_results),
+                Arc::clone(&hacker_news_results_awaited),
+                Arc::clone(&hacker_news_index),
+            ));
+        view.setup_btns(main_window.as_weak());
+        Some(view)
+    } else {
+        None
+    };
 
     // Event for when a search enter is hit
     main_window.on_query_enter({
@@ -112,24 +408,54 @@ pub fn ui() {
         let github_gist_clone = Arc::clone(&github_gist);
         let geeksforgeeks_clone = Arc::clone(&geeksforgeeks);
         let ddg_search_clone = Arc::clone(&ddg_search);
+        let rust_docs_clone = Arc::clone(&rust_docs);
+        let man_pages_clone = Arc::clone(&man_pages);
+        let offline_docs_clone = Arc::clone(&offline_docs);
+        let github_issues_clone = Arc::clone(&github_issues);
+        let discourse_clone = Arc::clone(&discourse);
+        let reddit_clone = Arc::clone(&reddit);
+        let hacker_news_clone = Arc::clone(&hacker_news);
         // results
         let stackoverflow_results_clone = Arc::clone(&stackoverflow_results);
         let stackexchange_results_clone = Arc::clone(&stackexchange_results);
         let github_gist_results_clone = Arc::clone(&github_gist_results);
         let geeksforgeeks_results_clone = Arc::clone(&geeksforgeeks_results);
         let ddg_search_results_clone = Arc::clone(&ddg_search_results);
+        let rust_docs_results_clone = Arc::clone(&rust_docs_results);
+        let man_pages_results_clone = Arc::clone(&man_pages_results);
+        let offline_docs_results_clone = Arc::clone(&offline_docs_results);
+        let github_issues_results_clone = Arc::clone(&github_issues_results);
+        let discourse_results_clone = Arc::clone(&discourse_results);
+        let reddit_results_clone = Arc::clone(&reddit_results);
+        let hacker_news_results_clone = Arc::clone(&hacker_news_results);
         // results awaited
         let stackoverflow_results_awaited_clone = Arc::clone(&stackoverflow_results_awaited);
         let stackexchange_results_awaited_clone = Arc::clone(&stackexchange_results_awaited);
         let github_gist_results_awaited_clone = Arc::clone(&github_gist_results_awaited);
         let geeksforgeeks_results_awaited_clone = Arc::clone(&geeksforgeeks_results_awaited);
         let ddg_search_results_awaited_clone = Arc::clone(&ddg_search_results_awaited);
+        let rust_docs_results_awaited_clone = Arc::clone(&rust_docs_results_awaited);
+        let man_pages_results_awaited_clone = Arc::clone(&man_pages_results_awaited);
+        let offline_docs_results_awaited_clone = Arc::clone(&offline_docs_results_awaited);
+        let github_issues_results_awaited_clone = Arc::clone(&github_issues_results_awaited);
+        let discourse_results_awaited_clone = Arc::clone(&discourse_results_awaited);
+        let reddit_results_awaited_clone = Arc::clone(&reddit_results_awaited);
+        let hacker_news_results_awaited_clone = Arc::clone(&hacker_news_results_awaited);
         // indexes
         let stackoverflow_index_clone = Arc::clone(&stackoverflow_index);
         let stackexchange_index_clone = Arc::clone(&stackexchange_index);
         let github_gist_index_clone = Arc::clone(&github_gist_index);
         let geeksforgeeks_index_clone = Arc::clone(&geeksforgeeks_index);
         let ddg_search_index_clone = Arc::clone(&ddg_search_index);
+        let rust_docs_index_clone = Arc::clone(&rust_docs_index);
+        let man_pages_index_clone = Arc::clone(&man_pages_index);
+        let offline_docs_index_clone = Arc::clone(&offline_docs_index);
+        let github_issues_index_clone = Arc::clone(&github_issues_index);
+        let discourse_index_clone = Arc::clone(&discourse_index);
+        let reddit_index_clone = Arc::clone(&reddit_index);
+        let hacker_news_index_clone = Arc::clone(&hacker_news_index);
+        // merged view
+        let merged_view_clone = merged_view.clone();
         move |text| {
             // clone the weak pointer in order to use it in event loops
             let ui_thread = ui_thread.clone();
@@ -162,36 +488,98 @@ pub fn ui() {
             let github_gist_clone = Arc::clone(&github_gist_clone);
             let geeksforgeeks_clone = Arc::clone(&geeksforgeeks_clone);
             let ddg_search_clone = Arc::clone(&ddg_search_clone);
+            let rust_docs_clone = Arc::clone(&rust_docs_clone);
+            let man_pages_clone = Arc::clone(&man_pages_clone);
+            let offline_docs_clone = Arc::clone(&offline_docs_clone);
+            let github_issues_clone = Arc::clone(&github_issues_clone);
+            let discourse_clone = Arc::clone(&discourse_clone);
+            let reddit_clone = Arc::clone(&reddit_clone);
+            let hacker_news_clone = Arc::clone(&hacker_news_clone);
             // results
             let stackoverflow_results_clone = Arc::clone(&stackoverflow_results_clone);
             let stackexchange_results_clone = Arc::clone(&stackexchange_results_clone);
             let github_gist_results_clone = Arc::clone(&github_gist_results_clone);
             let geeksforgeeks_results_clone = Arc::clone(&geeksforgeeks_results_clone);
             let ddg_search_results_clone = Arc::clone(&ddg_search_results_clone);
+            let rust_docs_results_clone = Arc::clone(&rust_docs_results_clone);
+            let man_pages_results_clone = Arc::clone(&man_pages_results_clone);
+            let offline_docs_results_clone = Arc::clone(&offline_docs_results_clone);
+            let github_issues_results_clone = Arc::clone(&github_issues_results_clone);
+            let discourse_results_clone = Arc::clone(&discourse_results_clone);
+            let reddit_results_clone = Arc::clone(&reddit_results_clone);
+            let hacker_news_results_clone = Arc::clone(&hacker_news_results_clone);
             // results awaited
             let stackoverflow_results_awaited_clone = Arc::clone(&stackoverflow_results_awaited_clone);
             let stackexchange_results_awaited_clone = Arc::clone(&stackexchange_results_awaited_clone);
             let github_gist_results_awaited_clone = Arc::clone(&github_gist_results_awaited_clone);
             let geeksforgeeks_results_awaited_clone = Arc::clone(&geeksforgeeks_results_awaited_clone);
             let ddg_search_results_awaited_clone = Arc::clone(&ddg_search_results_awaited_clone);
+            let rust_docs_results_awaited_clone = Arc::clone(&rust_docs_results_awaited_clone);
+            let man_pages_results_awaited_clone = Arc::clone(&man_pages_results_awaited_clone);
+            let offline_docs_results_awaited_clone = Arc::clone(&offline_docs_results_awaited_clone);
+            let github_issues_results_awaited_clone = Arc::clone(&github_issues_results_awaited_clone);
+            let discourse_results_awaited_clone = Arc::clone(&discourse_results_awaited_clone);
+            let reddit_results_awaited_clone = Arc::clone(&reddit_results_awaited_clone);
+            let hacker_news_results_awaited_clone = Arc::clone(&hacker_news_results_awaited_clone);
             // indexes
             let stackoverflow_index_clone = Arc::clone(&stackoverflow_index_clone);
             let stackexchange_index_clone = Arc::clone(&stackexchange_index_clone);
             let github_gist_index_clone = Arc::clone(&github_gist_index_clone);
             let geeksforgeeks_index_clone = Arc::clone(&geeksforgeeks_index_clone);
             let ddg_search_index_clone = Arc::clone(&ddg_search_index_clone);
+            let rust_docs_index_clone = Arc::clone(&rust_docs_index_clone);
+            let man_pages_index_clone = Arc::clone(&man_pages_index_clone);
+            let offline_docs_index_clone = Arc::clone(&offline_docs_index_clone);
+            let github_issues_index_clone = Arc::clone(&github_issues_index_clone);
+            let discourse_index_clone = Arc::clone(&discourse_index_clone);
+            let reddit_index_clone = Arc::clone(&reddit_index_clone);
+            let hacker_news_index_clone = Arc::clone(&hacker_news_index_clone);
+            // merged view
+            let merged_view_clone = merged_view_clone.clone();
+            // the web searches get the versions of the crates of the project, the local ones don't
+            let search_text = match &cargo_lock {
+                Some(cargo_lock) => cargo_lock.annotate(&text),
+                None => text.to_string(),
+            };
 
             // spawn task to get the results and show in a different thread.
             tokio::spawn(async move {
                 // get result
-                let so_res = stackoverflow_clone.get_multiple_questions_content(&text, Some(5));
-                let se_res = stackexchange_clone.get_multiple_questions_content(&text, Some(5));
-                let gg_res = github_gist_clone.get_multiple_gists_content(&text, Some(5));
-                let gfg_res = geeksforgeeks_clone.get_multiple_pages_content(&text, Some(5));
-                let ddg_res = ddg_search_clone.get_multiple_pages_content(&text, Some(5));
+                let so_res = stackoverflow_clone.get_multiple_questions_content(&search_text, Some(5));
+                let se_res = stackexchange_clone.get_multiple_questions_content(&search_text, Some(5));
+                let gg_res = github_gist_clone.get_multiple_gists_content(&search_text, Some(5));
+                let gfg_res = geeksforgeeks_clone.get_multiple_pages_content(&search_text, Some(5));
+                let ddg_res = ddg_search_clone.get_multiple_pages_content(&search_text, Some(5));
+                let docs_res = rust_docs_clone.get_multiple_docs_content(&search_text, Some(5));
+                let man_res = man_pages_clone.get_multiple_pages_content(&text, Some(5));
+                let offline_res = offline_docs_clone.get_multiple_docs_content(&text, Some(5));
+                let issues_res = github_issues_clone.get_multiple_issues_content(&search_text, Some(5));
+                let discourse_res = discourse_clone.get_multiple_topics_content(&search_text, Some(5));
+                let reddit_res = reddit_clone.get_multiple_posts_content(&search_text, Some(5));
+                let hn_res = hacker_news_clone.get_multiple_stories_content(&text, Some(5));
 
                 // await all results at the same time
-                let res = futures::join!(so_res, se_res, gg_res, gfg_res, ddg_res);
+                let mut res = futures::join!(so_res, se_res, gg_res, gfg_res, ddg_res, docs_res, man_res, offline_res, issues_res, discourse_res, reddit_res, hn_res);
+
+                // the same page is often found by more than one resource, keep it only in the
+                // first one so the web search is left with what the others didn't find
+                let mut seen = std::collections::HashSet::new();
+                for source in Source::DEDUP_ORDER {
+                    match source {
+                        Source::StackOverflow => merge::dedup_results(&mut res.0, &mut seen, || SofError::DdgError(DdgError::Duplicates)),
+                        Source::StackExchange => merge::dedup_results(&mut res.1, &mut seen, || SeError::DdgError(DdgError::Duplicates)),
+                        Source::GithubGist => merge::dedup_results(&mut res.2, &mut seen, || GithubGistError::DdgError(DdgError::Duplicates)),
+                        Source::GeeksForGeeks => merge::dedup_results(&mut res.3, &mut seen, || GfgError::DdgError(DdgError::Duplicates)),
+                        Source::DdgSearch => merge::dedup_results(&mut res.4, &mut seen, || DdgSearchError::DdgError(DdgError::Duplicates)),
+                        Source::RustDocs => merge::dedup_results(&mut res.5, &mut seen, || RustDocsError::DdgError(DdgError::Duplicates)),
+                        Source::ManPages => merge::dedup_results(&mut res.6, &mut seen, || ManError::NoResults(text.to_string())),
+                        Source::OfflineDocs => merge::dedup_results(&mut res.7, &mut seen, || OfflineDocsError::NoResults(text.to_string())),
+                        Source::GithubIssues => merge::dedup_results(&mut res.8, &mut seen, || GithubIssuesError::DdgError(DdgError::Duplicates)),
+                        Source::Discourse => merge::dedup_results(&mut res.9, &mut seen, || DiscourseError::DdgError(DdgError::Duplicates)),
+                        Source::Reddit => merge::dedup_results(&mut res.10, &mut seen, || RedditError::DdgError(DdgError::Duplicates)),
+                        Source::HackerNews => merge::dedup_results(&mut res.11, &mut seen, || HackerNewsError::NoResults(text.to_string())),
+                    }
+                }
 
                 // lock the mutex for the results in oder to update them
                 let locked = futures::join!(
@@ -200,6 +588,13 @@ pub fn ui() {
                     github_gist_results_clone.write(),
                     geeksforgeeks_results_clone.write(),
                     ddg_search_results_clone.write(),
+                    rust_docs_results_clone.write(),
+                    man_pages_results_clone.write(),
+                    offline_docs_results_clone.write(),
+                    github_issues_results_clone.write(),
+                    discourse_results_clone.write(),
+                    reddit_results_clone.write(),
+                    hacker_news_results_clone.write(),
                 );
 
                 // take out the locks
@@ -208,6 +603,13 @@ pub fn ui() {
                 let mut github_gist_results_clone_lock = locked.2;
                 let mut geeksforgeeks_results_clone_lock = locked.3;
                 let mut ddg_search_results_clone_lock = locked.4;
+                let mut rust_docs_results_clone_lock = locked.5;
+                let mut man_pages_results_clone_lock = locked.6;
+                let mut offline_docs_results_clone_lock = locked.7;
+                let mut github_issues_results_clone_lock = locked.8;
+                let mut discourse_results_clone_lock = locked.9;
+                let mut reddit_results_clone_lock = locked.10;
+                let mut hacker_news_results_clone_lock = locked.11;
 
                 // clear awaited results
                 stackoverflow_results_awaited_clone.clear();
@@ -215,14 +617,28 @@ pub fn ui() {
                 github_gist_results_awaited_clone.clear();
                 geeksforgeeks_results_awaited_clone.clear();
                 ddg_search_results_awaited_clone.clear();
+                rust_docs_results_awaited_clone.clear();
+                man_pages_results_awaited_clone.clear();
+                offline_docs_results_awaited_clone.clear();
+                github_issues_results_awaited_clone.clear();
+                discourse_results_awaited_clone.clear();
+                reddit_results_awaited_clone.clear();
+                hacker_news_results_awaited_clone.clear();
 
                 // resest index to 0
                 futures::join!(
-                    index::reset_result_index(stackoverflow_index_clone),
-                    index::reset_result_index(stackexchange_index_clone),
-                    index::reset_result_index(github_gist_index_clone),
-                    index::reset_result_index(geeksforgeeks_index_clone),
-                    index::reset_result_index(ddg_search_index_clone),
+                    index::reset_result_index(Arc::clone(&stackoverflow_index_clone)),
+                    index::reset_result_index(Arc::clone(&stackexchange_index_clone)),
+                    index::reset_result_index(Arc::clone(&github_gist_index_clone)),
+                    index::reset_result_index(Arc::clone(&geeksforgeeks_index_clone)),
+                    index::reset_result_index(Arc::clone(&ddg_search_index_clone)),
+                    index::reset_result_index(Arc::clone(&rust_docs_index_clone)),
+                    index::reset_result_index(Arc::clone(&man_pages_index_clone)),
+                    index::reset_result_index(Arc::clone(&offline_docs_index_clone)),
+                    index::reset_result_index(Arc::clone(&github_issues_index_clone)),
+                    index::reset_result_index(Arc::clone(&discourse_index_clone)),
+                    index::reset_result_index(Arc::clone(&reddit_index_clone)),
+                    index::reset_result_index(Arc::clone(&hacker_news_index_clone)),
                 );
 
                 // update results with the new ones
@@ -231,6 +647,13 @@ pub fn ui() {
                 github_gist_results_clone_lock.replace(res.2);
                 geeksforgeeks_results_clone_lock.replace(res.3);
                 ddg_search_results_clone_lock.replace(res.4);
+                rust_docs_results_clone_lock.replace(res.5);
+                man_pages_results_clone_lock.replace(res.6);
+                offline_docs_results_clone_lock.replace(res.7);
+                github_issues_results_clone_lock.replace(res.8);
+                discourse_results_clone_lock.replace(res.9);
+                reddit_results_clone_lock.replace(res.10);
+                hacker_news_results_clone_lock.replace(res.11);
 
                 // display the results and enable their respective buttons
                 // using if let and not handling none since we just set values above
@@ -254,6 +677,34 @@ pub fn ui() {
                 if let Some(results) = ddg_search_results_clone_lock.as_ref() {
                     display::display_first_result(ui_thread.clone(), results, results::ResultType::DdgSearch);
                 }
+                // Rust Docs
+                if let Some(results) = rust_docs_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::RustDocs);
+                }
+                // Man Pages
+                if let Some(results) = man_pages_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::ManPages);
+                }
+                // Offline Docs
+                if let Some(results) = offline_docs_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::OfflineDocs);
+                }
+                // GitHub Issues
+                if let Some(results) = github_issues_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::GithubIssues);
+                }
+                // Discourse
+                if let Some(results) = discourse_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::Discourse);
+                }
+                // Reddit
+                if let Some(results) = reddit_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::Reddit);
+                }
+                // Hacker News
+                if let Some(results) = hacker_news_results_clone_lock.as_ref() {
+                    display::display_first_result(ui_thread.clone(), results, results::ResultType::HackerNews);
+                }
 
                 // Enable the next and bach buttons aswell
                 let ui_thread_clone = ui_thread.clone();
@@ -271,6 +722,39 @@ pub fn ui() {
 
                 // log that we displayed the results successfully
                 tracing::info!("Displayed the results successfully!");
+
+                // drop the locks so the results can be listed with their metadata once loaded
+                drop(stackoverflow_results_clone_lock);
+                drop(stackexchange_results_clone_lock);
+                drop(github_gist_results_clone_lock);
+                drop(geeksforgeeks_results_clone_lock);
+                drop(ddg_search_results_clone_lock);
+                drop(rust_docs_results_clone_lock);
+                drop(man_pages_results_clone_lock);
+                drop(offline_docs_results_clone_lock);
+                drop(github_issues_results_clone_lock);
+                drop(discourse_results_clone_lock);
+                drop(reddit_results_clone_lock);
+                drop(hacker_news_results_clone_lock);
+                // rank the new results in the merged view
+                if let Some(view) = merged_view_clone {
+                    let ui = ui_thread.clone();
+                    tokio::task::spawn_blocking(move || view.reset(ui));
+                }
+                futures::join!(
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&stackoverflow_results_clone), Arc::clone(&stackoverflow_results_awaited_clone), Arc::clone(&stackoverflow_index_clone), results::ResultType::StackOverflow),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&stackexchange_results_clone), Arc::clone(&stackexchange_results_awaited_clone), Arc::clone(&stackexchange_index_clone), results::ResultType::StackExchange),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&github_gist_results_clone), Arc::clone(&github_gist_results_awaited_clone), Arc::clone(&github_gist_index_clone), results::ResultType::GithubGist),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&geeksforgeeks_results_clone), Arc::clone(&geeksforgeeks_results_awaited_clone), Arc::clone(&geeksforgeeks_index_clone), results::ResultType::GeeksForGeeks),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&ddg_search_results_clone), Arc::clone(&ddg_search_results_awaited_clone), Arc::clone(&ddg_search_index_clone), results::ResultType::DdgSearch),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&rust_docs_results_clone), Arc::clone(&rust_docs_results_awaited_clone), Arc::clone(&rust_docs_index_clone), results::ResultType::RustDocs),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&man_pages_results_clone), Arc::clone(&man_pages_results_awaited_clone), Arc::clone(&man_pages_index_clone), results::ResultType::ManPages),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&offline_docs_results_clone), Arc::clone(&offline_docs_results_awaited_clone), Arc::clone(&offline_docs_index_clone), results::ResultType::OfflineDocs),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&github_issues_results_clone), Arc::clone(&github_issues_results_awaited_clone), Arc::clone(&github_issues_index_clone), results::ResultType::GithubIssues),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&discourse_results_clone), Arc::clone(&discourse_results_awaited_clone), Arc::clone(&discourse_index_clone), results::ResultType::Discourse),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&reddit_results_clone), Arc::clone(&reddit_results_awaited_clone), Arc::clone(&reddit_index_clone), results::ResultType::Reddit),
+                    loading::watch_loading(ui_thread.clone(), Arc::clone(&hacker_news_results_clone), Arc::clone(&hacker_news_results_awaited_clone), Arc::clone(&hacker_news_index_clone), results::ResultType::HackerNews),
+                );
             });
         }
     });
@@ -284,11 +768,25 @@ pub fn ui() {
         let github_gist_results_clone = Arc::clone(&github_gist_results);
         let geeksforgeeks_results_clone = Arc::clone(&geeksforgeeks_results);
         let ddg_search_results_clone = Arc::clone(&ddg_search_results);
+        let rust_docs_results_clone = Arc::clone(&rust_docs_results);
+        let man_pages_results_clone = Arc::clone(&man_pages_results);
+        let offline_docs_results_clone = Arc::clone(&offline_docs_results);
+        let github_issues_results_clone = Arc::clone(&github_issues_results);
+        let discourse_results_clone = Arc::clone(&discourse_results);
+        let reddit_results_clone = Arc::clone(&reddit_results);
+        let hacker_news_results_clone = Arc::clone(&hacker_news_results);
         let stackoverflow_index_clone = Arc::clone(&stackoverflow_index);
         let stackexchange_index_clone = Arc::clone(&stackexchange_index);
         let github_gist_index_clone = Arc::clone(&github_gist_index);
         let geeksforgeeks_index_clone = Arc::clone(&geeksforgeeks_index);
         let ddg_search_index_clone = Arc::clone(&ddg_search_index);
+        let rust_docs_index_clone = Arc::clone(&rust_docs_index);
+        let man_pages_index_clone = Arc::clone(&man_pages_index);
+        let offline_docs_index_clone = Arc::clone(&offline_docs_index);
+        let github_issues_index_clone = Arc::clone(&github_issues_index);
+        let discourse_index_clone = Arc::clone(&discourse_index);
+        let reddit_index_clone = Arc::clone(&reddit_index);
+        let hacker_news_index_clone = Arc::clone(&hacker_news_index);
         // get a weak pointer to the main window
         let ui = main_window.as_weak();
 
@@ -300,11 +798,25 @@ pub fn ui() {
             let github_gist_results_clone = Arc::clone(&github_gist_results_clone);
             let geeksforgeeks_results_clone = Arc::clone(&geeksforgeeks_results_clone);
             let ddg_search_results_clone = Arc::clone(&ddg_search_results_clone);
+            let rust_docs_results_clone = Arc::clone(&rust_docs_results_clone);
+            let man_pages_results_clone = Arc::clone(&man_pages_results_clone);
+            let offline_docs_results_clone = Arc::clone(&offline_docs_results_clone);
+            let github_issues_results_clone = Arc::clone(&github_issues_results_clone);
+            let discourse_results_clone = Arc::clone(&discourse_results_clone);
+            let reddit_results_clone = Arc::clone(&reddit_results_clone);
+            let hacker_news_results_clone = Arc::clone(&hacker_news_results_clone);
             let stackoverflow_index_clone = Arc::clone(&stackoverflow_index_clone);
             let stackexchange_index_clone = Arc::clone(&stackexchange_index_clone);
             let github_gist_index_clone = Arc::clone(&github_gist_index_clone);
             let geeksforgeeks_index_clone = Arc::clone(&geeksforgeeks_index_clone);
             let ddg_search_index_clone = Arc::clone(&ddg_search_index_clone);
+            let rust_docs_index_clone = Arc::clone(&rust_docs_index_clone);
+            let man_pages_index_clone = Arc::clone(&man_pages_index_clone);
+            let offline_docs_index_clone = Arc::clone(&offline_docs_index_clone);
+            let github_issues_index_clone = Arc::clone(&github_issues_index_clone);
+            let discourse_index_clone = Arc::clone(&discourse_index_clone);
+            let reddit_index_clone = Arc::clone(&reddit_index_clone);
+            let hacker_news_index_clone = Arc::clone(&hacker_news_index_clone);
             // get a weak pointer to the main window
             let ui = ui.clone();
             tokio::task::spawn_blocking(move || {
@@ -329,6 +841,34 @@ pub fn ui() {
                     Arc::clone(&ddg_search_results_clone),
                     Arc::clone(&ddg_search_index_clone),
                 );
+                index::try_up_index(
+                    Arc::clone(&rust_docs_results_clone),
+                    Arc::clone(&rust_docs_index_clone),
+                );
+                index::try_up_index(
+                    Arc::clone(&man_pages_results_clone),
+                    Arc::clone(&man_pages_index_clone),
+                );
+                index::try_up_index(
+                    Arc::clone(&offline_docs_results_clone),
+                    Arc::clone(&offline_docs_index_clone),
+                );
+                index::try_up_index(
+                    Arc::clone(&github_issues_results_clone),
+                    Arc::clone(&github_issues_index_clone),
+                );
+                index::try_up_index(
+                    Arc::clone(&discourse_results_clone),
+                    Arc::clone(&discourse_index_clone),
+                );
+                index::try_up_index(
+                    Arc::clone(&reddit_results_clone),
+                    Arc::clone(&reddit_index_clone),
+                );
+                index::try_up_index(
+                    Arc::clone(&hacker_news_results_clone),
+                    Arc::clone(&hacker_news_index_clone),
+                );
 
                 // redisplay results
                 display::redisplay_result(
@@ -361,6 +901,48 @@ pub fn ui() {
                     Arc::clone(&ddg_search_index_clone),
                     results::ResultType::DdgSearch,
                 );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&rust_docs_results_clone),
+                    Arc::clone(&rust_docs_index_clone),
+                    results::ResultType::RustDocs,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&man_pages_results_clone),
+                    Arc::clone(&man_pages_index_clone),
+                    results::ResultType::ManPages,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&offline_docs_results_clone),
+                    Arc::clone(&offline_docs_index_clone),
+                    results::ResultType::OfflineDocs,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&github_issues_results_clone),
+                    Arc::clone(&github_issues_index_clone),
+                    results::ResultType::GithubIssues,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&discourse_results_clone),
+                    Arc::clone(&discourse_index_clone),
+                    results::ResultType::Discourse,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&reddit_results_clone),
+                    Arc::clone(&reddit_index_clone),
+                    results::ResultType::Reddit,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&hacker_news_results_clone),
+                    Arc::clone(&hacker_news_index_clone),
+                    results::ResultType::HackerNews,
+                );
 
                 // log the end of the function
                 tracing::info!("Up the results by one successfully and resdisplayed them.");
@@ -377,11 +959,25 @@ pub fn ui() {
         let github_gist_results_clone = Arc::clone(&github_gist_results);
         let geeksforgeeks_results_clone = Arc::clone(&geeksforgeeks_results);
         let ddg_search_results_clone = Arc::clone(&ddg_search_results);
+        let rust_docs_results_clone = Arc::clone(&rust_docs_results);
+        let man_pages_results_clone = Arc::clone(&man_pages_results);
+        let offline_docs_results_clone = Arc::clone(&offline_docs_results);
+        let github_issues_results_clone = Arc::clone(&github_issues_results);
+        let discourse_results_clone = Arc::clone(&discourse_results);
+        let reddit_results_clone = Arc::clone(&reddit_results);
+        let hacker_news_results_clone = Arc::clone(&hacker_news_results);
         let stackoverflow_index_clone = Arc::clone(&stackoverflow_index);
         let stackexchange_index_clone = Arc::clone(&stackexchange_index);
         let github_gist_index_clone = Arc::clone(&github_gist_index);
         let geeksforgeeks_index_clone = Arc::clone(&geeksforgeeks_index);
         let ddg_search_index_clone = Arc::clone(&ddg_search_index);
+        let rust_docs_index_clone = Arc::clone(&rust_docs_index);
+        let man_pages_index_clone = Arc::clone(&man_pages_index);
+        let offline_docs_index_clone = Arc::clone(&offline_docs_index);
+        let github_issues_index_clone = Arc::clone(&github_issues_index);
+        let discourse_index_clone = Arc::clone(&discourse_index);
+        let reddit_index_clone = Arc::clone(&reddit_index);
+        let hacker_news_index_clone = Arc::clone(&hacker_news_index);
         // get a weak pointer to the main window
         let ui = main_window.as_weak();
 
@@ -393,11 +989,25 @@ pub fn ui() {
             let github_gist_results_clone = Arc::clone(&github_gist_results_clone);
             let geeksforgeeks_results_clone = Arc::clone(&geeksforgeeks_results_clone);
             let ddg_search_results_clone = Arc::clone(&ddg_search_results_clone);
+            let rust_docs_results_clone = Arc::clone(&rust_docs_results_clone);
+            let man_pages_results_clone = Arc::clone(&man_pages_results_clone);
+            let offline_docs_results_clone = Arc::clone(&offline_docs_results_clone);
+            let github_issues_results_clone = Arc::clone(&github_issues_results_clone);
+            let discourse_results_clone = Arc::clone(&discourse_results_clone);
+            let reddit_results_clone = Arc::clone(&reddit_results_clone);
+            let hacker_news_results_clone = Arc::clone(&hacker_news_results_clone);
             let stackoverflow_index_clone = Arc::clone(&stackoverflow_index_clone);
             let stackexchange_index_clone = Arc::clone(&stackexchange_index_clone);
             let github_gist_index_clone = Arc::clone(&github_gist_index_clone);
             let geeksforgeeks_index_clone = Arc::clone(&geeksforgeeks_index_clone);
             let ddg_search_index_clone = Arc::clone(&ddg_search_index_clone);
+            let rust_docs_index_clone = Arc::clone(&rust_docs_index_clone);
+            let man_pages_index_clone = Arc::clone(&man_pages_index_clone);
+            let offline_docs_index_clone = Arc::clone(&offline_docs_index_clone);
+            let github_issues_index_clone = Arc::clone(&github_issues_index_clone);
+            let discourse_index_clone = Arc::clone(&discourse_index_clone);
+            let reddit_index_clone = Arc::clone(&reddit_index_clone);
+            let hacker_news_index_clone = Arc::clone(&hacker_news_index_clone);
             // get a weak pointer to the main window
             let ui = ui.clone();
 
@@ -408,6 +1018,13 @@ pub fn ui() {
                 index::try_down_index(Arc::clone(&github_gist_index_clone));
                 index::try_down_index(Arc::clone(&geeksforgeeks_index_clone));
                 index::try_down_index(Arc::clone(&ddg_search_index_clone));
+                index::try_down_index(Arc::clone(&rust_docs_index_clone));
+                index::try_down_index(Arc::clone(&man_pages_index_clone));
+                index::try_down_index(Arc::clone(&offline_docs_index_clone));
+                index::try_down_index(Arc::clone(&github_issues_index_clone));
+                index::try_down_index(Arc::clone(&discourse_index_clone));
+                index::try_down_index(Arc::clone(&reddit_index_clone));
+                index::try_down_index(Arc::clone(&hacker_news_index_clone));
 
                 // redisplay results
                 display::redisplay_result(
@@ -440,6 +1057,48 @@ pub fn ui() {
                     Arc::clone(&ddg_search_index_clone),
                     results::ResultType::DdgSearch,
                 );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&rust_docs_results_clone),
+                    Arc::clone(&rust_docs_index_clone),
+                    results::ResultType::RustDocs,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&man_pages_results_clone),
+                    Arc::clone(&man_pages_index_clone),
+                    results::ResultType::ManPages,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&offline_docs_results_clone),
+                    Arc::clone(&offline_docs_index_clone),
+                    results::ResultType::OfflineDocs,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&github_issues_results_clone),
+                    Arc::clone(&github_issues_index_clone),
+                    results::ResultType::GithubIssues,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&discourse_results_clone),
+                    Arc::clone(&discourse_index_clone),
+                    results::ResultType::Discourse,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&reddit_results_clone),
+                    Arc::clone(&reddit_index_clone),
+                    results::ResultType::Reddit,
+                );
+                display::redisplay_result(
+                    ui.clone(),
+                    Arc::clone(&hacker_news_results_clone),
+                    Arc::clone(&hacker_news_index_clone),
+                    results::ResultType::HackerNews,
+                );
 
                 // log the end of the function
                 tracing::info!("Down the results by one successfully and resdisplayed them.");
@@ -478,14 +1137,58 @@ pub fn ui() {
         Arc::clone(&ddg_search_index),
         results::ResultType::DdgSearch,
     );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&rust_docs_results),
+        Arc::clone(&rust_docs_index),
+        results::ResultType::RustDocs,
+    );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&man_pages_results),
+        Arc::clone(&man_pages_index),
+        results::ResultType::ManPages,
+    );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&offline_docs_results),
+        Arc::clone(&offline_docs_index),
+        results::ResultType::OfflineDocs,
+    );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&github_issues_results),
+        Arc::clone(&github_issues_index),
+        results::ResultType::GithubIssues,
+    );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&discourse_results),
+        Arc::clone(&discourse_index),
+        results::ResultType::Discourse,
+    );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&reddit_results),
+        Arc::clone(&reddit_index),
+        results::ResultType::Reddit,
+    );
+    results::setup_results_btns(
+        main_window.as_weak(),
+        Arc::clone(&hacker_news_results),
+        Arc::clone(&hacker_news_index),
+        results::ResultType::HackerNews,
+    );
 
     // setup displaying results content
+    dyn_content::setup_sorts(main_window.as_weak());
     dyn_content::setup_content_display(
         main_window.as_weak(),
         Arc::clone(&stackoverflow_results),
         Arc::clone(&stackoverflow_results_awaited),
         Arc::clone(&stackoverflow_index),
         Arc::clone(&stackoverflow_content_index),
+        Arc::clone(&answer_options),
         results::ResultType::StackOverflow,
     );
     dyn_content::setup_content_display(
@@ -494,6 +1197,7 @@ pub fn ui() {
         Arc::clone(&stackexchange_results_awaited),
         Arc::clone(&stackexchange_index),
         Arc::clone(&stackexchange_content_index),
+        Arc::clone(&answer_options),
         results::ResultType::StackExchange,
     );
     dyn_content::setup_content_display(
@@ -502,8 +1206,18 @@ pub fn ui() {
         Arc::clone(&github_gist_results_awaited),
         Arc::clone(&github_gist_index),
         Arc::clone(&github_gist_content_index),
+        Arc::clone(&answer_options),
         results::ResultType::GithubGist,
     );
+    dyn_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&rust_docs_results),
+        Arc::clone(&rust_docs_results_awaited),
+        Arc::clone(&rust_docs_index),
+        Arc::clone(&rust_docs_content_index),
+        Arc::clone(&answer_options),
+        results::ResultType::RustDocs,
+    );
     static_content::setup_content_display(
         main_window.as_weak(),
         Arc::clone(&geeksforgeeks_results),
@@ -518,6 +1232,56 @@ pub fn ui() {
         Arc::clone(&ddg_search_index),
         results::ResultType::DdgSearch,
     );
+    static_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&man_pages_results),
+        Arc::clone(&man_pages_results_awaited),
+        Arc::clone(&man_pages_index),
+        results::ResultType::ManPages,
+    );
+    static_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&offline_docs_results),
+        Arc::clone(&offline_docs_results_awaited),
+        Arc::clone(&offline_docs_index),
+        results::ResultType::OfflineDocs,
+    );
+    dyn_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&github_issues_results),
+        Arc::clone(&github_issues_results_awaited),
+        Arc::clone(&github_issues_index),
+        Arc::clone(&github_issues_content_index),
+        Arc::clone(&answer_options),
+        results::ResultType::GithubIssues,
+    );
+    dyn_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&discourse_results),
+        Arc::clone(&discourse_results_awaited),
+        Arc::clone(&discourse_index),
+        Arc::clone(&discourse_content_index),
+        Arc::clone(&answer_options),
+        results::ResultType::Discourse,
+    );
+    dyn_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&reddit_results),
+        Arc::clone(&reddit_results_awaited),
+        Arc::clone(&reddit_index),
+        Arc::clone(&reddit_content_index),
+        Arc::clone(&answer_options),
+        results::ResultType::Reddit,
+    );
+    dyn_content::setup_content_display(
+        main_window.as_weak(),
+        Arc::clone(&hacker_news_results),
+        Arc::clone(&hacker_news_results_awaited),
+        Arc::clone(&hacker_news_index),
+        Arc::clone(&hacker_news_content_index),
+        Arc::clone(&answer_options),
+        results::ResultType::HackerNews,
+    );
 
     // setup content return button
     main_window.on_content_return_enter({
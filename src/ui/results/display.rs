@@ -30,8 +30,8 @@ pub fn display_first_result<T, E>(
         // unwrap is fine here since it would have been an error if there were no
         // results, so there is at least one
         Ok(results) => {
-            let (title, _) = results.first().unwrap();
-            let res = slint::SharedString::from(title);
+            let (hit, _) = results.first().unwrap();
+            let res = slint::SharedString::from(&hit.title);
             if let Err(err) = slint::invoke_from_event_loop(move || {
                 let ui = util::get_ui(ui);
 
@@ -68,6 +68,48 @@ pub fn display_first_result<T, E>(
                         ui.set_is_ddg_back(true);
                         ui.set_is_ddg_next(true);
                     }
+                    ResultType::RustDocs => {
+                        ui.set_docs_result(res);
+                        ui.set_is_docs(true);
+                        ui.set_is_docs_back(true);
+                        ui.set_is_docs_next(true);
+                    }
+                    ResultType::ManPages => {
+                        ui.set_man_result(res);
+                        ui.set_is_man(true);
+                        ui.set_is_man_back(true);
+                        ui.set_is_man_next(true);
+                    }
+                    ResultType::OfflineDocs => {
+                        ui.set_offline_result(res);
+                        ui.set_is_offline(true);
+                        ui.set_is_offline_back(true);
+                        ui.set_is_offline_next(true);
+                    }
+                    ResultType::GithubIssues => {
+                        ui.set_issues_result(res);
+                        ui.set_is_issues(true);
+                        ui.set_is_issues_back(true);
+                        ui.set_is_issues_next(true);
+                    }
+                    ResultType::Discourse => {
+                        ui.set_discourse_result(res);
+                        ui.set_is_discourse(true);
+                        ui.set_is_discourse_back(true);
+                        ui.set_is_discourse_next(true);
+                    }
+                    ResultType::Reddit => {
+                        ui.set_reddit_result(res);
+                        ui.set_is_reddit(true);
+                        ui.set_is_reddit_back(true);
+                        ui.set_is_reddit_next(true);
+                    }
+                    ResultType::HackerNews => {
+                        ui.set_hn_result(res);
+                        ui.set_is_hn(true);
+                        ui.set_is_hn_back(true);
+                        ui.set_is_hn_next(true);
+                    }
                 }
             }) {
                 util::slint_event_loop_panic(err);
@@ -92,6 +134,27 @@ pub fn display_first_result<T, E>(
                 ResultType::DdgSearch => {
                     tracing::warn!("There were no results for DdgSearch. Error {}", err);
                 }
+                ResultType::RustDocs => {
+                    tracing::warn!("There were no results for RustDocs. Error {}", err);
+                }
+                ResultType::ManPages => {
+                    tracing::warn!("There were no results for ManPages. Error {}", err);
+                }
+                ResultType::OfflineDocs => {
+                    tracing::warn!("There were no results for OfflineDocs. Error {}", err);
+                }
+                ResultType::GithubIssues => {
+                    tracing::warn!("There were no results for GithubIssues. Error {}", err);
+                }
+                ResultType::Discourse => {
+                    tracing::warn!("There were no results for Discourse. Error {}", err);
+                }
+                ResultType::Reddit => {
+                    tracing::warn!("There were no results for Reddit. Error {}", err);
+                }
+                ResultType::HackerNews => {
+                    tracing::warn!("There were no results for HackerNews. Error {}", err);
+                }
             }
             if let Err(err) = slint::invoke_from_event_loop(move || {
                 let ui = util::get_ui(ui);
@@ -113,6 +176,27 @@ pub fn display_first_result<T, E>(
                     ResultType::DdgSearch => {
                         ui.set_sof_result(err);
                     }
+                    ResultType::RustDocs => {
+                        ui.set_docs_result(err);
+                    }
+                    ResultType::ManPages => {
+                        ui.set_man_result(err);
+                    }
+                    ResultType::OfflineDocs => {
+                        ui.set_offline_result(err);
+                    }
+                    ResultType::GithubIssues => {
+                        ui.set_issues_result(err);
+                    }
+                    ResultType::Discourse => {
+                        ui.set_discourse_result(err);
+                    }
+                    ResultType::Reddit => {
+                        ui.set_reddit_result(err);
+                    }
+                    ResultType::HackerNews => {
+                        ui.set_hn_result(err);
+                    }
                 }
             }) {
                 util::slint_event_loop_panic(err);
@@ -121,7 +205,7 @@ pub fn display_first_result<T, E>(
     }
 }
 
-/// Redisplay the result for the provide resource
+/// Redisplay the result for the provide resource, previewing its snippet from the search.
 ///
 /// # Arguments
 ///
@@ -146,32 +230,91 @@ pub fn redisplay_result<T, E>(
 {
     if let Some(Ok(results)) = results.blocking_read().as_ref() {
         if let Some(res) = results.get(*index.blocking_read()) {
-            let (title, _) = res;
-            let res = slint::SharedString::from(title);
-            if let Err(err) = slint::invoke_from_event_loop(move || {
-                let ui = util::get_ui(ui);
-
-                // redisplay results based on their type
-                match results_type {
-                    ResultType::StackOverflow => {
-                        ui.set_sof_result(res);
-                    }
-                    ResultType::StackExchange => {
-                        ui.set_se_result(res);
-                    }
-                    ResultType::GithubGist => {
-                        ui.set_gg_result(res);
-                    }
-                    ResultType::GeeksForGeeks => {
-                        ui.set_gfg_result(res);
-                    }
-                    ResultType::DdgSearch => {
-                        ui.set_ddg_result(res);
-                    }
-                }
-            }) {
-                util::slint_event_loop_panic(err);
-            };
+            let (hit, _) = res;
+            display_result(ui.clone(), hit.title.to_owned(), results_type);
+            display_preview(ui, hit.snippet.to_owned());
         };
     }
 }
+
+/// Display the given title as the current result of the provided resource, like when the
+/// current result is renamed after its content loads.
+///
+/// # Arguments
+///
+/// * `ui` - weak pointer to the slint ui
+/// * `title` - the title of the result.
+/// * `results_type` - the kind of result this is. Ex: StackOverflow.
+///
+/// # Panics
+///
+/// If it can't invoke the slint event loop.
+#[tracing::instrument(skip_all)]
+pub fn display_result(ui: Weak<MainWindow>, title: String, results_type: ResultType) {
+    let res = slint::SharedString::from(title);
+    if let Err(err) = slint::invoke_from_event_loop(move || {
+        let ui = util::get_ui(ui);
+
+        // redisplay results based on their type
+        match results_type {
+            ResultType::StackOverflow => {
+                ui.set_sof_result(res);
+            }
+            ResultType::StackExchange => {
+                ui.set_se_result(res);
+            }
+            ResultType::GithubGist => {
+                ui.set_gg_result(res);
+            }
+            ResultType::GeeksForGeeks => {
+                ui.set_gfg_result(res);
+            }
+            ResultType::DdgSearch => {
+                ui.set_ddg_result(res);
+            }
+            ResultType::RustDocs => {
+                ui.set_docs_result(res);
+            }
+            ResultType::ManPages => {
+                ui.set_man_result(res);
+            }
+            ResultType::OfflineDocs => {
+                ui.set_offline_result(res);
+            }
+            ResultType::GithubIssues => {
+                ui.set_issues_result(res);
+            }
+            ResultType::Discourse => {
+                ui.set_discourse_result(res);
+            }
+            ResultType::Reddit => {
+                ui.set_reddit_result(res);
+            }
+            ResultType::HackerNews => {
+                ui.set_hn_result(res);
+            }
+        }
+    }) {
+        util::slint_event_loop_panic(err);
+    };
+}
+
+/// Display the given snippet as the preview of the result the user went to.
+///
+/// # Arguments
+///
+/// * `ui` - weak pointer to the slint ui
+/// * `snippet` - the snippet of the result from the search, empty if there is none.
+///
+/// # Panics
+///
+/// If it can't invoke the slint event loop.
+#[tracing::instrument(skip_all)]
+pub fn display_preview(ui: Weak<MainWindow>, snippet: String) {
+    let preview = slint::SharedString::from(snippet);
+    if let Err(err) = slint::invoke_from_event_loop(move || {
+        util::get_ui(ui).set_preview(preview);
+    }) {
+        util::slint_event_loop_panic(err);
+    };
+}
@@ -0,0 +1,77 @@
+use super::display;
+use super::MainWindow;
+use super::ResultType;
+use crate::content::{self, Loaded};
+use crate::ui::ResultsContentType;
+use dashmap::DashMap;
+use slint::Weak;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+// How often to check if results finished loading
+const LOADING_POLL: Duration = Duration::from_millis(250);
+
+/// Await the results whose content finishes loading in the background, so they're listed under
+/// their title with their metadata, and redisplay the current result when it's renamed. Stops
+/// once every result is loaded.
+///
+/// # Arguments
+///
+/// * `ui` - weak pointer to the slint ui
+/// * `results` - ARC to the RwLock encapsulation of the Option for the results variable, from the main
+/// ui function.
+/// * `results_awaited` - ARC to the awaited results variable, from the main ui function.
+/// * `index` - ARC to the RwLock of the current results index for this particular resource
+/// * `results_type` - the kind of result this is. Ex: StackOverflow.
+///
+/// # Panics
+///
+/// If it can't invoke the slint event loop.
+#[tracing::instrument(skip_all)]
+pub async fn watch_loading<T, E, F>(
+    ui: Weak<MainWindow>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
+    index: Arc<RwLock<usize>>,
+    results_type: ResultType,
+) where
+    T: Loaded,
+    E: std::fmt::Display,
+{
+    loop {
+        tokio::time::sleep(LOADING_POLL).await;
+
+        let locked = futures::join!(results.write(), index.read());
+        let mut results_lock = locked.0;
+        let index_lock = locked.1;
+        let results = match results_lock.as_mut() {
+            Some(Ok(results)) => results,
+            _ => return,
+        };
+
+        let mut loading = false;
+        for (position, (hit, handle)) in results.iter_mut().enumerate() {
+            if results_awaited.contains_key(hit.title.as_str()) {
+                continue;
+            }
+            if !handle.is_finished() {
+                loading = true;
+                continue;
+            }
+
+            // save already awaited, under the title with the metadata
+            let awaited = content::await_content(handle).await;
+            hit.title = awaited.row_title(&hit.title);
+            results_awaited.insert(hit.title.to_owned(), awaited);
+            if position == *index_lock {
+                display::display_result(ui.clone(), hit.title.to_owned(), results_type);
+            }
+        }
+
+        if !loading {
+            tracing::info!("Every result finished loading.");
+            return;
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use super::display;
+use super::util;
+use super::MainWindow;
+use super::ResultType;
+use crate::content::Loaded;
+use crate::search::merge::{self, MergedResult, Source, Weights};
+use crate::ui::ResultsContentType;
+use dashmap::DashMap;
+use slint::Weak;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// What the merged view needs from a resource, whatever the content of its results is.
+pub trait MergeSource: Send + Sync {
+    /// The source the results are from.
+    fn source(&self) -> Source;
+
+    /// Get the results of the resource as results of the merged view, with the votes of the ones
+    /// whose content loaded.
+    ///
+    /// # Panics
+    ///
+    /// If blocking lock can't be called on the RwLock, like in an async function.
+    fn merged_results(&self) -> Vec<MergedResult>;
+
+    /// Move the resource to one of its results, redisplay it and access it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - weak pointer to the slint ui
+    /// * `index` - the index of the result in the results of the resource.
+    ///
+    /// # Panics
+    ///
+    /// If blocking lock can't be called on the RwLock, like in an async function, or if it can't
+    /// invoke the slint event loop.
+    fn access(&self, ui: Weak<MainWindow>, index: usize);
+}
+
+/// A resource as seen by the merged view: its results, the ones whose content loaded and its
+/// current index, the same ones the row of the resource uses.
+pub struct MergedSource<T, E, F> {
+    source: Source,
+    results_type: ResultType,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
+    index: Arc<RwLock<usize>>,
+}
+
+impl<T, E, F> MergedSource<T, E, F> {
+    /// Create the resource as seen by the merged view.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the source the results are from.
+    /// * `results_type` - the kind of result this is. Ex: StackOverflow.
+    /// * `results` - ARC to the RwLock encapsulation of the Option for the results variable, from
+    /// the main ui function.
+    /// * `results_awaited` - ARC to the awaited results variable, from the main ui function.
+    /// * `index` - ARC to the RwLock of the current results index for this particular resource
+    pub fn new(
+        source: Source,
+        results_type: ResultType,
+        results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+        results_awaited: Arc<DashMap<String, T>>,
+        index: Arc<RwLock<usize>>,
+    ) -> Self {
+        Self {
+            source,
+            results_type,
+            results,
+            results_awaited,
+            index,
+        }
+    }
+}
+
+impl<T, E, F> MergeSource for MergedSource<T, E, F>
+where
+    T: Loaded + Send + Sync,
+    E: Send + Sync,
+    F: std::fmt::Display + Send + Sync,
+{
+    fn source(&self) -> Source {
+        self.source
+    }
+
+    fn merged_results(&self) -> Vec<MergedResult> {
+        match self.results.blocking_read().as_ref() {
+            Some(results) => merge::merged_results(self.source, results, |hit| {
+                self.results_awaited
+                    .get(&hit.title)
+                    .and_then(|awaited| awaited.metadata().and_then(|metadata| metadata.score))
+            }),
+            None => Vec::new(),
+        }
+    }
+
+    fn access(&self, ui: Weak<MainWindow>, index: usize) {
+        *self.index.blocking_write() = index;
+        display::redisplay_result(
+            ui.clone(),
+            Arc::clone(&self.results),
+            Arc::clone(&self.index),
+            self.results_type,
+        );
+
+        let results_type = self.results_type;
+        if let Err(err) = slint::invoke_from_event_loop(move || {
+            let ui = util::get_ui(ui);
+
+            match results_type {
+                ResultType::StackOverflow => ui.invoke_sof_enter(),
+                ResultType::StackExchange => ui.invoke_se_enter(),
+                ResultType::GithubGist => ui.invoke_gg_enter(),
+                ResultType::GeeksForGeeks => ui.invoke_gfg_enter(),
+                ResultType::DdgSearch => ui.invoke_ddg_enter(),
+                ResultType::RustDocs => ui.invoke_docs_enter(),
+                ResultType::ManPages => ui.invoke_man_enter(),
+                ResultType::OfflineDocs => ui.invoke_offline_enter(),
+                ResultType::GithubIssues => ui.invoke_issues_enter(),
+                ResultType::Discourse => ui.invoke_discourse_enter(),
+                ResultType::Reddit => ui.invoke_reddit_enter(),
+                ResultType::HackerNews => ui.invoke_hn_enter(),
+            }
+        }) {
+            util::slint_event_loop_panic(err);
+        };
+    }
+}
+
+/// The row interleaving the results of every resource, ranked by search rank, votes and the
+/// weight of their resource. It's ranked again every time it's moved through, since the votes
+/// of the results are known once their content loaded.
+#[derive(Clone)]
+pub struct MergedView {
+    sources: Vec<Arc<dyn MergeSource>>,
+    weights: Weights,
+    index: Arc<RwLock<usize>>,
+}
+
+impl MergedView {
+    /// Create a merged view without any resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `weights` - how much the results of every resource count.
+    pub fn new(weights: Weights) -> Self {
+        Self {
+            sources: Vec::with_capacity(Source::ALL.len()),
+            weights,
+            index: Arc::new(RwLock::new(0)),
+        }
+    }
+
+    /// Add a resource to the merged view.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - the resource as seen by the merged view.
+    pub fn with_source(mut self, source: impl MergeSource + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Get the results of every resource, ranked.
+    fn ranked(&self) -> Vec<MergedResult> {
+        let results = self
+            .sources
+            .iter()
+            .flat_map(|source| source.merged_results())
+            .collect();
+
+        merge::rank(results, &self.weights)
+    }
+
+    /// Display the current result of the merged view, tagged with the resource it's from.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - weak pointer to the slint ui
+    /// * `ranked` - the results of every resource, ranked.
+    ///
+    /// # Panics
+    ///
+    /// If it can't invoke the slint event loop.
+    fn display(&self, ui: Weak<MainWindow>, ranked: &[MergedResult]) {
+        let mut index = self.index.blocking_write();
+        *index = (*index).min(ranked.len().saturating_sub(1));
+        let (title, any) = match ranked.get(*index) {
+            Some(result) => (
+                format!("[{}] {}", result.source.label(), result.title),
+                true,
+            ),
+            None => (String::from("No resource has any results."), false),
+        };
+
+        let title = slint::SharedString::from(title);
+        if let Err(err) = slint::invoke_from_event_loop(move || {
+            let ui = util::get_ui(ui);
+
+            ui.set_merged_result(title);
+            ui.set_is_merged(any);
+            ui.set_is_merged_back(any);
+            ui.set_is_merged_next(any);
+        }) {
+            util::slint_event_loop_panic(err);
+        };
+    }
+
+    /// Go back to the first result of the merged view and display it, for when new results
+    /// arrive.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - weak pointer to the slint ui
+    ///
+    /// # Panics
+    ///
+    /// If blocking lock can't be called on the RwLock, like in an async function, or if it can't
+    /// invoke the slint event loop.
+    #[tracing::instrument(skip_all)]
+    pub fn reset(&self, ui: Weak<MainWindow>) {
+        *self.index.blocking_write() = 0;
+        self.display(ui, &self.ranked());
+    }
+
+    /// Setup the buttons of the merged view: cycle through its results and access them.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - weak pointer to the slint ui
+    #[tracing::instrument(skip_all)]
+    pub fn setup_btns(&self, ui: Weak<MainWindow>) {
+        let ui_deref = util::get_ui(ui.clone());
+        ui_deref.set_show_merged(true);
+
+        ui_deref.on_merged_back_enter({
+            let view = self.clone();
+            let ui = ui.clone();
+            move || {
+                let view = view.clone();
+                let ui = ui.clone();
+                tokio::task::spawn_blocking(move || {
+                    let ranked = view.ranked();
+                    {
+                        let mut index = view.index.blocking_write();
+                        *index = index.saturating_sub(1);
+                    }
+                    view.display(ui, &ranked);
+                });
+            }
+        });
+
+        ui_deref.on_merged_next_enter({
+            let view = self.clone();
+            let ui = ui.clone();
+            move || {
+                let view = view.clone();
+                let ui = ui.clone();
+                tokio::task::spawn_blocking(move || {
+                    let ranked = view.ranked();
+                    {
+                        let mut index = view.index.blocking_write();
+                        if *index + 1 < ranked.len() {
+                            *index += 1;
+                        }
+                    }
+                    view.display(ui, &ranked);
+                });
+            }
+        });
+
+        ui_deref.on_merged_enter({
+            let view = self.clone();
+            move || {
+                let view = view.clone();
+                let ui = ui.clone();
+                tokio::task::spawn_blocking(move || {
+                    let ranked = view.ranked();
+                    let index = *view.index.blocking_read();
+                    let result = match ranked.get(index) {
+                        Some(result) => result,
+                        None => return,
+                    };
+                    tracing::info!(
+                        "Accessing the merged result at index: {} from {}",
+                        index,
+                        result.source
+                    );
+
+                    view.display(ui.clone(), &ranked);
+                    if let Some(source) = view
+                        .sources
+                        .iter()
+                        .find(|source| source.source() == result.source)
+                    {
+                        source.access(ui, result.index);
+                    }
+                });
+            }
+        });
+    }
+}
@@ -1,6 +1,8 @@
 pub mod display;
 pub mod helper;
 pub mod index;
+pub mod loading;
+pub mod merged;
 use super::util;
 use super::MainWindow;
 use super::Results;
@@ -16,6 +18,13 @@ pub enum ResultType {
     GithubGist,
     GeeksForGeeks,
     DdgSearch,
+    RustDocs,
+    ManPages,
+    OfflineDocs,
+    GithubIssues,
+    Discourse,
+    Reddit,
+    HackerNews,
 }
 
 /// Reset the results ui elements. Disabling the buttons and removing any button text.
@@ -59,9 +68,50 @@ pub fn reset_results(ui: Weak<MainWindow>) {
         ui.set_is_ddg_back(false);
         ui.set_is_ddg_next(false);
 
+        ui.set_docs_result(space_string.clone());
+        ui.set_is_docs(false);
+        ui.set_is_docs_back(false);
+        ui.set_is_docs_next(false);
+
+        ui.set_man_result(space_string.clone());
+        ui.set_is_man(false);
+        ui.set_is_man_back(false);
+        ui.set_is_man_next(false);
+
+        ui.set_offline_result(space_string.clone());
+        ui.set_is_offline(false);
+        ui.set_is_offline_back(false);
+        ui.set_is_offline_next(false);
+
+        ui.set_issues_result(space_string.clone());
+        ui.set_is_issues(false);
+        ui.set_is_issues_back(false);
+        ui.set_is_issues_next(false);
+
+        ui.set_discourse_result(space_string.clone());
+        ui.set_is_discourse(false);
+        ui.set_is_discourse_back(false);
+        ui.set_is_discourse_next(false);
+
+        ui.set_reddit_result(space_string.clone());
+        ui.set_is_reddit(false);
+        ui.set_is_reddit_back(false);
+        ui.set_is_reddit_next(false);
+
+        ui.set_hn_result(space_string.clone());
+        ui.set_is_hn(false);
+        ui.set_is_hn_back(false);
+        ui.set_is_hn_next(false);
+
+        ui.set_merged_result(space_string.clone());
+        ui.set_is_merged(false);
+        ui.set_is_merged_back(false);
+        ui.set_is_merged_next(false);
+
         ui.set_is_back(false);
         ui.set_is_next(false);
-        ui.set_error(space_string);
+        ui.set_error(space_string.clone());
+        ui.set_preview(space_string);
     }) {
         util::slint_event_loop_panic(err);
     };
@@ -177,5 +227,33 @@ pub fn setup_results_btns<T, E>(
             ui_deref.on_ddg_back_enter(back_event);
             ui_deref.on_ddg_next_enter(next_event);
         }
+        ResultType::RustDocs => {
+            ui_deref.on_docs_back_enter(back_event);
+            ui_deref.on_docs_next_enter(next_event);
+        }
+        ResultType::ManPages => {
+            ui_deref.on_man_back_enter(back_event);
+            ui_deref.on_man_next_enter(next_event);
+        }
+        ResultType::OfflineDocs => {
+            ui_deref.on_offline_back_enter(back_event);
+            ui_deref.on_offline_next_enter(next_event);
+        }
+        ResultType::GithubIssues => {
+            ui_deref.on_issues_back_enter(back_event);
+            ui_deref.on_issues_next_enter(next_event);
+        }
+        ResultType::Discourse => {
+            ui_deref.on_discourse_back_enter(back_event);
+            ui_deref.on_discourse_next_enter(next_event);
+        }
+        ResultType::Reddit => {
+            ui_deref.on_reddit_back_enter(back_event);
+            ui_deref.on_reddit_next_enter(next_event);
+        }
+        ResultType::HackerNews => {
+            ui_deref.on_hn_back_enter(back_event);
+            ui_deref.on_hn_next_enter(next_event);
+        }
     }
 }
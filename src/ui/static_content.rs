@@ -1,8 +1,10 @@
+use super::results;
 use super::results::ResultType;
 use super::util;
 use super::MainWindow;
-use super::ResultsStaticType;
+use super::ResultsContentType;
 use super::STATIC_CONTENT_VIEW;
+use crate::content::{self, Paged};
 use dashmap::DashMap;
 use slint::Weak;
 use std::sync::Arc;
@@ -24,13 +26,14 @@ use tokio::sync::RwLock;
 ///
 /// It the results type is not made for this function
 #[tracing::instrument(skip_all)]
-pub fn setup_content_display<E, F>(
+pub fn setup_content_display<T, E, F>(
     ui: Weak<MainWindow>,
-    results: Arc<RwLock<Option<ResultsStaticType<E, F>>>>,
-    results_awaited: Arc<DashMap<String, String>>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
     index: Arc<RwLock<usize>>,
     results_type: ResultType,
 ) where
+    T: Paged + std::marker::Send + std::marker::Sync + 'static,
     E: std::fmt::Display + std::marker::Send + 'static,
     F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
 {
@@ -46,12 +49,28 @@ pub fn setup_content_display<E, F>(
             Arc::clone(&results),
             Arc::clone(&results_awaited),
             Arc::clone(&index),
+            results_type,
         )),
         ResultType::DdgSearch => ui_strong.on_ddg_enter(get_resource_enter_fn(
             ui.clone(),
             Arc::clone(&results),
             Arc::clone(&results_awaited),
             Arc::clone(&index),
+            results_type,
+        )),
+        ResultType::ManPages => ui_strong.on_man_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            results_type,
+        )),
+        ResultType::OfflineDocs => ui_strong.on_offline_enter(get_resource_enter_fn(
+            ui.clone(),
+            Arc::clone(&results),
+            Arc::clone(&results_awaited),
+            Arc::clone(&index),
+            results_type,
         )),
         _ => {
             tracing::error!("Results type used on a function that doesn't support it.");
@@ -70,18 +89,21 @@ pub fn setup_content_display<E, F>(
 /// * `results_awaited` - ARC to the RwLock of the awaited results variable, from the main ui
 /// function.
 /// * `index` - ARC to the RwLock of the current results index for this particular resource
+/// * `results_type` - the kind of result this is. Ex: GeeksForGeeks.
 ///
 /// # Panics
 ///
 /// If slint couldn't be invoked from the event loop.
 #[tracing::instrument(skip_all)]
-fn get_resource_enter_fn<E, F>(
+fn get_resource_enter_fn<T, E, F>(
     ui: Weak<MainWindow>,
-    results: Arc<RwLock<Option<ResultsStaticType<E, F>>>>,
-    results_awaited: Arc<DashMap<String, String>>,
+    results: Arc<RwLock<Option<ResultsContentType<T, E, F>>>>,
+    results_awaited: Arc<DashMap<String, T>>,
     index: Arc<RwLock<usize>>,
+    results_type: ResultType,
 ) -> impl Fn()
 where
+    T: Paged + std::marker::Send + std::marker::Sync + 'static,
     E: std::fmt::Display + std::marker::Send + 'static,
     F: std::fmt::Display + std::marker::Send + std::marker::Sync + 'static,
 {
@@ -116,32 +138,23 @@ where
                                 util::slint_event_loop_panic(err);
                             };
                             // get content
-                            match results_awaited_clone.get(&result.0) {
+                            match results_awaited_clone.get(&result.0.title) {
                                 Some(result) => result,
                                 None => {
-                                    let (title, handle) = result;
-                                    let awaited = match handle.await {
-                                        Ok(handled) => match handled {
-                                            Ok(content) => content,
-                                            Err(error) => {
-                                                tracing::error!("There was an error getting the contetn for this a result. Error: {}", error);
-                                                format!("There has been an error getting the content for this result. Error: {}", error)
-                                            }
-                                        },
-                                        Err(error) => {
-                                            tracing::error!(
-                                                "There was an error handeling the future for a result. Error: {}",
-                                                error
-                                            );
-                                            format!("There has been an error handeling the future for this result. Error: {}", error)
-                                        }
-                                    };
+                                    let (hit, handle) = result;
+                                    let awaited = content::await_content(handle).await;
 
-                                    // save already awaited
-                                    results_awaited_clone.insert(title.to_owned(), awaited);
+                                    // save already awaited, under the title with the metadata
+                                    hit.title = awaited.row_title(&hit.title);
+                                    results_awaited_clone.insert(hit.title.to_owned(), awaited);
+                                    results::display::display_result(
+                                        ui.clone(),
+                                        hit.title.to_owned(),
+                                        results_type,
+                                    );
 
                                     // unwrap is safe since we just inserted this element
-                                    results_awaited_clone.get(title).unwrap()
+                                    results_awaited_clone.get(&hit.title).unwrap()
                                 }
                             }
                         }
@@ -164,7 +177,7 @@ where
             // set the first element
             let ui_clone = ui.clone();
             // get owned data for content
-            let content = content.to_owned();
+            let content = content.text().to_owned();
             // drop the RwLock locks
             drop(results_lock);
             drop(index_lock);